@@ -0,0 +1,263 @@
+use convert_case::{Case, Casing};
+use syn::spanned::Spanned;
+use syn::{Attribute, Field, Ident, LitInt, LitStr, Type};
+
+/// TL type of a single field, decides which `Serializer`/`Deserializer` method is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlType {
+    Nat,
+    Int,
+    Long,
+    Int128,
+    Int256,
+    Bytes,
+    String,
+    Bare,
+    Boxed,
+}
+
+impl TlType {
+    fn from_tl(name: &str) -> Self {
+        match name {
+            "#" => TlType::Nat,
+            "int" => TlType::Int,
+            "long" => TlType::Long,
+            "int128" => TlType::Int128,
+            "int256" => TlType::Int256,
+            "bytes" | "secureBytes" => TlType::Bytes,
+            "string" | "secureString" => TlType::String,
+            name if is_boxed_type(name) => TlType::Boxed,
+            _ => TlType::Bare,
+        }
+    }
+
+    fn from_attr(value: &LitStr) -> syn::Result<Self> {
+        match value.value().as_str() {
+            "bare" => Ok(TlType::Bare),
+            "boxed" => Ok(TlType::Boxed),
+            name @ ("#" | "int" | "long" | "int128" | "int256" | "bytes" | "string") => {
+                Ok(Self::from_tl(name))
+            }
+            name => Err(syn::Error::new(
+                value.span(),
+                format!("unsupported tl type `{}`", name),
+            )),
+        }
+    }
+
+    /// Guesses TL type by the Rust type, `adnl_tcp::types` aliases are recognized as well.
+    fn from_rust(ty: &Type) -> Self {
+        match ty {
+            Type::Array(array) if is_u8(&array.elem) => TlType::Int256,
+            Type::Path(path) => {
+                let Some(segment) = path.path.segments.last() else {
+                    return TlType::Bare;
+                };
+
+                match segment.ident.to_string().as_str() {
+                    "i32" | "Int" | "Int32" => TlType::Int,
+                    "Int31" => TlType::Nat,
+                    "i64" | "Long" | "Int53" | "Int64" => TlType::Long,
+                    "i128" | "Int128" => TlType::Int128,
+                    "Int256" => TlType::Int256,
+                    "Bytes" | "SecureBytes" | "Object" => TlType::Bytes,
+                    "String" | "SecureString" => TlType::String,
+                    "Vec" if generic_argument(ty).is_some_and(is_u8) => TlType::Bytes,
+                    _ => TlType::Bare,
+                }
+            }
+            _ => TlType::Bare,
+        }
+    }
+}
+
+/// Boxed TL types start with a capital letter after the namespace, e.g. `adnl.Message`.
+fn is_boxed_type(name: &str) -> bool {
+    let name = name.split_whitespace().next().unwrap_or(name);
+    let name = name.rsplit('.').next().unwrap_or(name);
+
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.is_ident("u8"))
+}
+
+/// First generic argument of the last path segment: `T` for `Option<T>` or `Vec<T>`.
+pub fn generic_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub field: String,
+    pub bit: u32,
+}
+
+impl Condition {
+    fn parse(value: &str, span: proc_macro2::Span) -> syn::Result<Self> {
+        let (field, bit) = value
+            .split_once('.')
+            .ok_or(syn::Error::new(span, "condition must look like `flags.0`"))?;
+        let bit = bit
+            .parse::<u32>()
+            .ok()
+            .filter(|bit| *bit < 31)
+            .ok_or(syn::Error::new(span, "condition bit must be in 0..31"))?;
+
+        Ok(Self {
+            field: field.to_owned(),
+            bit,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct ContainerAttrs {
+    pub constructor_number_be: Option<u32>,
+    pub scheme: Option<tl_parser::Combinator>,
+    pub result: Option<Type>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut output = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("tl")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("id") {
+                    let id: LitInt = meta.value()?.parse()?;
+                    output.constructor_number_be = Some(id.base10_parse::<u32>()?.swap_bytes());
+                } else if meta.path.is_ident("scheme") {
+                    let scheme: LitStr = meta.value()?.parse()?;
+                    let combinator = parse_scheme(&scheme)?;
+                    output.constructor_number_be = Some(combinator.constructor_number_be());
+                    output.scheme = Some(combinator);
+                } else if meta.path.is_ident("result") {
+                    output.result = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported tl attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(output)
+    }
+}
+
+fn parse_scheme(scheme: &LitStr) -> syn::Result<tl_parser::Combinator> {
+    let value = scheme.value();
+    let value = value.trim();
+    let value = if value.ends_with(';') {
+        value.to_owned()
+    } else {
+        format!("{};", value)
+    };
+
+    let combinators = tl_parser::parse(&value)
+        .map_err(|e| syn::Error::new(scheme.span(), format!("invalid tl scheme: {}", e)))?;
+    match <[_; 1]>::try_from(combinators) {
+        Ok([combinator]) => Ok(combinator),
+        Err(_) => Err(syn::Error::new(
+            scheme.span(),
+            "tl scheme must contain exactly one combinator",
+        )),
+    }
+}
+
+pub struct FieldAttrs {
+    pub ident: Ident,
+    pub ty: Type,
+    pub tl_type: TlType,
+    pub condition: Option<Condition>,
+}
+
+impl FieldAttrs {
+    /// Merges `#[tl(...)]` field attributes with the matching scheme field, attributes win.
+    pub fn parse(field: &Field, scheme: Option<&tl_parser::Field>) -> syn::Result<Self> {
+        let ident = field.ident.clone().ok_or(syn::Error::new(
+            field.span(),
+            "tuple structs are not supported",
+        ))?;
+
+        let mut tl_type = None;
+        let mut condition = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("tl")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("type") {
+                    tl_type = Some(TlType::from_attr(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("flags") {
+                    tl_type = Some(TlType::Nat);
+                } else if meta.path.is_ident("boxed") {
+                    tl_type = Some(TlType::Boxed);
+                } else if meta.path.is_ident("condition") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    condition = Some(Condition::parse(&value.value(), value.span())?);
+                } else {
+                    return Err(meta.error("unsupported tl attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        if let Some(scheme) = scheme {
+            if condition.is_none() {
+                condition = match scheme.type_condition() {
+                    None => None,
+                    Some(tl_parser::Condition {
+                        field_ref,
+                        bit_selector: Some(bit),
+                    }) => Some(Condition {
+                        field: field_ref.to_case(Case::Snake),
+                        bit: *bit,
+                    }),
+                    Some(_) => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "only `flags.N?` conditions are supported",
+                        ))
+                    }
+                };
+            }
+            if tl_type.is_none() {
+                tl_type = scheme.field_type().map(TlType::from_tl);
+            }
+        }
+
+        let value_ty = if condition.is_some() {
+            generic_argument(&field.ty)
+                .ok_or(syn::Error::new(
+                    field.ty.span(),
+                    "conditional field must be an `Option`",
+                ))?
+                .clone()
+        } else {
+            field.ty.clone()
+        };
+        let tl_type = tl_type.unwrap_or_else(|| TlType::from_rust(&value_ty));
+
+        Ok(Self {
+            ident,
+            ty: value_ty,
+            tl_type,
+            condition,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.ident.to_string().trim_start_matches("r#").to_owned()
+    }
+}
@@ -0,0 +1,410 @@
+//! Derive macros for `adnl_tcp::serializer::{Serialize, SerializeBoxed}` and
+//! `adnl_tcp::deserializer::{Deserialize, DeserializeBoxed}`.
+//!
+//! Structs are TL constructors, the constructor id comes either from `#[tl(id = 0x...)]`
+//! (as written after `#` in a TL scheme) or from `#[tl(scheme = "...")]`:
+//!
+//! ```ignore
+//! #[derive(TlSerialize, TlDeserialize)]
+//! #[tl(scheme = "my.ping flags:# nonce:long payload:flags.0?bytes = my.Pong")]
+//! struct MyPing {
+//!     flags: i32,
+//!     nonce: i64,
+//!     payload: Option<Vec<u8>>,
+//! }
+//! ```
+//!
+//! Field attributes:
+//! - `#[tl(type = "int256")]` overrides the TL type guessed from the Rust type
+//!   (`#`, `int`, `long`, `int128`, `int256`, `bytes`, `string`, `bare` or `boxed`);
+//! - `#[tl(flags)]` marks a `#` field, its condition bits are taken from the conditional fields;
+//! - `#[tl(condition = "flags.0")]` makes an `Option` field present only if the bit is set;
+//! - `#[tl(boxed)]` writes the constructor id of a nested type.
+//!
+//! Enums are boxed types, every variant wraps one constructor: `Ping(MyPing)`.
+//! `#[tl(result = Type)]` on a struct additionally implements `adnl_tcp::types::Functional`.
+
+mod attr;
+
+use crate::attr::{Condition, ContainerAttrs, FieldAttrs, TlType};
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields, Ident};
+
+#[proc_macro_derive(TlSerialize, attributes(tl))]
+pub fn derive_tl_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_serialize(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(TlDeserialize, attributes(tl))]
+pub fn derive_tl_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_deserialize(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Constructor {
+    constructor_number_be: u32,
+    fields: Vec<FieldAttrs>,
+}
+
+impl Constructor {
+    fn parse(input: &DeriveInput, attrs: &ContainerAttrs) -> syn::Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            unreachable!()
+        };
+        let constructor_number_be = attrs.constructor_number_be.ok_or(syn::Error::new(
+            input.ident.span(),
+            "missing `#[tl(id = ...)]` or `#[tl(scheme = \"...\")]`",
+        ))?;
+
+        let fields: Vec<_> = match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => vec![],
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "tuple structs are not supported",
+                ))
+            }
+        };
+
+        let fields = match attrs.scheme.as_ref() {
+            None => fields
+                .into_iter()
+                .map(|field| FieldAttrs::parse(field, None))
+                .collect::<syn::Result<Vec<_>>>()?,
+            Some(scheme) => {
+                if scheme.fields().len() != fields.len() {
+                    return Err(syn::Error::new(
+                        input.ident.span(),
+                        format!(
+                            "scheme declares {} fields, struct has {}",
+                            scheme.fields().len(),
+                            fields.len()
+                        ),
+                    ));
+                }
+
+                fields
+                    .into_iter()
+                    .zip(scheme.fields())
+                    .map(|(field, scheme_field)| {
+                        let output = FieldAttrs::parse(field, Some(scheme_field))?;
+                        let expected = scheme_field.id().unwrap_or_default().to_case(Case::Snake);
+                        if output.name() != expected {
+                            return Err(syn::Error::new(
+                                output.ident.span(),
+                                format!("expected field `{}` in scheme order", expected),
+                            ));
+                        }
+
+                        Ok(output)
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?
+            }
+        };
+
+        for field in fields.iter() {
+            if let Some(Condition { field: flags, .. }) = field.condition.as_ref() {
+                let declared = fields
+                    .iter()
+                    .take_while(|f| f.ident != field.ident)
+                    .any(|f| f.name() == *flags && f.tl_type == TlType::Nat);
+                if !declared {
+                    return Err(syn::Error::new(
+                        field.ident.span(),
+                        format!("`{}` must be a preceding `#` field", flags),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            constructor_number_be,
+            fields,
+        })
+    }
+
+    fn local(field: &FieldAttrs) -> Ident {
+        format_ident!("__field_{}", field.name())
+    }
+
+    fn local_by_name(name: &str) -> Ident {
+        format_ident!("__field_{}", name)
+    }
+}
+
+fn write_value(tl_type: TlType, value: TokenStream2) -> TokenStream2 {
+    match tl_type {
+        TlType::Nat => quote! { se.write_i31(*#value) },
+        TlType::Int => quote! { se.write_i32(*#value) },
+        TlType::Long => quote! { se.write_i64(*#value) },
+        TlType::Int128 => quote! { se.write_i128(*#value) },
+        TlType::Int256 => quote! { se.write_i256(#value) },
+        TlType::Bytes => quote! { se.write_bytes(#value) },
+        TlType::String => quote! { se.write_string(#value) },
+        TlType::Bare => quote! { ::adnl_tcp::serializer::Serialize::serialize(#value, se) },
+        TlType::Boxed => {
+            quote! { ::adnl_tcp::serializer::SerializeBoxed::serialize_boxed(#value, se) }
+        }
+    }
+}
+
+fn parse_value(tl_type: TlType, ty: &syn::Type) -> TokenStream2 {
+    match tl_type {
+        TlType::Nat => quote! { de.parse_i31()? },
+        TlType::Int => quote! { de.parse_i32()? },
+        TlType::Long => quote! { de.parse_i64()? },
+        TlType::Int128 => quote! { de.parse_i128()? },
+        TlType::Int256 => quote! { de.parse_i256()? },
+        TlType::Bytes => quote! { de.parse_bytes()? },
+        TlType::String => quote! { de.parse_string()? },
+        TlType::Bare => {
+            quote! { <#ty as ::adnl_tcp::deserializer::Deserialize>::deserialize(de)? }
+        }
+        TlType::Boxed => quote! {
+            {
+                let constructor_number = de.parse_constructor_numer()?;
+                <#ty as ::adnl_tcp::deserializer::DeserializeBoxed>::deserialize_boxed(constructor_number, de)?
+            }
+        },
+    }
+}
+
+fn expand_serialize(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    if let Data::Enum(data) = &input.data {
+        let variants = enum_variants(data)?;
+        let serialize_match = variants.iter().map(|(variant, _)| {
+            quote! { Self::#variant(inner) => ::adnl_tcp::serializer::SerializeBoxed::serialize_boxed(inner, se) }
+        });
+
+        return Ok(quote! {
+            impl #impl_generics ::adnl_tcp::serializer::Serialize for #name #ty_generics #where_clause {
+                fn serialize(&self, se: &mut ::adnl_tcp::serializer::Serializer) {
+                    match self {
+                        #(#serialize_match),*
+                    }
+                }
+            }
+
+            impl #impl_generics ::adnl_tcp::serializer::SerializeBoxed for #name #ty_generics #where_clause {
+                fn serialize_boxed(&self, se: &mut ::adnl_tcp::serializer::Serializer) {
+                    ::adnl_tcp::serializer::Serialize::serialize(self, se)
+                }
+            }
+        });
+    }
+    if let Data::Union(data) = &input.data {
+        return Err(syn::Error::new(
+            data.union_token.span(),
+            "unions are not supported",
+        ));
+    }
+
+    let constructor = Constructor::parse(input, &attrs)?;
+    let constructor_number_be = constructor.constructor_number_be;
+
+    let flags = constructor.fields.iter().filter(|field| {
+        constructor.fields.iter().any(|f| {
+            f.condition
+                .as_ref()
+                .is_some_and(|c| c.field == field.name())
+        })
+    });
+    let flags_defs = flags.clone().map(|field| {
+        let local = Constructor::local(field);
+        let ident = &field.ident;
+        // bits of absent fields must not leak from the stored value
+        let mask = constructor
+            .fields
+            .iter()
+            .filter_map(|f| f.condition.as_ref())
+            .filter(|c| c.field == field.name())
+            .fold(0u32, |mask, c| mask | 1 << c.bit) as i32;
+
+        quote! { let mut #local = self.#ident & !#mask; }
+    });
+    let flags_bits = constructor.fields.iter().filter_map(|field| {
+        let Condition { field: flags, bit } = field.condition.as_ref()?;
+        let local = Constructor::local_by_name(flags);
+        let ident = &field.ident;
+
+        Some(quote! {
+            if self.#ident.is_some() {
+                #local |= 1 << #bit;
+            }
+        })
+    });
+    let flags_names: Vec<_> = flags.map(FieldAttrs::name).collect();
+
+    let writes = constructor.fields.iter().map(|field| {
+        let ident = &field.ident;
+        match field.condition {
+            None if flags_names.contains(&field.name()) => {
+                let local = Constructor::local(field);
+                write_value(field.tl_type, quote! { &#local })
+            }
+            None => write_value(field.tl_type, quote! { &self.#ident }),
+            Some(_) => {
+                let write = write_value(field.tl_type, quote! { value });
+                quote! {
+                    if let Some(value) = self.#ident.as_ref() {
+                        #write;
+                    }
+                }
+            }
+        }
+    });
+
+    let functional = attrs.result.as_ref().map(|result| {
+        quote! {
+            impl #impl_generics ::adnl_tcp::types::Functional for #name #ty_generics #where_clause {
+                type Result = #result;
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::adnl_tcp::serializer::Serialize for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn serialize(&self, se: &mut ::adnl_tcp::serializer::Serializer) {
+                #(#flags_defs)*
+                #(#flags_bits)*
+
+                #(#writes;)*
+            }
+        }
+
+        impl #impl_generics ::adnl_tcp::serializer::SerializeBoxed for #name #ty_generics #where_clause {
+            fn serialize_boxed(&self, se: &mut ::adnl_tcp::serializer::Serializer) {
+                se.write_constructor_number(#constructor_number_be);
+                ::adnl_tcp::serializer::Serialize::serialize(self, se)
+            }
+        }
+
+        #functional
+    })
+}
+
+fn expand_deserialize(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    if let Data::Enum(data) = &input.data {
+        let variants = enum_variants(data)?;
+        let arms = variants.iter().map(|(variant, ty)| {
+            quote! {
+                constructor_number if constructor_number == <#ty>::CONSTRUCTOR_NUMBER_BE => {
+                    <#ty as ::adnl_tcp::deserializer::Deserialize>::deserialize(de).map(Self::#variant)
+                }
+            }
+        });
+
+        return Ok(quote! {
+            impl #impl_generics ::adnl_tcp::deserializer::Deserialize for #name #ty_generics #where_clause {
+                fn deserialize(de: &mut ::adnl_tcp::deserializer::Deserializer) -> Result<Self, ::adnl_tcp::deserializer::DeserializerBoxedError> {
+                    let constructor_number = de.parse_constructor_numer()?;
+
+                    <Self as ::adnl_tcp::deserializer::DeserializeBoxed>::deserialize_boxed(constructor_number, de)
+                }
+            }
+
+            impl #impl_generics ::adnl_tcp::deserializer::DeserializeBoxed for #name #ty_generics #where_clause {
+                fn deserialize_boxed(constructor_number: u32, de: &mut ::adnl_tcp::deserializer::Deserializer) -> Result<Self, ::adnl_tcp::deserializer::DeserializerBoxedError> {
+                    match constructor_number {
+                        #(#arms)*
+                        _ => Err(::adnl_tcp::deserializer::DeserializerBoxedError::UnexpectedConstructorNumber(constructor_number)),
+                    }
+                }
+            }
+        });
+    }
+    if let Data::Union(data) = &input.data {
+        return Err(syn::Error::new(
+            data.union_token.span(),
+            "unions are not supported",
+        ));
+    }
+
+    let constructor = Constructor::parse(input, &attrs)?;
+    let constructor_number_be = constructor.constructor_number_be;
+
+    let reads = constructor.fields.iter().map(|field| {
+        let local = Constructor::local(field);
+        let ty = &field.ty;
+        let parse = parse_value(field.tl_type, ty);
+
+        match field.condition.as_ref() {
+            None => quote! { let #local: #ty = #parse; },
+            Some(Condition { field: flags, bit }) => {
+                let flags = Constructor::local_by_name(flags);
+                quote! {
+                    let #local: Option<#ty> = if #flags & (1 << #bit) != 0 { Some(#parse) } else { None };
+                }
+            }
+        }
+    });
+    let pass = constructor.fields.iter().map(|field| {
+        let ident = &field.ident;
+        let local = Constructor::local(field);
+
+        quote! { #ident: #local }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub const CONSTRUCTOR_NUMBER_BE: u32 = #constructor_number_be;
+        }
+
+        impl #impl_generics ::adnl_tcp::deserializer::Deserialize for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn deserialize(de: &mut ::adnl_tcp::deserializer::Deserializer) -> Result<Self, ::adnl_tcp::deserializer::DeserializerBoxedError> {
+                #(#reads)*
+
+                Ok(Self {
+                    #(#pass),*
+                })
+            }
+        }
+
+        impl #impl_generics ::adnl_tcp::deserializer::DeserializeBoxed for #name #ty_generics #where_clause {
+            fn deserialize_boxed(constructor_number: u32, de: &mut ::adnl_tcp::deserializer::Deserializer) -> Result<Self, ::adnl_tcp::deserializer::DeserializerBoxedError> {
+                if constructor_number != #constructor_number_be {
+                    Err(::adnl_tcp::deserializer::DeserializerBoxedError::UnexpectedConstructorNumber(constructor_number))
+                } else {
+                    <Self as ::adnl_tcp::deserializer::Deserialize>::deserialize(de)
+                }
+            }
+        }
+    })
+}
+
+fn enum_variants(data: &DataEnum) -> syn::Result<Vec<(&Ident, &syn::Type)>> {
+    data.variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Ok((&variant.ident, &fields.unnamed[0].ty))
+            }
+            _ => Err(syn::Error::new(
+                variant.span(),
+                "boxed enum variants must wrap exactly one constructor, e.g. `Query(Query)`",
+            )),
+        })
+        .collect()
+}
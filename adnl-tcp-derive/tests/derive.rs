@@ -0,0 +1,219 @@
+use adnl_tcp::deserializer::{from_bytes_boxed, DeserializerBoxedError};
+use adnl_tcp::serializer::to_bytes_boxed;
+use adnl_tcp::types::{AdnlMessageAnswer, AdnlMessageQuery, Functional, Int256, PubEd25519};
+use adnl_tcp::{TlDeserialize, TlSerialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "adnl.message.query query_id:int256 query:bytes = adnl.Message")]
+struct Query {
+    query_id: Int256,
+    query: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(id = 0x0fac8416)]
+struct Answer {
+    query_id: [u8; 32],
+    answer: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+enum Message {
+    Query(Query),
+    Answer(Answer),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "test.forward message:adnl.Message = test.Envelope")]
+struct Forward {
+    #[tl(boxed)]
+    message: Query,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "test.discard reason:int = test.Envelope")]
+struct Discard {
+    reason: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+enum Envelope {
+    Forward(Forward),
+    Discard(Discard),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "test.flags mode:# seqno:int name:mode.0?string key:mode.1?PublicKey = test.Flags")]
+struct Flags {
+    mode: i32,
+    seqno: i32,
+    name: Option<String>,
+    key: Option<PubEd25519>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(id = 0x7af98bb5)]
+struct Attributes {
+    #[tl(flags)]
+    flags: i32,
+    #[tl(condition = "flags.3")]
+    value: Option<i64>,
+    #[tl(type = "int256")]
+    hash: Int256,
+    queries: Vec<Query>,
+    #[tl(boxed)]
+    last: Query,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "test.getTime = test.Time", result = Time)]
+struct GetTime;
+
+#[derive(Debug, Clone, PartialEq, Eq, TlSerialize, TlDeserialize)]
+#[tl(scheme = "test.time unixtime:int = test.Time")]
+struct Time {
+    unixtime: i32,
+}
+
+fn result_of<T: Functional>(_: &T) -> &'static str {
+    std::any::type_name::<T::Result>()
+}
+
+#[test]
+fn derive_matches_handwritten_query() {
+    let query = Query {
+        query_id: [1; 32],
+        query: vec![1, 2, 3],
+    };
+    let expected = AdnlMessageQuery {
+        query_id: [1; 32],
+        query: vec![1, 2, 3],
+    };
+
+    let actual = to_bytes_boxed(&query);
+
+    assert_eq!(actual, to_bytes_boxed(&expected));
+    assert_eq!(from_bytes_boxed::<Query>(&actual).unwrap(), query);
+}
+
+#[test]
+fn derive_constructor_id() {
+    let answer = Answer {
+        query_id: [2; 32],
+        answer: vec![4; 300],
+    };
+    let expected = AdnlMessageAnswer {
+        query_id: [2; 32],
+        answer: vec![4; 300],
+    };
+
+    assert_eq!(to_bytes_boxed(&answer), to_bytes_boxed(&expected));
+}
+
+#[test]
+fn derive_boxed_enum() {
+    let message = Message::Answer(Answer {
+        query_id: [3; 32],
+        answer: vec![],
+    });
+
+    let bytes = to_bytes_boxed(&message);
+
+    assert_eq!(&bytes[..4], &[0x16, 0x84, 0xac, 0x0f]);
+    assert_eq!(from_bytes_boxed::<Message>(&bytes).unwrap(), message);
+    assert!(matches!(
+        from_bytes_boxed::<Message>(&[0, 0, 0, 0]),
+        Err(DeserializerBoxedError::UnexpectedConstructorNumber(0))
+    ));
+}
+
+#[test]
+fn derive_boxed_enum_propagates_nested_error() {
+    let envelope = Envelope::Forward(Forward {
+        message: Query {
+            query_id: [4; 32],
+            query: vec![],
+        },
+    });
+    let mut bytes = to_bytes_boxed(&envelope);
+    bytes[4..8].copy_from_slice(&0xdeadbeefu32.to_be_bytes());
+
+    let actual = from_bytes_boxed::<Envelope>(&bytes);
+
+    assert!(matches!(
+        actual,
+        Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+            0xdeadbeef
+        ))
+    ));
+}
+
+#[test]
+fn derive_flags_from_scheme() {
+    let flags = Flags {
+        mode: 0,
+        seqno: 7,
+        name: None,
+        key: Some(PubEd25519 { key: [5; 32] }),
+    };
+
+    let bytes = to_bytes_boxed(&flags);
+    let actual = from_bytes_boxed::<Flags>(&bytes).unwrap();
+
+    assert_eq!(&bytes[4..8], &[2, 0, 0, 0]);
+    assert_eq!(&bytes[12..16], &[0xc6, 0xb4, 0x13, 0x48]);
+    assert_eq!(actual.mode, 2);
+    assert_eq!(actual.name, None);
+    assert_eq!(actual.key, flags.key);
+}
+
+#[test]
+fn derive_flags_clears_stale_bits() {
+    let flags = Flags {
+        mode: 0b111,
+        seqno: 7,
+        name: None,
+        key: Some(PubEd25519 { key: [5; 32] }),
+    };
+
+    let bytes = to_bytes_boxed(&flags);
+    let actual = from_bytes_boxed::<Flags>(&bytes).unwrap();
+
+    assert_eq!(&bytes[4..8], &[0b110, 0, 0, 0]);
+    assert_eq!(actual.mode, 0b110);
+    assert_eq!(actual.name, None);
+    assert_eq!(actual.key, flags.key);
+    assert_eq!(to_bytes_boxed(&actual), bytes);
+}
+
+#[test]
+fn derive_field_attributes() {
+    let query = Query {
+        query_id: [6; 32],
+        query: vec![6],
+    };
+    let attributes = Attributes {
+        flags: 1 << 3,
+        value: Some(-1),
+        hash: [7; 32],
+        queries: vec![query.clone(), query.clone()],
+        last: query,
+    };
+
+    let bytes = to_bytes_boxed(&attributes);
+
+    assert_eq!(from_bytes_boxed::<Attributes>(&bytes).unwrap(), attributes);
+    assert!(matches!(
+        from_bytes_boxed::<Attributes>(&bytes[..bytes.len() - 1]),
+        Err(DeserializerBoxedError::UnexpectedEof { .. })
+    ));
+}
+
+#[test]
+fn derive_unit_function() {
+    let bytes = to_bytes_boxed(&GetTime);
+
+    assert_eq!(bytes.len(), 4);
+    assert_eq!(from_bytes_boxed::<GetTime>(&bytes).unwrap(), GetTime);
+    assert!(result_of(&GetTime).ends_with("Time"));
+}
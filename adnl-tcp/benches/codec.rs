@@ -0,0 +1,60 @@
+//! Compare revisions with criterion baselines, e.g.
+//! `cargo bench -p adnl-tcp --features bench -- --save-baseline main` on the old revision and
+//! `cargo bench -p adnl-tcp --features bench -- --baseline main` on the new one.
+use adnl_tcp::aes_ctr::AesCtr;
+use adnl_tcp::codec::PacketCodec;
+use adnl_tcp::packet::Packet;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use tokio_util::bytes::{Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+
+fn basis() -> [u8; 160] {
+    AesCtr::generate().into_bytes()
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    for size in SIZES {
+        let payload = Bytes::from(vec![7u8; size]);
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            let mut codec = PacketCodec::from_bytes_as_server(&basis());
+            let mut dst = BytesMut::new();
+            b.iter(|| {
+                dst.clear();
+                codec
+                    .encode(Packet::new(payload.clone()), &mut dst)
+                    .unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for size in SIZES {
+        let basis = basis();
+        let mut frame = BytesMut::new();
+        PacketCodec::from_bytes_as_server(&basis)
+            .encode(Packet::new(vec![7u8; size]), &mut frame)
+            .unwrap();
+        group.throughput(Throughput::Bytes(size as u64));
+
+        // the fresh codec per iteration keeps the key stream position in sync with the frame
+        group.bench_with_input(BenchmarkId::from_parameter(size), &frame, |b, frame| {
+            b.iter_batched(
+                || (PacketCodec::from_bytes_as_client(&basis), frame.clone()),
+                |(mut codec, mut src)| codec.decode(&mut src).unwrap().unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encode, decode);
+criterion_main!(benches);
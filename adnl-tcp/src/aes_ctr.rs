@@ -72,7 +72,8 @@ impl AesCtr {
         (basis_encrypted, checksum)
     }
 
-    fn cipher(x: &[u8; 32], y: &[u8; 32]) -> Aes256Ctr128 {
+    /// Cipher keyed by shared secret `x` and checksum `y` of the plain text.
+    pub fn cipher(x: &[u8; 32], y: &[u8; 32]) -> Aes256Ctr128 {
         let key = [
             x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7], x[8], x[9], x[10], x[11], x[12], x[13],
             x[14], x[15], y[16], y[17], y[18], y[19], y[20], y[21], y[22], y[23], y[24], y[25],
@@ -1,10 +1,20 @@
 use crate::aes_ctr::AesCtr;
 use crate::codec::PacketCodec;
 use crate::connection::Connection;
+use crate::deserializer::from_bytes_boxed;
 use crate::key::{Ed25519Key, Ed25519KeyId};
+use crate::packet::Packet;
+use crate::ping::{is_pong_packet, ping_packet};
+use crate::proxy::Proxy;
+use crate::serializer::to_bytes_boxed;
+use crate::types::{
+    PubEd25519, TcpAuthentificate, TcpAuthentificationComplete, TcpAuthentificationNonce,
+};
 use anyhow::{anyhow, bail};
 use ed25519_dalek::VerifyingKey;
-use futures::StreamExt;
+use futures::{SinkExt, StreamExt};
+use rand::random;
+use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 use tokio::net::{TcpStream, ToSocketAddrs};
@@ -20,8 +30,27 @@ impl Client {
         addr: A,
         server_key: ServerKey,
     ) -> anyhow::Result<Connection> {
-        let mut stream = TcpStream::connect(addr).await?;
+        let stream = TcpStream::connect(addr).await?;
 
+        Self::handshake(stream, server_key).await
+    }
+
+    /// Connects to `addr` through a SOCKS5 or HTTP CONNECT `proxy`.
+    pub async fn connect_with_proxy(
+        proxy: &Proxy,
+        addr: SocketAddr,
+        server_key: ServerKey,
+    ) -> anyhow::Result<Connection> {
+        let stream = proxy.connect(addr).await?;
+
+        Self::handshake(stream, server_key).await
+    }
+
+    /// Performs ADNL handshake over an already established `stream`.
+    pub async fn handshake(
+        mut stream: TcpStream,
+        server_key: ServerKey,
+    ) -> anyhow::Result<Connection> {
         let aes_ctr = AesCtr::generate();
         let server_public_key = VerifyingKey::from_bytes(&server_key)?;
         let server_key_id = Ed25519KeyId::from_public_key_bytes(&server_key);
@@ -52,14 +81,74 @@ impl Client {
 
         Ok(Connection::new(framed))
     }
+
+    /// Connects and proves ownership of `client_key` to the server.
+    pub async fn connect_authenticated<A: ToSocketAddrs>(
+        addr: A,
+        server_key: ServerKey,
+        client_key: &Ed25519Key,
+    ) -> anyhow::Result<Connection> {
+        let mut connection = Self::connect(addr, server_key).await?;
+
+        Self::authenticate(&mut connection, client_key).await?;
+
+        Ok(connection)
+    }
+
+    /// Performs `tcp.authentificate` exchange: signs client and server nonces with `client_key`.
+    ///
+    /// The server doesn't acknowledge the key but closes the connection if it rejects it,
+    /// so a ping has to be answered after the exchange.
+    pub async fn authenticate(
+        connection: &mut Connection,
+        client_key: &Ed25519Key,
+    ) -> anyhow::Result<()> {
+        let nonce: [u8; 32] = random();
+        let request = TcpAuthentificate {
+            nonce: nonce.to_vec(),
+        };
+        connection
+            .send(Packet::new(to_bytes_boxed(&request)))
+            .await?;
+
+        let packet = timeout(Duration::from_secs(5), connection.next())
+            .await?
+            .ok_or(anyhow!("missed authentification nonce"))??;
+        let response = from_bytes_boxed::<TcpAuthentificationNonce>(&packet.data)?;
+        if response.nonce.is_empty() || response.nonce.len() > 512 {
+            bail!("invalid authentification nonce")
+        }
+
+        let signature = client_key.sign(&[nonce.as_slice(), &response.nonce].concat());
+        let complete = TcpAuthentificationComplete {
+            key: PubEd25519 {
+                key: *client_key.public_key().as_bytes(),
+            },
+            signature: signature.to_vec(),
+        };
+        connection
+            .send(Packet::new(to_bytes_boxed(&complete)))
+            .await?;
+
+        tracing::info!(client_key_id = %client_key.id(), "authentification sent");
+
+        connection.send(ping_packet()).await?;
+        let packet = timeout(Duration::from_secs(5), connection.next())
+            .await?
+            .ok_or(anyhow!("authentification rejected"))?
+            .map_err(|e| anyhow!("authentification rejected: {}", e))?;
+        if !is_pong_packet(&packet) {
+            bail!("pong expected after authentification")
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ping::{is_pong_packet, ping_packet};
     use base64::Engine;
-    use futures::SinkExt;
     use std::net::{Ipv4Addr, SocketAddrV4};
     use tracing_test::traced_test;
 
@@ -1,17 +1,40 @@
 use crate::aes_ctr::{Aes256Ctr128, AesCtr};
 use crate::packet::Packet;
 use aes::cipher::generic_array::GenericArray;
-use anyhow::bail;
 use ctr::cipher::KeyIvInit;
 use ctr::cipher::StreamCipher;
 use sha2::{Digest, Sha256};
-use tokio_util::bytes::{BufMut, BytesMut};
+use thiserror::Error;
+use tokio_util::bytes::{Buf, BufMut, BytesMut};
 use tokio_util::codec::{Decoder, Encoder};
 
+/// Same limit as the reference implementation applies to ADNL-TCP frames.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1 << 24;
+
+const MIN_FRAME_SIZE: usize = 64;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("small ADNL packet: {0}")]
+    FrameTooSmall(usize),
+    #[error("ADNL packet of {length} bytes exceeds limit of {max} bytes")]
+    FrameTooLarge { length: usize, max: usize },
+    #[error("incorrect checksum for ADNL packet")]
+    IncorrectChecksum,
+}
+
 pub struct PacketCodec {
     cipher_recv: Aes256Ctr128,
     cipher_send: Aes256Ctr128,
-    next_len: Option<usize>,
+    frame: Option<Frame>,
+    max_frame_size: usize,
+}
+
+/// Frame being received: bytes up to `processed` are already decrypted in place and hashed.
+struct Frame {
+    length: usize,
+    processed: usize,
+    hasher: Sha256,
 }
 
 impl Encoder<Packet> for PacketCodec {
@@ -20,13 +43,24 @@ impl Encoder<Packet> for PacketCodec {
     fn encode(&mut self, packet: Packet, dst: &mut BytesMut) -> Result<(), Self::Error> {
         let buf_size = dst.len();
         let len = packet.len();
+        if len + MIN_FRAME_SIZE > self.max_frame_size {
+            return Err(CodecError::FrameTooLarge {
+                length: len + MIN_FRAME_SIZE,
+                max: self.max_frame_size,
+            }
+            .into());
+        }
         dst.reserve(len + 68);
 
-        dst.put(&((len + 64) as u32).to_le_bytes()[..]);
+        let checksum = Sha256::new()
+            .chain_update(packet.nonce)
+            .chain_update(&packet.data)
+            .finalize();
 
-        dst.put(&packet.nonce[..]);
-        dst.put(&packet.data[..]);
-        dst.put(&packet.checksum[..]);
+        dst.put_u32_le((len + 64) as u32);
+        dst.put_slice(&packet.nonce);
+        dst.put_slice(&packet.data);
+        dst.put_slice(&checksum);
 
         self.cipher_send.apply_keystream(&mut dst[buf_size..]);
 
@@ -39,42 +73,66 @@ impl Decoder for PacketCodec {
     type Error = anyhow::Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        let length = match self.next_len.take() {
-            Some(len) => len,
+        let frame = match self.frame.as_mut() {
+            Some(frame) => frame,
             None => {
                 if src.len() < 4 {
                     return Ok(None);
                 }
 
-                let mut length = src.split_to(4);
+                let mut length = [0; 4];
+                length.copy_from_slice(&src[..4]);
                 self.cipher_recv.apply_keystream(&mut length);
+                src.advance(4);
 
-                u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize
+                let length = u32::from_le_bytes(length) as usize;
+                if length < MIN_FRAME_SIZE {
+                    return Err(CodecError::FrameTooSmall(length).into());
+                }
+                if length > self.max_frame_size {
+                    return Err(CodecError::FrameTooLarge {
+                        length,
+                        max: self.max_frame_size,
+                    }
+                    .into());
+                }
+                src.reserve(length.saturating_sub(src.len()));
+
+                self.frame.insert(Frame {
+                    length,
+                    processed: 0,
+                    hasher: Sha256::new(),
+                })
             }
         };
 
-        if length < 64 {
-            bail!("small ADNL packet: {}", length);
-        }
+        let available = src.len().min(frame.length);
+        if available > frame.processed {
+            self.cipher_recv
+                .apply_keystream(&mut src[frame.processed..available]);
 
-        if src.len() < length {
-            src.reserve(length);
-            self.next_len.replace(length);
+            let hashed = available.min(frame.length - 32);
+            if hashed > frame.processed {
+                frame.hasher.update(&src[frame.processed..hashed]);
+            }
+            frame.processed = available;
+        }
 
+        if frame.processed < frame.length {
             return Ok(None);
         }
 
-        self.cipher_recv.apply_keystream(&mut src[0..length]);
-        let sha256: [u8; 32] = Sha256::digest(&src[..length - 32]).into();
-        if sha256 != src[length - 32..length] {
-            bail!("incorrect checksum for ADNL packet");
+        let Some(Frame { length, hasher, .. }) = self.frame.take() else {
+            unreachable!()
+        };
+        let data = src.split_to(length).freeze();
+        if hasher.finalize().as_slice() != &data[length - 32..] {
+            return Err(CodecError::IncorrectChecksum.into());
         }
 
-        let data = src.split_to(length);
         let packet = Packet {
             nonce: data[0..32].try_into()?,
-            data: data[32..length - 32].to_vec(),
-            checksum: data[length - 32..length].try_into()?,
+            data: data.slice(32..length - 32),
         };
 
         src.reserve(68); // min size of packet is 68
@@ -84,6 +142,10 @@ impl Decoder for PacketCodec {
 }
 
 impl PacketCodec {
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.max_frame_size = max_frame_size;
+    }
+
     pub fn from_aes_ctr_as_client(aes_ctr: AesCtr) -> Self {
         let bytes = aes_ctr.into_bytes();
 
@@ -96,7 +158,8 @@ impl PacketCodec {
         Self::from_bytes_as_server(&bytes)
     }
 
-    fn from_bytes_as_client(bytes: &[u8; 160]) -> Self {
+    /// Builds the codec from a raw 160-byte handshake basis, `AesCtr::into_bytes` layout.
+    pub fn from_bytes_as_client(bytes: &[u8; 160]) -> Self {
         let cipher_recv = Aes256Ctr128::new(
             GenericArray::from_slice(&bytes[0..32]),
             GenericArray::from_slice(&bytes[64..80]),
@@ -109,11 +172,12 @@ impl PacketCodec {
         Self {
             cipher_recv,
             cipher_send,
-            next_len: None,
+            frame: None,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
         }
     }
 
-    fn from_bytes_as_server(bytes: &[u8; 160]) -> Self {
+    pub fn from_bytes_as_server(bytes: &[u8; 160]) -> Self {
         let cipher_recv = Aes256Ctr128::new(
             GenericArray::from_slice(&bytes[32..64]),
             GenericArray::from_slice(&bytes[80..96]),
@@ -126,17 +190,19 @@ impl PacketCodec {
         Self {
             cipher_recv,
             cipher_send,
-            next_len: None,
+            frame: None,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::codec::PacketCodec;
+    use crate::codec::{CodecError, PacketCodec};
     use crate::packet::Packet;
     use anyhow::Result;
-    use tokio_util::bytes::{BufMut, BytesMut};
+    use proptest::prelude::*;
+    use tokio_util::bytes::{BufMut, Bytes, BytesMut};
     use tokio_util::codec::{Decoder, Encoder};
     use tracing_test::traced_test;
 
@@ -186,17 +252,140 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[traced_test]
+    fn decode_large_packet_byte_by_byte() -> Result<()> {
+        let packet = Packet::new((0..=255).cycle().take(70_000).collect::<Vec<u8>>());
+        let mut buf = BytesMut::new();
+        given_codec_server().encode(packet.clone(), &mut buf)?;
+        let data = buf.split().freeze();
+
+        let mut codec = given_codec_client();
+        let mut decoded = None;
+        for byte in data.iter() {
+            assert!(decoded.is_none());
+            buf.put_u8(*byte);
+            decoded = codec.decode(&mut buf)?;
+        }
+
+        assert_eq!(decoded, Some(packet));
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[traced_test]
+    fn decode_consecutive_packets() -> Result<()> {
+        let packets = [
+            Packet::new(vec![1; 12]),
+            Packet::empty(),
+            Packet::new(vec![2; 1024]),
+        ];
+        let mut server = given_codec_server();
+        let mut buf = BytesMut::new();
+        for packet in packets.iter() {
+            server.encode(packet.clone(), &mut buf)?;
+        }
+
+        let mut codec = given_codec_client();
+        let mut decoded = Vec::new();
+        while let Some(packet) = codec.decode(&mut buf)? {
+            decoded.push(packet);
+        }
+
+        assert_eq!(decoded, packets);
+
+        Ok(())
+    }
+
+    #[test]
+    #[traced_test]
+    fn decode_rejects_incorrect_checksum() -> Result<()> {
+        let mut buf = BytesMut::new();
+        given_codec_server().encode(Packet::new(vec![1; 12]), &mut buf)?;
+        let last = buf.len() - 1;
+        buf[last] ^= 1;
+
+        let actual = given_codec_client().decode(&mut buf);
+
+        assert!(actual.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[traced_test]
+    fn decode_rejects_frame_over_limit() -> Result<()> {
+        let mut buf = BytesMut::new();
+        given_codec_server().encode(Packet::new(vec![1; 1024]), &mut buf)?;
+        let mut codec = given_codec_client();
+        codec.set_max_frame_size(1000);
+
+        let actual = codec.decode(&mut buf).unwrap_err();
+
+        assert_eq!(
+            actual.downcast_ref::<CodecError>(),
+            Some(&CodecError::FrameTooLarge {
+                length: 1088,
+                max: 1000
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[traced_test]
+    fn encode_rejects_frame_over_limit() {
+        let mut codec = given_codec_server();
+        codec.set_max_frame_size(1000);
+
+        let actual = codec.encode(Packet::new(vec![1; 1024]), &mut BytesMut::new());
+
+        assert!(actual.is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn decode_arbitrary_bytes_never_panics(data in proptest::collection::vec(any::<u8>(), 0..4096)) {
+            let mut codec = given_codec_client();
+            codec.set_max_frame_size(2048);
+            let mut buf = BytesMut::from(&data[..]);
+
+            while let Ok(Some(_)) = codec.decode(&mut buf) {}
+        }
+
+        #[test]
+        fn decode_corrupted_frame_never_panics(
+            payload in proptest::collection::vec(any::<u8>(), 0..512),
+            position in any::<prop::sample::Index>(),
+            flip in 1..=255u8,
+            chunk in 1..64usize,
+        ) {
+            let mut frame = BytesMut::new();
+            given_codec_server().encode(Packet::new(payload), &mut frame).unwrap();
+            let position = position.index(frame.len());
+            frame[position] ^= flip;
+
+            let mut codec = given_codec_client();
+            let mut buf = BytesMut::new();
+            for part in frame.chunks(chunk) {
+                buf.put(part);
+                if codec.decode(&mut buf).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
     fn empty_packet() -> Packet {
         Packet {
             nonce: [
                 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
                 24, 25, 26, 27, 28, 29, 30, 31, 32,
             ],
-            data: vec![],
-            checksum: [
-                174, 33, 108, 46, 245, 36, 122, 55, 130, 193, 53, 239, 162, 121, 163, 228, 205,
-                198, 16, 148, 39, 15, 93, 43, 229, 140, 98, 4, 183, 166, 18, 201,
-            ],
+            data: Bytes::new(),
         }
     }
 
@@ -18,6 +18,11 @@ impl Connection {
         Self { inner }
     }
 
+    /// Frames declaring a larger length fail the stream instead of being buffered.
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.inner.codec_mut().set_max_frame_size(max_frame_size)
+    }
+
     pub fn get_ref(&self) -> &TcpStream {
         self.inner.get_ref()
     }
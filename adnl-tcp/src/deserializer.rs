@@ -1,12 +1,18 @@
 use crate::types::Int256;
-use anyhow::bail;
 use bytes::Buf;
+use std::string::FromUtf8Error;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DeserializerBoxedError {
     #[error("Unexpected constructor number: {0}")]
     UnexpectedConstructorNumber(u32),
+    #[error("Unexpected end of input: {needed} bytes needed, {remaining} remaining")]
+    UnexpectedEof { needed: usize, remaining: usize },
+    #[error("Trailing bytes after value: {0}")]
+    TrailingBytes(usize),
+    #[error(transparent)]
+    InvalidUtf8(#[from] FromUtf8Error),
     #[error(transparent)]
     DeserializeError(#[from] anyhow::Error),
 }
@@ -25,6 +31,7 @@ pub trait DeserializeBoxed: Deserialize {
     ) -> Result<Self, DeserializerBoxedError>;
 }
 
+/// Reads TL values from a byte slice, every read is bounds checked and never panics.
 pub struct Deserializer<'de> {
     input: &'de [u8],
 }
@@ -34,66 +41,92 @@ impl<'de> Deserializer<'de> {
         Deserializer { input }
     }
 
-    pub fn parse_constructor_numer(&mut self) -> anyhow::Result<u32> {
+    pub fn remaining(&self) -> usize {
+        self.input.len()
+    }
+
+    fn ensure(&self, needed: usize) -> Result<(), DeserializerBoxedError> {
+        if self.input.len() < needed {
+            return Err(DeserializerBoxedError::UnexpectedEof {
+                needed,
+                remaining: self.input.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn parse_constructor_numer(&mut self) -> Result<u32, DeserializerBoxedError> {
+        self.ensure(4)?;
+
         Ok(self.input.get_u32())
     }
 
-    pub fn parse_i31(&mut self) -> anyhow::Result<i32> {
-        Ok(self.input.get_i32_le() & 0x7fffffff)
+    pub fn parse_i31(&mut self) -> Result<i32, DeserializerBoxedError> {
+        Ok(self.parse_i32()? & 0x7fffffff)
     }
 
-    pub fn parse_i32(&mut self) -> anyhow::Result<i32> {
+    pub fn parse_i32(&mut self) -> Result<i32, DeserializerBoxedError> {
+        self.ensure(4)?;
+
         Ok(self.input.get_i32_le())
     }
 
-    pub fn parse_i64(&mut self) -> anyhow::Result<i64> {
+    pub fn parse_i64(&mut self) -> Result<i64, DeserializerBoxedError> {
+        self.ensure(8)?;
+
         Ok(self.input.get_i64_le())
     }
 
-    pub fn parse_i256(&mut self) -> anyhow::Result<Int256> {
+    pub fn parse_i128(&mut self) -> Result<i128, DeserializerBoxedError> {
+        self.ensure(16)?;
+
+        Ok(self.input.get_i128_le())
+    }
+
+    pub fn parse_i256(&mut self) -> Result<Int256, DeserializerBoxedError> {
+        self.ensure(32)?;
         let mut result: [u8; 32] = [0; 32];
         self.input.copy_to_slice(&mut result);
 
         Ok(result)
     }
 
-    pub fn parse_bytes(&mut self) -> anyhow::Result<crate::types::Bytes> {
-        let len = self.input.get_u8();
-        if len <= 253 {
-            let mut result = vec![0; len as usize];
-            self.input.copy_to_slice(&mut result);
-
-            let padding = (len + 1) % 4;
-            if padding > 0 {
-                self.input.advance(4 - padding as usize)
-            }
+    /// Parses vector length, allocations should be capped by [`Deserializer::remaining`].
+    pub fn parse_vector_len(&mut self) -> Result<usize, DeserializerBoxedError> {
+        Ok(self.parse_i31()? as usize)
+    }
 
-            Ok(result)
+    pub fn parse_bytes(&mut self) -> Result<crate::types::Bytes, DeserializerBoxedError> {
+        self.ensure(1)?;
+        let len = self.input.get_u8();
+        let (len, header) = if len <= 253 {
+            (len as usize, 1)
         } else {
+            self.ensure(3)?;
             let mut len: [u8; 4] = [0; 4];
             self.input.copy_to_slice(&mut (len[..3]));
-            let len = u32::from_le_bytes(len);
 
-            let mut result = vec![0; len as usize];
-            self.input.copy_to_slice(&mut result);
+            (u32::from_le_bytes(len) as usize, 4)
+        };
 
-            let padding = len % 4;
-            if padding > 0 {
-                self.input.advance(4 - padding as usize)
-            }
+        let padding = (4 - (len + header) % 4) % 4;
+        self.ensure(len + padding)?;
 
-            Ok(result)
-        }
+        let result = self.input[..len].to_vec();
+        self.input.advance(len + padding);
+
+        Ok(result)
     }
 
-    pub fn parse_string(&mut self) -> anyhow::Result<String> {
+    pub fn parse_string(&mut self) -> Result<String, DeserializerBoxedError> {
         let bytes = self.parse_bytes()?;
 
         Ok(String::from_utf8(bytes)?)
     }
 }
 
-pub fn from_bytes_boxed<T>(bytes: &[u8]) -> anyhow::Result<T>
+pub fn from_bytes_boxed<T>(bytes: &[u8]) -> Result<T, DeserializerBoxedError>
 where
     T: DeserializeBoxed,
 {
@@ -103,13 +136,112 @@ where
     if deserializer.input.is_empty() {
         Ok(t)
     } else {
-        bail!("input is not empty")
+        Err(DeserializerBoxedError::TrailingBytes(
+            deserializer.input.len(),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serializer::to_bytes_boxed;
+    use crate::types::{
+        AdnlMessageAnswer, AdnlMessageQuery, TcpAuthentificate, TcpAuthentificationComplete,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn deserialize_truncated_i64() {
+        let mut deserializer = Deserializer::from_bytes(&[1, 2, 3]);
+
+        let actual = deserializer.parse_i64();
+
+        assert!(matches!(
+            actual,
+            Err(DeserializerBoxedError::UnexpectedEof {
+                needed: 8,
+                remaining: 3
+            })
+        ))
+    }
+
+    #[test]
+    fn deserialize_bytes_length_over_input() {
+        let buf = vec![254, 255, 255, 255, 1, 2, 3, 4];
+        let mut deserializer = Deserializer::from_bytes(&buf);
+
+        let actual = deserializer.parse_bytes();
+
+        assert!(matches!(
+            actual,
+            Err(DeserializerBoxedError::UnexpectedEof {
+                needed: 16777216,
+                remaining: 4
+            })
+        ))
+    }
+
+    #[test]
+    fn deserialize_vector_length_over_input() {
+        let mut buf = vec![0xff, 0xff, 0xff, 0x7f];
+        buf.extend_from_slice(&[0; 32]);
+        let mut deserializer = Deserializer::from_bytes(&buf);
+
+        let actual = Vec::<Int256>::deserialize(&mut deserializer);
+
+        assert!(matches!(
+            actual,
+            Err(DeserializerBoxedError::UnexpectedEof { .. })
+        ))
+    }
+
+    #[test]
+    fn deserialize_trailing_bytes() {
+        let mut buf = to_bytes_boxed(&AdnlMessageAnswer {
+            query_id: [1; 32],
+            answer: vec![1, 2, 3],
+        });
+        buf.extend_from_slice(&[0; 4]);
+
+        let actual = from_bytes_boxed::<AdnlMessageAnswer>(&buf);
+
+        assert!(matches!(
+            actual,
+            Err(DeserializerBoxedError::TrailingBytes(4))
+        ))
+    }
+
+    proptest! {
+        #[test]
+        fn deserialize_arbitrary_bytes_never_panics(data in proptest::collection::vec(any::<u8>(), 0..1024)) {
+            let _ = from_bytes_boxed::<AdnlMessageQuery>(&data);
+            let _ = from_bytes_boxed::<AdnlMessageAnswer>(&data);
+            let _ = from_bytes_boxed::<TcpAuthentificate>(&data);
+            let _ = from_bytes_boxed::<TcpAuthentificationComplete>(&data);
+            let _ = Vec::<Int256>::deserialize(&mut Deserializer::from_bytes(&data));
+            let _ = Deserializer::from_bytes(&data).parse_string();
+        }
+
+        #[test]
+        fn deserialize_truncated_answer_fails(
+            query_id in any::<[u8; 32]>(),
+            answer in proptest::collection::vec(any::<u8>(), 0..600),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let bytes = to_bytes_boxed(&AdnlMessageAnswer { query_id, answer: answer.clone() });
+            let cut = cut.index(bytes.len());
+
+            let actual = from_bytes_boxed::<AdnlMessageAnswer>(&bytes[..cut]);
+
+            prop_assert!(
+                matches!(actual, Err(DeserializerBoxedError::UnexpectedEof { .. })),
+                "unexpected result: {:?}",
+                actual
+            );
+            prop_assert_eq!(from_bytes_boxed::<AdnlMessageAnswer>(&bytes)?.answer, answer);
+        }
+    }
 
     #[test]
     fn deserialize_bytes_length255() {
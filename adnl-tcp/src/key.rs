@@ -1,8 +1,17 @@
+use anyhow::{anyhow, bail, Context};
+use base64::Engine;
 use ed25519_dalek::hazmat::ExpandedSecretKey;
-use ed25519_dalek::{SigningKey, VerifyingKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::ops::Mul;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::Path;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Ed25519KeyId([u8; 32]);
 
 impl Ed25519KeyId {
@@ -28,28 +37,118 @@ impl Ed25519KeyId {
     pub fn as_slice(&self) -> &[u8] {
         self.0.as_slice()
     }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl Display for Ed25519KeyId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
 }
 
 pub struct Ed25519Key {
     id: Ed25519KeyId,
+    private_key: SigningKey,
     pub_key: VerifyingKey,
     exp_key: ExpandedSecretKey,
 }
 
 impl Ed25519Key {
+    /// `pk.ed25519` constructor, prefixes the seed in key files
+    const KEY_TYPE: [u8; 4] = [0x17, 0x23, 0x68, 0x49];
+
     pub fn generate() -> Self {
-        let private_key = SigningKey::generate(&mut rand::thread_rng());
+        Self::from_signing_key(SigningKey::generate(&mut rand::thread_rng()))
+    }
+
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        Self::from_signing_key(SigningKey::from_bytes(seed))
+    }
+
+    pub fn from_base64(seed: &str) -> anyhow::Result<Self> {
+        let seed = base64::engine::general_purpose::STANDARD.decode(seed.trim())?;
+        let seed: &[u8; 32] = seed
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("expected 32 bytes seed, got {} bytes", seed.len()))?;
+
+        Ok(Self::from_seed(seed))
+    }
+
+    /// Reads a key file: `pk.ed25519` serialized key as written by [`Ed25519Key::save`],
+    /// raw 32 bytes seed or base64 encoded seed.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content =
+            std::fs::read(path).with_context(|| format!("cannot read key {}", path.display()))?;
+
+        match content.len() {
+            36 if content[0..4] == Self::KEY_TYPE => {
+                Ok(Self::from_seed(content[4..36].try_into()?))
+            }
+            32 => Ok(Self::from_seed(content.as_slice().try_into()?)),
+            _ => match std::str::from_utf8(&content) {
+                Ok(content) => Self::from_base64(content)
+                    .with_context(|| format!("malformed key {}", path.display())),
+                Err(_) => bail!("malformed key {}", path.display()),
+            },
+        }
+    }
+
+    /// Writes the key as serialized `pk.ed25519`, the format of TON key files.
+    /// On unix the file is readable by the owner only.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let mut content = Vec::with_capacity(36);
+        content.extend_from_slice(&Self::KEY_TYPE);
+        content.extend_from_slice(self.seed());
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let write = || -> std::io::Result<()> {
+            let mut file = options.open(path)?;
+            // an existing file keeps its permissions otherwise
+            #[cfg(unix)]
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+
+            file.write_all(&content)
+        };
+
+        write().with_context(|| format!("cannot write key {}", path.display()))
+    }
+
+    fn from_signing_key(private_key: SigningKey) -> Self {
         let pub_key = private_key.verifying_key();
         let id = Ed25519KeyId::from_public_key_bytes(pub_key.as_bytes());
         let exp_key: ExpandedSecretKey = private_key.as_bytes().into();
 
         Self {
             id,
+            private_key,
             pub_key,
             exp_key,
         }
     }
 
+    pub fn seed(&self) -> &[u8; 32] {
+        self.private_key.as_bytes()
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.seed())
+    }
+
+    /// Public key in the base64 form used by `id.key` of global config.
+    pub fn public_key_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.pub_key.as_bytes())
+    }
+
     pub fn id(&self) -> &Ed25519KeyId {
         &self.id
     }
@@ -61,4 +160,107 @@ impl Ed25519Key {
     pub fn expanded_secret_key(&self) -> &ExpandedSecretKey {
         &self.exp_key
     }
+
+    /// x25519 shared secret with the owner of `public_key`.
+    pub fn shared_secret(&self, public_key: &VerifyingKey) -> [u8; 32] {
+        public_key
+            .to_montgomery()
+            .mul(self.exp_key.scalar)
+            .to_bytes()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.private_key.sign(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_from_seed_is_stable() {
+        let seed = [7u8; 32];
+
+        let lhs = Ed25519Key::from_seed(&seed);
+        let rhs = Ed25519Key::from_seed(&seed);
+
+        assert_eq!(lhs.id(), rhs.id());
+        assert_eq!(lhs.public_key(), rhs.public_key());
+        assert_eq!(lhs.seed(), &seed);
+    }
+
+    #[test]
+    fn key_base64_roundtrip() -> anyhow::Result<()> {
+        let key = Ed25519Key::generate();
+
+        let actual = Ed25519Key::from_base64(&key.to_base64())?;
+
+        assert_eq!(actual.id(), key.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_base64_wrong_length() {
+        let actual = Ed25519Key::from_base64("aF91CuUHuuOv9rm2W5+O/4h38M3sRm40DtSdRxQh");
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn key_file_roundtrip() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("adnl-key-{}", rand::random::<u64>()));
+        let key = Ed25519Key::generate();
+
+        key.save(&path)?;
+        let content = std::fs::read(&path)?;
+        let actual = Ed25519Key::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(content.len(), 36);
+        assert_eq!(actual.id(), key.id());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_file_is_private() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("adnl-key-{}", rand::random::<u64>()));
+        std::fs::write(&path, [])?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))?;
+
+        Ed25519Key::generate().save(&path)?;
+        let mode = std::fs::metadata(&path)?.permissions().mode();
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(mode & 0o777, 0o600);
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_file_raw_and_base64() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("adnl-key-{}", rand::random::<u64>()));
+        let key = Ed25519Key::generate();
+
+        std::fs::write(&path, key.seed())?;
+        let raw = Ed25519Key::load(&path)?;
+        std::fs::write(&path, format!("{}\n", key.to_base64()))?;
+        let base64 = Ed25519Key::load(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(raw.id(), key.id());
+        assert_eq!(base64.id(), key.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_id_display() {
+        let id = Ed25519KeyId::from_slice(&[0xab; 32]);
+
+        assert_eq!(id.to_string(), "ab".repeat(32));
+    }
 }
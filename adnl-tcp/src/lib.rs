@@ -1,13 +1,22 @@
-mod aes_ctr;
+pub mod aes_ctr;
 #[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod codec;
+#[cfg(not(feature = "bench"))]
 mod codec;
 pub mod connection;
 pub mod deserializer;
-mod key;
+pub mod key;
 pub mod packet;
 pub mod ping;
+#[cfg(feature = "client")]
+pub mod proxy;
 pub mod serializer;
 #[cfg(feature = "server")]
 pub mod server;
 pub mod types;
+
+#[cfg(feature = "derive")]
+pub use adnl_tcp_derive::{TlDeserialize, TlSerialize};
@@ -1,32 +1,23 @@
+use bytes::Bytes;
 use rand::random;
-use sha2::{digest::Update, Digest, Sha256};
 use std::fmt::{Debug, Formatter};
 
-#[derive(PartialEq, Eq)]
+/// ADNL-TCP frame payload, the checksum is computed by the codec while the frame is written.
+#[derive(Clone, PartialEq, Eq)]
 pub struct Packet {
     pub nonce: [u8; 32],
-    pub checksum: [u8; 32],
-    pub data: Vec<u8>,
+    pub data: Bytes,
 }
 
 impl Packet {
     pub fn empty() -> Self {
-        Self::new(vec![])
+        Self::new(Bytes::new())
     }
 
-    pub fn new(data: Vec<u8>) -> Self {
-        let nonce: [u8; 32] = random();
-
-        let checksum: [u8; 32] = Sha256::default()
-            .chain(nonce)
-            .chain(&data)
-            .finalize()
-            .into();
-
+    pub fn new(data: impl Into<Bytes>) -> Self {
         Self {
-            nonce,
-            data,
-            checksum,
+            nonce: random(),
+            data: data.into(),
         }
     }
 
@@ -42,7 +33,7 @@ impl Packet {
 impl Debug for Packet {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Packet")
-            .field("checksum", &hex::encode(self.checksum))
+            .field("nonce", &hex::encode(self.nonce))
             .field("data", &hex::encode(&self.data))
             .field("length", &self.data.len())
             .finish()
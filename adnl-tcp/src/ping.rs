@@ -8,6 +8,12 @@ pub fn ping_packet() -> Packet {
     Packet::new([&crc32, nonce.to_le_bytes().as_slice()].concat())
 }
 
+pub fn pong_packet(ping: &Packet) -> Packet {
+    let crc32 = [0x03, 0xFB, 0x69, 0xDC];
+
+    Packet::new([&crc32, &ping.data[4..12]].concat())
+}
+
 pub fn is_ping_packet(packet: &Packet) -> bool {
     packet.data.len() == 12 && packet.data.starts_with(&[0x9a, 0x2b, 0x08, 0x4d])
 }
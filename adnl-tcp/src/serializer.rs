@@ -1,5 +1,6 @@
 use crate::types::Int256;
 use bytes::BufMut;
+use std::convert::Infallible;
 use std::fmt::Debug;
 
 pub trait Serialize {
@@ -10,6 +11,12 @@ pub trait SerializeBoxed {
     fn serialize_boxed(&self, se: &mut Serializer);
 }
 
+impl SerializeBoxed for Infallible {
+    fn serialize_boxed(&self, _: &mut Serializer) {
+        match *self {}
+    }
+}
+
 #[derive(Debug)]
 pub struct Serializer {
     output: Vec<u8>,
@@ -36,6 +43,10 @@ impl Serializer {
         self.output.put_i64_le(val)
     }
 
+    pub fn write_i128(&mut self, val: i128) {
+        self.output.put_i128_le(val)
+    }
+
     pub fn write_i256(&mut self, val: &Int256) {
         self.output.put_slice(val)
     }
@@ -71,7 +82,6 @@ impl Serializer {
                 self.output.put_u8(254);
                 self.output
                     .put_slice(&(val.len() as u32).to_le_bytes()[..3]);
-                self.output.put_u8(val.len() as u8);
                 self.output.put_slice(val);
             }
         }
@@ -104,4 +114,16 @@ mod tests {
 
         assert_eq!(serializer.output, expected)
     }
+
+    #[test]
+    fn serialize_bytes_length256() {
+        let mut serializer = Serializer { output: Vec::new() };
+        let value = vec![1; 256];
+        let mut expected = vec![254, 0, 1, 0];
+        expected.append(&mut vec![1; 256]);
+
+        serializer.write_bytes(&value);
+
+        assert_eq!(serializer.output, expected)
+    }
 }
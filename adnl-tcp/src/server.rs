@@ -1,18 +1,41 @@
 use crate::aes_ctr::AesCtr;
-use crate::codec::PacketCodec;
+use crate::codec::{PacketCodec, DEFAULT_MAX_FRAME_SIZE};
 use crate::connection::Connection;
+use crate::deserializer::from_bytes_boxed;
 use crate::key::{Ed25519Key, Ed25519KeyId};
 use crate::packet::Packet;
+use crate::ping::{is_ping_packet, pong_packet};
+use crate::serializer::{to_bytes_boxed, SerializeBoxed};
+use crate::types::{
+    AdnlMessageAnswer, AdnlMessageQuery, Bytes, TcpAuthentificate, TcpAuthentificationComplete,
+    TcpAuthentificationNonce,
+};
 use anyhow::bail;
-use ed25519_dalek::VerifyingKey;
-use futures::SinkExt;
+use ed25519_dalek::{Signature, VerifyingKey};
+use futures::stream::FuturesUnordered;
+use futures::{SinkExt, StreamExt};
+use rand::random;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::future::{poll_fn, Future};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::AsyncReadExt;
-use tokio::net::TcpStream;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::select;
+use tokio::time::timeout;
 use tokio_util::codec::Framed;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tower::Service;
 
 pub struct Server;
 
 impl Server {
+    pub fn builder(server_key: Ed25519Key) -> ServerBuilder {
+        ServerBuilder::new(server_key)
+    }
+
     pub async fn handshake(
         mut stream: TcpStream,
         server_key: &Ed25519Key,
@@ -45,12 +68,298 @@ impl Server {
     }
 }
 
+pub struct ServerBuilder {
+    server_key: Arc<Ed25519Key>,
+    handshake_timeout: Duration,
+    max_concurrent_queries: usize,
+    max_frame_size: usize,
+    allowed_clients: Option<Arc<HashSet<[u8; 32]>>>,
+}
+
+impl ServerBuilder {
+    fn new(server_key: Ed25519Key) -> Self {
+        Self {
+            server_key: Arc::new(server_key),
+            handshake_timeout: Duration::from_secs(5),
+            max_concurrent_queries: 256,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            allowed_clients: None,
+        }
+    }
+
+    pub fn set_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+
+        self
+    }
+
+    /// Limits the number of queries processed at once within a single connection,
+    /// the connection is not read any further until one of them is answered.
+    pub fn set_max_concurrent_queries(mut self, max_concurrent_queries: usize) -> Self {
+        self.max_concurrent_queries = max_concurrent_queries.max(1);
+
+        self
+    }
+
+    /// Closes connections which declare frames larger than `max_frame_size` bytes.
+    pub fn set_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+
+        self
+    }
+
+    /// Serves only clients authenticated with one of the given public keys,
+    /// a query before successful `tcp.authentificate` closes the connection.
+    pub fn set_allowed_clients(mut self, public_keys: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        self.allowed_clients = Some(Arc::new(public_keys.into_iter().collect()));
+
+        self
+    }
+
+    pub async fn serve<S>(self, listener: TcpListener, service: S) -> anyhow::Result<()>
+    where
+        S: Service<Bytes, Response = Bytes> + Clone + Send + 'static,
+        S::Error: SerializeBoxed + Debug + Send,
+        S::Future: Send,
+    {
+        self.serve_with_shutdown(listener, service, std::future::pending())
+            .await
+    }
+
+    /// Accepts connections until `signal` resolves, then stops reading new queries,
+    /// waits for in-flight queries to be answered and closes all connections.
+    pub async fn serve_with_shutdown<S, F>(
+        self,
+        listener: TcpListener,
+        service: S,
+        signal: F,
+    ) -> anyhow::Result<()>
+    where
+        S: Service<Bytes, Response = Bytes> + Clone + Send + 'static,
+        S::Error: SerializeBoxed + Debug + Send,
+        S::Future: Send,
+        F: Future<Output = ()>,
+    {
+        let shutdown = CancellationToken::new();
+        let tracker = TaskTracker::new();
+
+        tokio::pin!(signal);
+        loop {
+            select! {
+                _ = &mut signal => {
+                    tracing::info!("shutdown signal received");
+                    break;
+                },
+                accepted = listener.accept() => {
+                    let (stream, addr) = match accepted {
+                        Ok(accepted) => accepted,
+                        Err(error) => {
+                            tracing::error!(error = ?error, "accept error");
+                            continue;
+                        }
+                    };
+
+                    let server_key = self.server_key.clone();
+                    let handshake_timeout = self.handshake_timeout;
+                    let max_concurrent_queries = self.max_concurrent_queries;
+                    let max_frame_size = self.max_frame_size;
+                    let allowed_clients = self.allowed_clients.clone();
+                    let service = service.clone();
+                    let cancellation_token = shutdown.child_token();
+
+                    tracker.spawn(async move {
+                        let connection = select! {
+                            _ = cancellation_token.cancelled() => return,
+                            connection = timeout(handshake_timeout, Server::handshake(stream, &server_key)) => connection
+                        };
+                        let mut connection = match connection {
+                            Ok(Ok((_, connection))) => connection,
+                            Ok(Err(error)) => {
+                                tracing::warn!(addr = ?addr, error = ?error, "handshake failed");
+                                return;
+                            }
+                            Err(_) => {
+                                tracing::warn!(addr = ?addr, "handshake timed out");
+                                return;
+                            }
+                        };
+
+                        connection.set_max_frame_size(max_frame_size);
+
+                        tracing::debug!(addr = ?addr, "connection accepted");
+                        if let Err(error) = serve_connection(connection, service, max_concurrent_queries, allowed_clients, cancellation_token).await {
+                            tracing::warn!(addr = ?addr, error = ?error, "connection error");
+                        }
+                        tracing::debug!(addr = ?addr, "connection closed");
+                    });
+                }
+            }
+        }
+
+        shutdown.cancel();
+        tracker.close();
+        tracker.wait().await;
+
+        Ok(())
+    }
+}
+
+async fn serve_connection<S>(
+    mut connection: Connection,
+    mut service: S,
+    max_concurrent_queries: usize,
+    allowed_clients: Option<Arc<HashSet<[u8; 32]>>>,
+    cancellation_token: CancellationToken,
+) -> anyhow::Result<()>
+where
+    S: Service<Bytes, Response = Bytes>,
+    S::Error: SerializeBoxed + Debug,
+{
+    let mut in_flight = FuturesUnordered::new();
+    let mut authentication = Authentication::default();
+    // query waiting for the service to be ready, answers are still sent meanwhile
+    let mut pending: Option<AdnlMessageQuery> = None;
+
+    loop {
+        select! {
+            Some((query_id, answer)) = in_flight.next() => {
+                send_answer(&mut connection, query_id, answer).await?;
+            },
+            _ = cancellation_token.cancelled() => break,
+            ready = poll_fn(|cx| service.poll_ready(cx)), if pending.is_some() => {
+                let Some(query) = pending.take() else {
+                    unreachable!()
+                };
+                let future = ready.map(|_| service.call(query.query));
+                in_flight.push(async move {
+                    match future {
+                        Ok(future) => (query.query_id, future.await),
+                        Err(error) => (query.query_id, Err(error)),
+                    }
+                });
+            },
+            packet = connection.next(), if pending.is_none() && in_flight.len() < max_concurrent_queries => {
+                let Some(packet) = packet else {
+                    return Ok(());
+                };
+                let packet = packet?;
+
+                if packet.is_empty() {
+                    continue;
+                }
+                if is_ping_packet(&packet) {
+                    connection.send(pong_packet(&packet)).await?;
+                    continue;
+                }
+                if let Ok(request) = from_bytes_boxed::<TcpAuthentificate>(&packet.data) {
+                    let response = authentication.nonce(request)?;
+                    connection.send(Packet::new(to_bytes_boxed(&response))).await?;
+                    continue;
+                }
+                if let Ok(request) = from_bytes_boxed::<TcpAuthentificationComplete>(&packet.data) {
+                    let client_key = authentication.complete(request)?;
+                    if allowed_clients.as_ref().is_some_and(|allowed| !allowed.contains(client_key.as_bytes())) {
+                        bail!("client {} is not allowed", Ed25519KeyId::from_public_key_bytes(client_key.as_bytes()));
+                    }
+                    continue;
+                }
+                if allowed_clients.is_some() && authentication.client_key.is_none() {
+                    bail!("query from unauthenticated client");
+                }
+
+                match from_bytes_boxed::<AdnlMessageQuery>(&packet.data) {
+                    Ok(query) => pending = Some(query),
+                    Err(error) => tracing::warn!(error = ?error, "malformed query skipped"),
+                }
+            }
+        }
+    }
+
+    if let Some(query) = pending {
+        let answer = match poll_fn(|cx| service.poll_ready(cx)).await {
+            Ok(()) => service.call(query.query).await,
+            Err(error) => Err(error),
+        };
+        send_answer(&mut connection, query.query_id, answer).await?;
+    }
+    while let Some((query_id, answer)) = in_flight.next().await {
+        send_answer(&mut connection, query_id, answer).await?;
+    }
+
+    connection.close().await
+}
+
+#[derive(Default)]
+struct Authentication {
+    nonce: Option<Bytes>,
+    client_key: Option<VerifyingKey>,
+}
+
+impl Authentication {
+    fn nonce(&mut self, request: TcpAuthentificate) -> anyhow::Result<TcpAuthentificationNonce> {
+        if self.nonce.is_some() || self.client_key.is_some() {
+            bail!("duplicate authentification");
+        }
+        if request.nonce.is_empty() || request.nonce.len() > 512 {
+            bail!("invalid authentification nonce");
+        }
+
+        let nonce: [u8; 32] = random();
+        self.nonce = Some([request.nonce.as_slice(), nonce.as_slice()].concat());
+
+        Ok(TcpAuthentificationNonce {
+            nonce: nonce.to_vec(),
+        })
+    }
+
+    fn complete(&mut self, request: TcpAuthentificationComplete) -> anyhow::Result<VerifyingKey> {
+        let Some(nonce) = self.nonce.take() else {
+            bail!("unexpected authentification complete");
+        };
+
+        let client_key = VerifyingKey::from_bytes(&request.key.key)?;
+        let signature = Signature::from_slice(&request.signature)?;
+        client_key.verify_strict(&nonce, &signature)?;
+
+        tracing::debug!(client_key_id = %Ed25519KeyId::from_public_key_bytes(client_key.as_bytes()), "client authenticated");
+        self.client_key = Some(client_key);
+
+        Ok(client_key)
+    }
+}
+
+/// Errors of the service are sent as the answer, so the service decides which
+/// error object the client gets, e.g. `liteServer.error`.
+async fn send_answer<E: SerializeBoxed + Debug>(
+    connection: &mut Connection,
+    query_id: [u8; 32],
+    answer: Result<Bytes, E>,
+) -> anyhow::Result<()> {
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(error) => {
+            tracing::warn!(query_id = hex::encode(query_id), error = ?error, "query failed");
+
+            to_bytes_boxed(&error)
+        }
+    };
+    let answer = AdnlMessageAnswer { query_id, answer };
+
+    connection.send(Packet::new(to_bytes_boxed(&answer))).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::client::Client;
+    use crate::client::{Client, ServerKey};
+    use crate::ping::{is_pong_packet, ping_packet};
+    use crate::serializer::Serializer;
+    use crate::types::PubEd25519;
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
     use std::sync::OnceLock;
     use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
 
     #[tokio::test]
     #[tracing_test::traced_test]
@@ -69,6 +378,260 @@ mod tests {
         assert!(connected.is_ok());
     }
 
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_answers_ping() -> anyhow::Result<()> {
+        let (mut client, _shutdown) = given_echo_server().await?;
+
+        client.send(ping_packet()).await?;
+        let received = client.next().await.unwrap()?;
+
+        assert!(is_pong_packet(&received));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_answers_query() -> anyhow::Result<()> {
+        let (mut client, _shutdown) = given_echo_server().await?;
+        let query = AdnlMessageQuery {
+            query_id: [7; 32],
+            query: vec![1, 2, 3, 4],
+        };
+
+        client.send(Packet::new(to_bytes_boxed(&query))).await?;
+        let received = client.next().await.unwrap()?;
+        let answer = from_bytes_boxed::<AdnlMessageAnswer>(&received.data)?;
+
+        assert_eq!(
+            answer,
+            AdnlMessageAnswer {
+                query_id: [7; 32],
+                answer: vec![4, 3, 2, 1]
+            }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_skips_malformed_query() -> anyhow::Result<()> {
+        let (mut client, _shutdown) = given_echo_server().await?;
+
+        client.send(Packet::new(vec![1, 2, 3, 4, 5])).await?;
+        client.send(given_query()).await?;
+        let received = client.next().await.unwrap()?;
+        let answer = from_bytes_boxed::<AdnlMessageAnswer>(&received.data)?;
+
+        assert_eq!(answer.answer, vec![4, 3, 2, 1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_answers_while_service_is_busy() -> anyhow::Result<()> {
+        let service = tower::limit::ConcurrencyLimit::new(tower::service_fn(echo), 1);
+        let (mut client, _shutdown) = given_server(service).await?;
+
+        for query_id in 0..3 {
+            let query = AdnlMessageQuery {
+                query_id: [query_id; 32],
+                query: vec![query_id],
+            };
+            client.send(Packet::new(to_bytes_boxed(&query))).await?;
+        }
+        let mut answered = Vec::new();
+        for _ in 0..3 {
+            let received = timeout(Duration::from_secs(5), client.next())
+                .await?
+                .unwrap()?;
+            answered.push(from_bytes_boxed::<AdnlMessageAnswer>(&received.data)?.query_id[0]);
+        }
+        answered.sort();
+
+        assert_eq!(answered, vec![0, 1, 2]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_answers_failed_query_with_error() -> anyhow::Result<()> {
+        let service = tower::service_fn(|_: Bytes| async {
+            Err::<Bytes, _>(Failure("not implemented".to_owned()))
+        });
+        let (mut client, _shutdown) = given_server(service).await?;
+
+        client.send(given_query()).await?;
+        let received = client.next().await.unwrap()?;
+        let answer = from_bytes_boxed::<AdnlMessageAnswer>(&received.data)?;
+
+        assert_eq!(answer.query_id, [7; 32]);
+        assert_eq!(
+            answer.answer,
+            to_bytes_boxed(&Failure("not implemented".to_owned()))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_graceful_shutdown() -> anyhow::Result<()> {
+        let (mut client, shutdown) = given_echo_server().await?;
+
+        shutdown.send(()).unwrap();
+        let received = client.next().await;
+
+        assert!(received.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_answers_authenticated_client() -> anyhow::Result<()> {
+        let client_key = Ed25519Key::generate();
+        let (addr, server_key, _shutdown) = given_echo_server_with(|builder| {
+            builder.set_allowed_clients([*client_key.public_key().as_bytes()])
+        })
+        .await?;
+        let mut client = Client::connect_authenticated(addr, server_key, &client_key).await?;
+
+        client.send(given_query()).await?;
+        let received = client.next().await.unwrap()?;
+        let answer = from_bytes_boxed::<AdnlMessageAnswer>(&received.data)?;
+
+        assert_eq!(answer.answer, vec![4, 3, 2, 1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_rejects_unauthenticated_client() -> anyhow::Result<()> {
+        let (addr, server_key, _shutdown) = given_echo_server_with(|builder| {
+            builder.set_allowed_clients([*Ed25519Key::generate().public_key().as_bytes()])
+        })
+        .await?;
+        let mut client = Client::connect(addr, server_key).await?;
+
+        client.send(given_query()).await?;
+        let received = client.next().await;
+
+        assert!(received.is_none_or(|packet| packet.is_err()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn server_rejects_not_allowed_client() -> anyhow::Result<()> {
+        let (addr, server_key, _shutdown) = given_echo_server_with(|builder| {
+            builder.set_allowed_clients([*Ed25519Key::generate().public_key().as_bytes()])
+        })
+        .await?;
+        let client = Client::connect_authenticated(addr, server_key, &Ed25519Key::generate()).await;
+
+        assert!(client.is_err_and(|e| e.to_string().contains("authentification rejected")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn authentication_rejects_wrong_signature() -> anyhow::Result<()> {
+        let client_key = Ed25519Key::generate();
+        let mut authentication = Authentication::default();
+
+        let nonce = authentication.nonce(TcpAuthentificate { nonce: vec![1; 32] })?;
+        let actual = authentication.complete(TcpAuthentificationComplete {
+            key: PubEd25519 {
+                key: *client_key.public_key().as_bytes(),
+            },
+            signature: client_key.sign(&nonce.nonce).to_vec(),
+        });
+
+        assert!(actual.is_err());
+        assert!(authentication.client_key.is_none());
+
+        Ok(())
+    }
+
+    fn given_query() -> Packet {
+        Packet::new(to_bytes_boxed(&AdnlMessageQuery {
+            query_id: [7; 32],
+            query: vec![1, 2, 3, 4],
+        }))
+    }
+
+    async fn given_echo_server() -> anyhow::Result<(Connection, oneshot::Sender<()>)> {
+        given_server(tower::service_fn(echo)).await
+    }
+
+    async fn given_server<S>(service: S) -> anyhow::Result<(Connection, oneshot::Sender<()>)>
+    where
+        S: Service<Bytes, Response = Bytes> + Clone + Send + 'static,
+        S::Error: SerializeBoxed + Debug + Send,
+        S::Future: Send,
+    {
+        let (addr, server_key, shutdown) = given_server_with(|builder| builder, service).await?;
+
+        let client = Client::connect(addr, server_key).await?;
+
+        Ok((client, shutdown))
+    }
+
+    #[derive(Debug)]
+    struct Failure(String);
+
+    impl SerializeBoxed for Failure {
+        fn serialize_boxed(&self, se: &mut Serializer) {
+            se.write_constructor_number(0xdeadbeef);
+            se.write_string(&self.0);
+        }
+    }
+
+    async fn echo(mut query: Bytes) -> Result<Bytes, Infallible> {
+        query.reverse();
+
+        Ok(query)
+    }
+
+    async fn given_echo_server_with(
+        configure: impl FnOnce(ServerBuilder) -> ServerBuilder,
+    ) -> anyhow::Result<(SocketAddr, ServerKey, oneshot::Sender<()>)> {
+        given_server_with(configure, tower::service_fn(echo)).await
+    }
+
+    async fn given_server_with<S>(
+        configure: impl FnOnce(ServerBuilder) -> ServerBuilder,
+        service: S,
+    ) -> anyhow::Result<(SocketAddr, ServerKey, oneshot::Sender<()>)>
+    where
+        S: Service<Bytes, Response = Bytes> + Clone + Send + 'static,
+        S::Error: SerializeBoxed + Debug + Send,
+        S::Future: Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let server_key = Ed25519Key::generate();
+        let server_public_key = *server_key.public_key().as_bytes();
+        tokio::spawn(configure(Server::builder(server_key)).serve_with_shutdown(
+            listener,
+            service,
+            async move {
+                let _ = rx.await;
+            },
+        ));
+
+        Ok((addr, server_public_key, tx))
+    }
+
     static SERVER_KEY: OnceLock<Ed25519Key> = OnceLock::new();
 
     fn provided_server_key() -> &'static Ed25519Key {
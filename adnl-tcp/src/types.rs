@@ -1,5 +1,5 @@
 use crate::deserializer::{Deserialize, DeserializeBoxed, Deserializer, DeserializerBoxedError};
-use crate::serializer::{Serialize, Serializer};
+use crate::serializer::{Serialize, SerializeBoxed, Serializer};
 
 pub trait Functional {
     type Result;
@@ -33,8 +33,8 @@ impl Serialize for Vector<Int256> {
 
 impl Deserialize for Vector<Int256> {
     fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
-        let len = de.parse_i31()?;
-        let mut buf = Vec::with_capacity(len as usize);
+        let len = de.parse_vector_len()?;
+        let mut buf = Vec::with_capacity(len.min(de.remaining()));
         for _ in 0..len {
             let val = de.parse_i256()?;
             buf.push(val)
@@ -56,8 +56,8 @@ impl Serialize for Vector<Int32> {
 
 impl Deserialize for Vector<Int32> {
     fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
-        let len = de.parse_i31()?;
-        let mut buf = Vec::with_capacity(len as usize);
+        let len = de.parse_vector_len()?;
+        let mut buf = Vec::with_capacity(len.min(de.remaining()));
         for _ in 0..len {
             let val = de.parse_i32()?;
             buf.push(val)
@@ -79,8 +79,8 @@ impl Serialize for Vector<Int64> {
 
 impl Deserialize for Vector<Int64> {
     fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
-        let len = de.parse_i31()?;
-        let mut buf = Vec::with_capacity(len as usize);
+        let len = de.parse_vector_len()?;
+        let mut buf = Vec::with_capacity(len.min(de.remaining()));
         for _ in 0..len {
             let val = de.parse_i64()?;
             buf.push(val)
@@ -107,8 +107,8 @@ where
     T: Deserialize,
 {
     fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
-        let len = de.parse_i31()?;
-        let mut buf = Vec::with_capacity(len as usize);
+        let len = de.parse_vector_len()?;
+        let mut buf = Vec::with_capacity(len.min(de.remaining()));
         for _ in 0..len {
             let val = T::deserialize(de)?;
             buf.push(val)
@@ -144,9 +144,304 @@ where
             Err(DeserializerBoxedError::UnexpectedConstructorNumber { .. }) => {
                 Ok(Err(E::deserialize_boxed(constructor_number, de)?))
             }
-            Err(DeserializerBoxedError::DeserializeError(e)) => {
-                Err(DeserializerBoxedError::DeserializeError(e))
-            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.query query_id:int256 query:bytes = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageQuery {
+    pub query_id: Int256,
+    pub query: Bytes,
+}
+
+impl AdnlMessageQuery {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x7af98bb4;
+}
+
+impl Serialize for AdnlMessageQuery {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.query_id);
+        se.write_bytes(&self.query);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageQuery {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageQuery {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let query_id = de.parse_i256()?;
+        let query = de.parse_bytes()?;
+
+        Ok(Self { query_id, query })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageQuery {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.answer query_id:int256 answer:bytes = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageAnswer {
+    pub query_id: Int256,
+    pub answer: Bytes,
+}
+
+impl AdnlMessageAnswer {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x1684ac0f;
+}
+
+impl Serialize for AdnlMessageAnswer {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.query_id);
+        se.write_bytes(&self.answer);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageAnswer {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageAnswer {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let query_id = de.parse_i256()?;
+        let answer = de.parse_bytes()?;
+
+        Ok(Self { query_id, answer })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageAnswer {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// pub.ed25519 key:int256 = PublicKey;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PubEd25519 {
+    pub key: Int256,
+}
+
+impl PubEd25519 {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xc6b41348;
+}
+
+impl Serialize for PubEd25519 {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.key);
+    }
+}
+
+impl SerializeBoxed for PubEd25519 {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for PubEd25519 {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let key = de.parse_i256()?;
+
+        Ok(Self { key })
+    }
+}
+
+impl DeserializeBoxed for PubEd25519 {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// tcp.authentificate nonce:bytes = tcp.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpAuthentificate {
+    pub nonce: Bytes,
+}
+
+impl TcpAuthentificate {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x12ab5b44;
+}
+
+impl Serialize for TcpAuthentificate {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_bytes(&self.nonce);
+    }
+}
+
+impl SerializeBoxed for TcpAuthentificate {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for TcpAuthentificate {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let nonce = de.parse_bytes()?;
+
+        Ok(Self { nonce })
+    }
+}
+
+impl DeserializeBoxed for TcpAuthentificate {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// tcp.authentificationNonce nonce:bytes = tcp.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpAuthentificationNonce {
+    pub nonce: Bytes,
+}
+
+impl TcpAuthentificationNonce {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xb64a5de3;
+}
+
+impl Serialize for TcpAuthentificationNonce {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_bytes(&self.nonce);
+    }
+}
+
+impl SerializeBoxed for TcpAuthentificationNonce {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for TcpAuthentificationNonce {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let nonce = de.parse_bytes()?;
+
+        Ok(Self { nonce })
+    }
+}
+
+impl DeserializeBoxed for TcpAuthentificationNonce {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// tcp.authentificationComplete key:PublicKey signature:bytes = tcp.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpAuthentificationComplete {
+    pub key: PubEd25519,
+    pub signature: Bytes,
+}
+
+impl TcpAuthentificationComplete {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xa69eadf7;
+}
+
+impl Serialize for TcpAuthentificationComplete {
+    fn serialize(&self, se: &mut Serializer) {
+        self.key.serialize_boxed(se);
+        se.write_bytes(&self.signature);
+    }
+}
+
+impl SerializeBoxed for TcpAuthentificationComplete {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for TcpAuthentificationComplete {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let key = PubEd25519::deserialize_boxed(de.parse_constructor_numer()?, de)?;
+        let signature = de.parse_bytes()?;
+
+        Ok(Self { key, signature })
+    }
+}
+
+impl DeserializeBoxed for TcpAuthentificationComplete {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
         }
     }
 }
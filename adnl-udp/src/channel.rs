@@ -0,0 +1,126 @@
+use crate::crypto;
+use adnl_tcp::key::{Ed25519Key, Ed25519KeyId};
+use adnl_tcp::types::Int256;
+use ed25519_dalek::VerifyingKey;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+
+/// Encrypted point-to-point channel, keys are derived from channel keys of both peers.
+pub struct Channel {
+    in_id: Int256,
+    in_secret: [u8; 32],
+    out_id: Int256,
+    out_secret: [u8; 32],
+    peer_key: Int256,
+    date: i32,
+    ready: bool,
+}
+
+impl Channel {
+    /// `pub.aes` constructor, prefixes the secret to compute channel id
+    const KEY_TYPE: [u8; 4] = [0xd4, 0xad, 0xbc, 0x2d];
+
+    pub fn new(
+        local_key: &Ed25519Key,
+        peer_key: &VerifyingKey,
+        local_id: &Ed25519KeyId,
+        peer_id: &Ed25519KeyId,
+        date: i32,
+    ) -> Self {
+        let secret = local_key.shared_secret(peer_key);
+        let mut reversed = secret;
+        reversed.reverse();
+
+        let (in_secret, out_secret) = match local_id.as_slice().cmp(peer_id.as_slice()) {
+            Ordering::Less => (secret, reversed),
+            Ordering::Greater => (reversed, secret),
+            Ordering::Equal => (secret, secret),
+        };
+
+        Self {
+            in_id: Self::id(&in_secret),
+            in_secret,
+            out_id: Self::id(&out_secret),
+            out_secret,
+            peer_key: peer_key.to_bytes(),
+            date,
+            ready: false,
+        }
+    }
+
+    fn id(secret: &[u8; 32]) -> Int256 {
+        Sha256::default()
+            .chain_update(Self::KEY_TYPE)
+            .chain_update(secret)
+            .finalize()
+            .into()
+    }
+
+    pub fn in_id(&self) -> &Int256 {
+        &self.in_id
+    }
+
+    pub fn peer_key(&self) -> &Int256 {
+        &self.peer_key
+    }
+
+    pub fn date(&self) -> i32 {
+        self.date
+    }
+
+    /// Channel is ready once the peer is known to have it too.
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+
+    pub fn set_ready(&mut self) {
+        self.ready = true;
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        [
+            self.out_id.as_slice(),
+            &crypto::encrypt(&self.out_secret, data),
+        ]
+        .concat()
+    }
+
+    /// Decrypts a datagram without the channel id.
+    pub fn decrypt(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        crypto::decrypt(&self.in_secret, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_keys_match() {
+        let (alice, alice_channel) = (Ed25519Key::generate(), Ed25519Key::generate());
+        let (bob, bob_channel) = (Ed25519Key::generate(), Ed25519Key::generate());
+
+        let lhs = Channel::new(
+            &alice_channel,
+            bob_channel.public_key(),
+            alice.id(),
+            bob.id(),
+            0,
+        );
+        let rhs = Channel::new(
+            &bob_channel,
+            alice_channel.public_key(),
+            bob.id(),
+            alice.id(),
+            0,
+        );
+
+        assert_eq!(lhs.in_id, rhs.out_id);
+        assert_eq!(lhs.out_id, rhs.in_id);
+        assert_ne!(lhs.in_id, lhs.out_id);
+
+        let encrypted = lhs.encrypt(b"hello");
+        assert_eq!(&encrypted[..32], rhs.in_id());
+        assert_eq!(rhs.decrypt(&encrypted[32..]).unwrap(), b"hello");
+    }
+}
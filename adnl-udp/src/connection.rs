@@ -0,0 +1,136 @@
+use crate::node::Command;
+use crate::types::{AdnlMessage, AdnlMessageQuery, Bytes, Int256};
+use adnl_tcp::deserializer::from_bytes_boxed;
+use adnl_tcp::key::Ed25519KeyId;
+use adnl_tcp::packet::Packet;
+use anyhow::anyhow;
+use dashmap::DashMap;
+use futures::{Sink, Stream};
+use rand::random;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::select;
+use tokio::sync::{mpsc, oneshot};
+
+/// Pending queries keyed by the peer they were sent to and their id.
+pub(crate) type Queries = Arc<DashMap<(Ed25519KeyId, Int256), oneshot::Sender<Bytes>>>;
+
+/// Messages exchanged with a single peer.
+///
+/// Like the TCP connection every packet carries one serialized `adnl.Message`,
+/// answers to [`Connection::query`] are not yielded by the stream.
+pub struct Connection {
+    peer_id: Ed25519KeyId,
+    commands: mpsc::UnboundedSender<Command>,
+    receiver: mpsc::UnboundedReceiver<Packet>,
+    queries: Queries,
+    query_resend_interval: Duration,
+}
+
+impl Connection {
+    pub(crate) fn new(
+        peer_id: Ed25519KeyId,
+        commands: mpsc::UnboundedSender<Command>,
+        receiver: mpsc::UnboundedReceiver<Packet>,
+        queries: Queries,
+        query_resend_interval: Duration,
+    ) -> Self {
+        Self {
+            peer_id,
+            commands,
+            receiver,
+            queries,
+            query_resend_interval,
+        }
+    }
+
+    pub fn peer_id(&self) -> &Ed25519KeyId {
+        &self.peer_id
+    }
+
+    /// Sends `query` until it is answered or `timeout` elapses.
+    pub async fn query(&self, query: Bytes, timeout: Duration) -> anyhow::Result<Bytes> {
+        let query = AdnlMessageQuery {
+            query_id: random(),
+            query,
+        };
+        let (tx, mut rx) = oneshot::channel();
+        let key = (self.peer_id.clone(), query.query_id);
+        self.queries.insert(key.clone(), tx);
+        let _guard = QueryGuard {
+            queries: &self.queries,
+            key,
+        };
+
+        tokio::time::timeout(timeout, async {
+            loop {
+                self.send(AdnlMessage::Query(query.clone()))?;
+
+                select! {
+                    answer = &mut rx => return answer.map_err(|_| anyhow!("node is closed")),
+                    _ = tokio::time::sleep(self.query_resend_interval) => {
+                        tracing::trace!(query_id = ?query.query_id, "resend query");
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow!("query timed out"))?
+    }
+
+    fn send(&self, message: AdnlMessage) -> anyhow::Result<()> {
+        self.commands
+            .send(Command::Send {
+                peer_id: self.peer_id.clone(),
+                message,
+            })
+            .map_err(|_| anyhow!("node is closed"))
+    }
+}
+
+struct QueryGuard<'a> {
+    queries: &'a Queries,
+    key: (Ed25519KeyId, Int256),
+}
+
+impl Drop for QueryGuard<'_> {
+    fn drop(&mut self) {
+        self.queries.remove(&self.key);
+    }
+}
+
+impl Sink<Packet> for Connection {
+    type Error = anyhow::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.commands.is_closed() {
+            return Poll::Ready(Err(anyhow!("node is closed")));
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Packet) -> Result<(), Self::Error> {
+        let message = from_bytes_boxed::<AdnlMessage>(&item.data)?;
+
+        self.send(message)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for Connection {
+    type Item = Result<Packet, anyhow::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().receiver.poll_recv(cx).map(|p| p.map(Ok))
+    }
+}
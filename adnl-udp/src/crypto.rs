@@ -0,0 +1,57 @@
+use adnl_tcp::aes_ctr::AesCtr;
+use anyhow::bail;
+use ctr::cipher::StreamCipher;
+use sha2::{Digest, Sha256};
+
+/// Encrypts `data` with the shared `secret`, output is `checksum || cipher text`.
+pub fn encrypt(secret: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    let checksum: [u8; 32] = Sha256::digest(data).into();
+
+    let mut output = Vec::with_capacity(32 + data.len());
+    output.extend_from_slice(&checksum);
+    output.extend_from_slice(data);
+    AesCtr::cipher(secret, &checksum).apply_keystream(&mut output[32..]);
+
+    output
+}
+
+pub fn decrypt(secret: &[u8; 32], data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    if data.len() < 32 {
+        bail!("encrypted data is too short");
+    }
+
+    let checksum: [u8; 32] = data[..32].try_into()?;
+    let mut output = data[32..].to_vec();
+    AesCtr::cipher(secret, &checksum).apply_keystream(&mut output);
+
+    if Sha256::digest(&output).as_slice() != checksum {
+        bail!("wrong checksum");
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt() {
+        let secret = [7; 32];
+
+        let encrypted = encrypt(&secret, b"hello");
+        let decrypted = decrypt(&secret, &encrypted).unwrap();
+
+        assert_ne!(&encrypted[32..], b"hello");
+        assert_eq!(decrypted, b"hello");
+    }
+
+    #[test]
+    fn decrypt_wrong_secret() {
+        let encrypted = encrypt(&[7; 32], b"hello");
+
+        let actual = decrypt(&[8; 32], &encrypted);
+
+        assert!(actual.is_err());
+    }
+}
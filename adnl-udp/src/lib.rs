@@ -0,0 +1,6 @@
+mod channel;
+pub mod connection;
+mod crypto;
+pub mod node;
+mod parts;
+pub mod types;
@@ -0,0 +1,949 @@
+use crate::channel::Channel;
+use crate::connection::{Connection, Queries};
+use crate::crypto;
+use crate::parts::{split, PartsBuffer, MAX_MESSAGE_SIZE};
+use crate::types::{
+    AdnlAddress, AdnlAddressList, AdnlAddressUdp, AdnlAddressUdp6, AdnlMessage,
+    AdnlMessageConfirmChannel, AdnlMessageCreateChannel, AdnlPacketContents, PubEd25519,
+};
+use adnl_tcp::deserializer::from_bytes_boxed;
+use adnl_tcp::key::{Ed25519Key, Ed25519KeyId};
+use adnl_tcp::packet::Packet;
+use adnl_tcp::serializer::to_bytes_boxed;
+use anyhow::{anyhow, bail};
+use ed25519_dalek::{Signature, VerifyingKey};
+use rand::{random, Rng};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tokio::select;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+const MAX_DATAGRAM_SIZE: usize = 4096;
+
+pub(crate) enum Command {
+    Connect {
+        addr: SocketAddr,
+        peer_key: VerifyingKey,
+        reply: oneshot::Sender<Connection>,
+    },
+    Send {
+        peer_id: Ed25519KeyId,
+        message: AdnlMessage,
+    },
+}
+
+/// ADNL node listening on a UDP socket.
+///
+/// Peers are addressed by their public key, the first packet to a peer is encrypted
+/// with its key and creates a channel, the following packets go through the channel.
+pub struct Node {
+    key_id: Ed25519KeyId,
+    local_addr: SocketAddr,
+    commands: mpsc::UnboundedSender<Command>,
+    incoming: mpsc::UnboundedReceiver<Connection>,
+    _drop_guard: DropGuard,
+}
+
+impl Node {
+    pub fn builder(key: Ed25519Key) -> NodeBuilder {
+        NodeBuilder::new(key)
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(addr: A, key: Ed25519Key) -> anyhow::Result<Self> {
+        Self::builder(key).bind(addr).await
+    }
+
+    pub fn key_id(&self) -> &Ed25519KeyId {
+        &self.key_id
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Opens a connection to the peer, nothing is sent until the first message.
+    pub async fn connect(
+        &self,
+        addr: SocketAddr,
+        peer_key: [u8; 32],
+    ) -> anyhow::Result<Connection> {
+        let peer_key = VerifyingKey::from_bytes(&peer_key)?;
+        let (reply, rx) = oneshot::channel();
+
+        self.commands
+            .send(Command::Connect {
+                addr,
+                peer_key,
+                reply,
+            })
+            .map_err(|_| anyhow!("node is closed"))?;
+
+        Ok(rx.await?)
+    }
+
+    /// Waits for a message from a peer without an open connection.
+    pub async fn accept(&mut self) -> Option<Connection> {
+        self.incoming.recv().await
+    }
+}
+
+pub struct NodeBuilder {
+    key: Ed25519Key,
+    query_resend_interval: Duration,
+    max_peers: usize,
+}
+
+impl NodeBuilder {
+    fn new(key: Ed25519Key) -> Self {
+        Self {
+            key,
+            query_resend_interval: Duration::from_secs(1),
+            max_peers: 1024,
+        }
+    }
+
+    /// Limits the number of known peers, a new peer replaces the least recently heard one
+    /// without an open connection, its packets are dropped if there is none.
+    pub fn set_max_peers(mut self, max_peers: usize) -> Self {
+        self.max_peers = max_peers.max(1);
+
+        self
+    }
+
+    /// Unanswered queries are sent again after this interval.
+    pub fn set_query_resend_interval(mut self, query_resend_interval: Duration) -> Self {
+        self.query_resend_interval = query_resend_interval;
+
+        self
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(self, addr: A) -> anyhow::Result<Node> {
+        let socket = UdpSocket::bind(addr).await?;
+        let local_addr = socket.local_addr()?;
+        let key_id = self.key.id().clone();
+        let (commands, commands_rx) = mpsc::unbounded_channel();
+        let (incoming_tx, incoming) = mpsc::unbounded_channel();
+        let cancellation_token = CancellationToken::new();
+
+        let actor = NodeActor {
+            socket,
+            key: self.key,
+            local_addr,
+            reinit_date: unix_time(),
+            query_resend_interval: self.query_resend_interval,
+            max_peers: self.max_peers,
+            peers: HashMap::new(),
+            channels: HashMap::new(),
+            commands: commands.clone(),
+            incoming: incoming_tx,
+            queries: Default::default(),
+        };
+        tokio::spawn(actor.run(commands_rx, cancellation_token.clone()));
+
+        tracing::info!(key_id = %key_id, addr = %local_addr, "adnl node started");
+
+        Ok(Node {
+            key_id,
+            local_addr,
+            commands,
+            incoming,
+            _drop_guard: cancellation_token.drop_guard(),
+        })
+    }
+}
+
+struct Peer {
+    key: VerifyingKey,
+    addr: SocketAddr,
+    seqno: i64,
+    received: ReceivedSeqno,
+    reinit_date: i32,
+    channel_key: Ed25519Key,
+    channel_date: i32,
+    channel: Option<Channel>,
+    parts: PartsBuffer,
+    sender: Option<mpsc::UnboundedSender<Packet>>,
+    last_received: Instant,
+}
+
+impl Peer {
+    fn new(key: VerifyingKey, addr: SocketAddr) -> Self {
+        Self {
+            key,
+            addr,
+            seqno: 0,
+            received: Default::default(),
+            reinit_date: 0,
+            channel_key: Ed25519Key::generate(),
+            channel_date: unix_time(),
+            channel: None,
+            parts: Default::default(),
+            sender: None,
+            last_received: Instant::now(),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.sender.as_ref().is_none_or(|sender| sender.is_closed())
+    }
+}
+
+/// Window of the last received packet seqnos, used to drop duplicates.
+#[derive(Default)]
+struct ReceivedSeqno {
+    last: i64,
+    mask: u64,
+}
+
+impl ReceivedSeqno {
+    fn insert(&mut self, seqno: i64) -> bool {
+        if seqno > self.last {
+            let shift = seqno - self.last;
+            self.mask = if shift >= 64 {
+                1
+            } else {
+                (self.mask << shift) | 1
+            };
+            self.last = seqno;
+
+            return true;
+        }
+
+        let offset = self.last - seqno;
+        if offset >= 64 || self.mask & (1 << offset) != 0 {
+            return false;
+        }
+        self.mask |= 1 << offset;
+
+        true
+    }
+}
+
+struct NodeActor {
+    socket: UdpSocket,
+    key: Ed25519Key,
+    local_addr: SocketAddr,
+    reinit_date: i32,
+    query_resend_interval: Duration,
+    max_peers: usize,
+    peers: HashMap<Ed25519KeyId, Peer>,
+    channels: HashMap<[u8; 32], Ed25519KeyId>,
+    commands: mpsc::UnboundedSender<Command>,
+    incoming: mpsc::UnboundedSender<Connection>,
+    queries: Queries,
+}
+
+impl NodeActor {
+    async fn run(
+        mut self,
+        mut commands: mpsc::UnboundedReceiver<Command>,
+        cancellation_token: CancellationToken,
+    ) {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+
+        loop {
+            select! {
+                _ = cancellation_token.cancelled() => break,
+                received = self.socket.recv_from(&mut buf) => match received {
+                    Ok((len, addr)) => {
+                        if let Err(error) = self.handle_datagram(addr, &buf[..len]).await {
+                            tracing::debug!(addr = %addr, error = ?error, "datagram dropped");
+                        }
+                    }
+                    Err(error) => tracing::warn!(error = ?error, "receive error"),
+                },
+                Some(command) = commands.recv() => {
+                    if let Err(error) = self.handle_command(command).await {
+                        tracing::warn!(error = ?error, "command failed");
+                    }
+                }
+            }
+        }
+
+        tracing::trace!("adnl node closed");
+    }
+
+    async fn handle_command(&mut self, command: Command) -> anyhow::Result<()> {
+        match command {
+            Command::Connect {
+                addr,
+                peer_key,
+                reply,
+            } => {
+                let peer_id = Ed25519KeyId::from_public_key_bytes(peer_key.as_bytes());
+                let peer = self
+                    .peers
+                    .entry(peer_id.clone())
+                    .or_insert_with(|| Peer::new(peer_key, addr));
+                peer.addr = addr;
+
+                let _ = reply.send(self.open_connection(peer_id));
+
+                Ok(())
+            }
+            Command::Send { peer_id, message } => {
+                let bytes = to_bytes_boxed(&message);
+                if bytes.len() <= MAX_MESSAGE_SIZE {
+                    return self.send_packet(&peer_id, vec![message]).await;
+                }
+
+                for part in split(&bytes) {
+                    self.send_packet(&peer_id, vec![AdnlMessage::Part(part)])
+                        .await?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn open_connection(&mut self, peer_id: Ed25519KeyId) -> Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.sender = Some(tx);
+        }
+
+        Connection::new(
+            peer_id,
+            self.commands.clone(),
+            rx,
+            self.queries.clone(),
+            self.query_resend_interval,
+        )
+    }
+
+    /// Sends messages in a single packet.
+    async fn send_packet(
+        &mut self,
+        peer_id: &Ed25519KeyId,
+        mut messages: Vec<AdnlMessage>,
+    ) -> anyhow::Result<()> {
+        let address = self.address_list();
+        let Some(peer) = self.peers.get_mut(peer_id) else {
+            bail!("unknown peer {}", peer_id);
+        };
+        peer.seqno += 1;
+
+        let mut contents = AdnlPacketContents {
+            rand1: rand_bytes(),
+            seqno: Some(peer.seqno),
+            confirm_seqno: Some(peer.received.last),
+            rand2: rand_bytes(),
+            ..Default::default()
+        };
+
+        let datagram = match &peer.channel {
+            Some(channel) if channel.is_ready() => {
+                set_messages(&mut contents, messages);
+
+                channel.encrypt(&to_bytes_boxed(&contents))
+            }
+            channel => {
+                let channel_key = *peer.channel_key.public_key().as_bytes();
+                messages.insert(
+                    0,
+                    match channel {
+                        None => AdnlMessage::CreateChannel(AdnlMessageCreateChannel {
+                            key: channel_key,
+                            date: peer.channel_date,
+                        }),
+                        Some(channel) => AdnlMessage::ConfirmChannel(AdnlMessageConfirmChannel {
+                            key: channel_key,
+                            peer_key: *channel.peer_key(),
+                            date: channel.date(),
+                        }),
+                    },
+                );
+                set_messages(&mut contents, messages);
+                contents.address = Some(address);
+                contents.reinit_date = Some((self.reinit_date, peer.reinit_date));
+
+                encrypt_with_key(&self.key, &peer.key, contents)
+            }
+        };
+
+        self.socket.send_to(&datagram, peer.addr).await?;
+
+        Ok(())
+    }
+
+    async fn handle_datagram(&mut self, addr: SocketAddr, data: &[u8]) -> anyhow::Result<()> {
+        if data.len() < 64 {
+            bail!("datagram is too short");
+        }
+
+        let destination: [u8; 32] = data[..32].try_into()?;
+        let (peer_id, contents) = if destination == self.key.id().to_bytes() {
+            self.decrypt_with_key(addr, &data[32..])?
+        } else if let Some(peer_id) = self.channels.get(&destination).cloned() {
+            let channel = self
+                .peers
+                .get_mut(&peer_id)
+                .and_then(|peer| peer.channel.as_mut())
+                .ok_or_else(|| anyhow!("channel is closed"))?;
+            let contents = from_bytes_boxed::<AdnlPacketContents>(&channel.decrypt(&data[32..])?)?;
+            channel.set_ready();
+
+            (peer_id, contents)
+        } else {
+            bail!("unknown destination");
+        };
+
+        let reinit_date = self.reinit_date;
+        let peer = self
+            .peers
+            .get_mut(&peer_id)
+            .ok_or_else(|| anyhow!("unknown peer"))?;
+        peer.addr = addr;
+        peer.last_received = Instant::now();
+
+        if let Some((peer_reinit_date, dst_reinit_date)) = contents.reinit_date {
+            if dst_reinit_date != 0 && dst_reinit_date != reinit_date {
+                bail!("packet is addressed to previous reinit date");
+            }
+            if peer_reinit_date > peer.reinit_date {
+                if peer.reinit_date != 0 {
+                    tracing::debug!(peer_id = %peer_id, "peer reinitialized");
+                    if let Some(channel) = peer.channel.take() {
+                        self.channels.remove(channel.in_id());
+                    }
+                }
+                peer.reinit_date = peer_reinit_date;
+                peer.received = Default::default();
+            }
+        }
+        if let Some(seqno) = contents.seqno {
+            if !peer.received.insert(seqno) {
+                tracing::trace!(peer_id = %peer_id, seqno, "duplicate packet dropped");
+
+                return Ok(());
+            }
+        }
+
+        let messages = contents
+            .message
+            .into_iter()
+            .chain(contents.messages.into_iter().flatten());
+        for message in messages {
+            self.handle_message(&peer_id, message).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Decrypts a packet sent to the node key, the sender is known from the signed packet.
+    fn decrypt_with_key(
+        &mut self,
+        addr: SocketAddr,
+        data: &[u8],
+    ) -> anyhow::Result<(Ed25519KeyId, AdnlPacketContents)> {
+        let ephemeral_key = VerifyingKey::from_bytes(data[..32].try_into()?)?;
+        let secret = self.key.shared_secret(&ephemeral_key);
+        let mut contents =
+            from_bytes_boxed::<AdnlPacketContents>(&crypto::decrypt(&secret, &data[32..])?)?;
+
+        let Some(from) = &contents.from else {
+            bail!("packet without sender");
+        };
+        let Some(signature) = contents.signature.take() else {
+            bail!("packet without signature");
+        };
+        let peer_key = VerifyingKey::from_bytes(&from.key)?;
+        peer_key.verify_strict(
+            &to_bytes_boxed(&contents),
+            &Signature::from_slice(&signature)?,
+        )?;
+
+        let peer_id = Ed25519KeyId::from_public_key_bytes(&from.key);
+        if !self.peers.contains_key(&peer_id) {
+            // a new peer starts with a channel, a signed packet is not enough to be remembered
+            let creates_channel = contents
+                .message
+                .iter()
+                .chain(contents.messages.iter().flatten())
+                .any(|message| matches!(message, AdnlMessage::CreateChannel(_)));
+            if !creates_channel {
+                bail!("unknown peer {} without channel creation", peer_id);
+            }
+
+            self.make_room_for_peer()?;
+            self.peers
+                .insert(peer_id.clone(), Peer::new(peer_key, addr));
+        }
+
+        Ok((peer_id, contents))
+    }
+
+    /// Evicts the least recently heard idle peer if there are too many of them.
+    fn make_room_for_peer(&mut self) -> anyhow::Result<()> {
+        if self.peers.len() < self.max_peers {
+            return Ok(());
+        }
+
+        let Some(peer_id) = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.is_idle())
+            .min_by_key(|(_, peer)| peer.last_received)
+            .map(|(peer_id, _)| peer_id.clone())
+        else {
+            bail!("too many peers");
+        };
+
+        tracing::debug!(peer_id = %peer_id, "idle peer evicted");
+        if let Some(channel) = self.peers.remove(&peer_id).and_then(|peer| peer.channel) {
+            self.channels.remove(channel.in_id());
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        peer_id: &Ed25519KeyId,
+        message: AdnlMessage,
+    ) -> anyhow::Result<()> {
+        let local_id = self.key.id().clone();
+        let Some(peer) = self.peers.get_mut(peer_id) else {
+            bail!("unknown peer {}", peer_id);
+        };
+
+        let message = match message {
+            AdnlMessage::Part(part) => match peer.parts.push(part)? {
+                Some(message) => message,
+                None => return Ok(()),
+            },
+            message => message,
+        };
+
+        match message {
+            AdnlMessage::CreateChannel(create) => {
+                if peer
+                    .channel
+                    .as_ref()
+                    .is_some_and(|channel| channel.peer_key() == &create.key)
+                {
+                    return Ok(());
+                }
+
+                let channel = Channel::new(
+                    &peer.channel_key,
+                    &VerifyingKey::from_bytes(&create.key)?,
+                    &local_id,
+                    peer_id,
+                    create.date,
+                );
+                self.channels.insert(*channel.in_id(), peer_id.clone());
+                if let Some(previous) = peer.channel.replace(channel) {
+                    self.channels.remove(previous.in_id());
+                }
+
+                // confirm the channel right away
+                self.send_packet(peer_id, vec![]).await
+            }
+            AdnlMessage::ConfirmChannel(confirm) => {
+                if confirm.peer_key != *peer.channel_key.public_key().as_bytes() {
+                    bail!("confirmation of unknown channel");
+                }
+
+                match &mut peer.channel {
+                    Some(channel) if channel.peer_key() == &confirm.key => channel.set_ready(),
+                    _ => {
+                        let mut channel = Channel::new(
+                            &peer.channel_key,
+                            &VerifyingKey::from_bytes(&confirm.key)?,
+                            &local_id,
+                            peer_id,
+                            confirm.date,
+                        );
+                        channel.set_ready();
+                        self.channels.insert(*channel.in_id(), peer_id.clone());
+                        if let Some(previous) = peer.channel.replace(channel) {
+                            self.channels.remove(previous.in_id());
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            AdnlMessage::Answer(answer) => {
+                match self.queries.remove(&(peer_id.clone(), answer.query_id)) {
+                    Some((_, tx)) => {
+                        let _ = tx.send(answer.answer);
+
+                        Ok(())
+                    }
+                    None => self.deliver(peer_id, AdnlMessage::Answer(answer)),
+                }
+            }
+            message @ (AdnlMessage::Query(_) | AdnlMessage::Custom(_)) => {
+                self.deliver(peer_id, message)
+            }
+            AdnlMessage::Nop(_) | AdnlMessage::Reinit(_) => Ok(()),
+            AdnlMessage::Part(_) => bail!("nested message part from {}", peer_id),
+        }
+    }
+
+    fn deliver(&mut self, peer_id: &Ed25519KeyId, message: AdnlMessage) -> anyhow::Result<()> {
+        let Some(peer) = self.peers.get_mut(peer_id) else {
+            bail!("unknown peer {}", peer_id);
+        };
+
+        let packet = Packet::new(to_bytes_boxed(&message));
+        let packet = match &peer.sender {
+            Some(sender) => match sender.send(packet) {
+                Ok(()) => return Ok(()),
+                Err(error) => error.0,
+            },
+            None => packet,
+        };
+
+        let connection = self.open_connection(peer_id.clone());
+        if self.incoming.send(connection).is_err() {
+            bail!("incoming connections are not accepted");
+        }
+        if let Some(sender) = self
+            .peers
+            .get(peer_id)
+            .and_then(|peer| peer.sender.as_ref())
+        {
+            let _ = sender.send(packet);
+        }
+
+        Ok(())
+    }
+
+    fn address_list(&self) -> AdnlAddressList {
+        let address = match self.local_addr {
+            addr if addr.ip().is_unspecified() => None,
+            SocketAddr::V4(addr) => Some(AdnlAddress::Udp(AdnlAddressUdp {
+                ip: u32::from(*addr.ip()) as i32,
+                port: addr.port() as i32,
+            })),
+            SocketAddr::V6(addr) => Some(AdnlAddress::Udp6(AdnlAddressUdp6 {
+                ip: u128::from(*addr.ip()) as i128,
+                port: addr.port() as i32,
+            })),
+        };
+
+        AdnlAddressList {
+            addrs: address.into_iter().collect(),
+            version: self.reinit_date,
+            reinit_date: self.reinit_date,
+            priority: 0,
+            expire_at: 0,
+        }
+    }
+}
+
+/// Signs `contents` by `key` and encrypts them for `peer_key` with an ephemeral key.
+fn encrypt_with_key(
+    key: &Ed25519Key,
+    peer_key: &VerifyingKey,
+    mut contents: AdnlPacketContents,
+) -> Vec<u8> {
+    contents.from = Some(PubEd25519 {
+        key: *key.public_key().as_bytes(),
+    });
+    contents.signature = Some(key.sign(&to_bytes_boxed(&contents)).to_vec());
+
+    let ephemeral_key = Ed25519Key::generate();
+    let secret = ephemeral_key.shared_secret(peer_key);
+    let peer_id = Ed25519KeyId::from_public_key_bytes(peer_key.as_bytes());
+
+    [
+        peer_id.as_slice(),
+        ephemeral_key.public_key().as_bytes(),
+        &crypto::encrypt(&secret, &to_bytes_boxed(&contents)),
+    ]
+    .concat()
+}
+
+fn set_messages(contents: &mut AdnlPacketContents, mut messages: Vec<AdnlMessage>) {
+    if messages.len() == 1 {
+        contents.message = messages.pop();
+    } else {
+        contents.messages = Some(messages);
+    }
+}
+
+fn rand_bytes() -> Vec<u8> {
+    let len = if random::<bool>() { 7 } else { 15 };
+
+    (0..len).map(|_| rand::thread_rng().gen()).collect()
+}
+
+fn unix_time() -> i32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_secs() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AdnlMessageAnswer, AdnlMessageCustom};
+    use futures::{SinkExt, StreamExt};
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_query_answer() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+        tokio::spawn(async move { given_echo(&mut server).await });
+
+        let connection = client.0.connect(client.1, client.2).await?;
+        let answer = connection
+            .query(vec![1, 2, 3], Duration::from_secs(5))
+            .await?;
+
+        assert_eq!(answer, vec![3, 2, 1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_uses_channel_after_confirmation() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+        tokio::spawn(async move { given_echo(&mut server).await });
+
+        let connection = client.0.connect(client.1, client.2).await?;
+        for i in 0..5u8 {
+            let answer = connection.query(vec![i, 0], Duration::from_secs(5)).await?;
+
+            assert_eq!(answer, vec![0, i]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_huge_query() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+        tokio::spawn(async move { given_echo(&mut server).await });
+
+        let query: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+        let connection = client.0.connect(client.1, client.2).await?;
+        let answer = connection
+            .query(query.clone(), Duration::from_secs(5))
+            .await?;
+
+        assert_eq!(answer, query.into_iter().rev().collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_custom_message() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+
+        let mut connection = client.0.connect(client.1, client.2).await?;
+        connection
+            .send(Packet::new(to_bytes_boxed(&AdnlMessage::Custom(
+                AdnlMessageCustom { data: vec![42] },
+            ))))
+            .await?;
+        let mut incoming = server.accept().await.unwrap();
+        let received = incoming.next().await.unwrap()?;
+
+        assert_eq!(incoming.peer_id(), client.0.key_id());
+        assert_eq!(
+            from_bytes_boxed::<AdnlMessage>(&received.data)?,
+            AdnlMessage::Custom(AdnlMessageCustom { data: vec![42] })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    #[traced_test]
+    async fn node_query_resent_until_answered() -> anyhow::Result<()> {
+        let silent = UdpSocket::bind("127.0.0.1:0").await?;
+        let addr = silent.local_addr()?;
+        let server_key = Ed25519Key::generate();
+        let server_public_key = *server_key.public_key().as_bytes();
+        let client = Node::builder(Ed25519Key::generate())
+            .set_query_resend_interval(Duration::from_millis(50))
+            .bind("127.0.0.1:0")
+            .await?;
+
+        let connection = client.connect(addr, server_public_key).await?;
+        let query = tokio::spawn(async move {
+            connection
+                .query(vec![1, 2], Duration::from_secs(3600))
+                .await
+        });
+        let mut buf = [0; 2048];
+        for _ in 0..3 {
+            silent.recv_from(&mut buf).await?;
+        }
+        drop(silent);
+        let mut server = Node::bind(addr, server_key).await?;
+        tokio::spawn(async move { given_echo(&mut server).await });
+
+        assert_eq!(query.await??, vec![2, 1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_query_timeout() -> anyhow::Result<()> {
+        let (client, _server) = given_nodes().await?;
+
+        let connection = client.0.connect(client.1, client.2).await?;
+        let answer = connection.query(vec![1], Duration::from_millis(100)).await;
+
+        assert_eq!(answer.unwrap_err().to_string(), "query timed out");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_drops_packet_for_other_key() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+        tokio::spawn(async move { given_echo(&mut server).await });
+
+        let connection = client
+            .0
+            .connect(client.1, *Ed25519Key::generate().public_key().as_bytes())
+            .await?;
+        let answer = connection.query(vec![1], Duration::from_millis(200)).await;
+
+        assert!(answer.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_ignores_unknown_peer_without_channel() -> anyhow::Result<()> {
+        let (client, mut server) = given_nodes().await?;
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        let key = Ed25519Key::generate();
+        let server_key = VerifyingKey::from_bytes(&client.2)?;
+        let given_packet = |messages| {
+            let mut contents = AdnlPacketContents {
+                seqno: Some(1),
+                ..Default::default()
+            };
+            set_messages(&mut contents, messages);
+
+            encrypt_with_key(&key, &server_key, contents)
+        };
+        let custom = AdnlMessage::Custom(AdnlMessageCustom { data: vec![42] });
+        let create_channel = AdnlMessage::CreateChannel(AdnlMessageCreateChannel {
+            key: *Ed25519Key::generate().public_key().as_bytes(),
+            date: unix_time(),
+        });
+
+        socket
+            .send_to(&given_packet(vec![custom.clone()]), client.1)
+            .await?;
+        let ignored = tokio::time::timeout(Duration::from_millis(200), server.accept()).await;
+        socket
+            .send_to(&given_packet(vec![create_channel, custom]), client.1)
+            .await?;
+        let accepted = server.accept().await.unwrap();
+
+        assert!(ignored.is_err());
+        assert_eq!(accepted.peer_id(), key.id());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn node_evicts_idle_peers() -> anyhow::Result<()> {
+        let server_key = Ed25519Key::generate();
+        let server_public_key = *server_key.public_key().as_bytes();
+        let mut server = Node::builder(server_key)
+            .set_max_peers(1)
+            .bind("127.0.0.1:0")
+            .await?;
+        let mut clients = Vec::new();
+        for _ in 0..3 {
+            let client = Node::bind("127.0.0.1:0", Ed25519Key::generate()).await?;
+            let connection = client
+                .connect(server.local_addr(), server_public_key)
+                .await?;
+            clients.push((client, connection));
+        }
+        let custom = Packet::new(to_bytes_boxed(&AdnlMessage::Custom(AdnlMessageCustom {
+            data: vec![42],
+        })));
+        clients[0].1.send(custom.clone()).await?;
+        let first = given_accepted(&mut server).await;
+        clients[1].1.send(custom.clone()).await?;
+        let second = given_accepted(&mut server).await;
+        drop(first);
+        clients[2].1.send(custom).await?;
+        let third = given_accepted(&mut server).await;
+
+        assert!(second.is_none());
+        assert_eq!(third.unwrap().peer_id(), clients[2].0.key_id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn received_seqno_drops_duplicates() {
+        let mut received = ReceivedSeqno::default();
+
+        assert!(received.insert(1));
+        assert!(received.insert(3));
+        assert!(!received.insert(3));
+        assert!(received.insert(2));
+        assert!(!received.insert(1));
+        assert!(received.insert(100));
+        assert!(!received.insert(3));
+    }
+
+    async fn given_accepted(node: &mut Node) -> Option<Connection> {
+        tokio::time::timeout(Duration::from_millis(200), node.accept())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn given_echo(node: &mut Node) {
+        while let Some(mut connection) = node.accept().await {
+            tokio::spawn(async move {
+                while let Some(Ok(packet)) = connection.next().await {
+                    if let Ok(AdnlMessage::Query(query)) = from_bytes_boxed(&packet.data) {
+                        let answer = AdnlMessageAnswer {
+                            query_id: query.query_id,
+                            answer: query.query.into_iter().rev().collect(),
+                        };
+
+                        let _ = connection
+                            .send(Packet::new(to_bytes_boxed(&AdnlMessage::Answer(answer))))
+                            .await;
+                    }
+                }
+            });
+        }
+    }
+
+    async fn given_nodes() -> anyhow::Result<((Node, SocketAddr, [u8; 32]), Node)> {
+        let server_key = Ed25519Key::generate();
+        let server_public_key = *server_key.public_key().as_bytes();
+        let server = Node::bind("127.0.0.1:0", server_key).await?;
+        let client = Node::builder(Ed25519Key::generate())
+            .set_query_resend_interval(Duration::from_millis(100))
+            .bind("127.0.0.1:0")
+            .await?;
+        let addr = server.local_addr();
+
+        Ok(((client, addr, server_public_key), server))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::types::{AdnlMessage, AdnlMessagePart, Int256};
+use adnl_tcp::deserializer::from_bytes_boxed;
+use anyhow::bail;
+use sha2::{Digest, Sha256};
+
+/// Messages larger than this are sent as `adnl.message.part`.
+pub const MAX_MESSAGE_SIZE: usize = 1024;
+/// Limit for a message assembled from parts.
+pub const MAX_HUGE_MESSAGE_SIZE: usize = 1 << 20;
+
+pub fn split(message: &[u8]) -> Vec<AdnlMessagePart> {
+    let hash: Int256 = Sha256::digest(message).into();
+
+    message
+        .chunks(MAX_MESSAGE_SIZE)
+        .enumerate()
+        .map(|(n, chunk)| AdnlMessagePart {
+            hash,
+            total_size: message.len() as i32,
+            offset: (n * MAX_MESSAGE_SIZE) as i32,
+            data: chunk.to_vec(),
+        })
+        .collect()
+}
+
+/// Assembles a single message of a peer from parts received in order.
+#[derive(Default)]
+pub struct PartsBuffer {
+    hash: Int256,
+    total_size: usize,
+    data: Vec<u8>,
+}
+
+impl PartsBuffer {
+    pub fn push(&mut self, part: AdnlMessagePart) -> anyhow::Result<Option<AdnlMessage>> {
+        let total_size = usize::try_from(part.total_size)?;
+        if total_size > MAX_HUGE_MESSAGE_SIZE {
+            bail!("message of {} bytes is too big", total_size);
+        }
+
+        if part.offset == 0 {
+            self.hash = part.hash;
+            self.total_size = total_size;
+            self.data.clear();
+        } else if part.hash != self.hash || part.offset as usize != self.data.len() {
+            tracing::debug!(offset = part.offset, "out of order part dropped");
+
+            return Ok(None);
+        }
+
+        if self.data.len() + part.data.len() > self.total_size {
+            bail!("part is out of message bounds");
+        }
+        self.data.extend_from_slice(&part.data);
+        if self.data.len() < self.total_size {
+            return Ok(None);
+        }
+
+        let data = std::mem::take(&mut self.data);
+        if Sha256::digest(&data).as_slice() != self.hash {
+            bail!("wrong message hash");
+        }
+
+        match from_bytes_boxed::<AdnlMessage>(&data)? {
+            AdnlMessage::Part(_) => bail!("nested part"),
+            message => Ok(Some(message)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AdnlMessageCustom;
+    use adnl_tcp::serializer::to_bytes_boxed;
+
+    #[test]
+    fn split_and_assemble() {
+        let message = AdnlMessage::Custom(AdnlMessageCustom {
+            data: vec![7; 3000],
+        });
+        let mut buffer = PartsBuffer::default();
+
+        let parts = split(&to_bytes_boxed(&message));
+        let count = parts.len();
+        let assembled: Vec<_> = parts
+            .into_iter()
+            .filter_map(|part| buffer.push(part).unwrap())
+            .collect();
+
+        assert_eq!(count, 3);
+        assert_eq!(assembled, vec![message]);
+    }
+
+    #[test]
+    fn reject_too_big_message() {
+        let mut buffer = PartsBuffer::default();
+
+        let actual = buffer.push(AdnlMessagePart {
+            hash: [0; 32],
+            total_size: (MAX_HUGE_MESSAGE_SIZE + 1) as i32,
+            offset: 0,
+            data: vec![0; 16],
+        });
+
+        assert!(actual.is_err());
+    }
+}
@@ -0,0 +1,942 @@
+use adnl_tcp::deserializer::{Deserialize, DeserializeBoxed, Deserializer, DeserializerBoxedError};
+use adnl_tcp::serializer::{Serialize, SerializeBoxed, Serializer};
+pub use adnl_tcp::types::*;
+
+/// ```tl
+/// adnl.id.short id:int256 = adnl.id.Short;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlIdShort {
+    pub id: Int256,
+}
+
+impl AdnlIdShort {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x4f653f3e;
+}
+
+impl Serialize for AdnlIdShort {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.id);
+    }
+}
+
+impl SerializeBoxed for AdnlIdShort {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlIdShort {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let id = de.parse_i256()?;
+
+        Ok(Self { id })
+    }
+}
+
+impl DeserializeBoxed for AdnlIdShort {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.address.udp ip:int port:int = adnl.Address;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlAddressUdp {
+    pub ip: Int,
+    pub port: Int,
+}
+
+impl AdnlAddressUdp {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xe7a60d67;
+}
+
+impl Serialize for AdnlAddressUdp {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i32(self.ip);
+        se.write_i32(self.port);
+    }
+}
+
+impl SerializeBoxed for AdnlAddressUdp {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlAddressUdp {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let ip = de.parse_i32()?;
+        let port = de.parse_i32()?;
+
+        Ok(Self { ip, port })
+    }
+}
+
+impl DeserializeBoxed for AdnlAddressUdp {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.address.udp6 ip:int128 port:int = adnl.Address;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlAddressUdp6 {
+    pub ip: Int128,
+    pub port: Int,
+}
+
+impl AdnlAddressUdp6 {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xfa631de3;
+}
+
+impl Serialize for AdnlAddressUdp6 {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i128(self.ip);
+        se.write_i32(self.port);
+    }
+}
+
+impl SerializeBoxed for AdnlAddressUdp6 {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlAddressUdp6 {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let ip = de.parse_i128()?;
+        let port = de.parse_i32()?;
+
+        Ok(Self { ip, port })
+    }
+}
+
+impl DeserializeBoxed for AdnlAddressUdp6 {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// `adnl.Address`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdnlAddress {
+    Udp(AdnlAddressUdp),
+    Udp6(AdnlAddressUdp6),
+}
+
+impl Serialize for AdnlAddress {
+    fn serialize(&self, se: &mut Serializer) {
+        match self {
+            Self::Udp(inner) => inner.serialize_boxed(se),
+            Self::Udp6(inner) => inner.serialize_boxed(se),
+        }
+    }
+}
+
+impl SerializeBoxed for AdnlAddress {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        self.serialize(se);
+    }
+}
+
+impl Deserialize for AdnlAddress {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let constructor_number = de.parse_constructor_numer()?;
+
+        Self::deserialize_boxed(constructor_number, de)
+    }
+}
+
+impl DeserializeBoxed for AdnlAddress {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        match constructor_number {
+            AdnlAddressUdp::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Udp(AdnlAddressUdp::deserialize(de)?))
+            }
+            AdnlAddressUdp6::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Udp6(AdnlAddressUdp6::deserialize(de)?))
+            }
+            _ => Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            )),
+        }
+    }
+}
+
+/// ```tl
+/// adnl.addressList addrs:(vector adnl.Address) version:int reinit_date:int priority:int expire_at:int = adnl.AddressList;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AdnlAddressList {
+    pub addrs: Vector<AdnlAddress>,
+    pub version: Int,
+    pub reinit_date: Int,
+    pub priority: Int,
+    pub expire_at: Int,
+}
+
+impl AdnlAddressList {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x58e62722;
+}
+
+impl Serialize for AdnlAddressList {
+    fn serialize(&self, se: &mut Serializer) {
+        self.addrs.serialize(se);
+        se.write_i32(self.version);
+        se.write_i32(self.reinit_date);
+        se.write_i32(self.priority);
+        se.write_i32(self.expire_at);
+    }
+}
+
+impl SerializeBoxed for AdnlAddressList {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlAddressList {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let addrs = Vector::<AdnlAddress>::deserialize(de)?;
+        let version = de.parse_i32()?;
+        let reinit_date = de.parse_i32()?;
+        let priority = de.parse_i32()?;
+        let expire_at = de.parse_i32()?;
+
+        Ok(Self {
+            addrs,
+            version,
+            reinit_date,
+            priority,
+            expire_at,
+        })
+    }
+}
+
+impl DeserializeBoxed for AdnlAddressList {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.createChannel key:int256 date:int = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageCreateChannel {
+    pub key: Int256,
+    pub date: Int,
+}
+
+impl AdnlMessageCreateChannel {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xbbc373e6;
+}
+
+impl Serialize for AdnlMessageCreateChannel {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.key);
+        se.write_i32(self.date);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageCreateChannel {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageCreateChannel {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let key = de.parse_i256()?;
+        let date = de.parse_i32()?;
+
+        Ok(Self { key, date })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageCreateChannel {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.confirmChannel key:int256 peer_key:int256 date:int = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageConfirmChannel {
+    pub key: Int256,
+    pub peer_key: Int256,
+    pub date: Int,
+}
+
+impl AdnlMessageConfirmChannel {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x691ddd60;
+}
+
+impl Serialize for AdnlMessageConfirmChannel {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.key);
+        se.write_i256(&self.peer_key);
+        se.write_i32(self.date);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageConfirmChannel {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageConfirmChannel {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let key = de.parse_i256()?;
+        let peer_key = de.parse_i256()?;
+        let date = de.parse_i32()?;
+
+        Ok(Self {
+            key,
+            peer_key,
+            date,
+        })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageConfirmChannel {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.custom data:bytes = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageCustom {
+    pub data: Bytes,
+}
+
+impl AdnlMessageCustom {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xf5184820;
+}
+
+impl Serialize for AdnlMessageCustom {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_bytes(&self.data);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageCustom {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageCustom {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let data = de.parse_bytes()?;
+
+        Ok(Self { data })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageCustom {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.nop = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AdnlMessageNop {}
+
+impl AdnlMessageNop {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0xdadff817;
+}
+
+impl Serialize for AdnlMessageNop {
+    fn serialize(&self, _: &mut Serializer) {}
+}
+
+impl SerializeBoxed for AdnlMessageNop {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageNop {
+    fn deserialize(_: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        Ok(Self {})
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageNop {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.reinit date:int = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessageReinit {
+    pub date: Int,
+}
+
+impl AdnlMessageReinit {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x2005c210;
+}
+
+impl Serialize for AdnlMessageReinit {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i32(self.date);
+    }
+}
+
+impl SerializeBoxed for AdnlMessageReinit {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessageReinit {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let date = de.parse_i32()?;
+
+        Ok(Self { date })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessageReinit {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+/// ```tl
+/// adnl.message.part hash:int256 total_size:int offset:int data:bytes = adnl.Message;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdnlMessagePart {
+    pub hash: Int256,
+    pub total_size: Int,
+    pub offset: Int,
+    pub data: Bytes,
+}
+
+impl AdnlMessagePart {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x392d45fd;
+}
+
+impl Serialize for AdnlMessagePart {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_i256(&self.hash);
+        se.write_i32(self.total_size);
+        se.write_i32(self.offset);
+        se.write_bytes(&self.data);
+    }
+}
+
+impl SerializeBoxed for AdnlMessagePart {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlMessagePart {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let hash = de.parse_i256()?;
+        let total_size = de.parse_i32()?;
+        let offset = de.parse_i32()?;
+        let data = de.parse_bytes()?;
+
+        Ok(Self {
+            hash,
+            total_size,
+            offset,
+            data,
+        })
+    }
+}
+
+impl DeserializeBoxed for AdnlMessagePart {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+const ADNL_MESSAGE_QUERY: u32 = 0x7af98bb4;
+const ADNL_MESSAGE_ANSWER: u32 = 0x1684ac0f;
+
+/// `adnl.Message`, query and answer are shared with ADNL over TCP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdnlMessage {
+    CreateChannel(AdnlMessageCreateChannel),
+    ConfirmChannel(AdnlMessageConfirmChannel),
+    Custom(AdnlMessageCustom),
+    Nop(AdnlMessageNop),
+    Reinit(AdnlMessageReinit),
+    Query(AdnlMessageQuery),
+    Answer(AdnlMessageAnswer),
+    Part(AdnlMessagePart),
+}
+
+impl Serialize for AdnlMessage {
+    fn serialize(&self, se: &mut Serializer) {
+        match self {
+            Self::CreateChannel(inner) => inner.serialize_boxed(se),
+            Self::ConfirmChannel(inner) => inner.serialize_boxed(se),
+            Self::Custom(inner) => inner.serialize_boxed(se),
+            Self::Nop(inner) => inner.serialize_boxed(se),
+            Self::Reinit(inner) => inner.serialize_boxed(se),
+            Self::Query(inner) => inner.serialize_boxed(se),
+            Self::Answer(inner) => inner.serialize_boxed(se),
+            Self::Part(inner) => inner.serialize_boxed(se),
+        }
+    }
+}
+
+impl SerializeBoxed for AdnlMessage {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        self.serialize(se);
+    }
+}
+
+impl Deserialize for AdnlMessage {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let constructor_number = de.parse_constructor_numer()?;
+
+        Self::deserialize_boxed(constructor_number, de)
+    }
+}
+
+impl DeserializeBoxed for AdnlMessage {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        match constructor_number {
+            AdnlMessageCreateChannel::CONSTRUCTOR_NUMBER_BE => Ok(Self::CreateChannel(
+                AdnlMessageCreateChannel::deserialize(de)?,
+            )),
+            AdnlMessageConfirmChannel::CONSTRUCTOR_NUMBER_BE => Ok(Self::ConfirmChannel(
+                AdnlMessageConfirmChannel::deserialize(de)?,
+            )),
+            AdnlMessageCustom::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Custom(AdnlMessageCustom::deserialize(de)?))
+            }
+            AdnlMessageNop::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Nop(AdnlMessageNop::deserialize(de)?))
+            }
+            AdnlMessageReinit::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Reinit(AdnlMessageReinit::deserialize(de)?))
+            }
+            AdnlMessagePart::CONSTRUCTOR_NUMBER_BE => {
+                Ok(Self::Part(AdnlMessagePart::deserialize(de)?))
+            }
+            ADNL_MESSAGE_QUERY => Ok(Self::Query(AdnlMessageQuery::deserialize(de)?)),
+            ADNL_MESSAGE_ANSWER => Ok(Self::Answer(AdnlMessageAnswer::deserialize(de)?)),
+            _ => Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            )),
+        }
+    }
+}
+
+/// ```tl
+/// adnl.packetContents
+///   rand1:bytes
+///   flags:#
+///   from:flags.0?PublicKey
+///   from_short:flags.1?adnl.id.short
+///   message:flags.2?adnl.Message
+///   messages:flags.3?(vector adnl.Message)
+///   address:flags.4?adnl.addressList
+///   priority_address:flags.5?adnl.addressList
+///   seqno:flags.6?long
+///   confirm_seqno:flags.7?long
+///   recv_addr_list_version:flags.8?int
+///   recv_priority_addr_list_version:flags.9?int
+///   reinit_date:flags.10?int
+///   dst_reinit_date:flags.10?int
+///   signature:flags.11?bytes
+///   rand2:bytes
+///   = adnl.PacketContents;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AdnlPacketContents {
+    pub rand1: Bytes,
+    pub from: Option<PubEd25519>,
+    pub from_short: Option<AdnlIdShort>,
+    pub message: Option<AdnlMessage>,
+    pub messages: Option<Vector<AdnlMessage>>,
+    pub address: Option<AdnlAddressList>,
+    pub priority_address: Option<AdnlAddressList>,
+    pub seqno: Option<Long>,
+    pub confirm_seqno: Option<Long>,
+    pub recv_addr_list_version: Option<Int>,
+    pub recv_priority_addr_list_version: Option<Int>,
+    pub reinit_date: Option<(Int, Int)>,
+    pub signature: Option<Bytes>,
+    pub rand2: Bytes,
+}
+
+impl AdnlPacketContents {
+    const CONSTRUCTOR_NUMBER_BE: u32 = 0x89cd42d1;
+
+    fn flags(&self) -> Int31 {
+        [
+            self.from.is_some(),
+            self.from_short.is_some(),
+            self.message.is_some(),
+            self.messages.is_some(),
+            self.address.is_some(),
+            self.priority_address.is_some(),
+            self.seqno.is_some(),
+            self.confirm_seqno.is_some(),
+            self.recv_addr_list_version.is_some(),
+            self.recv_priority_addr_list_version.is_some(),
+            self.reinit_date.is_some(),
+            self.signature.is_some(),
+        ]
+        .into_iter()
+        .enumerate()
+        .fold(0, |flags, (bit, set)| flags | ((set as i32) << bit))
+    }
+}
+
+impl Serialize for AdnlPacketContents {
+    fn serialize(&self, se: &mut Serializer) {
+        se.write_bytes(&self.rand1);
+        se.write_i31(self.flags());
+        if let Some(from) = &self.from {
+            from.serialize_boxed(se);
+        }
+        if let Some(from_short) = &self.from_short {
+            from_short.serialize(se);
+        }
+        if let Some(message) = &self.message {
+            message.serialize_boxed(se);
+        }
+        if let Some(messages) = &self.messages {
+            messages.serialize(se);
+        }
+        if let Some(address) = &self.address {
+            address.serialize(se);
+        }
+        if let Some(priority_address) = &self.priority_address {
+            priority_address.serialize(se);
+        }
+        if let Some(seqno) = self.seqno {
+            se.write_i64(seqno);
+        }
+        if let Some(confirm_seqno) = self.confirm_seqno {
+            se.write_i64(confirm_seqno);
+        }
+        if let Some(version) = self.recv_addr_list_version {
+            se.write_i32(version);
+        }
+        if let Some(version) = self.recv_priority_addr_list_version {
+            se.write_i32(version);
+        }
+        if let Some((reinit_date, dst_reinit_date)) = self.reinit_date {
+            se.write_i32(reinit_date);
+            se.write_i32(dst_reinit_date);
+        }
+        if let Some(signature) = &self.signature {
+            se.write_bytes(signature);
+        }
+        se.write_bytes(&self.rand2);
+    }
+}
+
+impl SerializeBoxed for AdnlPacketContents {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        se.write_constructor_number(Self::CONSTRUCTOR_NUMBER_BE);
+        self.serialize(se)
+    }
+}
+
+impl Deserialize for AdnlPacketContents {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+        let rand1 = de.parse_bytes()?;
+        let flags = de.parse_i31()?;
+        let is_set = |bit: i32| flags & (1 << bit) != 0;
+
+        let from = if is_set(0) {
+            Some(PubEd25519::deserialize_boxed(
+                de.parse_constructor_numer()?,
+                de,
+            )?)
+        } else {
+            None
+        };
+        let from_short = if is_set(1) {
+            Some(AdnlIdShort::deserialize(de)?)
+        } else {
+            None
+        };
+        let message = if is_set(2) {
+            Some(AdnlMessage::deserialize(de)?)
+        } else {
+            None
+        };
+        let messages = if is_set(3) {
+            Some(Vector::<AdnlMessage>::deserialize(de)?)
+        } else {
+            None
+        };
+        let address = if is_set(4) {
+            Some(AdnlAddressList::deserialize(de)?)
+        } else {
+            None
+        };
+        let priority_address = if is_set(5) {
+            Some(AdnlAddressList::deserialize(de)?)
+        } else {
+            None
+        };
+        let seqno = if is_set(6) {
+            Some(de.parse_i64()?)
+        } else {
+            None
+        };
+        let confirm_seqno = if is_set(7) {
+            Some(de.parse_i64()?)
+        } else {
+            None
+        };
+        let recv_addr_list_version = if is_set(8) {
+            Some(de.parse_i32()?)
+        } else {
+            None
+        };
+        let recv_priority_addr_list_version = if is_set(9) {
+            Some(de.parse_i32()?)
+        } else {
+            None
+        };
+        let reinit_date = if is_set(10) {
+            Some((de.parse_i32()?, de.parse_i32()?))
+        } else {
+            None
+        };
+        let signature = if is_set(11) {
+            Some(de.parse_bytes()?)
+        } else {
+            None
+        };
+        let rand2 = de.parse_bytes()?;
+
+        Ok(Self {
+            rand1,
+            from,
+            from_short,
+            message,
+            messages,
+            address,
+            priority_address,
+            seqno,
+            confirm_seqno,
+            recv_addr_list_version,
+            recv_priority_addr_list_version,
+            reinit_date,
+            signature,
+            rand2,
+        })
+    }
+}
+
+impl DeserializeBoxed for AdnlPacketContents {
+    fn deserialize_boxed(
+        constructor_number: u32,
+        de: &mut Deserializer,
+    ) -> Result<Self, DeserializerBoxedError> {
+        if constructor_number != Self::CONSTRUCTOR_NUMBER_BE {
+            Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                constructor_number,
+            ))
+        } else {
+            Self::deserialize(de)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adnl_tcp::deserializer::from_bytes_boxed;
+    use adnl_tcp::serializer::to_bytes_boxed;
+
+    #[test]
+    fn serialize_address_udp() {
+        let address = AdnlAddress::Udp(AdnlAddressUdp {
+            ip: 0x7f000001,
+            port: 30303,
+        });
+
+        let bytes = to_bytes_boxed(&address);
+
+        assert_eq!(bytes, hex::decode("e7a60d670100007f5f760000").unwrap());
+    }
+
+    #[test]
+    fn serialize_nop() {
+        let bytes = to_bytes_boxed(&AdnlMessage::Nop(AdnlMessageNop {}));
+
+        assert_eq!(bytes, hex::decode("dadff817").unwrap());
+    }
+
+    #[test]
+    fn deserialize_query_as_message() {
+        let bytes = hex::decode("7af98bb477c1545b96fa136b8e01cc08338bec47e8a43215492dda6d4d7e286382bb00c40cdf068c79042ee6b589000000000000").unwrap();
+
+        let message = from_bytes_boxed::<AdnlMessage>(&bytes).unwrap();
+
+        assert!(
+            matches!(message, AdnlMessage::Query(AdnlMessageQuery { query, .. }) if query == hex::decode("df068c79042ee6b589000000").unwrap())
+        );
+    }
+
+    #[test]
+    fn packet_contents_roundtrip() {
+        let contents = AdnlPacketContents {
+            rand1: vec![1; 15],
+            from: Some(PubEd25519 { key: [2; 32] }),
+            messages: Some(vec![
+                AdnlMessage::CreateChannel(AdnlMessageCreateChannel {
+                    key: [3; 32],
+                    date: 1700000000,
+                }),
+                AdnlMessage::Custom(AdnlMessageCustom { data: vec![4; 300] }),
+            ]),
+            address: Some(AdnlAddressList {
+                addrs: vec![AdnlAddress::Udp6(AdnlAddressUdp6 { ip: 1, port: 3 })],
+                ..Default::default()
+            }),
+            seqno: Some(1),
+            confirm_seqno: Some(0),
+            reinit_date: Some((1700000000, 0)),
+            signature: Some(vec![5; 64]),
+            rand2: vec![6; 7],
+            ..Default::default()
+        };
+
+        let bytes = to_bytes_boxed(&contents);
+        let actual = from_bytes_boxed::<AdnlPacketContents>(&bytes).unwrap();
+
+        assert_eq!(bytes[0..4], [0x89, 0xcd, 0x42, 0xd1]);
+        assert_eq!(actual, contents);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::backend::Backend;
+use crate::config::TypeConfiguration;
+use crate::{field_name, structure_ident};
+use anyhow::bail;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+use tl_parser::{Combinator, Condition};
+
+/// Binary TL serialization on top of `adnl_tcp::serializer` and `adnl_tcp::deserializer`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AdnlBinary;
+
+impl Backend for AdnlBinary {
+    fn skip_list(&self) -> &[&str] {
+        &[
+            "Vector t",
+            "Int32",
+            "Int53",
+            "Int64",
+            "Int128",
+            "Int256",
+            "Bytes",
+            "SecureString",
+            "SecureBytes",
+            "Function",
+            // provided by adnl_tcp::types
+            "adnl.Message",
+        ]
+    }
+
+    fn default_derives(&self) -> Vec<String> {
+        ["Debug", "Clone", "PartialEq", "Eq"]
+            .map(str::to_owned)
+            .to_vec()
+    }
+
+    fn derives(&self, definition: &Combinator, configuration: &TypeConfiguration) -> Vec<String> {
+        let mut derives = configuration
+            .derives
+            .clone()
+            .unwrap_or_else(|| self.default_derives());
+        if definition.fields().is_empty() && !derives.iter().any(|d| d == "Default") {
+            derives.push("Default".to_owned());
+        }
+
+        derives
+    }
+
+    fn boxed_type(
+        &self,
+        name: &Ident,
+        constructors: &[&Combinator],
+    ) -> anyhow::Result<TokenStream> {
+        let variants: Vec<_> = constructors
+            .iter()
+            .map(|combinator| structure_ident(combinator.id()))
+            .collect();
+
+        Ok(quote! {
+            #[derive(Clone, Debug, PartialEq, Eq)]
+            pub enum #name {
+                #(#variants(#variants)),*
+            }
+
+            impl Serialize for #name {
+                fn serialize(&self, se: &mut Serializer) {
+                    se.write_constructor_number(match self {
+                        #(Self::#variants { .. } => #variants::CONSTRUCTOR_NUMBER_BE),*
+                    });
+                    match self {
+                        #(Self::#variants(inner) => inner.serialize(se)),*
+                    }
+                }
+            }
+
+            impl SerializeBoxed for #name {
+                fn serialize_boxed(&self, se: &mut Serializer) {
+                    self.serialize(se);
+                }
+            }
+
+            impl Deserialize for #name {
+                fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+                    let constructor_number = de.parse_constructor_numer()?;
+
+                    Self::deserialize_boxed(constructor_number, de)
+                }
+            }
+
+            impl DeserializeBoxed for #name {
+                fn deserialize_boxed(constructor_number: u32, de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+                    match constructor_number {
+                        #(#variants::CONSTRUCTOR_NUMBER_BE => { Ok(Self::#variants(#variants::deserialize(de)?)) }),*
+                        _ => Err(DeserializerBoxedError::UnexpectedConstructorNumber(constructor_number))
+                    }
+                }
+            }
+        })
+    }
+
+    fn bare_impls(
+        &self,
+        name: &Ident,
+        definition: &Combinator,
+        configuration: &TypeConfiguration,
+    ) -> anyhow::Result<TokenStream> {
+        if configuration.fields.values().any(|field| {
+            field.skip
+                || field.optional
+                || field.serialize_with.is_some()
+                || field.deserialize_with.is_some()
+                || field.rust_type.is_some()
+        }) {
+            bail!(
+                "{}: only derives can be configured for binary format",
+                definition.id()
+            );
+        }
+
+        let mut serialize_defs = Vec::new();
+        let mut serialize_fields = Vec::new();
+        let mut deserialize_fields = Vec::new();
+        let mut deserialize_pass = Vec::new();
+        for field in definition.fields() {
+            let field_name = format_ident!("{}", field_name(field)?);
+
+            let (def, write) = match field.type_condition() {
+                None => {
+                    let def = match field.field_type() {
+                        Some("#") => quote! { let mut #field_name = self.#field_name; },
+                        // TODO[akostylev0] bool optimization
+                        Some("int") | Some("long") => {
+                            quote! { let #field_name = self.#field_name; }
+                        }
+                        _ => quote! { let #field_name = &self.#field_name; },
+                    };
+                    let write = match field.field_type() {
+                        Some("#") => quote! { se.write_i31(#field_name); },
+                        Some("int") => quote! { se.write_i32(#field_name); },
+                        Some("long") => quote! { se.write_i64(#field_name); },
+                        Some("int256") => quote! { se.write_i256(#field_name); },
+                        Some("bytes") => quote! { se.write_bytes(#field_name); },
+                        Some("string") => quote! { se.write_string(#field_name); },
+                        _ => quote! { #field_name.serialize(se); },
+                    };
+
+                    (def, write)
+                }
+                Some(Condition {
+                    field_ref,
+                    bit_selector: Some(bit_selector),
+                }) => {
+                    let field_ref = format_ident!("{}", field_ref);
+                    let def = quote! {
+                        let #field_name = self.#field_name.as_ref();
+                        if #field_name.is_some() {
+                            #field_ref |= 1 << #bit_selector;
+                        }
+                    };
+                    let inner = match field.field_type() {
+                        Some("#") => quote! { se.write_i31(*value) },
+                        Some("int") => quote! { se.write_i32(*value) },
+                        Some("long") => quote! { se.write_i64(*value) },
+                        Some("int256") => quote! { se.write_i256(value) },
+                        Some("bytes") => quote! { se.write_bytes(value) },
+                        Some("string") => quote! { se.write_string(value) },
+                        _ => quote! { value.serialize(se) },
+                    };
+                    let write = quote! {
+                        match #field_name {
+                            None => {},
+                            Some(value) => #inner,
+                        };
+                    };
+
+                    (def, write)
+                }
+                Some(Condition {
+                    bit_selector: None, ..
+                }) => bail!(
+                    "{}: only `flags.N?` conditions are supported",
+                    definition.id()
+                ),
+            };
+            serialize_defs.push(def);
+            serialize_fields.push(write);
+
+            let parse_fn = match field.field_type() {
+                Some("#") => quote! { de.parse_i31()? },
+                Some("int") => quote! { de.parse_i32()? },
+                Some("long") => quote! { de.parse_i64()? },
+                Some("int256") => quote! { de.parse_i256()? },
+                Some("bytes") => quote! { de.parse_bytes()? },
+                Some("string") => quote! { de.parse_string()? },
+                Some(field_type) => {
+                    let field_type = structure_ident(field_type);
+                    quote! { #field_type::deserialize(de)? }
+                }
+                None => bail!("{}: repetitions are not supported", definition.id()),
+            };
+            deserialize_fields.push(match field.type_condition() {
+                Some(Condition {
+                    field_ref,
+                    bit_selector: Some(bit_selector),
+                }) => {
+                    let field_ref = format_ident!("{}", field_ref);
+                    quote! {
+                        let #field_name = if #field_ref & (1 << #bit_selector) > 0 { Some(#parse_fn) } else { None };
+                    }
+                }
+                _ => quote! { let #field_name = #parse_fn; },
+            });
+            deserialize_pass.push(quote! { #field_name, });
+        }
+
+        let constructor_number_be = definition.constructor_number_be();
+
+        Ok(quote! {
+            impl #name {
+                const CONSTRUCTOR_NUMBER_BE: u32 = #constructor_number_be;
+            }
+
+            impl Serialize for #name {
+                #[allow(unused_variables)]
+                fn serialize(&self, se: &mut Serializer) {
+                    #(#serialize_defs)*
+
+                    #(#serialize_fields)*
+                }
+            }
+
+            impl SerializeBoxed for #name {
+                #[allow(unused_variables)]
+                fn serialize_boxed(&self, se: &mut Serializer) {
+                    se.write_constructor_number(#constructor_number_be);
+                    self.serialize(se)
+                }
+            }
+
+            impl Deserialize for #name {
+                #[allow(unused_variables)]
+                fn deserialize(de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+                    #(#deserialize_fields)*
+
+                    Ok(Self {
+                        #(#deserialize_pass)*
+                    })
+                }
+            }
+
+            impl DeserializeBoxed for #name {
+                #[allow(unused_variables)]
+                fn deserialize_boxed(constructor_number: u32, de: &mut Deserializer) -> Result<Self, DeserializerBoxedError> {
+                    if constructor_number != #constructor_number_be {
+                        Err(DeserializerBoxedError::UnexpectedConstructorNumber(constructor_number))
+                    } else {
+                        Self::deserialize(de)
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use crate::backend::Backend;
+use crate::config::FieldConfiguration;
+use crate::structure_ident;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use tl_parser::Combinator;
+
+/// `serde` (de)serialization in the tonlib JSON format: `{"@type": "...", ...}`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SerdeJson;
+
+impl Backend for SerdeJson {
+    fn skip_list(&self) -> &[&str] {
+        &[
+            "Vector t",
+            "Bool",
+            "Int32",
+            "Int53",
+            "Int64",
+            "Int256",
+            "Bytes",
+            "SecureString",
+            "SecureBytes",
+            "Object",
+            "Function",
+        ]
+    }
+
+    fn default_derives(&self) -> Vec<String> {
+        ["Debug", "Clone", "Serialize", "Deserialize"]
+            .map(str::to_owned)
+            .to_vec()
+    }
+
+    fn boxed_type(
+        &self,
+        name: &Ident,
+        constructors: &[&Combinator],
+    ) -> anyhow::Result<TokenStream> {
+        let variants = constructors.iter().map(|combinator| {
+            let variant = structure_ident(combinator.id());
+
+            quote! { #variant(#variant) }
+        });
+
+        Ok(quote! {
+            #[derive(Deserialize, Serialize, Clone, Debug)]
+            #[serde(untagged)]
+            pub enum #name {
+                #(#variants),*
+            }
+        })
+    }
+
+    fn type_attributes(&self, definition: &Combinator) -> TokenStream {
+        let id = definition.id();
+
+        quote! {
+            #[serde(tag = "@type", rename = #id)]
+        }
+    }
+
+    fn field_attributes(
+        &self,
+        field: &tl_parser::Field,
+        configuration: &FieldConfiguration,
+    ) -> TokenStream {
+        if configuration.rust_type.is_some() {
+            return typed_field_attributes(field, configuration);
+        }
+
+        let serialize_with = configuration
+            .serialize_with
+            .as_ref()
+            .map(|serialize_with| quote! { #[serde(serialize_with = #serialize_with)] });
+
+        // TODO[akostylev0]: just write custom wrappers for primitive types
+        let number_from_string = is_number(field);
+
+        match &configuration.deserialize_with {
+            Some(deserialize_with) => quote! {
+                #serialize_with
+                #[serde(deserialize_with = #deserialize_with)]
+            },
+            None if number_from_string => quote! {
+                #serialize_with
+                #[serde(default)]
+                #[serde(deserialize_with = "deserialize_number_from_string")]
+            },
+            None => quote! { #serialize_with },
+        }
+    }
+}
+
+/// Fields with a configured Rust type go through `json_repr`, which is expected in scope of the
+/// generated code and keeps the tonlib representation, e.g. base64 for bytes.
+fn typed_field_attributes(
+    field: &tl_parser::Field,
+    configuration: &FieldConfiguration,
+) -> TokenStream {
+    let serialize_with = configuration
+        .serialize_with
+        .as_deref()
+        .unwrap_or("json_repr::serialize");
+    let deserialize_with = configuration
+        .deserialize_with
+        .as_deref()
+        .unwrap_or("json_repr::deserialize");
+    let default = (field.type_is_optional() || configuration.optional || is_number(field))
+        .then(|| quote! { #[serde(default)] });
+
+    quote! {
+        #default
+        #[serde(serialize_with = #serialize_with)]
+        #[serde(deserialize_with = #deserialize_with)]
+    }
+}
+
+fn is_number(field: &tl_parser::Field) -> bool {
+    !field.type_is_polymorphic()
+        && field
+            .field_type()
+            .is_some_and(|typ| matches!(typ, "#" | "int32" | "int64" | "int53" | "int256"))
+}
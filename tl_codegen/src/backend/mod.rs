@@ -0,0 +1,53 @@
+mod binary;
+mod json;
+
+pub use binary::AdnlBinary;
+pub use json::SerdeJson;
+
+use crate::config::{FieldConfiguration, TypeConfiguration};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+use tl_parser::Combinator;
+
+/// Wire format specific part of the generated code.
+pub trait Backend {
+    /// Boxed types provided by the runtime crate, neither they nor their constructors are generated.
+    fn skip_list(&self) -> &[&str];
+
+    /// Derives of a bare type without an explicit configuration.
+    fn default_derives(&self) -> Vec<String>;
+
+    fn derives(&self, _definition: &Combinator, configuration: &TypeConfiguration) -> Vec<String> {
+        configuration
+            .derives
+            .clone()
+            .unwrap_or_else(|| self.default_derives())
+    }
+
+    /// Enum over the constructors of a boxed type with more than one constructor.
+    fn boxed_type(&self, name: &Ident, constructors: &[&Combinator])
+        -> anyhow::Result<TokenStream>;
+
+    fn type_attributes(&self, _definition: &Combinator) -> TokenStream {
+        quote! {}
+    }
+
+    fn field_attributes(
+        &self,
+        _field: &tl_parser::Field,
+        _configuration: &FieldConfiguration,
+    ) -> TokenStream {
+        quote! {}
+    }
+
+    /// Additional items emitted next to a bare type, e.g. trait implementations.
+    fn bare_impls(
+        &self,
+        _name: &Ident,
+        _definition: &Combinator,
+        _configuration: &TypeConfiguration,
+    ) -> anyhow::Result<TokenStream> {
+        Ok(quote! {})
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+pub fn configure_type() -> TypeConfigurationBuilder {
+    Default::default()
+}
+
+pub fn configure_field() -> FieldConfigurationBuilder {
+    Default::default()
+}
+
+#[derive(Default)]
+pub struct TypeConfigurationBuilder {
+    derives: Option<Vec<String>>,
+    fields: HashMap<String, FieldConfiguration>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TypeConfiguration {
+    /// `None` falls back to the backend defaults.
+    pub derives: Option<Vec<String>>,
+    pub fields: HashMap<String, FieldConfiguration>,
+}
+
+impl TypeConfiguration {
+    pub fn field(&self, name: &str) -> &FieldConfiguration {
+        static DEFAULT: FieldConfiguration = FieldConfiguration {
+            skip: false,
+            optional: false,
+            deserialize_with: None,
+            serialize_with: None,
+            rust_type: None,
+        };
+
+        self.fields.get(name).unwrap_or(&DEFAULT)
+    }
+}
+
+#[derive(Default)]
+pub struct FieldConfigurationBuilder {
+    skip: bool,
+    optional: bool,
+    deserialize_with: Option<String>,
+    serialize_with: Option<String>,
+    rust_type: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FieldConfiguration {
+    /// Left out of the generated TL type. The [`ProtoGenerator`](crate::ProtoGenerator) leaves
+    /// it out of the message only and fills it with `Default::default()` converting back.
+    pub skip: bool,
+    pub optional: bool,
+    pub deserialize_with: Option<String>,
+    pub serialize_with: Option<String>,
+    /// Replaces the Rust type of the field, `Option` is still added for optional fields.
+    pub rust_type: Option<String>,
+}
+
+impl FieldConfigurationBuilder {
+    pub fn skip(mut self) -> Self {
+        self.skip = true;
+
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+
+        self
+    }
+
+    pub fn deserialize_with(mut self, deserialize_with: &str) -> Self {
+        self.deserialize_with = Some(deserialize_with.to_owned());
+
+        self
+    }
+
+    pub fn serialize_with(mut self, serialize_with: &str) -> Self {
+        self.serialize_with = Some(serialize_with.to_owned());
+
+        self
+    }
+
+    pub fn rust_type(mut self, rust_type: &str) -> Self {
+        self.rust_type = Some(rust_type.to_owned());
+
+        self
+    }
+
+    pub fn build(self) -> FieldConfiguration {
+        FieldConfiguration {
+            skip: self.skip,
+            optional: self.optional,
+            deserialize_with: self.deserialize_with,
+            serialize_with: self.serialize_with,
+            rust_type: self.rust_type,
+        }
+    }
+}
+
+impl TypeConfigurationBuilder {
+    pub fn derives(mut self, derives: Vec<&str>) -> Self {
+        let mut derives: Vec<_> = derives.into_iter().map(|s| s.to_owned()).collect();
+        derives.push("Debug".to_owned());
+        self.derives = Some(derives);
+
+        self
+    }
+
+    pub fn field(mut self, field: &str, configuration: FieldConfiguration) -> Self {
+        self.fields.insert(field.to_owned(), configuration);
+
+        self
+    }
+
+    pub fn build(self) -> TypeConfiguration {
+        TypeConfiguration {
+            derives: self.derives,
+            fields: self.fields,
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Rust code generation from TL schemes.
+//!
+//! The [`Generator`] groups combinators by their result type, emits a Rust type per boxed and
+//! bare type and leaves everything wire-format specific to a [`Backend`]:
+//! [`SerdeJson`](backend::SerdeJson) for `tonlib_api` and [`AdnlBinary`](backend::AdnlBinary)
+//! for `lite_api`.
+
+pub mod backend;
+mod config;
+mod naming;
+mod proto;
+mod service;
+
+pub use crate::backend::Backend;
+pub use crate::config::{
+    configure_field, configure_type, FieldConfiguration, FieldConfigurationBuilder,
+    TypeConfiguration, TypeConfigurationBuilder,
+};
+pub use crate::naming::{generate_type_name, structure_ident};
+pub use crate::proto::{ProtoGenerator, ProtoOutput};
+
+use anyhow::{anyhow, Context};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::{env, fs};
+use syn::MetaList;
+use tl_parser::Combinator;
+
+pub struct Generator<B> {
+    backend: B,
+    types: HashMap<String, TypeConfiguration>,
+    type_mappings: HashMap<String, String>,
+    service_trait: Option<String>,
+}
+
+impl<B: Backend> Generator<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            types: Default::default(),
+            type_mappings: Default::default(),
+            service_trait: None,
+        }
+    }
+
+    pub fn configure(mut self, name: &str, derives: Vec<&str>) -> Self {
+        self.types
+            .insert(name.to_owned(), configure_type().derives(derives).build());
+
+        self
+    }
+
+    pub fn configure_full(mut self, name: &str, configuration: TypeConfiguration) -> Self {
+        self.types.insert(name.to_owned(), configuration);
+
+        self
+    }
+
+    /// Maps every field of the TL type `tl_type`, vector elements included, to `rust_type`.
+    /// Configured fields take precedence.
+    pub fn map_type(mut self, tl_type: &str, rust_type: &str) -> Self {
+        self.type_mappings
+            .insert(tl_type.to_owned(), rust_type.to_owned());
+
+        self
+    }
+
+    /// Also emits an extension trait `name` with a method per function, calling it on any
+    /// `tower::Service` accepting the function's request type.
+    pub fn service_trait(mut self, name: &str) -> Self {
+        self.service_trait = Some(name.to_owned());
+
+        self
+    }
+
+    /// Reads the scheme from `input` and writes the generated code to `$OUT_DIR/output`.
+    pub fn generate<I: AsRef<Path>, O: AsRef<Path>>(
+        &self,
+        input: I,
+        output: O,
+    ) -> anyhow::Result<()> {
+        let content = fs::read_to_string(input.as_ref())
+            .with_context(|| format!("failed to read {}", input.as_ref().display()))?;
+        let formatted = self.generate_string(&content)?;
+
+        let out_dir = env::var_os("OUT_DIR").ok_or_else(|| anyhow!("OUT_DIR is not set"))?;
+        let dest_path = Path::new(&out_dir).join(output);
+
+        fs::write(dest_path, formatted)?;
+
+        Ok(())
+    }
+
+    /// Generates formatted code for the scheme, types are emitted in a stable order.
+    pub fn generate_string(&self, scheme: &str) -> anyhow::Result<String> {
+        let combinators = tl_parser::validate(scheme)?;
+
+        let mut map: BTreeMap<String, Vec<Combinator>> = BTreeMap::default();
+        for combinator in combinators.into_iter() {
+            map.entry(combinator.result_type().to_owned())
+                .or_default()
+                .push(combinator);
+        }
+
+        let mut formatted = String::new();
+        let mut functions = Vec::new();
+        for (type_ident, types) in map.iter() {
+            if self.backend.skip_list().contains(&type_ident.as_str()) {
+                continue;
+            }
+
+            let constructors: Vec<_> = types
+                .iter()
+                .filter(|combinator| !combinator.is_functional())
+                .collect();
+
+            let struct_name = structure_ident(type_ident);
+            let output = if constructors.len() == 1 {
+                let name = structure_ident(constructors[0].id());
+
+                quote! {
+                    pub type #struct_name = #name;
+                }
+            } else {
+                self.backend.boxed_type(&struct_name, &constructors)?
+            };
+
+            formatted += &unparse(output)?;
+
+            for definition in types.iter() {
+                if definition.is_builtin()
+                    || definition.id() == "vector"
+                    || definition.id() == "int256"
+                {
+                    continue;
+                }
+
+                formatted += &unparse(self.bare_type(definition)?)?;
+            }
+
+            functions.extend(types.iter().filter(|combinator| combinator.is_functional()));
+        }
+
+        if let Some(name) = &self.service_trait {
+            functions.sort_by_key(|function| function.id());
+            let name = format_ident!("{}", name);
+
+            formatted += &unparse(service::service_trait(&name, &functions)?)?;
+        }
+
+        Ok(formatted)
+    }
+
+    fn bare_type(&self, definition: &Combinator) -> anyhow::Result<TokenStream> {
+        let configuration = &self.type_configuration(definition)?;
+
+        let struct_name = structure_ident(definition.id());
+
+        let derives = self.backend.derives(definition, configuration);
+        let derives = syn::parse_str::<MetaList>(&format!("derive({})", derives.join(",")))?;
+        let attributes = self.backend.type_attributes(definition);
+
+        let fields = definition
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = field_name(field)?;
+                let field_configuration = configuration.field(&name);
+                if field_configuration.skip {
+                    return Ok(None);
+                }
+
+                let field_ident = format_ident!("{}", name);
+                let field_type = field_type(field, field_configuration)?;
+                let attributes = self.backend.field_attributes(field, field_configuration);
+
+                Ok(Some(quote! {
+                    #attributes
+                    pub #field_ident: #field_type
+                }))
+            })
+            .filter_map(Result::transpose)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let functional = if definition.is_functional() {
+            let result_name = structure_ident(definition.result_type());
+            quote! {
+                impl Functional for #struct_name {
+                    type Result = #result_name;
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let impls = self
+            .backend
+            .bare_impls(&struct_name, definition, configuration)?;
+
+        Ok(quote! {
+            #[#derives]
+            #attributes
+            pub struct #struct_name {
+                #(#fields),*
+            }
+
+            #functional
+
+            #impls
+        })
+    }
+
+    /// Configuration of the type with the global type mappings applied to its fields.
+    fn type_configuration(&self, definition: &Combinator) -> anyhow::Result<TypeConfiguration> {
+        let mut configuration = self.types.get(definition.id()).cloned().unwrap_or_default();
+
+        for field in definition.fields() {
+            let mapped = match (field.field_type(), field.type_variables()) {
+                (Some("vector" | "Vector"), Some(variables)) => variables
+                    .first()
+                    .and_then(|element| self.type_mappings.get(element))
+                    .map(|element| format!("Vector<{}>", element)),
+                (Some(field_type), _) if !field.type_is_polymorphic() => {
+                    self.type_mappings.get(field_type).cloned()
+                }
+                _ => None,
+            };
+            let Some(mapped) = mapped else {
+                continue;
+            };
+
+            let field_configuration = configuration.fields.entry(field_name(field)?).or_default();
+            if field_configuration.rust_type.is_none() {
+                field_configuration.rust_type = Some(mapped);
+            }
+        }
+
+        Ok(configuration)
+    }
+}
+
+fn unparse(tokens: TokenStream) -> anyhow::Result<String> {
+    let syntax_tree = syn::parse2(tokens)?;
+
+    Ok(prettyplease::unparse(&syntax_tree))
+}
+
+pub(crate) fn field_name(field: &tl_parser::Field) -> anyhow::Result<String> {
+    field
+        .id()
+        .map(|id| id.to_case(Case::Snake))
+        .ok_or_else(|| anyhow!("anonymous fields are not supported: {:?}", field))
+}
+
+/// Rust type of the field, `#` is `Int31` and conditional fields are wrapped into `Option`.
+pub(crate) fn field_type(
+    field: &tl_parser::Field,
+    configuration: &FieldConfiguration,
+) -> anyhow::Result<syn::Type> {
+    let field_type = field
+        .field_type()
+        .ok_or_else(|| anyhow!("repetitions are not supported: {:?}", field))?;
+
+    let mut name = if let Some(rust_type) = &configuration.rust_type {
+        rust_type.clone()
+    } else if field_type == "#" {
+        "Int31".to_owned()
+    } else if field.type_is_polymorphic() {
+        let args: Vec<_> = field
+            .type_variables()
+            .unwrap_or_default()
+            .iter()
+            .map(|s| generate_type_name(s))
+            .collect();
+
+        format!("{}<{}>", generate_type_name(field_type), args.join(","))
+    } else {
+        generate_type_name(field_type)
+    };
+
+    if field.type_is_optional() || configuration.optional {
+        name = format!("Option<{}>", name);
+    }
+
+    Ok(syn::parse_str(&name)?)
+}
@@ -0,0 +1,54 @@
+use convert_case::Case::UpperCamel;
+use convert_case::Casing;
+use quote::format_ident;
+use syn::Ident;
+
+/// Rust name of a TL type: `liteServer.accountId` -> `LiteServerAccountId`,
+/// boxed types get a `Boxed` prefix: `liteServer.AccountId` -> `LiteServerBoxedAccountId`.
+pub fn generate_type_name(s: &str) -> String {
+    let (ns, name) = s.rsplit_once('.').unwrap_or(("", s));
+
+    let boxed_prefix = if name.starts_with(|c: char| c.is_uppercase()) {
+        "Boxed"
+    } else {
+        ""
+    };
+
+    let ns_prefix = ns
+        .split('.')
+        .map(|f| f.to_case(UpperCamel))
+        .collect::<Vec<_>>()
+        .join("");
+
+    format!("{}{}{}", ns_prefix, boxed_prefix, name.to_case(UpperCamel))
+}
+
+pub fn structure_ident(s: &str) -> Ident {
+    format_ident!("{}", generate_type_name(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_name_bare() {
+        assert_eq!(
+            generate_type_name("liteServer.accountId"),
+            "LiteServerAccountId"
+        );
+        assert_eq!(generate_type_name("int256"), "Int256");
+    }
+
+    #[test]
+    fn type_name_boxed() {
+        assert_eq!(
+            generate_type_name("liteServer.AccountId"),
+            "LiteServerBoxedAccountId"
+        );
+        assert_eq!(
+            generate_type_name("tonNode.blockIdExt"),
+            "TonNodeBlockIdExt"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Protobuf messages and conversions for selected TL types.
+//!
+//! Every selected combinator becomes a proto3 message, boxed types with several constructors
+//! become a message with a `oneof value`. Referenced types are included as well, named after
+//! their Rust type unless selected explicitly. The generated Rust code is meant to be included
+//! next to the prost types and converts them from and into the generated TL types.
+
+use crate::config::{FieldConfiguration, TypeConfiguration};
+use crate::{field_name, generate_type_name, structure_ident, unparse};
+use anyhow::{anyhow, bail, Context};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs};
+use tl_parser::{Combinator, Field};
+
+pub struct ProtoGenerator {
+    package: String,
+    rust_module: String,
+    base64_bytes: bool,
+    messages: Vec<(String, String)>,
+    types: HashMap<String, TypeConfiguration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtoOutput {
+    pub proto: String,
+    pub conversions: String,
+}
+
+impl ProtoGenerator {
+    /// `rust_module` is the path of the generated TL types, e.g. `tonlibjson_client::block`.
+    pub fn new(package: &str, rust_module: &str) -> Self {
+        Self {
+            package: package.to_owned(),
+            rust_module: rust_module.to_owned(),
+            base64_bytes: false,
+            messages: Default::default(),
+            types: Default::default(),
+        }
+    }
+
+    /// `bytes`, `secureBytes` and `int256` are base64 strings in the TL types, as in `tonlib_api`.
+    pub fn base64_bytes(mut self) -> Self {
+        self.base64_bytes = true;
+
+        self
+    }
+
+    /// Selects a bare or boxed TL type and names its message.
+    pub fn message(mut self, id: &str, name: &str) -> Self {
+        self.messages.push((id.to_owned(), name.to_owned()));
+
+        self
+    }
+
+    /// Optional fields are `optional` as in the [`Generator`](crate::Generator) of the TL types.
+    /// Skipped fields are left out of the message only: the TL type keeps them and the conversion
+    /// back fills them with `Default::default()`, so their types have to implement `Default`.
+    pub fn configure_full(mut self, id: &str, configuration: TypeConfiguration) -> Self {
+        self.types.insert(id.to_owned(), configuration);
+
+        self
+    }
+
+    /// Reads the scheme from `input` and writes `$OUT_DIR/proto_output` and `$OUT_DIR/rust_output`.
+    pub fn generate<I: AsRef<Path>, P: AsRef<Path>, R: AsRef<Path>>(
+        &self,
+        input: I,
+        proto_output: P,
+        rust_output: R,
+    ) -> anyhow::Result<()> {
+        let content = fs::read_to_string(input.as_ref())
+            .with_context(|| format!("failed to read {}", input.as_ref().display()))?;
+        let output = self.generate_string(&content)?;
+
+        let out_dir = env::var_os("OUT_DIR").ok_or_else(|| anyhow!("OUT_DIR is not set"))?;
+        fs::write(Path::new(&out_dir).join(proto_output), output.proto)?;
+        fs::write(Path::new(&out_dir).join(rust_output), output.conversions)?;
+
+        Ok(())
+    }
+
+    pub fn generate_string(&self, scheme: &str) -> anyhow::Result<ProtoOutput> {
+        let combinators = tl_parser::validate(scheme)?;
+        let scope = Scope::new(&combinators, &self.messages)?;
+        let module = syn::parse_str::<syn::Path>(&self.rust_module)?;
+
+        let mut queue = self
+            .messages
+            .iter()
+            .map(|(id, _)| scope.canonical(id))
+            .collect::<anyhow::Result<VecDeque<_>>>()?;
+
+        let mut done: HashSet<String> = HashSet::new();
+        let mut proto = format!(
+            "// Generated from a TL scheme, do not edit.\nsyntax = \"proto3\";\n\npackage {};\n",
+            self.package
+        );
+        let mut conversions = TokenStream::new();
+        while let Some(id) = queue.pop_front() {
+            if !done.insert(id.clone()) {
+                continue;
+            }
+
+            let mut dependencies = Vec::new();
+            let (message, conversion) = match scope.combinators.get(id.as_str()) {
+                Some(combinator) => {
+                    self.bare_message(&scope, &module, combinator, &mut dependencies)?
+                }
+                None => self.boxed_message(&scope, &module, &id, &mut dependencies)?,
+            };
+
+            for dependency in dependencies {
+                queue.push_back(scope.canonical(&dependency)?);
+            }
+
+            proto += &message;
+            conversions.extend(conversion);
+        }
+
+        Ok(ProtoOutput {
+            proto,
+            conversions: unparse(conversions)?,
+        })
+    }
+
+    fn bare_message(
+        &self,
+        scope: &Scope,
+        module: &syn::Path,
+        combinator: &Combinator,
+        dependencies: &mut Vec<String>,
+    ) -> anyhow::Result<(String, TokenStream)> {
+        let default = TypeConfiguration::default();
+        let configuration = self.types.get(combinator.id()).unwrap_or(&default);
+
+        let proto = scope.message_ident(combinator.id())?;
+        let mut message = format!("\nmessage {} {{\n", proto);
+        let mut to_proto = Vec::new();
+        let mut from_proto = Vec::new();
+        for (tag, field) in combinator.fields().iter().enumerate() {
+            let field_name = field_name(field)?;
+            let field_configuration = configuration.field(&field_name);
+            if field_configuration.skip {
+                let ident = format_ident!("{}", field_name);
+                from_proto.push(quote! { #ident: Default::default() });
+
+                continue;
+            }
+
+            let kind = self.field_kind(scope, combinator, field, field_configuration)?;
+            if let FieldType::Message { id, .. } = &kind.ty {
+                dependencies.push(id.clone());
+            }
+
+            writeln!(
+                message,
+                "  {}{} {} = {};",
+                kind.label(),
+                kind.ty.proto(),
+                field_name,
+                tag + 1
+            )?;
+
+            let ident = format_ident!("{}", field_name);
+            to_proto.push(kind.proto_value(&ident));
+            from_proto.push(kind.tl_value(&ident, &field_name));
+        }
+        message += "}\n";
+
+        let tl = structure_ident(combinator.id());
+
+        Ok((
+            message,
+            quote! {
+                #[allow(clippy::useless_conversion)]
+                impl From<#module::#tl> for #proto {
+                    fn from(value: #module::#tl) -> Self {
+                        Self { #(#to_proto),* }
+                    }
+                }
+
+                #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+                impl TryFrom<#proto> for #module::#tl {
+                    type Error = ::anyhow::Error;
+
+                    fn try_from(value: #proto) -> Result<Self, Self::Error> {
+                        Ok(Self { #(#from_proto),* })
+                    }
+                }
+            },
+        ))
+    }
+
+    fn boxed_message(
+        &self,
+        scope: &Scope,
+        module: &syn::Path,
+        id: &str,
+        dependencies: &mut Vec<String>,
+    ) -> anyhow::Result<(String, TokenStream)> {
+        let proto = scope.message_ident(id)?;
+        let oneof = format_ident!("{}", proto.to_string().to_case(Case::Snake));
+        let tl = structure_ident(id);
+
+        let mut message = format!("\nmessage {} {{\n  oneof value {{\n", proto);
+        let mut to_proto = Vec::new();
+        let mut from_proto = Vec::new();
+        for (tag, constructor) in scope.boxed[id].iter().enumerate() {
+            let (_, field_name) = constructor
+                .id()
+                .rsplit_once('.')
+                .unwrap_or(("", constructor.id()));
+            let field_name = field_name.to_case(Case::Snake);
+            writeln!(
+                message,
+                "    {} {} = {};",
+                scope.message_ident(constructor.id())?,
+                field_name,
+                tag + 1
+            )?;
+            dependencies.push(constructor.id().to_owned());
+
+            let variant = structure_ident(constructor.id());
+            let oneof_variant = format_ident!("{}", field_name.to_case(Case::UpperCamel));
+            to_proto.push(quote! {
+                #module::#tl::#variant(inner) => #oneof::Value::#oneof_variant(inner.into())
+            });
+            from_proto.push(quote! {
+                #oneof::Value::#oneof_variant(inner) => Self::#variant(inner.try_into()?)
+            });
+        }
+        message += "  }\n}\n";
+
+        Ok((
+            message,
+            quote! {
+                impl From<#module::#tl> for #proto {
+                    fn from(value: #module::#tl) -> Self {
+                        Self {
+                            value: Some(match value {
+                                #(#to_proto),*
+                            }),
+                        }
+                    }
+                }
+
+                impl TryFrom<#proto> for #module::#tl {
+                    type Error = ::anyhow::Error;
+
+                    fn try_from(value: #proto) -> Result<Self, Self::Error> {
+                        Ok(match value.value.ok_or_else(|| ::anyhow::anyhow!("missing `value`"))? {
+                            #(#from_proto),*
+                        })
+                    }
+                }
+            },
+        ))
+    }
+
+    fn field_kind(
+        &self,
+        scope: &Scope,
+        combinator: &Combinator,
+        field: &Field,
+        configuration: &FieldConfiguration,
+    ) -> anyhow::Result<FieldKind> {
+        let optional = field.type_is_optional() || configuration.optional;
+        let field_type = field
+            .field_type()
+            .ok_or_else(|| anyhow!("repetitions are not supported: {:?}", field))?;
+
+        let (repeated, element) = match field_type {
+            "vector" | "Vector" => {
+                let variables = field.type_variables().unwrap_or_default();
+                let [element] = variables.as_slice() else {
+                    bail!("`{}`: unexpected vector {:?}", combinator.id(), field);
+                };
+
+                (true, element.clone())
+            }
+            _ => (false, field_type.to_owned()),
+        };
+
+        let ty = match element.as_str() {
+            "#" | "int" | "int32" | "Int" => FieldType::Scalar("int32"),
+            "long" | "int53" | "int64" | "Long" => FieldType::Scalar("int64"),
+            "double" | "Double" => FieldType::Scalar("double"),
+            "Bool" => FieldType::Scalar("bool"),
+            "string" | "secureString" | "String" => FieldType::Scalar("string"),
+            "bytes" | "secureBytes" | "int256" | "Bytes" if self.base64_bytes => {
+                FieldType::Scalar("string")
+            }
+            "bytes" | "secureBytes" | "int256" | "Bytes" => FieldType::Scalar("bytes"),
+            "vector" | "Vector" => {
+                bail!("`{}`: nested vectors are not supported", combinator.id())
+            }
+            name if scope.resolves(name) => FieldType::Message {
+                id: name.to_owned(),
+                name: scope.message_ident(name)?.to_string(),
+            },
+            name => bail!(
+                "`{}`: type `{}` has no protobuf mapping",
+                combinator.id(),
+                name
+            ),
+        };
+
+        Ok(FieldKind {
+            ty,
+            repeated,
+            optional,
+        })
+    }
+}
+
+struct Scope<'a> {
+    combinators: HashMap<&'a str, &'a Combinator>,
+    boxed: HashMap<&'a str, Vec<&'a Combinator>>,
+    /// Explicitly selected types by their canonical id.
+    names: HashMap<String, String>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(combinators: &'a [Combinator], messages: &[(String, String)]) -> anyhow::Result<Self> {
+        let mut boxed: HashMap<&str, Vec<&Combinator>> = HashMap::new();
+        for combinator in combinators.iter().filter(|c| !c.is_functional()) {
+            boxed
+                .entry(combinator.result_type())
+                .or_default()
+                .push(combinator);
+        }
+
+        let mut scope = Self {
+            combinators: combinators
+                .iter()
+                .filter(|c| !c.is_builtin())
+                .map(|c| (c.id(), c))
+                .collect(),
+            boxed,
+            names: Default::default(),
+        };
+        for (id, name) in messages {
+            let id = scope.canonical(id)?;
+            scope.names.insert(id, name.clone());
+        }
+
+        Ok(scope)
+    }
+
+    fn resolves(&self, name: &str) -> bool {
+        self.combinators.contains_key(name) || self.boxed.contains_key(name)
+    }
+
+    /// Boxed types with a single constructor are the constructor itself in the generated code.
+    fn canonical(&self, id: &str) -> anyhow::Result<String> {
+        if self.combinators.contains_key(id) {
+            return Ok(id.to_owned());
+        }
+
+        match self.boxed.get(id).map(Vec::as_slice) {
+            Some([constructor]) => Ok(constructor.id().to_owned()),
+            Some(_) => Ok(id.to_owned()),
+            None => bail!("unknown type `{}`", id),
+        }
+    }
+
+    /// Selected name or the name of the generated Rust type.
+    fn message_ident(&self, id: &str) -> anyhow::Result<syn::Ident> {
+        let id = self.canonical(id)?;
+        let name = self
+            .names
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| generate_type_name(&id));
+
+        Ok(format_ident!("{}", name))
+    }
+}
+
+enum FieldType {
+    Scalar(&'static str),
+    Message { id: String, name: String },
+}
+
+impl FieldType {
+    fn proto(&self) -> &str {
+        match self {
+            FieldType::Scalar(name) => name,
+            FieldType::Message { name, .. } => name,
+        }
+    }
+}
+
+struct FieldKind {
+    ty: FieldType,
+    repeated: bool,
+    optional: bool,
+}
+
+impl FieldKind {
+    fn label(&self) -> &'static str {
+        match (self.repeated, self.optional, &self.ty) {
+            (true, _, _) => "repeated ",
+            (false, true, FieldType::Scalar(_)) => "optional ",
+            _ => "",
+        }
+    }
+
+    fn proto_value(&self, ident: &syn::Ident) -> TokenStream {
+        let value = match (self.repeated, self.optional, &self.ty) {
+            (true, true, _) => {
+                quote! { value.#ident.into_iter().flatten().map(Into::into).collect() }
+            }
+            (true, false, _) => quote! { value.#ident.into_iter().map(Into::into).collect() },
+            (false, true, _) => quote! { value.#ident.map(Into::into) },
+            (false, false, FieldType::Message { .. }) => quote! { Some(value.#ident.into()) },
+            (false, false, FieldType::Scalar(_)) => quote! { value.#ident.into() },
+        };
+
+        quote! { #ident: #value }
+    }
+
+    fn tl_value(&self, ident: &syn::Ident, name: &str) -> TokenStream {
+        let invalid = format!("invalid `{}`", name);
+        let missing = format!("missing `{}`", name);
+        let convert = match &self.ty {
+            FieldType::Message { .. } => quote! { TryInto::try_into },
+            FieldType::Scalar(_) => quote! {
+                |value| TryInto::try_into(value).map_err(|_| ::anyhow::anyhow!(#invalid))
+            },
+        };
+
+        let value = match (self.repeated, self.optional, &self.ty) {
+            (true, optional, _) => {
+                let collected = quote! {
+                    value.#ident.into_iter().map(#convert).collect::<Result<_, ::anyhow::Error>>()?
+                };
+                if optional {
+                    quote! { Some(#collected) }
+                } else {
+                    collected
+                }
+            }
+            (false, true, _) => quote! { value.#ident.map(#convert).transpose()? },
+            (false, false, FieldType::Message { .. }) => quote! {
+                value.#ident.ok_or_else(|| ::anyhow::anyhow!(#missing))?.try_into()?
+            },
+            (false, false, FieldType::Scalar(_)) => quote! {
+                TryInto::try_into(value.#ident).map_err(|_| ::anyhow::anyhow!(#invalid))?
+            },
+        };
+
+        quote! { #ident: #value }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::config::FieldConfiguration;
+use crate::{field_name, field_type, structure_ident};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use syn::Ident;
+use tl_parser::Combinator;
+
+/// Extension trait with a method per function, implemented for every `tower::Service`
+/// accepting all the functions. Functions returning `Object`, i.e. query wrappers, are skipped.
+pub(crate) fn service_trait(
+    name: &Ident,
+    functions: &[&Combinator],
+) -> anyhow::Result<TokenStream> {
+    let functions: Vec<_> = functions
+        .iter()
+        .filter(|function| function.result_type() != "Object")
+        .collect();
+    let methods = functions
+        .iter()
+        .map(|function| method(function))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let bounds = functions.iter().map(|function| {
+        let request = structure_ident(function.id());
+        let response = structure_ident(function.result_type());
+
+        quote! { S: ::tower::Service<#request, Response = #response> }
+    });
+
+    Ok(quote! {
+        pub trait #name {
+            #(#methods)*
+        }
+
+        impl<S> #name for S where #(#bounds),* {}
+    })
+}
+
+/// Conditional fields are `Option` arguments, `true` ones are `bool`, their bits in the
+/// referenced `#` argument are overwritten by the presence of the arguments.
+fn method(function: &Combinator) -> anyhow::Result<TokenStream> {
+    let request = structure_ident(function.id());
+    let response = structure_ident(function.result_type());
+    let method = format_ident!("{}", method_name(function.id()));
+    let doc = format!(" `{}`", function.constructor_number_form());
+
+    let mut flags: BTreeMap<&str, (i32, Vec<TokenStream>)> = BTreeMap::new();
+    let mut arguments = Vec::new();
+    let mut values = Vec::new();
+    for field in function.fields() {
+        let ident = format_ident!("{}", field_name(field)?);
+
+        if let Some(condition) = field.type_condition() {
+            let bit = condition.bit_selector.unwrap_or_default();
+            let present = if field.field_type() == Some("true") {
+                quote! { #ident }
+            } else {
+                quote! { #ident.is_some() }
+            };
+            let (mask, bits) = flags.entry(condition.field_ref.as_str()).or_default();
+            *mask |= (1u32 << bit) as i32;
+            bits.push(quote! { if #present { 1 << #bit } else { 0 } });
+        }
+
+        if field.field_type() == Some("true") {
+            arguments.push(quote! { #ident: bool });
+            values.push(quote! { #ident: #ident.then(True::default) });
+        } else {
+            let ty = field_type(field, &FieldConfiguration::default())?;
+            arguments.push(quote! { #ident: #ty });
+            values.push(quote! { #ident });
+        }
+    }
+
+    let flags = flags
+        .into_iter()
+        .map(|(field_ref, (mask, bits))| {
+            let ident = format_ident!("{}", field_ref.to_case(Case::Snake));
+
+            quote! { let #ident = (#ident & !#mask) #(| #bits)*; }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[doc = #doc]
+        fn #method(&mut self, #(#arguments),*) -> ::tower::util::Oneshot<&mut Self, #request>
+        where
+            Self: Sized + ::tower::Service<#request, Response = #response>,
+        {
+            #(#flags)*
+
+            ::tower::ServiceExt::oneshot(self, #request { #(#values),* })
+        }
+    })
+}
+
+/// `liteServer.getMasterchainInfo` -> `get_masterchain_info`.
+fn method_name(id: &str) -> String {
+    let (_, name) = id.rsplit_once('.').unwrap_or(("", id));
+
+    name.to_case(Case::Snake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_name_strips_namespace() {
+        assert_eq!(
+            method_name("liteServer.getMasterchainInfo"),
+            "get_masterchain_info"
+        );
+        assert_eq!(
+            method_name("liteServer.listBlockTransactionsExt"),
+            "list_block_transactions_ext"
+        );
+        assert_eq!(method_name("getVersion"), "get_version");
+    }
+}
@@ -0,0 +1,173 @@
+use tl_codegen::backend::{AdnlBinary, SerdeJson};
+use tl_codegen::{configure_field, configure_type, Generator, ProtoGenerator};
+
+const TONLIB_API: &str = include_str!("fixtures/tonlib_api.tl");
+const LITE_API: &str = include_str!("fixtures/lite_api.tl");
+
+#[test]
+fn serde_json_default() {
+    let generated = Generator::new(SerdeJson)
+        .generate_string(TONLIB_API)
+        .unwrap();
+
+    insta::assert_snapshot!(generated);
+}
+
+#[test]
+fn serde_json_configured() {
+    let generated = Generator::new(SerdeJson)
+        .configure("ok", vec!["Deserialize"])
+        .configure("sync", vec!["Default", "Serialize"])
+        .configure_full(
+            "accountAddress",
+            configure_type()
+                .derives(vec!["Clone", "Deserialize", "Serialize"])
+                .field(
+                    "account_address",
+                    configure_field()
+                        .optional()
+                        .serialize_with("serialize_none_as_empty")
+                        .deserialize_with("deserialize_empty_as_none")
+                        .build(),
+                )
+                .build(),
+        )
+        .configure_full(
+            "raw.transactions",
+            configure_type()
+                .field(
+                    "previous_transaction_id",
+                    configure_field()
+                        .optional()
+                        .deserialize_with("deserialize_default_as_none")
+                        .build(),
+                )
+                .build(),
+        )
+        .configure_full(
+            "raw.getTransactionsV2",
+            configure_type()
+                .derives(vec!["Clone", "Serialize", "new"])
+                .field("private_key", configure_field().skip().build())
+                .build(),
+        )
+        .generate_string(TONLIB_API)
+        .unwrap();
+
+    insta::assert_snapshot!(generated);
+}
+
+#[test]
+fn serde_json_typed() {
+    let generated = Generator::new(SerdeJson)
+        .map_type("bytes", "Vec<u8>")
+        .map_type("int256", "[u8; 32]")
+        .map_type("int64", "i64")
+        .configure_full(
+            "raw.transaction",
+            configure_type()
+                .field("fee", configure_field().rust_type("u64").build())
+                .build(),
+        )
+        .configure_full(
+            "accountAddress",
+            configure_type()
+                .field(
+                    "account_address",
+                    configure_field()
+                        .optional()
+                        .rust_type("AccountAddressData")
+                        .build(),
+                )
+                .build(),
+        )
+        .generate_string(TONLIB_API)
+        .unwrap();
+
+    insta::assert_snapshot!(generated);
+}
+
+#[test]
+fn adnl_binary_default() {
+    let generated = Generator::new(AdnlBinary)
+        .generate_string(LITE_API)
+        .unwrap();
+
+    insta::assert_snapshot!(generated);
+}
+
+#[test]
+fn adnl_binary_configured() {
+    let generated = Generator::new(AdnlBinary)
+        .configure(
+            "tonNode.blockIdExt",
+            vec!["Clone", "PartialEq", "Eq", "Hash"],
+        )
+        .generate_string(LITE_API)
+        .unwrap();
+
+    assert!(generated
+        .contains("#[derive(Clone, PartialEq, Eq, Hash, Debug)]\npub struct TonNodeBlockIdExt {"));
+    assert!(
+        generated.contains("#[derive(Debug, Clone, PartialEq, Eq)]\npub struct TonNodeBlockId {")
+    );
+}
+
+#[test]
+fn adnl_binary_service_trait() {
+    let generated = Generator::new(AdnlBinary)
+        .service_trait("LiteServerExt")
+        .generate_string(LITE_API)
+        .unwrap();
+    let (_, service_trait) = generated.split_once("pub trait LiteServerExt").unwrap();
+
+    insta::assert_snapshot!(service_trait);
+}
+
+#[test]
+fn adnl_binary_rejects_field_configuration() {
+    let result = Generator::new(AdnlBinary)
+        .configure_full(
+            "liteServer.accountId",
+            configure_type()
+                .field("workchain", configure_field().skip().build())
+                .build(),
+        )
+        .generate_string(LITE_API);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn proto_lite_api() {
+    let generated = ProtoGenerator::new("lite_server", "ton_liteserver_client::tl")
+        .message("liteServer.TransactionId", "TransactionId")
+        .message("liteServer.transactionList", "TransactionList")
+        .generate_string(LITE_API)
+        .unwrap();
+
+    insta::assert_snapshot!("proto_lite_api_proto", generated.proto);
+    insta::assert_snapshot!("proto_lite_api_conversions", generated.conversions);
+}
+
+#[test]
+fn proto_rejects_unknown_types() {
+    let error = ProtoGenerator::new("ton", "tonlibjson_client::block")
+        .message("unknown.type", "Unknown")
+        .generate_string(TONLIB_API)
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "unknown type `unknown.type`");
+}
+
+#[test]
+fn generate_reports_scheme_errors() {
+    let error = Generator::new(AdnlBinary)
+        .generate_string("int ? = Int;\ntest.item value:int other:test.unknown = test.Item;")
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "2:1: `test.item` references unknown type `test.unknown`"
+    );
+}
@@ -0,0 +1,165 @@
+pub type BoxedAccountAddress = AccountAddress;
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "@type", rename = "accountAddress")]
+pub struct AccountAddress {
+    pub account_address: String,
+}
+pub type BoxedDouble = Double;
+pub type BoxedError = Error;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "error")]
+pub struct Error {
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub code: Int32,
+    pub message: String,
+}
+pub type BoxedFullAccountState = FullAccountState;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "fullAccountState")]
+pub struct FullAccountState {
+    pub address: AccountAddress,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub balance: Int64,
+    pub last_transaction_id: InternalTransactionId,
+    pub block_id: TonBlockIdExt,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub sync_utime: Int53,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "getAccountState")]
+pub struct GetAccountState {
+    pub account_address: AccountAddress,
+}
+impl Functional for GetAccountState {
+    type Result = BoxedFullAccountState;
+}
+pub type BoxedInputKey = InputKeyRegular;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "inputKeyRegular")]
+pub struct InputKeyRegular {
+    pub key: Key,
+    pub local_password: SecureBytes,
+}
+pub type BoxedKey = Key;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "key")]
+pub struct Key {
+    pub public_key: String,
+    pub secret: SecureBytes,
+}
+pub type BoxedOk = Ok;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "ok")]
+pub struct Ok {}
+pub type BoxedString = String;
+pub type InternalBoxedTransactionId = InternalTransactionId;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "internal.transactionId")]
+pub struct InternalTransactionId {
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lt: Int64,
+    pub hash: Bytes,
+}
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum MsgBoxedData {
+    MsgDataRaw(MsgDataRaw),
+    MsgDataText(MsgDataText),
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "msg.dataRaw")]
+pub struct MsgDataRaw {
+    pub body: Bytes,
+    pub init_state: Bytes,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "msg.dataText")]
+pub struct MsgDataText {
+    pub text: Bytes,
+}
+pub type RawBoxedMessage = RawMessage;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "raw.message")]
+pub struct RawMessage {
+    pub source: AccountAddress,
+    pub destination: AccountAddress,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub value: Int64,
+    pub msg_data: MsgBoxedData,
+}
+pub type RawBoxedTransaction = RawTransaction;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "raw.transaction")]
+pub struct RawTransaction {
+    pub address: AccountAddress,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub utime: Int53,
+    pub data: Bytes,
+    pub transaction_id: InternalTransactionId,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub fee: Int64,
+    pub in_msg: Option<RawMessage>,
+    pub out_msgs: Vector<RawMessage>,
+}
+pub type RawBoxedTransactions = RawTransactions;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "raw.transactions")]
+pub struct RawTransactions {
+    pub transactions: Vector<RawTransaction>,
+    pub previous_transaction_id: InternalTransactionId,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "raw.getTransactionsV2")]
+pub struct RawGetTransactionsV2 {
+    pub private_key: BoxedInputKey,
+    pub account_address: AccountAddress,
+    pub from_transaction_id: InternalTransactionId,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub count: Int31,
+    pub try_decode_messages: BoxedBool,
+}
+impl Functional for RawGetTransactionsV2 {
+    type Result = RawBoxedTransactions;
+}
+pub type TonBoxedBlockIdExt = TonBlockIdExt;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "ton.blockIdExt")]
+pub struct TonBlockIdExt {
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub workchain: Int32,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub shard: Int64,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub seqno: Int32,
+    pub root_hash: Bytes,
+    pub file_hash: Bytes,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "sync")]
+pub struct Sync {}
+impl Functional for Sync {
+    type Result = TonBoxedBlockIdExt;
+}
+pub type TvmBoxedNumber = TvmNumberDecimal;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "tvm.numberDecimal")]
+pub struct TvmNumberDecimal {
+    pub number: String,
+}
+pub type TvmBoxedStackEntry = TvmStackEntryNumber;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type", rename = "tvm.stackEntryNumber")]
+pub struct TvmStackEntryNumber {
+    pub number: TvmBoxedNumber,
+}
@@ -0,0 +1,208 @@
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::TonBlockIdExt> for BlockIdExt {
+    fn from(value: crate::block::TonBlockIdExt) -> Self {
+        Self {
+            workchain: value.workchain.into(),
+            shard: value.shard.into(),
+            seqno: value.seqno.into(),
+            root_hash: value.root_hash.into(),
+            file_hash: value.file_hash.into(),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<BlockIdExt> for crate::block::TonBlockIdExt {
+    type Error = ::anyhow::Error;
+    fn try_from(value: BlockIdExt) -> Result<Self, Self::Error> {
+        Ok(Self {
+            workchain: TryInto::try_into(value.workchain)
+                .map_err(|_| ::anyhow::anyhow!("invalid `workchain`"))?,
+            shard: TryInto::try_into(value.shard)
+                .map_err(|_| ::anyhow::anyhow!("invalid `shard`"))?,
+            seqno: TryInto::try_into(value.seqno)
+                .map_err(|_| ::anyhow::anyhow!("invalid `seqno`"))?,
+            root_hash: TryInto::try_into(value.root_hash)
+                .map_err(|_| ::anyhow::anyhow!("invalid `root_hash`"))?,
+            file_hash: TryInto::try_into(value.file_hash)
+                .map_err(|_| ::anyhow::anyhow!("invalid `file_hash`"))?,
+        })
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::RawTransaction> for Transaction {
+    fn from(value: crate::block::RawTransaction) -> Self {
+        Self {
+            utime: value.utime.into(),
+            data: value.data.into(),
+            transaction_id: Some(value.transaction_id.into()),
+            fee: value.fee.into(),
+            in_msg: value.in_msg.map(Into::into),
+            out_msgs: value.out_msgs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<Transaction> for crate::block::RawTransaction {
+    type Error = ::anyhow::Error;
+    fn try_from(value: Transaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: Default::default(),
+            utime: TryInto::try_into(value.utime)
+                .map_err(|_| ::anyhow::anyhow!("invalid `utime`"))?,
+            data: TryInto::try_into(value.data)
+                .map_err(|_| ::anyhow::anyhow!("invalid `data`"))?,
+            transaction_id: value
+                .transaction_id
+                .ok_or_else(|| ::anyhow::anyhow!("missing `transaction_id`"))?
+                .try_into()?,
+            fee: TryInto::try_into(value.fee)
+                .map_err(|_| ::anyhow::anyhow!("invalid `fee`"))?,
+            in_msg: value.in_msg.map(TryInto::try_into).transpose()?,
+            out_msgs: value
+                .out_msgs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, ::anyhow::Error>>()?,
+        })
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::InternalTransactionId> for InternalTransactionId {
+    fn from(value: crate::block::InternalTransactionId) -> Self {
+        Self {
+            lt: value.lt.into(),
+            hash: value.hash.into(),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<InternalTransactionId> for crate::block::InternalTransactionId {
+    type Error = ::anyhow::Error;
+    fn try_from(value: InternalTransactionId) -> Result<Self, Self::Error> {
+        Ok(Self {
+            lt: TryInto::try_into(value.lt)
+                .map_err(|_| ::anyhow::anyhow!("invalid `lt`"))?,
+            hash: TryInto::try_into(value.hash)
+                .map_err(|_| ::anyhow::anyhow!("invalid `hash`"))?,
+        })
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::RawMessage> for RawMessage {
+    fn from(value: crate::block::RawMessage) -> Self {
+        Self {
+            source: Some(value.source.into()),
+            destination: Some(value.destination.into()),
+            value: value.value.into(),
+            msg_data: Some(value.msg_data.into()),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<RawMessage> for crate::block::RawMessage {
+    type Error = ::anyhow::Error;
+    fn try_from(value: RawMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            source: value
+                .source
+                .ok_or_else(|| ::anyhow::anyhow!("missing `source`"))?
+                .try_into()?,
+            destination: value
+                .destination
+                .ok_or_else(|| ::anyhow::anyhow!("missing `destination`"))?
+                .try_into()?,
+            value: TryInto::try_into(value.value)
+                .map_err(|_| ::anyhow::anyhow!("invalid `value`"))?,
+            msg_data: value
+                .msg_data
+                .ok_or_else(|| ::anyhow::anyhow!("missing `msg_data`"))?
+                .try_into()?,
+        })
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::AccountAddress> for AccountAddress {
+    fn from(value: crate::block::AccountAddress) -> Self {
+        Self {
+            account_address: value.account_address.into(),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<AccountAddress> for crate::block::AccountAddress {
+    type Error = ::anyhow::Error;
+    fn try_from(value: AccountAddress) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_address: TryInto::try_into(value.account_address)
+                .map_err(|_| ::anyhow::anyhow!("invalid `account_address`"))?,
+        })
+    }
+}
+impl From<crate::block::MsgBoxedData> for MsgBoxedData {
+    fn from(value: crate::block::MsgBoxedData) -> Self {
+        Self {
+            value: Some(
+                match value {
+                    crate::block::MsgBoxedData::MsgDataRaw(inner) => {
+                        msg_boxed_data::Value::DataRaw(inner.into())
+                    }
+                    crate::block::MsgBoxedData::MsgDataText(inner) => {
+                        msg_boxed_data::Value::DataText(inner.into())
+                    }
+                },
+            ),
+        }
+    }
+}
+impl TryFrom<MsgBoxedData> for crate::block::MsgBoxedData {
+    type Error = ::anyhow::Error;
+    fn try_from(value: MsgBoxedData) -> Result<Self, Self::Error> {
+        Ok(
+            match value.value.ok_or_else(|| ::anyhow::anyhow!("missing `value`"))? {
+                msg_boxed_data::Value::DataRaw(inner) => {
+                    Self::MsgDataRaw(inner.try_into()?)
+                }
+                msg_boxed_data::Value::DataText(inner) => {
+                    Self::MsgDataText(inner.try_into()?)
+                }
+            },
+        )
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::MsgDataRaw> for MsgDataRaw {
+    fn from(value: crate::block::MsgDataRaw) -> Self {
+        Self {
+            body: value.body.into(),
+            init_state: value.init_state.into(),
+        }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<MsgDataRaw> for crate::block::MsgDataRaw {
+    type Error = ::anyhow::Error;
+    fn try_from(value: MsgDataRaw) -> Result<Self, Self::Error> {
+        Ok(Self {
+            body: TryInto::try_into(value.body)
+                .map_err(|_| ::anyhow::anyhow!("invalid `body`"))?,
+            init_state: TryInto::try_into(value.init_state)
+                .map_err(|_| ::anyhow::anyhow!("invalid `init_state`"))?,
+        })
+    }
+}
+#[allow(clippy::useless_conversion)]
+impl From<crate::block::MsgDataText> for MsgDataText {
+    fn from(value: crate::block::MsgDataText) -> Self {
+        Self { text: value.text.into() }
+    }
+}
+#[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+impl TryFrom<MsgDataText> for crate::block::MsgDataText {
+    type Error = ::anyhow::Error;
+    fn try_from(value: MsgDataText) -> Result<Self, Self::Error> {
+        Ok(Self {
+            text: TryInto::try_into(value.text)
+                .map_err(|_| ::anyhow::anyhow!("invalid `text`"))?,
+        })
+    }
+}
@@ -0,0 +1,253 @@
+//! Conversions of the [`ProtoGenerator`] compiled against the TL types of the [`Generator`] and
+//! the prost messages of the same scheme. The generated code is kept in `generated/` and the
+//! tests fail once it is outdated, `UPDATE_GENERATED=1` writes it again.
+
+use std::path::Path;
+use tl_codegen::backend::SerdeJson;
+use tl_codegen::{configure_field, configure_type, Generator, ProtoGenerator};
+
+const TONLIB_API: &str = include_str!("fixtures/tonlib_api.tl");
+
+#[allow(dead_code, clippy::enum_variant_names)]
+mod block {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    type Double = f64;
+    type Int31 = i32;
+    type Int32 = i32;
+    type Int53 = i64;
+    type Int64 = i64;
+    type Int256 = String;
+    type BoxedBool = bool;
+    type Bytes = String;
+    type SecureString = String;
+    type SecureBytes = String;
+    type Vector<T> = Vec<T>;
+
+    pub trait Functional {
+        type Result;
+    }
+
+    fn deserialize_number_from_string<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr + Deserialize<'de>,
+        T::Err: Display,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrNumber<T> {
+            String(String),
+            Number(T),
+        }
+
+        match StringOrNumber::<T>::deserialize(deserializer)? {
+            StringOrNumber::String(s) => s.parse().map_err(D::Error::custom),
+            StringOrNumber::Number(n) => Ok(n),
+        }
+    }
+
+    include!("generated/tonlib_api.rs");
+}
+
+/// prost messages of `proto_tonlib_api_proto.snap`, as `prost-build` generates them.
+mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BlockIdExt {
+        #[prost(int32, tag = "1")]
+        pub workchain: i32,
+        #[prost(int64, tag = "2")]
+        pub shard: i64,
+        #[prost(int32, tag = "3")]
+        pub seqno: i32,
+        #[prost(string, tag = "4")]
+        pub root_hash: String,
+        #[prost(string, tag = "5")]
+        pub file_hash: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Transaction {
+        #[prost(int64, tag = "2")]
+        pub utime: i64,
+        #[prost(string, tag = "3")]
+        pub data: String,
+        #[prost(message, optional, tag = "4")]
+        pub transaction_id: Option<InternalTransactionId>,
+        #[prost(int64, tag = "5")]
+        pub fee: i64,
+        #[prost(message, optional, tag = "6")]
+        pub in_msg: Option<RawMessage>,
+        #[prost(message, repeated, tag = "7")]
+        pub out_msgs: Vec<RawMessage>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct InternalTransactionId {
+        #[prost(int64, tag = "1")]
+        pub lt: i64,
+        #[prost(string, tag = "2")]
+        pub hash: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RawMessage {
+        #[prost(message, optional, tag = "1")]
+        pub source: Option<AccountAddress>,
+        #[prost(message, optional, tag = "2")]
+        pub destination: Option<AccountAddress>,
+        #[prost(int64, tag = "3")]
+        pub value: i64,
+        #[prost(message, optional, tag = "4")]
+        pub msg_data: Option<MsgBoxedData>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AccountAddress {
+        #[prost(string, tag = "1")]
+        pub account_address: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgBoxedData {
+        #[prost(oneof = "msg_boxed_data::Value", tags = "1, 2")]
+        pub value: Option<msg_boxed_data::Value>,
+    }
+
+    pub mod msg_boxed_data {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Value {
+            #[prost(message, tag = "1")]
+            DataRaw(super::MsgDataRaw),
+            #[prost(message, tag = "2")]
+            DataText(super::MsgDataText),
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgDataRaw {
+        #[prost(string, tag = "1")]
+        pub body: String,
+        #[prost(string, tag = "2")]
+        pub init_state: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgDataText {
+        #[prost(string, tag = "1")]
+        pub text: String,
+    }
+
+    include!("generated/tonlib_api_conversions.rs");
+}
+
+#[test]
+fn generated_tl_types_are_up_to_date() {
+    let generated = Generator::new(SerdeJson)
+        .configure_full(
+            "accountAddress",
+            configure_type()
+                .derives(vec!["Default", "Clone", "Serialize", "Deserialize"])
+                .build(),
+        )
+        .configure_full(
+            "raw.transaction",
+            configure_type()
+                .field("in_msg", configure_field().optional().build())
+                .build(),
+        )
+        .generate_string(TONLIB_API)
+        .unwrap();
+
+    assert_up_to_date("tonlib_api.rs", &generated);
+}
+
+#[test]
+fn generated_conversions_are_up_to_date() {
+    let generated = given_proto_generator().generate_string(TONLIB_API).unwrap();
+
+    assert_up_to_date("tonlib_api_conversions.rs", &generated.conversions);
+}
+
+#[test]
+fn proto_tonlib_api() {
+    let generated = given_proto_generator().generate_string(TONLIB_API).unwrap();
+
+    insta::assert_snapshot!("proto_tonlib_api_proto", generated.proto);
+}
+
+#[test]
+fn transaction_roundtrip_fills_skipped_address() {
+    let message = block::RawMessage {
+        source: block::AccountAddress {
+            account_address: "EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS".to_owned(),
+        },
+        destination: block::AccountAddress::default(),
+        value: 1000,
+        msg_data: block::MsgBoxedData::MsgDataText(block::MsgDataText {
+            text: "dGVzdA==".to_owned(),
+        }),
+    };
+    let transaction = block::RawTransaction {
+        address: block::AccountAddress {
+            account_address: "EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS".to_owned(),
+        },
+        utime: 1700000000,
+        data: "te6ccgEBAQEAAgAAAA==".to_owned(),
+        transaction_id: block::InternalTransactionId {
+            lt: 47000000000001,
+            hash: "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_owned(),
+        },
+        fee: 1,
+        in_msg: Some(message.clone()),
+        out_msgs: vec![message],
+    };
+
+    let proto = proto::Transaction::from(transaction.clone());
+    let actual = block::RawTransaction::try_from(proto.clone()).unwrap();
+
+    assert_eq!(proto.out_msgs.len(), 1);
+    assert_eq!(actual.address.account_address, "");
+    assert_eq!(actual.utime, transaction.utime);
+    assert_eq!(actual.transaction_id.lt, transaction.transaction_id.lt);
+    assert_eq!(proto::Transaction::from(actual), proto);
+}
+
+#[test]
+fn missing_message_field_is_an_error() {
+    let transaction = proto::Transaction::default();
+
+    let actual = block::RawTransaction::try_from(transaction);
+
+    assert_eq!(actual.unwrap_err().to_string(), "missing `transaction_id`");
+}
+
+fn given_proto_generator() -> ProtoGenerator {
+    ProtoGenerator::new("ton", "crate::block")
+        .base64_bytes()
+        .message("ton.blockIdExt", "BlockIdExt")
+        .message("raw.transaction", "Transaction")
+        .configure_full(
+            "raw.transaction",
+            configure_type()
+                .field("in_msg", configure_field().optional().build())
+                .field("address", configure_field().skip().build())
+                .build(),
+        )
+}
+
+fn assert_up_to_date(name: &str, generated: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/generated")
+        .join(name);
+    if std::env::var_os("UPDATE_GENERATED").is_some() {
+        std::fs::write(&path, generated).unwrap();
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap();
+
+    assert_eq!(generated, expected, "{} is outdated", path.display());
+}
@@ -0,0 +1,54 @@
+//! Sends a liteserver query written as JSON and prints the answer as JSON.
+//!
+//! ```sh
+//! cargo run -p tl_dynamic --example liteserver_query -- '{"@type": "liteServer.getMasterchainInfo"}'
+//! ```
+//!
+//! An optional second argument points to a `.tl` scheme used instead of the bundled `lite_api.tl`.
+
+use adnl_tcp::client::ServerKey;
+use anyhow::Context;
+use base64::Engine;
+use serde_json::Value;
+use tl_dynamic::Schema;
+use ton_client_util::discover::config::load_ton_config;
+use ton_liteserver_client::client::LiteServerClient;
+
+const LITE_API: &str = include_str!("../../tonlibjson-sys/ton/tl/generate/scheme/lite_api.tl");
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let mut args = std::env::args().skip(1);
+    let query: Value = serde_json::from_str(
+        &args
+            .next()
+            .context("usage: liteserver_query <json> [scheme.tl]")?,
+    )?;
+    let schema: Schema = match args.next() {
+        Some(path) => std::fs::read_to_string(path)?.parse()?,
+        None => LITE_API.parse()?,
+    };
+
+    let config = load_ton_config("https://ton.org/global-config.json").await?;
+    let liteserver = config
+        .liteservers
+        .into_iter()
+        .find(|liteserver| liteserver.ip.is_some())
+        .context("no liteserver with ip in config")?;
+    let key: ServerKey = base64::engine::general_purpose::STANDARD
+        .decode(&liteserver.id.key)?
+        .as_slice()
+        .try_into()?;
+
+    tracing::info!(id = liteserver.id(), "connecting");
+    let client = LiteServerClient::connect(liteserver.try_into()?, key).await?;
+
+    let answer = client.query_raw(schema.to_bytes_boxed(&query)?).await?;
+    let answer = schema.from_bytes_any(&answer)?;
+
+    println!("{}", serde_json::to_string_pretty(&answer)?);
+
+    Ok(())
+}
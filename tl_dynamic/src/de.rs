@@ -0,0 +1,146 @@
+use crate::{child_path, Error, Schema, TypeRef};
+use adnl_tcp::deserializer::{Deserializer, DeserializerBoxedError};
+use base64::Engine;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use tl_parser::{Combinator, Condition};
+
+pub(crate) fn from_bytes(schema: &Schema, ty: &TypeRef, bytes: &[u8]) -> Result<Value, Error> {
+    let mut decoder = Decoder {
+        schema,
+        de: Deserializer::from_bytes(bytes),
+    };
+    let value = decoder.value(ty, "")?;
+
+    decoder.finish(value)
+}
+
+pub(crate) fn from_bytes_any(schema: &Schema, bytes: &[u8]) -> Result<Value, Error> {
+    let mut decoder = Decoder {
+        schema,
+        de: Deserializer::from_bytes(bytes),
+    };
+    let constructor_number = decoder.de.parse_constructor_numer()?;
+    let combinator = schema.by_constructor_number(constructor_number)?;
+    let value = decoder.object(combinator, combinator.id())?;
+
+    decoder.finish(value)
+}
+
+struct Decoder<'a, 'de> {
+    schema: &'a Schema,
+    de: Deserializer<'de>,
+}
+
+impl Decoder<'_, '_> {
+    fn finish(self, value: Value) -> Result<Value, Error> {
+        match self.de.remaining() {
+            0 => Ok(value),
+            remaining => Err(DeserializerBoxedError::TrailingBytes(remaining).into()),
+        }
+    }
+
+    fn value(&mut self, ty: &TypeRef, path: &str) -> Result<Value, Error> {
+        let value = match ty {
+            TypeRef::Nat => self.de.parse_i31()?.into(),
+            TypeRef::Int => self.de.parse_i32()?.into(),
+            TypeRef::Long => self.de.parse_i64()?.to_string().into(),
+            TypeRef::Int128 => base64(&self.de.parse_i128()?.to_le_bytes()),
+            TypeRef::Int256 => base64(&self.de.parse_i256()?),
+            TypeRef::Bytes => base64(&self.de.parse_bytes()?),
+            TypeRef::String => self.de.parse_string()?.into(),
+            TypeRef::Bool => {
+                let constructor_number = self.de.parse_constructor_numer()?;
+                if constructor_number == self.schema.constructor_number("boolTrue")? {
+                    true.into()
+                } else if constructor_number == self.schema.constructor_number("boolFalse")? {
+                    false.into()
+                } else {
+                    return Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                        constructor_number,
+                    )
+                    .into());
+                }
+            }
+            TypeRef::True => true.into(),
+            TypeRef::Vector { boxed, element } => {
+                if *boxed {
+                    let constructor_number = self.de.parse_constructor_numer()?;
+                    if constructor_number != self.schema.constructor_number("vector")? {
+                        return Err(DeserializerBoxedError::UnexpectedConstructorNumber(
+                            constructor_number,
+                        )
+                        .into());
+                    }
+                }
+
+                let len = self.de.parse_vector_len()?;
+                let mut items = Vec::with_capacity(len.min(self.de.remaining()));
+                for i in 0..len {
+                    items.push(self.value(element, &format!("{}[{}]", path, i))?);
+                }
+
+                items.into()
+            }
+            TypeRef::Bare(combinator) => self.object(combinator, path)?,
+            TypeRef::Boxed(name) => {
+                let constructor_number = self.de.parse_constructor_numer()?;
+                let combinator = self.schema.by_constructor_number(constructor_number)?;
+                if combinator.is_functional() || crate::type_head(combinator.result_type()) != *name
+                {
+                    return Err(Error::UnexpectedConstructor {
+                        path: path.to_owned(),
+                        constructor: combinator.id().to_owned(),
+                        expected: name.to_string(),
+                    });
+                }
+
+                self.object(combinator, path)?
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn object(&mut self, combinator: &Combinator, path: &str) -> Result<Value, Error> {
+        let mut object = Map::new();
+        object.insert("@type".to_owned(), combinator.id().into());
+
+        let mut nats: HashMap<&str, i32> = HashMap::new();
+        for field in combinator.fields() {
+            let name = field
+                .id()
+                .ok_or_else(|| Error::UnsupportedType(field.constructor_number_form()))?;
+
+            match field.type_condition() {
+                None => {}
+                Some(Condition {
+                    field_ref,
+                    bit_selector: Some(bit),
+                }) => {
+                    let nat = nats.get(field_ref.as_str()).copied().unwrap_or_default();
+                    if nat & (1 << bit) == 0 {
+                        continue;
+                    }
+                }
+                Some(_) => return Err(Error::UnsupportedType(field.constructor_number_form())),
+            }
+
+            let ty = self.schema.resolve_field(field)?;
+            let value = self.value(&ty, &child_path(path, name))?;
+            if let (TypeRef::Nat, Some(nat)) = (&ty, value.as_i64()) {
+                nats.insert(name, nat as i32);
+            }
+
+            object.insert(name.to_owned(), value);
+        }
+
+        Ok(object.into())
+    }
+}
+
+fn base64(bytes: &[u8]) -> Value {
+    base64::engine::general_purpose::STANDARD
+        .encode(bytes)
+        .into()
+}
@@ -0,0 +1,472 @@
+//! Schema driven TL codec, converts between JSON and TL binary at runtime without generated types.
+//!
+//! The JSON mapping follows tonlib: objects carry their constructor in `"@type"`, `#` and `int`
+//! are numbers, `long` is a decimal string, `bytes`, `int128` and `int256` are base64 strings,
+//! `Bool` is a boolean and vectors are arrays. Absent conditional fields are omitted, their flag
+//! bits are set automatically on serialization.
+//!
+//! ```ignore
+//! let schema: Schema = include_str!("lite_api.tl").parse()?;
+//!
+//! let query = schema.to_bytes_boxed(&json!({"@type": "liteServer.getMasterchainInfo"}))?;
+//! let answer = schema.from_bytes_any(&bytes)?;
+//! ```
+
+mod de;
+mod ser;
+
+use adnl_tcp::deserializer::DeserializerBoxedError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tl_parser::{Combinator, Diagnostics};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("unknown combinator `{0}`")]
+    UnknownCombinator(String),
+    #[error("unknown type `{0}`")]
+    UnknownType(String),
+    #[error("unsupported type `{0}`")]
+    UnsupportedType(String),
+    #[error("{path}: missing field `{field}`")]
+    MissingField { path: String, field: String },
+    #[error("{path}: expected {expected}")]
+    InvalidValue {
+        path: String,
+        expected: &'static str,
+    },
+    #[error("{path}: `{constructor}` is not a constructor of `{expected}`")]
+    UnexpectedConstructor {
+        path: String,
+        constructor: String,
+        expected: String,
+    },
+    #[error(transparent)]
+    Deserialize(#[from] DeserializerBoxedError),
+}
+
+/// Type of a field resolved against the schema.
+#[derive(Debug, Clone)]
+enum TypeRef<'a> {
+    Nat,
+    Int,
+    Long,
+    Int128,
+    Int256,
+    Bytes,
+    String,
+    Bool,
+    True,
+    Vector {
+        boxed: bool,
+        element: Box<TypeRef<'a>>,
+    },
+    Bare(&'a Combinator),
+    Boxed(&'a str),
+}
+
+#[derive(Debug, Clone)]
+pub struct Schema {
+    combinators: HashMap<String, Combinator>,
+    constructors: HashMap<String, Vec<String>>,
+    constructor_numbers: HashMap<u32, String>,
+}
+
+impl FromStr for Schema {
+    type Err = Diagnostics;
+
+    fn from_str(scheme: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_combinators(tl_parser::validate(scheme)?))
+    }
+}
+
+impl Schema {
+    /// Builds the schema from already validated combinators.
+    pub fn from_combinators(combinators: Vec<Combinator>) -> Self {
+        let mut constructors: HashMap<String, Vec<String>> = HashMap::new();
+        let mut constructor_numbers = HashMap::new();
+        for combinator in combinators.iter() {
+            if combinator.is_builtin() {
+                continue;
+            }
+
+            constructor_numbers.insert(
+                combinator.constructor_number_be(),
+                combinator.id().to_owned(),
+            );
+            if !combinator.is_functional() {
+                constructors
+                    .entry(type_head(combinator.result_type()).to_owned())
+                    .or_default()
+                    .push(combinator.id().to_owned());
+            }
+        }
+
+        Self {
+            combinators: combinators
+                .into_iter()
+                .map(|combinator| (combinator.id().to_owned(), combinator))
+                .collect(),
+            constructors,
+            constructor_numbers,
+        }
+    }
+
+    pub fn combinator(&self, id: &str) -> Option<&Combinator> {
+        self.combinators.get(id)
+    }
+
+    /// Result type of a function, e.g. `liteServer.MasterchainInfo`.
+    pub fn result_type(&self, function: &str) -> Option<&str> {
+        self.combinators
+            .get(function)
+            .filter(|combinator| combinator.is_functional())
+            .map(Combinator::result_type)
+    }
+
+    /// Serializes an object with `"@type"` naming a constructor or a function.
+    pub fn to_bytes_boxed(&self, value: &Value) -> Result<Vec<u8>, Error> {
+        ser::to_bytes_boxed(self, value)
+    }
+
+    /// Deserializes a value of the given type, e.g. `liteServer.MasterchainInfo` or `Vector long`.
+    pub fn from_bytes_boxed(&self, type_name: &str, bytes: &[u8]) -> Result<Value, Error> {
+        let ty = self.resolve_expr(type_name)?;
+
+        de::from_bytes(self, &ty, bytes)
+    }
+
+    /// Deserializes any boxed constructor or function known to the schema.
+    pub fn from_bytes_any(&self, bytes: &[u8]) -> Result<Value, Error> {
+        de::from_bytes_any(self, bytes)
+    }
+
+    fn constructor_number(&self, id: &str) -> Result<u32, Error> {
+        self.combinators
+            .get(id)
+            .map(Combinator::constructor_number_be)
+            .ok_or_else(|| Error::UnknownCombinator(id.to_owned()))
+    }
+
+    fn by_constructor_number(&self, constructor_number: u32) -> Result<&Combinator, Error> {
+        self.constructor_numbers
+            .get(&constructor_number)
+            .and_then(|id| self.combinators.get(id))
+            .ok_or(DeserializerBoxedError::UnexpectedConstructorNumber(constructor_number).into())
+    }
+
+    /// Resolves a type expression like `long`, `vector<long>` or `Vector tonNode.blockIdExt`.
+    fn resolve_expr(&self, expr: &str) -> Result<TypeRef<'_>, Error> {
+        let mut parts = expr
+            .split(|c: char| c.is_whitespace() || "<>,()".contains(c))
+            .filter(|s| !s.is_empty());
+        let name = parts
+            .next()
+            .ok_or_else(|| Error::UnknownType(expr.to_owned()))?;
+        let args: Vec<_> = parts.collect();
+
+        self.resolve(name, &args.join(" "))
+    }
+
+    fn resolve_field(&self, field: &tl_parser::Field) -> Result<TypeRef<'_>, Error> {
+        let name = field
+            .field_type()
+            .ok_or_else(|| Error::UnsupportedType(field.constructor_number_form()))?;
+        let args = if field.type_is_polymorphic() {
+            field.type_variables().unwrap_or_default().join(" ")
+        } else {
+            String::new()
+        };
+
+        self.resolve(name, &args)
+    }
+
+    fn resolve(&self, name: &str, args: &str) -> Result<TypeRef<'_>, Error> {
+        let ty = match name {
+            "#" => TypeRef::Nat,
+            "int" | "int32" | "Int" => TypeRef::Int,
+            "long" | "int53" | "int64" | "Long" => TypeRef::Long,
+            "int128" => TypeRef::Int128,
+            "int256" => TypeRef::Int256,
+            "bytes" | "secureBytes" | "Bytes" => TypeRef::Bytes,
+            "string" | "secureString" | "String" => TypeRef::String,
+            "Bool" => TypeRef::Bool,
+            "true" | "True" => TypeRef::True,
+            "vector" | "Vector" if !args.is_empty() => TypeRef::Vector {
+                boxed: name == "Vector",
+                element: Box::new(self.resolve_expr(args)?),
+            },
+            name if is_boxed(name) => {
+                let (name, _) = self
+                    .constructors
+                    .get_key_value(name)
+                    .ok_or_else(|| Error::UnknownType(name.to_owned()))?;
+
+                TypeRef::Boxed(name)
+            }
+            name => TypeRef::Bare(
+                self.combinators
+                    .get(name)
+                    .filter(|combinator| !combinator.is_builtin())
+                    .ok_or_else(|| Error::UnsupportedType(name.to_owned()))?,
+            ),
+        };
+
+        Ok(ty)
+    }
+}
+
+fn type_head(name: &str) -> &str {
+    name.split_whitespace().next().unwrap_or(name)
+}
+
+/// Boxed TL types start with a capital letter after the namespace, e.g. `liteServer.Error`.
+fn is_boxed(name: &str) -> bool {
+    name.rsplit('.')
+        .next()
+        .is_some_and(|name| name.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+fn child_path(path: &str, field: &str) -> String {
+    format!("{}.{}", path, field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adnl_tcp::deserializer::from_bytes_boxed;
+    use adnl_tcp::serializer::to_bytes_boxed;
+    use serde_json::json;
+    use ton_liteserver_client::tl::{
+        LiteServerGetAccountState, LiteServerLookupBlock, LiteServerMasterchainInfo,
+        LiteServerTransactionId, TonNodeBlockId, TonNodeBlockIdExt,
+    };
+
+    fn lite_api() -> Schema {
+        include_str!("../../tonlibjson-sys/ton/tl/generate/scheme/lite_api.tl")
+            .parse()
+            .unwrap()
+    }
+
+    fn block_id_ext() -> TonNodeBlockIdExt {
+        TonNodeBlockIdExt {
+            workchain: -1,
+            shard: i64::MIN,
+            seqno: 42,
+            root_hash: [1; 32],
+            file_hash: [2; 32],
+        }
+    }
+
+    fn block_id_ext_json() -> Value {
+        json!({
+            "@type": "tonNode.blockIdExt",
+            "workchain": -1,
+            "shard": "-9223372036854775808",
+            "seqno": 42,
+            "root_hash": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=",
+            "file_hash": "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=",
+        })
+    }
+
+    #[test]
+    fn serialize_function_without_fields() {
+        let schema = lite_api();
+
+        let actual = schema
+            .to_bytes_boxed(&json!({"@type": "liteServer.getMasterchainInfo"}))
+            .unwrap();
+
+        assert_eq!(actual, hex::decode("2ee6b589").unwrap());
+    }
+
+    #[test]
+    fn serialize_matches_generated() {
+        let schema = lite_api();
+        let expected = LiteServerGetAccountState {
+            id: block_id_ext(),
+            account: ton_liteserver_client::tl::LiteServerAccountId {
+                workchain: 0,
+                id: [3; 32],
+            },
+        };
+
+        let actual = schema
+            .to_bytes_boxed(&json!({
+                "@type": "liteServer.getAccountState",
+                "id": block_id_ext_json(),
+                "account": {
+                    "workchain": 0,
+                    "id": "AwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwM="
+                }
+            }))
+            .unwrap();
+
+        assert_eq!(actual, to_bytes_boxed(&expected));
+    }
+
+    #[test]
+    fn serialize_sets_flags_from_present_fields() {
+        let schema = lite_api();
+
+        let actual = schema
+            .to_bytes_boxed(&json!({
+                "@type": "liteServer.lookupBlock",
+                "mode": 1,
+                "id": {"workchain": -1, "shard": "-9223372036854775808", "seqno": 7},
+                "utime": 1700000000
+            }))
+            .unwrap();
+        let actual = from_bytes_boxed::<LiteServerLookupBlock>(&actual).unwrap();
+
+        assert_eq!(
+            actual,
+            LiteServerLookupBlock {
+                mode: 5,
+                id: TonNodeBlockId::new(-1, i64::MIN, 7),
+                lt: None,
+                utime: Some(1700000000),
+            }
+        );
+    }
+
+    #[test]
+    fn serialize_missing_conditional_field() {
+        let schema = lite_api();
+
+        let actual = schema.to_bytes_boxed(&json!({
+            "@type": "liteServer.lookupBlock",
+            "mode": 2,
+            "id": {"workchain": -1, "shard": "0", "seqno": 7},
+        }));
+
+        assert!(matches!(
+            actual,
+            Err(Error::MissingField { ref path, ref field }) if path == "liteServer.lookupBlock" && field == "lt"
+        ));
+    }
+
+    #[test]
+    fn serialize_invalid_value_reports_path() {
+        let schema = lite_api();
+
+        let actual = schema
+            .to_bytes_boxed(&json!({
+                "@type": "liteServer.getAccountState",
+                "id": block_id_ext_json(),
+                "account": {"workchain": "zero", "id": ""}
+            }))
+            .unwrap_err();
+
+        assert_eq!(
+            actual.to_string(),
+            "liteServer.getAccountState.account.workchain: expected int"
+        );
+    }
+
+    #[test]
+    fn deserialize_matches_generated() {
+        let schema = lite_api();
+        let info = LiteServerMasterchainInfo {
+            last: block_id_ext(),
+            state_root_hash: [4; 32],
+            init: ton_liteserver_client::tl::TonNodeZeroStateIdExt {
+                workchain: -1,
+                root_hash: [5; 32],
+                file_hash: [6; 32],
+            },
+        };
+
+        let actual = schema
+            .from_bytes_boxed("liteServer.MasterchainInfo", &to_bytes_boxed(&info))
+            .unwrap();
+
+        assert_eq!(actual["@type"], "liteServer.masterchainInfo");
+        assert_eq!(actual["last"], block_id_ext_json());
+        assert_eq!(actual["init"]["workchain"], -1);
+    }
+
+    #[test]
+    fn deserialize_any_with_vectors_and_flags() {
+        let schema = lite_api();
+        let transactions = ton_liteserver_client::tl::LiteServerBlockTransactions {
+            id: block_id_ext(),
+            req_count: 2,
+            incomplete: ton_liteserver_client::tl::BoxedBool::BoolTrue(Default::default()),
+            ids: vec![LiteServerTransactionId {
+                mode: 3,
+                account: Some([7; 32]),
+                lt: Some(100),
+                hash: None,
+            }],
+            proof: vec![1, 2, 3],
+        };
+        let bytes = to_bytes_boxed(&transactions);
+
+        let actual = schema.from_bytes_any(&bytes).unwrap();
+
+        assert_eq!(actual["incomplete"], true);
+        assert_eq!(actual["ids"][0]["lt"], "100");
+        assert!(actual["ids"][0].get("hash").is_none());
+        assert_eq!(actual["proof"], "AQID");
+        assert_eq!(schema.to_bytes_boxed(&actual).unwrap(), bytes);
+    }
+
+    #[test]
+    fn deserialize_unknown_constructor() {
+        let schema = lite_api();
+
+        let actual = schema.from_bytes_any(&[0, 0, 0, 0]);
+
+        assert!(matches!(
+            actual,
+            Err(Error::Deserialize(
+                DeserializerBoxedError::UnexpectedConstructorNumber(0)
+            ))
+        ));
+    }
+
+    #[test]
+    fn deserialize_trailing_bytes() {
+        let schema = lite_api();
+
+        let actual =
+            schema.from_bytes_boxed("liteServer.MasterchainInfo", &[0x2e, 0xe6, 0xb5, 0x89, 0]);
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn roundtrip_boxed_vector() {
+        let schema = lite_api();
+        let value = json!([block_id_ext_json(), block_id_ext_json()]);
+        let bytes = schema
+            .to_bytes_boxed(&json!({
+                "@type": "liteServer.getShardBlockProof",
+                "id": block_id_ext_json()
+            }))
+            .unwrap();
+
+        assert_eq!(
+            schema.result_type("liteServer.getShardBlockProof"),
+            Some("liteServer.ShardBlockProof")
+        );
+        assert_eq!(&bytes[..4], &hex::decode("5003a64c").unwrap()[..]);
+        assert_eq!(
+            schema
+                .from_bytes_boxed("Vector tonNode.blockIdExt", &vector_bytes(&schema, &value))
+                .unwrap(),
+            value
+        );
+    }
+
+    fn vector_bytes(schema: &Schema, value: &Value) -> Vec<u8> {
+        let mut bytes = hex::decode("15c4b51c02000000").unwrap();
+        for item in value.as_array().unwrap() {
+            bytes.extend_from_slice(&schema.to_bytes_boxed(item).unwrap()[4..]);
+        }
+
+        bytes
+    }
+}
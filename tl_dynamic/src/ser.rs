@@ -0,0 +1,285 @@
+use crate::{child_path, Error, Schema, TypeRef};
+use adnl_tcp::serializer::{SerializeBoxed, Serializer};
+use base64::Engine;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use tl_parser::{Combinator, Condition};
+
+/// Values are validated first and written by the infallible `adnl_tcp` serializer afterwards.
+enum Op {
+    ConstructorNumber(u32),
+    Nat(i32),
+    Int(i32),
+    Long(i64),
+    Int128(i128),
+    Int256([u8; 32]),
+    Bytes(Vec<u8>),
+}
+
+struct Ops(Vec<Op>);
+
+impl SerializeBoxed for Ops {
+    fn serialize_boxed(&self, se: &mut Serializer) {
+        for op in self.0.iter() {
+            match op {
+                Op::ConstructorNumber(n) => se.write_constructor_number(*n),
+                Op::Nat(n) => se.write_i31(*n),
+                Op::Int(n) => se.write_i32(*n),
+                Op::Long(n) => se.write_i64(*n),
+                Op::Int128(n) => se.write_i128(*n),
+                Op::Int256(n) => se.write_i256(n),
+                Op::Bytes(bytes) => se.write_bytes(bytes),
+            }
+        }
+    }
+}
+
+pub(crate) fn to_bytes_boxed(schema: &Schema, value: &Value) -> Result<Vec<u8>, Error> {
+    let object = as_object(value, "")?;
+    let id = type_of(object, "")?;
+    let combinator = schema
+        .combinator(id)
+        .filter(|combinator| !combinator.is_builtin())
+        .ok_or_else(|| Error::UnknownCombinator(id.to_owned()))?;
+
+    let mut encoder = Encoder {
+        schema,
+        ops: vec![Op::ConstructorNumber(combinator.constructor_number_be())],
+    };
+    encoder.fields(combinator, object, id)?;
+
+    Ok(adnl_tcp::serializer::to_bytes_boxed(&Ops(encoder.ops)))
+}
+
+struct Encoder<'a> {
+    schema: &'a Schema,
+    ops: Vec<Op>,
+}
+
+impl Encoder<'_> {
+    fn value(&mut self, ty: &TypeRef, value: &Value, path: &str) -> Result<(), Error> {
+        let op = match ty {
+            TypeRef::Nat => Op::Nat(as_nat(value, path)?),
+            TypeRef::Int => Op::Int(as_int(value, path)?),
+            TypeRef::Long => Op::Long(as_long(value, path)?),
+            TypeRef::Int128 => Op::Int128(i128::from_le_bytes(as_base64(value, path)?)),
+            TypeRef::Int256 => Op::Int256(as_base64(value, path)?),
+            TypeRef::Bytes => Op::Bytes(as_bytes(value, path)?),
+            TypeRef::String => Op::Bytes(
+                value
+                    .as_str()
+                    .ok_or_else(|| invalid(path, "string"))?
+                    .as_bytes()
+                    .to_vec(),
+            ),
+            TypeRef::Bool => {
+                let id = match value.as_bool().ok_or_else(|| invalid(path, "bool"))? {
+                    true => "boolTrue",
+                    false => "boolFalse",
+                };
+
+                Op::ConstructorNumber(self.schema.constructor_number(id)?)
+            }
+            TypeRef::True => return Ok(()),
+            TypeRef::Vector { boxed, element } => {
+                let items = value.as_array().ok_or_else(|| invalid(path, "array"))?;
+                if *boxed {
+                    let vector = self.schema.constructor_number("vector")?;
+                    self.ops.push(Op::ConstructorNumber(vector));
+                }
+                self.ops.push(Op::Nat(items.len() as i32));
+                for (i, item) in items.iter().enumerate() {
+                    self.value(element, item, &format!("{}[{}]", path, i))?;
+                }
+
+                return Ok(());
+            }
+            TypeRef::Bare(combinator) => {
+                let object = as_object(value, path)?;
+                if let Some(id) = object.get("@type") {
+                    if id.as_str() != Some(combinator.id()) {
+                        return Err(unexpected(path, id, combinator.id()));
+                    }
+                }
+
+                return self.fields(combinator, object, path);
+            }
+            TypeRef::Boxed(name) => {
+                let object = as_object(value, path)?;
+                let constructors = &self.schema.constructors[*name];
+                let id = match (object.get("@type"), constructors.as_slice()) {
+                    (Some(id), _) => id.as_str().ok_or_else(|| invalid(path, "string @type"))?,
+                    (None, [id]) => id.as_str(),
+                    (None, _) => {
+                        return Err(Error::MissingField {
+                            path: path.to_owned(),
+                            field: "@type".to_owned(),
+                        })
+                    }
+                };
+                if !constructors.iter().any(|c| c == id) {
+                    return Err(unexpected(path, &Value::from(id), name));
+                }
+
+                let combinator = self
+                    .schema
+                    .combinator(id)
+                    .ok_or_else(|| Error::UnknownCombinator(id.to_owned()))?;
+                self.ops
+                    .push(Op::ConstructorNumber(combinator.constructor_number_be()));
+
+                return self.fields(combinator, object, path);
+            }
+        };
+
+        self.ops.push(op);
+
+        Ok(())
+    }
+
+    fn fields(
+        &mut self,
+        combinator: &Combinator,
+        object: &Map<String, Value>,
+        path: &str,
+    ) -> Result<(), Error> {
+        let mut flags: HashMap<&str, i32> = HashMap::new();
+        for field in combinator.fields() {
+            let (Some(name), Some(condition)) = (field.id(), field.type_condition()) else {
+                continue;
+            };
+            let Some(bit) = condition.bit_selector else {
+                return Err(Error::UnsupportedType(field.constructor_number_form()));
+            };
+
+            let present = match object.get(name) {
+                None | Some(Value::Null) => false,
+                Some(Value::Bool(value)) if field.field_type() == Some("true") => *value,
+                Some(_) => true,
+            };
+            if present {
+                *flags.entry(condition.field_ref.as_str()).or_default() |= 1 << bit;
+            }
+        }
+
+        let mut nats: HashMap<&str, i32> = HashMap::new();
+        for field in combinator.fields() {
+            let name = field
+                .id()
+                .ok_or_else(|| Error::UnsupportedType(field.constructor_number_form()))?;
+            let field_path = child_path(path, name);
+            let value = object.get(name).filter(|value| !value.is_null());
+            let missing = || Error::MissingField {
+                path: path.to_owned(),
+                field: name.to_owned(),
+            };
+
+            if let Some(Condition {
+                field_ref,
+                bit_selector: Some(bit),
+            }) = field.type_condition()
+            {
+                let nat = nats.get(field_ref.as_str()).copied().unwrap_or_default();
+                if nat & (1 << bit) == 0 {
+                    continue;
+                }
+
+                let ty = self.schema.resolve_field(field)?;
+                match (value, ty) {
+                    (_, TypeRef::True) => {}
+                    (Some(value), ty) => self.value(&ty, value, &field_path)?,
+                    (None, _) => return Err(missing()),
+                }
+
+                continue;
+            }
+
+            let ty = self.schema.resolve_field(field)?;
+            if let TypeRef::Nat = ty {
+                let nat = match value {
+                    Some(value) => as_nat(value, &field_path)?,
+                    None if flags.contains_key(name) => 0,
+                    None => return Err(missing()),
+                } | flags.get(name).copied().unwrap_or_default();
+
+                nats.insert(name, nat);
+                self.ops.push(Op::Nat(nat));
+
+                continue;
+            }
+
+            self.value(&ty, value.ok_or_else(missing)?, &field_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(path: &str, expected: &'static str) -> Error {
+    Error::InvalidValue {
+        path: path.to_owned(),
+        expected,
+    }
+}
+
+fn unexpected(path: &str, constructor: &Value, expected: &str) -> Error {
+    Error::UnexpectedConstructor {
+        path: path.to_owned(),
+        constructor: constructor.as_str().unwrap_or_default().to_owned(),
+        expected: expected.to_owned(),
+    }
+}
+
+fn as_object<'v>(value: &'v Value, path: &str) -> Result<&'v Map<String, Value>, Error> {
+    value.as_object().ok_or_else(|| invalid(path, "object"))
+}
+
+fn type_of<'v>(object: &'v Map<String, Value>, path: &str) -> Result<&'v str, Error> {
+    object
+        .get("@type")
+        .ok_or_else(|| Error::MissingField {
+            path: path.to_owned(),
+            field: "@type".to_owned(),
+        })?
+        .as_str()
+        .ok_or_else(|| invalid(path, "string @type"))
+}
+
+/// Numbers are accepted as JSON numbers and as decimal strings.
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_nat(value: &Value, path: &str) -> Result<i32, Error> {
+    as_i64(value)
+        .filter(|n| (0..=i32::MAX as i64).contains(n))
+        .map(|n| n as i32)
+        .ok_or_else(|| invalid(path, "nat"))
+}
+
+fn as_int(value: &Value, path: &str) -> Result<i32, Error> {
+    as_i64(value)
+        .and_then(|n| i32::try_from(n).ok())
+        .ok_or_else(|| invalid(path, "int"))
+}
+
+fn as_long(value: &Value, path: &str) -> Result<i64, Error> {
+    as_i64(value).ok_or_else(|| invalid(path, "long"))
+}
+
+fn as_bytes(value: &Value, path: &str) -> Result<Vec<u8>, Error> {
+    value
+        .as_str()
+        .and_then(|s| base64::engine::general_purpose::STANDARD.decode(s).ok())
+        .ok_or_else(|| invalid(path, "base64 bytes"))
+}
+
+fn as_base64<const N: usize>(value: &Value, path: &str) -> Result<[u8; N], Error> {
+    as_bytes(value, path)?
+        .try_into()
+        .map_err(|_| invalid(path, "base64 of a fixed size"))
+}
@@ -0,0 +1,523 @@
+//! Semantic checks of a parsed scheme: type resolution, constructor numbers,
+//! conditional fields and duplicate names.
+
+use crate::FieldType::{Plain, Repetition};
+use crate::{parse_with_positions, Combinator, Field};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// 1-based line and column in the scheme source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DiagnosticKind {
+    #[error("syntax error: {0}")]
+    Syntax(String),
+    #[error("`{combinator}` references unknown type `{name}`")]
+    UnknownType { combinator: String, name: String },
+    #[error(
+        "`{combinator}` declares constructor number #{declared:08x}, computed #{computed:08x}"
+    )]
+    ConstructorNumberMismatch {
+        combinator: String,
+        declared: u32,
+        computed: u32,
+    },
+    #[error("`{combinator}`: condition of `{field}` references unknown field `{field_ref}`")]
+    UnknownConditionField {
+        combinator: String,
+        field: String,
+        field_ref: String,
+    },
+    #[error("`{combinator}`: condition of `{field}` references `{field_ref}` which is not `#`")]
+    ConditionFieldNotNat {
+        combinator: String,
+        field: String,
+        field_ref: String,
+    },
+    #[error("`{combinator}`: condition of `{field}` selects bit {bit}, must be below 32")]
+    InvalidBitSelector {
+        combinator: String,
+        field: String,
+        bit: u32,
+    },
+    #[error("duplicate combinator `{combinator}`, first declared at {first}")]
+    DuplicateCombinator { combinator: String, first: Position },
+    #[error(
+        "`{combinator}` has the same constructor number #{constructor_number:08x} as `{other}`"
+    )]
+    DuplicateConstructorNumber {
+        combinator: String,
+        other: String,
+        constructor_number: u32,
+    },
+    #[error("`{combinator}` declares field `{field}` more than once")]
+    DuplicateField { combinator: String, field: String },
+}
+
+impl DiagnosticKind {
+    /// Explicit constructor numbers are allowed to differ from the computed ones,
+    /// upstream keeps old numbers this way when a combinator is extended.
+    pub fn severity(&self) -> Severity {
+        match self {
+            DiagnosticKind::ConstructorNumberMismatch { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{position}: {kind}")]
+pub struct Diagnostic {
+    pub position: Position,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    pub fn new(position: Position, kind: DiagnosticKind) -> Self {
+        Self { position, kind }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, diagnostic) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", diagnostic)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses and checks the scheme, fails with every error found. Warnings are not reported,
+/// use [`check`] to get them.
+pub fn validate(input: &str) -> Result<Vec<Combinator>, Diagnostics> {
+    let combinators = parse_with_positions(input).map_err(|e| Diagnostics(vec![e]))?;
+
+    let errors: Vec<_> = check(&combinators)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+        .collect();
+    if !errors.is_empty() {
+        return Err(Diagnostics(errors));
+    }
+
+    Ok(combinators
+        .into_iter()
+        .map(|(_, combinator)| combinator)
+        .collect())
+}
+
+/// Runs all semantic checks, diagnostics are ordered by position.
+pub fn check(combinators: &[(Position, Combinator)]) -> Vec<Diagnostic> {
+    let mut bare_types: HashSet<&str> = HashSet::from(["#", "Type"]);
+    let mut boxed_types: HashSet<&str> = HashSet::new();
+    for (_, combinator) in combinators.iter() {
+        bare_types.insert(combinator.id());
+        if !combinator.is_functional() {
+            boxed_types.insert(type_head(combinator.result_type()));
+        }
+    }
+
+    let mut output = Vec::new();
+    let mut ids: HashMap<&str, Position> = HashMap::new();
+    let mut constructor_numbers: HashMap<u32, &str> = HashMap::new();
+    for (position, combinator) in combinators.iter() {
+        let mut report = |kind| output.push(Diagnostic::new(*position, kind));
+        let name = combinator.id().to_owned();
+
+        if combinator.id() != "_" {
+            if let Some(first) = ids.insert(combinator.id(), *position) {
+                report(DiagnosticKind::DuplicateCombinator {
+                    combinator: name.clone(),
+                    first,
+                });
+            }
+        }
+
+        if !combinator.is_builtin() {
+            let computed = crc32fast::hash(combinator.constructor_number_form().as_bytes());
+            if let Some(declared) = combinator.constructor_number.filter(|n| *n != computed) {
+                report(DiagnosticKind::ConstructorNumberMismatch {
+                    combinator: name.clone(),
+                    declared,
+                    computed,
+                });
+            }
+
+            let constructor_number = combinator.constructor_number_le();
+            if let Some(other) = constructor_numbers.insert(constructor_number, combinator.id()) {
+                report(DiagnosticKind::DuplicateConstructorNumber {
+                    combinator: name.clone(),
+                    other: other.to_owned(),
+                    constructor_number,
+                });
+            }
+        }
+
+        let mut scope = Scope {
+            combinator: combinator.id(),
+            types: &bare_types,
+            boxed_types: &boxed_types,
+            variables: combinator
+                .optional_fields
+                .iter()
+                .map(|field| (field.name.as_str(), field.r#type.as_str()))
+                .collect(),
+            fields: Vec::new(),
+        };
+        scope.check_fields(combinator.fields(), &mut report);
+
+        if combinator.is_functional() {
+            for name in type_names(combinator.result_type()) {
+                if !scope.resolves(name) {
+                    report(DiagnosticKind::UnknownType {
+                        combinator: combinator.id().to_owned(),
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+    }
+
+    output.sort_by_key(|diagnostic| diagnostic.position);
+
+    output
+}
+
+struct Scope<'a> {
+    combinator: &'a str,
+    types: &'a HashSet<&'a str>,
+    boxed_types: &'a HashSet<&'a str>,
+    /// Type variables from `{t:Type}` and `{n:#}`.
+    variables: HashMap<&'a str, &'a str>,
+    /// Preceding fields with their types.
+    fields: Vec<(&'a str, Option<&'a str>)>,
+}
+
+impl<'a> Scope<'a> {
+    fn resolves(&self, name: &str) -> bool {
+        name.starts_with(|c: char| c.is_ascii_digit())
+            || self.types.contains(name)
+            || self.boxed_types.contains(name)
+            || self.variables.contains_key(name)
+            || self.fields.iter().any(|(field, _)| *field == name)
+    }
+
+    fn field_type(&self, name: &str) -> Option<&'a str> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(field, _)| *field == name)
+            .and_then(|(_, ty)| *ty)
+            .or_else(|| self.variables.get(name).copied())
+    }
+
+    fn check_fields(&mut self, fields: &'a [Field], report: &mut impl FnMut(DiagnosticKind)) {
+        for field in fields {
+            let field_name = field.id().unwrap_or("_");
+
+            match &field.r#type {
+                Plain { name, condition } => {
+                    if let Some(condition) = condition {
+                        self.check_condition(field_name, condition, report);
+                    }
+
+                    for name in type_names(name) {
+                        if !self.resolves(name) {
+                            report(DiagnosticKind::UnknownType {
+                                combinator: self.combinator.to_owned(),
+                                name: name.to_owned(),
+                            });
+                        }
+                    }
+                }
+                Repetition {
+                    multiplicity,
+                    fields,
+                } => {
+                    for name in multiplicity.iter().flat_map(|m| type_names(m)) {
+                        if !self.resolves(name) {
+                            report(DiagnosticKind::UnknownType {
+                                combinator: self.combinator.to_owned(),
+                                name: name.to_owned(),
+                            });
+                        }
+                    }
+
+                    let mut inner = Scope {
+                        combinator: self.combinator,
+                        types: self.types,
+                        boxed_types: self.boxed_types,
+                        variables: self.variables.clone(),
+                        fields: self.fields.clone(),
+                    };
+                    inner.check_fields(fields, report);
+                }
+            }
+
+            if let Some(name) = field.id().filter(|name| *name != "_") {
+                if self.fields.iter().any(|(field, _)| *field == name) {
+                    report(DiagnosticKind::DuplicateField {
+                        combinator: self.combinator.to_owned(),
+                        field: name.to_owned(),
+                    });
+                }
+
+                let ty = match &field.r#type {
+                    Plain { name, .. } => Some(name.as_str()),
+                    Repetition { .. } => None,
+                };
+                self.fields.push((name, ty));
+            }
+        }
+    }
+
+    fn check_condition(
+        &self,
+        field: &str,
+        condition: &crate::Condition,
+        report: &mut impl FnMut(DiagnosticKind),
+    ) {
+        let field_ref = condition.field_ref.as_str();
+        if !self.resolves(field_ref) {
+            report(DiagnosticKind::UnknownConditionField {
+                combinator: self.combinator.to_owned(),
+                field: field.to_owned(),
+                field_ref: field_ref.to_owned(),
+            });
+
+            return;
+        }
+
+        let Some(bit) = condition.bit_selector else {
+            return;
+        };
+        if self.field_type(field_ref) != Some("#") {
+            report(DiagnosticKind::ConditionFieldNotNat {
+                combinator: self.combinator.to_owned(),
+                field: field.to_owned(),
+                field_ref: field_ref.to_owned(),
+            });
+        }
+        if bit >= 32 {
+            report(DiagnosticKind::InvalidBitSelector {
+                combinator: self.combinator.to_owned(),
+                field: field.to_owned(),
+                bit,
+            });
+        }
+    }
+}
+
+fn type_head(name: &str) -> &str {
+    name.split_whitespace().next().unwrap_or(name)
+}
+
+/// Identifiers used in a type expression: `vector<raw.message>` -> `vector`, `raw.message`.
+fn type_names(expr: &str) -> impl Iterator<Item = &str> {
+    expr.split(|c: char| c.is_whitespace() || "<>,()+%!".contains(c))
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRELUDE: &str = "int ? = Int;\nlong ? = Long;\nbytes data:string = Bytes;\nstring ? = String;\nvector {t:Type} # [ t ] = Vector t;\n";
+
+    fn kinds(scheme: &str) -> Vec<DiagnosticKind> {
+        let combinators = parse_with_positions(&format!("{}{}", PRELUDE, scheme)).unwrap();
+
+        check(&combinators).into_iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn position_from_offset() {
+        let source = "first\nsecond line\nthird";
+
+        assert_eq!(Position::from_offset(source, 0).to_string(), "1:1");
+        assert_eq!(Position::from_offset(source, 13).to_string(), "2:8");
+        assert_eq!(Position::from_offset(source, 18).to_string(), "3:1");
+    }
+
+    #[test]
+    fn check_valid_scheme() {
+        let actual = kinds(
+            "test.item id:int flags:# name:flags.0?string ids:(vector long) = test.Item;\n\
+             ---functions---\n\
+             test.getItems ids:vector<long> = Vector test.Item;",
+        );
+
+        assert_eq!(actual, vec![]);
+    }
+
+    #[test]
+    fn check_unknown_type() {
+        let actual = kinds("test.item id:int256 items:vector<test.unknown> = test.Item;");
+
+        assert_eq!(
+            actual,
+            vec![
+                DiagnosticKind::UnknownType {
+                    combinator: "test.item".to_owned(),
+                    name: "int256".to_owned()
+                },
+                DiagnosticKind::UnknownType {
+                    combinator: "test.item".to_owned(),
+                    name: "test.unknown".to_owned()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn check_unknown_function_result() {
+        let actual = kinds("---functions---\ntest.get = test.Unknown;");
+
+        assert_eq!(
+            actual,
+            vec![DiagnosticKind::UnknownType {
+                combinator: "test.get".to_owned(),
+                name: "test.Unknown".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn check_constructor_number_mismatch() {
+        let combinators = parse_with_positions(
+            "int ? = Int;\ntest.valid#ef863b8b value:int = test.Valid;\ntest.item#deadbeef value:int = test.Item;",
+        )
+        .unwrap();
+
+        let actual = check(&combinators);
+
+        assert_eq!(
+            actual,
+            vec![Diagnostic::new(
+                Position { line: 3, column: 1 },
+                DiagnosticKind::ConstructorNumberMismatch {
+                    combinator: "test.item".to_owned(),
+                    declared: 0xdeadbeef,
+                    computed: 0x676ab91b,
+                }
+            )]
+        );
+        assert_eq!(actual[0].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn check_conditions() {
+        let actual = kinds(
+            "test.item mode:# id:int a:mode.0?int b:flags.1?int c:id.2?int d:mode.40?int = test.Item;",
+        );
+
+        assert_eq!(
+            actual,
+            vec![
+                DiagnosticKind::UnknownConditionField {
+                    combinator: "test.item".to_owned(),
+                    field: "b".to_owned(),
+                    field_ref: "flags".to_owned()
+                },
+                DiagnosticKind::ConditionFieldNotNat {
+                    combinator: "test.item".to_owned(),
+                    field: "c".to_owned(),
+                    field_ref: "id".to_owned()
+                },
+                DiagnosticKind::InvalidBitSelector {
+                    combinator: "test.item".to_owned(),
+                    field: "d".to_owned(),
+                    bit: 40
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_duplicates() {
+        let combinators = parse_with_positions(
+            "int ? = Int;\ntest.item a:int a:int = test.Item;\n\ntest.item b:int = test.Item;",
+        )
+        .unwrap();
+
+        let actual: Vec<_> = check(&combinators)
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                "2:1: `test.item` declares field `a` more than once",
+                "4:1: duplicate combinator `test.item`, first declared at 2:1",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_syntax_position() {
+        let actual =
+            validate("int ? = Int;\n\ntest.item a:int = test.Item;\n  test.broken a:int\n");
+
+        let Err(Diagnostics(diagnostics)) = actual else {
+            panic!("expected error")
+        };
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].position, Position { line: 4, column: 3 });
+        assert!(matches!(diagnostics[0].kind, DiagnosticKind::Syntax(_)));
+    }
+
+    #[test]
+    fn validate_ignores_warnings() {
+        let actual = validate("int ? = Int;\ntest.item#deadbeef value:int = test.Item;");
+
+        assert_eq!(actual.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn validate_lite_api() {
+        let scheme = include_str!("../../tonlibjson-sys/ton/tl/generate/scheme/lite_api.tl");
+
+        assert!(validate(scheme).is_ok());
+    }
+}
@@ -0,0 +1,56 @@
+//! Compares two `.tl` schemes and exits with a non-zero status on breaking changes.
+//! `--json` classifies them for the tonlib JSON encoding instead of TL binary.
+//!
+//! ```sh
+//! cargo run -p tl_parser --bin tl_diff -- --json \
+//!     tonlibjson-sys/ton/tl/generate/scheme/tonlib_api.tl \
+//!     tonlibjson-sys/ton-testnet/tl/generate/scheme/tonlib_api.tl
+//! ```
+
+use anyhow::{bail, Context};
+use std::process::ExitCode;
+use tl_parser::Encoding;
+
+fn main() -> anyhow::Result<ExitCode> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let encoding = match args.iter().position(|arg| arg == "--json") {
+        Some(position) => {
+            args.remove(position);
+            Encoding::Json
+        }
+        None => Encoding::Binary,
+    };
+    let [old, new] = args.as_slice() else {
+        bail!("usage: tl_diff [--json] <old.tl> <new.tl>");
+    };
+
+    let read = |path: &str| {
+        let source =
+            std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path))?;
+
+        tl_parser::parse(&source).with_context(|| format!("cannot parse {}", path))
+    };
+    let changes = tl_parser::diff(&read(old)?, &read(new)?);
+
+    for change in changes.iter() {
+        let severity = if change.is_breaking_in(encoding) {
+            "breaking"
+        } else {
+            "compatible"
+        };
+
+        println!("{}: {}", severity, change);
+    }
+
+    let breaking = changes
+        .iter()
+        .filter(|change| change.is_breaking_in(encoding))
+        .count();
+    println!("{} changes, {} breaking", changes.len(), breaking);
+
+    Ok(if breaking > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
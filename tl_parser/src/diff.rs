@@ -0,0 +1,302 @@
+//! Comparison of two schemes, e.g. the mainnet and testnet trees, with every change
+//! classified as breaking or compatible.
+
+use crate::{Combinator, Field};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub combinator: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    ResultType { old: String, new: String },
+    ConstructorNumber { old: u32, new: u32 },
+    FieldAdded(Field),
+    FieldRemoved(Field),
+    FieldChanged { old: Field, new: Field },
+    FieldsReordered,
+}
+
+/// Encoding the scheme is used with, it decides which changes break existing peers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// TL binary, e.g. `lite_api` over ADNL.
+    #[default]
+    Binary,
+    /// tonlib JSON, e.g. `tonlib_api` through `tonlib_client_json`: objects are matched by the
+    /// `@type` name and fields by name.
+    Json,
+}
+
+impl Change {
+    /// Only new combinators keep existing peers working. The implicit constructor number is
+    /// the crc32 of the combinator, so any field or result type change, even an added
+    /// conditional field, changes the binary encoding.
+    pub fn is_breaking(&self) -> bool {
+        self.is_breaking_in(Encoding::Binary)
+    }
+
+    /// In JSON constructor numbers and field order don't matter and added fields are ignored
+    /// by older peers, removed and changed fields or result types still break them.
+    pub fn is_breaking_in(&self, encoding: Encoding) -> bool {
+        match encoding {
+            Encoding::Binary => !matches!(self.kind, ChangeKind::Added),
+            Encoding::Json => !matches!(
+                self.kind,
+                ChangeKind::Added
+                    | ChangeKind::ConstructorNumber { .. }
+                    | ChangeKind::FieldAdded(_)
+                    | ChangeKind::FieldsReordered
+            ),
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` ", self.combinator)?;
+
+        match &self.kind {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::ResultType { old, new } => {
+                write!(f, "result type changed from `{}` to `{}`", old, new)
+            }
+            ChangeKind::ConstructorNumber { old, new } => {
+                write!(
+                    f,
+                    "constructor number changed from #{:08x} to #{:08x}",
+                    old, new
+                )
+            }
+            ChangeKind::FieldAdded(field) => {
+                write!(f, "field added `{}`", field.constructor_number_form())
+            }
+            ChangeKind::FieldRemoved(field) => {
+                write!(f, "field removed `{}`", field.constructor_number_form())
+            }
+            ChangeKind::FieldChanged { old, new } => write!(
+                f,
+                "field changed from `{}` to `{}`",
+                old.constructor_number_form(),
+                new.constructor_number_form()
+            ),
+            ChangeKind::FieldsReordered => write!(f, "fields reordered"),
+        }
+    }
+}
+
+/// Changes of combinators present in `new`, in its order, followed by the removed ones.
+pub fn diff(old: &[Combinator], new: &[Combinator]) -> Vec<Change> {
+    let old_by_id: HashMap<&str, &Combinator> = old.iter().map(|c| (c.id(), c)).collect();
+    let new_by_id: HashMap<&str, &Combinator> = new.iter().map(|c| (c.id(), c)).collect();
+
+    let mut changes = Vec::new();
+    let mut push = |combinator: &Combinator, kind| {
+        changes.push(Change {
+            combinator: combinator.id().to_owned(),
+            kind,
+        })
+    };
+
+    for combinator in new {
+        let Some(previous) = old_by_id.get(combinator.id()) else {
+            push(combinator, ChangeKind::Added);
+            continue;
+        };
+
+        if previous.result_type() != combinator.result_type() {
+            push(
+                combinator,
+                ChangeKind::ResultType {
+                    old: previous.result_type().to_owned(),
+                    new: combinator.result_type().to_owned(),
+                },
+            );
+        }
+
+        for kind in diff_fields(previous.fields(), combinator.fields()) {
+            push(combinator, kind);
+        }
+
+        if previous.constructor_number_le() != combinator.constructor_number_le() {
+            push(
+                combinator,
+                ChangeKind::ConstructorNumber {
+                    old: previous.constructor_number_le(),
+                    new: combinator.constructor_number_le(),
+                },
+            );
+        }
+    }
+
+    for combinator in old {
+        if !new_by_id.contains_key(combinator.id()) {
+            push(combinator, ChangeKind::Removed);
+        }
+    }
+
+    changes
+}
+
+fn diff_fields(old: &[Field], new: &[Field]) -> Vec<ChangeKind> {
+    let old_by_key: HashMap<String, &Field> = old.iter().map(|f| (field_key(f), f)).collect();
+    let new_by_key: HashMap<String, &Field> = new.iter().map(|f| (field_key(f), f)).collect();
+
+    let mut changes = Vec::new();
+    for field in new {
+        match old_by_key.get(&field_key(field)) {
+            None => changes.push(ChangeKind::FieldAdded(field.clone())),
+            Some(previous) if *previous != field => changes.push(ChangeKind::FieldChanged {
+                old: (*previous).clone(),
+                new: field.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for field in old {
+        if !new_by_key.contains_key(&field_key(field)) {
+            changes.push(ChangeKind::FieldRemoved(field.clone()));
+        }
+    }
+
+    let common = |fields: &[Field], other: &HashMap<String, &Field>| {
+        fields
+            .iter()
+            .map(field_key)
+            .filter(|key| other.contains_key(key))
+            .collect::<Vec<_>>()
+    };
+    if common(old, &new_by_key) != common(new, &old_by_key) {
+        changes.push(ChangeKind::FieldsReordered);
+    }
+
+    changes
+}
+
+/// Fields are matched by name, anonymous ones by their whole form.
+fn field_key(field: &Field) -> String {
+    field
+        .id()
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| field.constructor_number_form())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn changes(old: &str, new: &str) -> Vec<String> {
+        changes_in(Encoding::Binary, old, new)
+    }
+
+    fn changes_in(encoding: Encoding, old: &str, new: &str) -> Vec<String> {
+        diff(&parse(old).unwrap(), &parse(new).unwrap())
+            .iter()
+            .map(|change| format!("{} {}", change.is_breaking_in(encoding), change))
+            .collect()
+    }
+
+    #[test]
+    fn diff_same_scheme() {
+        let scheme = "a.foo x:int = a.Foo;\n---functions---\na.get = a.Foo;\n";
+
+        assert!(changes(scheme, scheme).is_empty());
+    }
+
+    #[test]
+    fn diff_added_and_removed() {
+        let actual = changes(
+            "a.foo x:int = a.Foo;\na.old = a.Foo;\n",
+            "a.foo x:int = a.Foo;\na.new = a.Foo;\n",
+        );
+
+        assert_eq!(actual, vec!["false `a.new` added", "true `a.old` removed"]);
+    }
+
+    #[test]
+    fn diff_fields() {
+        let actual = changes(
+            "a.foo mode:# x:int y:long = a.Foo;\n",
+            "a.foo mode:# x:long z:mode.0?int = a.Foo;\n",
+        );
+
+        assert_eq!(
+            actual,
+            vec![
+                "true `a.foo` field changed from `x:int` to `x:long`",
+                "true `a.foo` field added `z:mode.0?int`",
+                "true `a.foo` field removed `y:long`",
+                "true `a.foo` constructor number changed from #a4cb296b to #3279c09a",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reordered_fields() {
+        let actual = changes(
+            "a.foo x:int y:int = a.Foo;\n",
+            "a.foo y:int x:int = a.Foo;\n",
+        );
+
+        assert_eq!(actual[0], "true `a.foo` fields reordered");
+    }
+
+    #[test]
+    fn diff_result_type_and_explicit_constructor_number() {
+        let actual = changes("a.foo#00000001 = a.Foo;\n", "a.foo#00000002 = a.Bar;\n");
+
+        assert_eq!(
+            actual,
+            vec![
+                "true `a.foo` result type changed from `a.Foo` to `a.Bar`",
+                "true `a.foo` constructor number changed from #00000001 to #00000002",
+            ]
+        );
+    }
+
+    /// `raw.message` of tonlib_api.tl before and after extra currencies were added.
+    const TONLIB_API_OLD: &str = "raw.message source:accountAddress destination:accountAddress value:int64 fwd_fee:int64 ihr_fee:int64 created_lt:int64 body_hash:bytes msg_data:msg.Data = raw.Message;\n";
+    const TONLIB_API_NEW: &str = "extraCurrency id:int32 amount:int64 = ExtraCurrency;\nraw.message hash:bytes source:accountAddress destination:accountAddress value:int64 extra_currencies:vector<extraCurrency> fwd_fee:int64 ihr_fee:int64 created_lt:int64 body_hash:bytes msg_data:msg.Data = raw.Message;\n";
+
+    #[test]
+    fn diff_tonlib_api_json() {
+        let actual = changes_in(Encoding::Json, TONLIB_API_OLD, TONLIB_API_NEW);
+
+        assert_eq!(
+            actual,
+            vec![
+                "false `extraCurrency` added",
+                "false `raw.message` field added `hash:bytes`",
+                "false `raw.message` field added `extra_currencies:vector<extraCurrency>`",
+                "false `raw.message` constructor number changed from #518b724f to #4b5d0a03",
+            ]
+        );
+        assert!(changes(TONLIB_API_OLD, TONLIB_API_NEW)[1..]
+            .iter()
+            .all(|change| change.starts_with("true")));
+    }
+
+    #[test]
+    fn diff_json_removed_and_changed_fields() {
+        let actual = changes_in(
+            Encoding::Json,
+            "a.foo x:int32 y:int64 = a.Foo;\n",
+            "a.foo x:int64 = a.Foo;\n",
+        );
+
+        assert_eq!(
+            actual[0],
+            "true `a.foo` field changed from `x:int32` to `x:int64`"
+        );
+        assert_eq!(actual[1], "true `a.foo` field removed `y:int64`");
+    }
+}
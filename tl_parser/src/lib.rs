@@ -1,5 +1,12 @@
+mod analysis;
+mod diff;
+
+pub use crate::analysis::{
+    check, validate, Diagnostic, DiagnosticKind, Diagnostics, Position, Severity,
+};
+pub use crate::diff::{diff, Change, ChangeKind, Encoding};
+
 use crate::FieldType::{Plain, Repetition};
-use anyhow::{anyhow, bail};
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_till, take_until, take_while, take_while1, take_while_m_n};
 use nom::character::complete::{line_ending, multispace0, multispace1, satisfy, space0};
@@ -251,7 +258,26 @@ impl OptionalField {
 }
 
 pub fn parse(input: &str) -> anyhow::Result<Vec<Combinator>> {
-    let mut input = input;
+    Ok(parse_with_positions(input)?
+        .into_iter()
+        .map(|(_, combinator)| combinator)
+        .collect())
+}
+
+/// Parses the scheme and keeps the position of every combinator for diagnostics.
+pub fn parse_with_positions(source: &str) -> Result<Vec<(Position, Combinator)>, Diagnostic> {
+    let syntax_error = |input: &str, message: String| {
+        Diagnostic::new(
+            Position::from_offset(source, source.len() - input.len()),
+            DiagnosticKind::Syntax(message),
+        )
+    };
+    let nom_error = |e: nom::Err<Error<&str>>| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => syntax_error(e.input, format!("{:?}", e.code)),
+        nom::Err::Incomplete(_) => syntax_error("", "unexpected end of input".to_owned()),
+    };
+
+    let mut input = source;
     let mut collect = Vec::new();
 
     loop {
@@ -266,12 +292,12 @@ pub fn parse(input: &str) -> anyhow::Result<Vec<Combinator>> {
             )),
             many0(delimited(
                 opt(space_or_comment),
-                alt((combinator_decl, builtin_combinator_decl)),
+                pair(remaining, alt((combinator_decl, builtin_combinator_decl))),
                 opt(space_or_comment),
             )),
         ))
         .parse(input)
-        .map_err(|e| anyhow!("parse error: {}", e))?;
+        .map_err(nom_error)?;
 
         if let Some(types) = types {
             collect.extend(types)
@@ -285,27 +311,45 @@ pub fn parse(input: &str) -> anyhow::Result<Vec<Combinator>> {
             )),
             many0(delimited(
                 opt(space_or_comment),
-                alt((functional_combinator_decl, builtin_combinator_decl)),
+                pair(
+                    remaining,
+                    alt((functional_combinator_decl, builtin_combinator_decl)),
+                ),
                 opt(space_or_comment),
             )),
         ))
         .parse(input)
-        .map_err(|e: nom::Err<Error<&str>>| anyhow!("parse error: {}", e))?;
+        .map_err(nom_error)?;
 
         if let Some(funcs) = funcs {
             collect.extend(funcs);
         }
 
         if input.is_empty() {
-            return Ok(collect);
+            return Ok(collect
+                .into_iter()
+                .map(|(remaining, combinator)| {
+                    let offset = source.len() - remaining;
+
+                    (Position::from_offset(source, offset), combinator)
+                })
+                .collect());
         }
 
         if prev == input {
-            bail!("infinity loop")
+            let input = space_or_comment(input).map_or(input, |(input, _)| input);
+            let line = input.lines().next().unwrap_or_default();
+
+            return Err(syntax_error(input, format!("unexpected `{}`", line.trim())));
         }
     }
 }
 
+/// Length of the remaining input, used to restore positions of parsed items.
+fn remaining(input: &str) -> nom::IResult<&str, usize> {
+    Ok((input, input.len()))
+}
+
 fn is_lc_letter(c: char) -> bool {
     c.is_ascii_lowercase()
 }
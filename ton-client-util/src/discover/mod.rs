@@ -41,14 +41,50 @@ pub fn read_ton_config_from_url_stream(
         .then(load_ton_config)
 }
 
+/// Which address family to use when a liteserver host resolves to both A and AAAA records,
+/// an `ip` given by the config is dropped as well if its family is not allowed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IpPreference {
+    #[default]
+    PreferIpv4,
+    PreferIpv6,
+    Ipv4Only,
+    Ipv6Only,
+}
+
+impl IpPreference {
+    /// Filters out disallowed families and orders the rest, keeping the resolver order within a family.
+    pub fn sort(&self, addrs: impl IntoIterator<Item = IpAddr>) -> Vec<IpAddr> {
+        let (v4, v6): (Vec<IpAddr>, Vec<IpAddr>) = addrs.into_iter().partition(IpAddr::is_ipv4);
+
+        match self {
+            IpPreference::PreferIpv4 => v4.into_iter().chain(v6).collect(),
+            IpPreference::PreferIpv6 => v6.into_iter().chain(v4).collect(),
+            IpPreference::Ipv4Only => v4,
+            IpPreference::Ipv6Only => v6,
+        }
+    }
+}
+
 pub struct LiteServerDiscoverActor<S> {
     stream: S,
     sender: mpsc::Sender<Change<LiteServerId, TonConfig>>,
+    ip_preference: IpPreference,
 }
 
 impl<S> LiteServerDiscoverActor<S> {
     pub fn new(stream: S, sender: mpsc::Sender<Change<LiteServerId, TonConfig>>) -> Self {
-        Self { stream, sender }
+        Self {
+            stream,
+            sender,
+            ip_preference: IpPreference::default(),
+        }
+    }
+
+    pub fn set_ip_preference(mut self, ip_preference: IpPreference) -> Self {
+        self.ip_preference = ip_preference;
+
+        self
     }
 }
 
@@ -72,8 +108,11 @@ where
 
             let mut liteserver_new: HashSet<LiteServer> = HashSet::default();
             for ls in new_config.liteservers.iter() {
-                match apply_dns(dns.clone(), ls.clone()).await {
+                match apply_dns(dns.clone(), ls.clone(), self.ip_preference).await {
                     Err(e) => tracing::error!("dns error: {:?}", e),
+                    Ok(ls) if ls.ip.is_none() => {
+                        tracing::warn!("no suitable address for {:?}", ls.id())
+                    }
                     Ok(ls) => {
                         liteserver_new.insert(ls);
                     }
@@ -122,12 +161,20 @@ pub struct LiteServerDiscover {
 
 impl LiteServerDiscover {
     pub fn new<S>(stream: S) -> Self
+    where
+        LiteServerDiscoverActor<S>: Actor,
+    {
+        Self::with_ip_preference(stream, IpPreference::default())
+    }
+
+    pub fn with_ip_preference<S>(stream: S, ip_preference: IpPreference) -> Self
     where
         LiteServerDiscoverActor<S>: Actor,
     {
         let token = CancellationToken::new();
         let (tx, rx) = mpsc::channel(100);
-        CancellableActor::new(LiteServerDiscoverActor::new(stream, tx), token.clone()).spawn();
+        let actor = LiteServerDiscoverActor::new(stream, tx).set_ip_preference(ip_preference);
+        CancellableActor::new(actor, token.clone()).spawn();
 
         Self {
             receiver: rx,
@@ -154,19 +201,149 @@ fn dns_resolver() -> TokioAsyncResolver {
     TokioAsyncResolver::tokio(resolver_config, resolver_opts)
 }
 
+/// Resolves both A and AAAA records of `host`, ordered by `ip_preference`.
+pub async fn resolve(
+    dns_resolver: &TokioAsyncResolver,
+    host: &str,
+    ip_preference: IpPreference,
+) -> Result<Vec<IpAddr>, ResolveError> {
+    let records = dns_resolver.lookup_ip(host).await?;
+
+    Ok(ip_preference.sort(records))
+}
+
 async fn apply_dns(
     dns_resolver: TokioAsyncResolver,
     ls: LiteServer,
+    ip_preference: IpPreference,
 ) -> Result<LiteServer, ResolveError> {
-    if let Some(host) = ls.host.as_ref() {
-        let records = dns_resolver.lookup_ip(host).await?;
+    let records = match ls.host.as_ref() {
+        Some(host) => resolve(&dns_resolver, host, ip_preference).await?,
+        None => Vec::new(),
+    };
 
-        for record in records {
-            if let IpAddr::V4(ip) = record {
-                return Ok(ls.with_ip(Into::<u32>::into(ip) as i32));
-            }
-        }
+    let ip = records.into_iter().chain(ip_preference.sort(ls.ip)).next();
+
+    Ok(LiteServer { ip, ..ls })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn addrs() -> Vec<IpAddr> {
+        vec![
+            Ipv6Addr::LOCALHOST.into(),
+            Ipv4Addr::new(10, 0, 0, 1).into(),
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into(),
+            Ipv4Addr::new(10, 0, 0, 2).into(),
+        ]
     }
 
-    Ok(ls)
+    #[test]
+    fn ip_preference_prefer_ipv4() {
+        let actual = IpPreference::PreferIpv4.sort(addrs());
+
+        assert_eq!(actual, vec![addrs()[1], addrs()[3], addrs()[0], addrs()[2]]);
+    }
+
+    #[test]
+    fn ip_preference_prefer_ipv6() {
+        let actual = IpPreference::PreferIpv6.sort(addrs());
+
+        assert_eq!(actual, vec![addrs()[0], addrs()[2], addrs()[1], addrs()[3]]);
+    }
+
+    #[test]
+    fn ip_preference_only() {
+        assert_eq!(
+            IpPreference::Ipv4Only.sort(addrs()),
+            vec![addrs()[1], addrs()[3]]
+        );
+        assert_eq!(
+            IpPreference::Ipv6Only.sort(addrs()),
+            vec![addrs()[0], addrs()[2]]
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_dns_ipv6_literal_host() {
+        let dns = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+        let ls = LiteServer {
+            id: LiteServerId {
+                r#type: "pub.ed25519".to_owned(),
+                key: "".to_owned(),
+            },
+            ip: None,
+            host: Some("2001:db8::1".to_owned()),
+            port: 53312,
+            proxy: None,
+        };
+
+        let ls = apply_dns(dns, ls, IpPreference::default()).await.unwrap();
+
+        assert_eq!(
+            ls.ip,
+            Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into())
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_dns_ipv4_only_drops_ipv6_host() {
+        let dns = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+        let ls = LiteServer {
+            id: LiteServerId {
+                r#type: "pub.ed25519".to_owned(),
+                key: "".to_owned(),
+            },
+            ip: None,
+            host: Some("::1".to_owned()),
+            port: 53312,
+            proxy: None,
+        };
+
+        let ls = apply_dns(dns, ls, IpPreference::Ipv4Only).await.unwrap();
+
+        assert_eq!(ls.ip, None);
+    }
+
+    #[tokio::test]
+    async fn apply_dns_ipv4_only_drops_ipv6_ip() {
+        let dns = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+        let ls = LiteServer {
+            id: LiteServerId {
+                r#type: "pub.ed25519".to_owned(),
+                key: "".to_owned(),
+            },
+            ip: Some(Ipv6Addr::LOCALHOST.into()),
+            host: None,
+            port: 53312,
+            proxy: None,
+        };
+
+        let ls = apply_dns(dns, ls, IpPreference::Ipv4Only).await.unwrap();
+
+        assert_eq!(ls.ip, None);
+    }
+
+    #[tokio::test]
+    async fn apply_dns_keeps_allowed_ip() {
+        let dns = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+        let ls = LiteServer {
+            id: LiteServerId {
+                r#type: "pub.ed25519".to_owned(),
+                key: "".to_owned(),
+            },
+            ip: Some(Ipv4Addr::new(10, 0, 0, 1).into()),
+            host: None,
+            port: 53312,
+            proxy: None,
+        };
+
+        let ls = apply_dns(dns, ls, IpPreference::Ipv4Only).await.unwrap();
+
+        assert_eq!(ls.ip, Some(Ipv4Addr::new(10, 0, 0, 1).into()));
+    }
 }
@@ -1,6 +1,6 @@
 use base64::Engine;
 use futures::{stream, StreamExt};
-use std::net::SocketAddrV4;
+use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::time::Instant;
 use ton_client_util::discover::{read_ton_config_from_url_stream, LiteServerDiscover};
@@ -36,7 +36,12 @@ async fn main() -> Result<(), tower::BoxError> {
                 let mut secret_key: [u8; 32] = [0; 32];
                 base64::engine::general_purpose::STANDARD
                     .decode_slice(&ls.id.key, &mut secret_key[..])?;
-                let addr: SocketAddrV4 = ls.into();
+                let proxy = ls.proxy.clone();
+                let addr = SocketAddr::try_from(ls)?;
+                let make_client = match proxy {
+                    None => MakeClient::new(addr, secret_key),
+                    Some(proxy) => MakeClient::new(addr, secret_key).set_proxy(proxy),
+                };
 
                 let client = ServiceBuilder::new()
                     .layer_fn(TrackedClient::new)
@@ -51,10 +56,7 @@ async fn main() -> Result<(), tower::BoxError> {
                         Ok(e) => *e,
                         Err(e) => Error::Connection(e.to_string()),
                     })
-                    .service(Reconnect::new::<LiteServerClient, ()>(
-                        MakeClient::new(addr, secret_key),
-                        (),
-                    ));
+                    .service(Reconnect::new::<LiteServerClient, ()>(make_client, ()));
 
                 anyhow::Ok(Change::Insert(k, client))
             }
@@ -0,0 +1,62 @@
+use adnl_tcp::client::ServerKey;
+use base64::Engine;
+use std::net::{Ipv4Addr, SocketAddr};
+use ton_liteserver_client::client::LiteServerClient;
+use ton_liteserver_client::mock::Recorder;
+use ton_liteserver_client::tl::{
+    LiteServerGetAllShardsInfo, LiteServerGetBlockHeader, LiteServerGetMasterchainInfo,
+    LiteServerLookupBlock, TonNodeBlockId,
+};
+use tower::ServiceExt;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "fixtures.txt".to_owned());
+
+    let mut recorder = Recorder::new(provided_client().await?);
+
+    let last = (&mut recorder)
+        .oneshot(LiteServerGetMasterchainInfo::default())
+        .await?
+        .last;
+    (&mut recorder)
+        .oneshot(LiteServerGetAllShardsInfo::new(last.clone()))
+        .await?;
+    (&mut recorder)
+        .oneshot(LiteServerGetBlockHeader::new(last.clone()))
+        .await?;
+    (&mut recorder)
+        .oneshot(LiteServerLookupBlock::seqno(TonNodeBlockId::new(
+            last.workchain,
+            last.shard,
+            last.seqno - 1,
+        )))
+        .await?;
+
+    let fixtures = recorder.fixtures();
+    fixtures.save(&path)?;
+
+    tracing::info!("{} fixtures saved to {}", fixtures.len(), path);
+
+    Ok(())
+}
+
+async fn provided_client() -> anyhow::Result<LiteServerClient> {
+    let ip: i32 = -2018135749;
+    let ip = Ipv4Addr::from(ip as u32);
+    let port = 53312;
+    let key: ServerKey = base64::engine::general_purpose::STANDARD
+        .decode("aF91CuUHuuOv9rm2W5+O/4h38M3sRm40DtSdRxQhmtQ=")?
+        .as_slice()
+        .try_into()?;
+
+    tracing::info!("Connecting to {}:{} with key {:?}", ip, port, key);
+
+    let client = LiteServerClient::connect(SocketAddr::new(ip.into(), port), key).await?;
+
+    Ok(client)
+}
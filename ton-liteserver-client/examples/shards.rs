@@ -1,7 +1,7 @@
 use adnl_tcp::client::ServerKey;
 use anyhow::anyhow;
 use base64::Engine;
-use std::net::{Ipv4Addr, SocketAddrV4};
+use std::net::{Ipv4Addr, SocketAddr};
 use std::time::Duration;
 use ton_liteserver_client::client::LiteServerClient;
 use ton_liteserver_client::tl::{
@@ -78,7 +78,7 @@ async fn provided_client() -> anyhow::Result<LiteServerClient> {
 
     tracing::info!("Connecting to {}:{} with key {:?}", ip, port, key);
 
-    let client = LiteServerClient::connect(SocketAddrV4::new(ip, port), key).await?;
+    let client = LiteServerClient::connect(SocketAddr::new(ip.into(), port), key).await?;
 
     Ok(client)
 }
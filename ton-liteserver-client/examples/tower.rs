@@ -1,12 +1,10 @@
 use adnl_tcp::client::ServerKey;
 use base64::Engine;
 use futures::{stream, StreamExt};
-use std::net::{Ipv4Addr, SocketAddrV4};
+use std::net::{Ipv4Addr, SocketAddr};
 use std::time::Duration;
 use ton_liteserver_client::client::LiteServerClient;
-use ton_liteserver_client::tl::{
-    LiteServerGetMasterchainInfo, LiteServerLookupBlock, TonNodeBlockId,
-};
+use ton_liteserver_client::tl::{LiteServerExt, LiteServerLookupBlock, TonNodeBlockId};
 use tower::{ServiceBuilder, ServiceExt};
 
 #[tokio::main]
@@ -19,10 +17,10 @@ async fn main() -> Result<(), tower::BoxError> {
         .timeout(Duration::from_secs(3))
         .service(client);
 
-    let last = (&mut svc)
-        .oneshot(LiteServerGetMasterchainInfo::default())
-        .await?
-        .last;
+    let last = svc.get_masterchain_info().await?.last;
+    let id = TonNodeBlockId::new(last.workchain, last.shard, last.seqno);
+    let header = svc.lookup_block(1, id, None, None).await?;
+    tracing::info!(?header.id.seqno);
 
     let requests = stream::iter((1..last.seqno).rev()).map(|seqno| LiteServerLookupBlock {
         mode: 1,
@@ -59,7 +57,7 @@ async fn provided_client() -> anyhow::Result<LiteServerClient> {
 
     tracing::info!("Connecting to {}:{} with key {:?}", ip, port, key);
 
-    let client = LiteServerClient::connect(SocketAddrV4::new(ip, port), key).await?;
+    let client = LiteServerClient::connect(SocketAddr::new(ip.into(), port), key).await?;
 
     Ok(client)
 }
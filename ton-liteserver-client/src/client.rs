@@ -1,12 +1,18 @@
+use crate::proof::{config_params, verify_config_info, ProofError, Verifiable};
 use crate::request::Requestable;
 use crate::tl::{
-    AdnlMessageAnswer, AdnlMessageQuery, Bytes, Int256, LiteServerError, LiteServerQuery,
+    AdnlMessageAnswer, AdnlMessageQuery, Bytes, Int256, LiteServerAccountId, LiteServerError,
+    LiteServerGetAccountState, LiteServerGetConfigParams, LiteServerQuery, TonNodeBlockIdExt,
 };
+use crate::tlb::account::Account;
+use crate::tlb::blockchain_config::BlockchainConfig;
 use adnl_tcp::client::{Client, ServerKey};
 use adnl_tcp::connection::Connection;
 use adnl_tcp::deserializer::{from_bytes_boxed, DeserializeBoxed};
+use adnl_tcp::key::Ed25519Key;
 use adnl_tcp::packet::Packet;
 use adnl_tcp::ping::{is_pong_packet, ping_packet};
+use adnl_tcp::proxy::Proxy;
 use adnl_tcp::serializer::to_bytes_boxed;
 use futures::{ready, SinkExt, StreamExt};
 use pin_project::pin_project;
@@ -14,7 +20,7 @@ use rand::random;
 use std::collections::HashMap;
 use std::future::Future;
 use std::marker::PhantomData;
-use std::net::SocketAddrV4;
+use std::net::SocketAddr;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -23,10 +29,11 @@ use thiserror::Error;
 use tokio::select;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
-use tokio::time::MissedTickBehavior;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio::time::{Instant, MissedTickBehavior};
 use tokio_util::sync::{CancellationToken, DropGuard};
-use tower::Service;
+use toner::tlb::bits::de::unpack_bytes_fully;
+use toner::ton::boc::BoC;
+use tower::{Service, ServiceExt};
 
 pub type RequestId = Int256;
 
@@ -44,6 +51,8 @@ pub enum Error {
     Elapsed,
     #[error("connection error: {0}")]
     Connection(String),
+    #[error("proof error: {0}")]
+    Proof(#[from] ProofError),
 }
 
 #[derive(Debug, Clone)]
@@ -73,53 +82,83 @@ impl ClientActor {
 
     pub fn run(mut self) {
         tokio::spawn(async move {
-            let mut responses: HashMap<RequestId, oneshot::Sender<Bytes>> = Default::default();
+            let mut responses: HashMap<RequestId, oneshot::Sender<Result<Bytes, Error>>> =
+                Default::default();
 
-            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            let period = Duration::from_secs(5);
+            let mut interval = tokio::time::interval_at(Instant::now() + period, period);
             interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
-            let stream = UnboundedReceiverStream::new(self.receiver);
-            let mut stream = tokio_stream::StreamExt::timeout_repeating(stream, interval);
-
-            loop {
+            let error = loop {
                 select! {
                     _ = self.cancellation_token.cancelled() => {
                         tracing::error!("LiteServerClient cancelled");
-                        break;
+                        break None;
                     },
-                    Some(response) = self.connection.next() => {
+                    response = self.connection.next() => {
                         match response {
-                            Ok(packet) if is_pong_packet(&packet) => {
+                            Some(Ok(packet)) if is_pong_packet(&packet) => {
                                 tracing::trace!("pong packet received");
                             },
-                            Ok(packet) => {
-                                let adnl_answer = from_bytes_boxed::<AdnlMessageAnswer>(&packet.data)
-                                    .expect("expect adnl answer packet");
+                            Some(Ok(packet)) => {
+                                let adnl_answer = match from_bytes_boxed::<AdnlMessageAnswer>(&packet.data) {
+                                    Ok(adnl_answer) => adnl_answer,
+                                    Err(error) => break Some(format!("unexpected packet: {}", error)),
+                                };
 
                                 if let Some(oneshot) = responses.remove(&adnl_answer.query_id) {
-                                    let _ = oneshot.send(adnl_answer.answer);
+                                    let _ = oneshot.send(Ok(adnl_answer.answer));
                                 }
                             }
-                            Err(error) => {
-                                tracing::error!(error = ?error, "reading error");
-
-                                return
-                            }
+                            Some(Err(error)) => break Some(format!("reading error: {}", error)),
+                            None => break Some("connection closed".to_owned()),
                         }
                     },
-                    Some(request) = stream.next() => {
-                        match request {
-                            Ok(ClientActorMessage::Query { query, oneshot }) => {
+                    Some(message) = self.receiver.recv() => {
+                        let sent = match message {
+                            ClientActorMessage::Query { query, oneshot } => {
                                 responses.insert(query.query_id, oneshot);
+                                interval.reset();
 
                                 let data = to_bytes_boxed(&query);
-                                self.connection.send(Packet::new(data)).await.expect("expect to send adnl query packet")
+                                self.connection.send(Packet::new(data)).await
                             }
-                            Err(_) => {
-                                self.connection.send(ping_packet()).await.expect("expect to send ping packet")
+                            ClientActorMessage::Cancel { query_id } => {
+                                responses.remove(&query_id);
+
+                                Ok(())
                             }
+                        };
+
+                        if let Err(error) = sent {
+                            break Some(format!("sending error: {}", error));
                         }
                     }
+                    _ = interval.tick() => {
+                        if let Err(error) = self.connection.send(ping_packet()).await {
+                            break Some(format!("sending error: {}", error));
+                        }
+                    }
+                }
+            };
+
+            if let Some(error) = error {
+                // queries which aren't sent yet fail the same way as the in-flight ones
+                self.receiver.close();
+                while let Ok(message) = self.receiver.try_recv() {
+                    if let ClientActorMessage::Query { query, oneshot } = message {
+                        responses.insert(query.query_id, oneshot);
+                    }
+                }
+
+                tracing::error!(
+                    error = error,
+                    pending = responses.len(),
+                    "connection failed"
+                );
+
+                for (_, oneshot) in responses.drain() {
+                    let _ = oneshot.send(Err(Error::Connection(error.clone())));
                 }
             }
 
@@ -131,21 +170,146 @@ impl ClientActor {
 enum ClientActorMessage {
     Query {
         query: AdnlMessageQuery,
-        oneshot: oneshot::Sender<Bytes>,
+        oneshot: oneshot::Sender<Result<Bytes, Error>>,
+    },
+    Cancel {
+        query_id: RequestId,
     },
 }
 
+/// Removes the query from the actor if the answer isn't awaited anymore.
+pub struct QueryGuard {
+    query_id: RequestId,
+    tx: mpsc::UnboundedSender<ClientActorMessage>,
+    answered: bool,
+}
+
+impl Drop for QueryGuard {
+    fn drop(&mut self) {
+        if !self.answered {
+            let _ = self.tx.send(ClientActorMessage::Cancel {
+                query_id: self.query_id,
+            });
+        }
+    }
+}
+
 impl LiteServerClient {
-    pub async fn connect(addr: SocketAddrV4, server_key: ServerKey) -> anyhow::Result<Self> {
+    pub async fn connect(addr: SocketAddr, server_key: ServerKey) -> anyhow::Result<Self> {
         let inner = Client::connect(addr, server_key).await?;
+
+        Ok(Self::from_connection(inner))
+    }
+
+    /// Connects through a SOCKS5 or HTTP CONNECT `proxy`.
+    pub async fn connect_with_proxy(
+        addr: SocketAddr,
+        server_key: ServerKey,
+        proxy: &Proxy,
+    ) -> anyhow::Result<Self> {
+        let inner = Client::connect_with_proxy(proxy, addr, server_key).await?;
+
+        Ok(Self::from_connection(inner))
+    }
+
+    /// Connects to a private liteserver which serves only clients owning `client_key`.
+    pub async fn connect_authenticated(
+        addr: SocketAddr,
+        server_key: ServerKey,
+        client_key: &Ed25519Key,
+    ) -> anyhow::Result<Self> {
+        let inner = Client::connect_authenticated(addr, server_key, client_key).await?;
+
+        Ok(Self::from_connection(inner))
+    }
+
+    /// Sends an already serialized boxed liteserver function and returns the raw boxed answer.
+    pub async fn query_raw(&self, data: Vec<u8>) -> Result<Bytes, Error> {
+        let (rx, mut guard) = self.enqueue(data)?;
+        let response = rx.await;
+        guard.answered = true;
+        let response = response.map_err(|_| Error::OneshotClosed)??;
+
+        if let Ok(error) = from_bytes_boxed::<LiteServerError>(&response) {
+            return Err(Error::LiteServerError(error));
+        }
+
+        Ok(response)
+    }
+
+    /// Account at the block `id`, [`Account::None`] when it doesn't exist.
+    /// The state is proven by the block `id`.
+    pub async fn get_account(
+        &self,
+        id: TonNodeBlockIdExt,
+        account: LiteServerAccountId,
+    ) -> Result<Account, Error> {
+        let request = LiteServerGetAccountState { id, account };
+        let state = self.clone().oneshot(request.clone()).await?;
+        request.verify(&state)?;
+
+        if state.state.is_empty() {
+            return Ok(Account::None);
+        }
+
+        let boc: BoC = unpack_bytes_fully(&state.state).map_err(|_| Error::Deserialize)?;
+        let root = boc.single_root().ok_or(Error::Deserialize)?;
+
+        root.parse_fully().map_err(|_| Error::Deserialize)
+    }
+
+    /// Commonly used config params at the masterchain block `id`, proven by its state.
+    pub async fn get_config(&self, id: TonNodeBlockIdExt) -> Result<BlockchainConfig, Error> {
+        let request = LiteServerGetConfigParams {
+            mode: 0,
+            id,
+            param_list: BlockchainConfig::PARAMS.to_vec(),
+        };
+        let response = self.clone().oneshot(request.clone()).await?;
+
+        let config = verify_config_info(&request.id, &response)?;
+        let params = config_params(&config, &request.param_list)?;
+
+        BlockchainConfig::from_params(&params)
+            .map_err(|e| Error::Proof(ProofError::Tlb(e.to_string())))
+    }
+
+    fn enqueue(
+        &self,
+        data: Vec<u8>,
+    ) -> Result<(oneshot::Receiver<Result<Bytes, Error>>, QueryGuard), Error> {
+        let query = LiteServerQuery { data };
+        let query = to_bytes_boxed(&query);
+
+        let query = AdnlMessageQuery {
+            query_id: random(),
+            query,
+        };
+
+        let guard = QueryGuard {
+            query_id: query.query_id,
+            tx: self.tx.clone(),
+            answered: false,
+        };
+        let (tx, rx) = oneshot::channel();
+
+        self.tx
+            .send(ClientActorMessage::Query { query, oneshot: tx })
+            .map_err(|_| Error::ChannelClosed)?;
+
+        Ok((rx, guard))
+    }
+
+    /// Wraps an established connection, e.g. one with a custom maximum frame size.
+    pub fn from_connection(inner: Connection) -> Self {
         let cancel_token = CancellationToken::new();
         let (tx, rx) = mpsc::unbounded_channel::<ClientActorMessage>();
         ClientActor::new(inner, rx, cancel_token.clone()).run();
 
-        Ok(Self {
+        Self {
             tx,
             drop_guard: Arc::new(cancel_token.drop_guard()),
-        })
+        }
     }
 }
 
@@ -166,27 +330,10 @@ where
     }
 
     fn call(&mut self, req: R) -> Self::Future {
-        let data = to_bytes_boxed(&req);
-
-        let query = LiteServerQuery { data };
-        let query = to_bytes_boxed(&query);
-
-        let query = AdnlMessageQuery {
-            query_id: random(),
-            query,
-        };
-
-        let (tx, rx) = oneshot::channel();
-
-        if self
-            .tx
-            .send(ClientActorMessage::Query { query, oneshot: tx })
-            .is_err()
-        {
-            return ResponseFuture::failed(Error::ChannelClosed);
+        match self.enqueue(to_bytes_boxed(&req)) {
+            Ok((rx, guard)) => ResponseFuture::new(rx, guard, self.drop_guard.clone()),
+            Err(error) => ResponseFuture::failed(error),
         }
-
-        ResponseFuture::new(rx, self.drop_guard.clone())
     }
 }
 
@@ -197,7 +344,8 @@ pub enum ResponseState {
     },
     Rx {
         #[pin]
-        rx: oneshot::Receiver<Bytes>,
+        rx: oneshot::Receiver<Result<Bytes, Error>>,
+        guard: QueryGuard,
         drop_guard: Arc<DropGuard>,
     },
 }
@@ -210,14 +358,22 @@ pub struct ResponseFuture<Response> {
 }
 
 impl<Response> ResponseFuture<Response> {
-    fn new(rx: oneshot::Receiver<Bytes>, drop_guard: Arc<DropGuard>) -> Self {
+    fn new(
+        rx: oneshot::Receiver<Result<Bytes, Error>>,
+        guard: QueryGuard,
+        drop_guard: Arc<DropGuard>,
+    ) -> Self {
         Self {
-            state: ResponseState::Rx { rx, drop_guard },
+            state: ResponseState::Rx {
+                rx,
+                guard,
+                drop_guard,
+            },
             _phantom: PhantomData,
         }
     }
 
-    fn failed(error: Error) -> Self {
+    pub(crate) fn failed(error: Error) -> Self {
         Self {
             state: ResponseState::Failed { error: Some(error) },
             _phantom: PhantomData,
@@ -238,9 +394,13 @@ where
             ResponseStateProj::Failed { error } => {
                 Poll::Ready(Err(error.take().expect("polled after error")))
             }
-            ResponseStateProj::Rx { rx, .. } => {
-                match ready!(rx.poll(cx)) {
+            ResponseStateProj::Rx { rx, guard, .. } => {
+                let response = ready!(rx.poll(cx));
+                guard.answered = true;
+
+                match response {
                     Ok(response) => {
+                        let response = response?;
                         let response =
                             from_bytes_boxed::<Result<Response, LiteServerError>>(&response)
                                 .map_err(|_| Error::Deserialize)?
@@ -258,15 +418,29 @@ where
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
+    use crate::mock::tests::MASTERCHAIN_INFO_FIXTURES;
+    use crate::mock::{Fixtures, MockLiteServer};
+    use crate::proof::boc::{deserialize_single, serialize};
+    use crate::proof::cell::ProofCell;
+    use crate::proof::fixtures::{
+        account_state, blockchain_config, config, config_info, validator_set,
+    };
+    use crate::proof::ProofError;
     use crate::tl::{
         LiteServerGetAllShardsInfo, LiteServerGetBlockProof, LiteServerGetMasterchainInfo,
         LiteServerGetMasterchainInfoExt, LiteServerGetVersion,
     };
+    use crate::tlb::account::tests::{given_account, given_cell};
     use crate::wait_seqno::WaitSeqno;
     use base64::Engine;
     use std::net::Ipv4Addr;
     use std::time::SystemTime;
     use std::time::UNIX_EPOCH;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+    use toner::tlb::bits::ser::pack_with;
+    use toner::tlb::Cell;
+    use toner::ton::boc::{BagOfCellsArgs, BoC};
     use tower::ServiceExt;
     use tracing_test::traced_test;
 
@@ -405,6 +579,72 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn client_fails_pending_request_on_malformed_packet() -> anyhow::Result<()> {
+        let (client, server) = given_raw_server().await?;
+        tokio::spawn(async move {
+            let mut connection = server.await??;
+            let _query = connection.next().await;
+            connection.send(Packet::new(vec![1, 2, 3])).await?;
+            connection.next().await;
+
+            anyhow::Ok(())
+        });
+
+        let actual = client
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await;
+
+        assert!(matches!(actual, Err(Error::Connection(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_fails_pending_request_on_oversized_frame() -> anyhow::Result<()> {
+        let (client, server) = given_raw_server().await?;
+        tokio::spawn(async move {
+            let mut connection = server.await??;
+            let _query = connection.next().await;
+            connection.send(Packet::new(vec![0; 4096])).await?;
+            connection.next().await;
+
+            anyhow::Ok(())
+        });
+
+        let actual = client
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await;
+
+        assert!(matches!(actual, Err(Error::Connection(_))));
+
+        Ok(())
+    }
+
+    /// Client limited to 1024 bytes frames and the raw server side of its connection.
+    async fn given_raw_server() -> anyhow::Result<(
+        LiteServerClient,
+        tokio::task::JoinHandle<anyhow::Result<Connection>>,
+    )> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let server_key = Ed25519Key::generate();
+        let server_public_key = *server_key.public_key().as_bytes();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            let (_, connection) = adnl_tcp::server::Server::handshake(stream, &server_key).await?;
+
+            Ok(connection)
+        });
+
+        let mut connection = Client::connect(addr, server_public_key).await?;
+        connection.set_max_frame_size(1024);
+
+        Ok((LiteServerClient::from_connection(connection), server))
+    }
+
     #[allow(dead_code)]
     pub(crate) async fn provided_archive_client() -> anyhow::Result<LiteServerClient> {
         let ip: i32 = -2018135749;
@@ -417,7 +657,7 @@ pub(crate) mod tests {
 
         tracing::info!("Connecting to {}:{} with key {:?}", ip, port, key);
 
-        let client = LiteServerClient::connect(SocketAddrV4::new(ip, port), key).await?;
+        let client = LiteServerClient::connect(SocketAddr::new(ip.into(), port), key).await?;
 
         Ok(client)
     }
@@ -433,8 +673,146 @@ pub(crate) mod tests {
 
         tracing::info!("Connecting to {}:{} with key {:?}", ip, port, key);
 
-        let client = LiteServerClient::connect(SocketAddrV4::new(ip, port), key).await?;
+        let client = LiteServerClient::connect(SocketAddr::new(ip.into(), port), key).await?;
 
         Ok(client)
     }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_get_account() -> anyhow::Result<()> {
+        let code = given_cell(1);
+        let account = given_account(true, &code, &given_cell(2));
+        let (request, response) = account_state([7; 32], &given_proof_cell(account)?);
+        let fixtures = Fixtures::new().with(&request, &response);
+        let server = MockLiteServer::new(fixtures).spawn().await?;
+        let client = server.connect().await?;
+
+        let account = client.get_account(request.id, request.account).await?;
+
+        assert_eq!(account.code(), Some(&code));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_get_account_rejects_unproven_state() -> anyhow::Result<()> {
+        let account = given_account(true, &given_cell(1), &given_cell(2));
+        let (request, mut response) = account_state([7; 32], &given_proof_cell(account)?);
+        let forged = given_account(true, &given_cell(3), &given_cell(2));
+        response.state = serialize(&[given_proof_cell(forged)?]);
+        let fixtures = Fixtures::new().with(&request, &response);
+        let server = MockLiteServer::new(fixtures).spawn().await?;
+        let client = server.connect().await?;
+
+        let actual = client.get_account(request.id, request.account).await;
+
+        assert!(matches!(
+            actual,
+            Err(Error::Proof(ProofError::AccountStateMismatch))
+        ));
+
+        Ok(())
+    }
+
+    fn given_proof_cell(cell: Cell) -> anyhow::Result<Arc<ProofCell>> {
+        let boc = pack_with(
+            BoC::from_root(cell),
+            BagOfCellsArgs {
+                has_idx: false,
+                has_crc32c: true,
+            },
+        )?;
+
+        Ok(deserialize_single(&boc.into_vec())?)
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_get_config() -> anyhow::Result<()> {
+        let (id, response) = config_info(&blockchain_config());
+        let fixtures = Fixtures::new().with(
+            &LiteServerGetConfigParams {
+                mode: 0,
+                id: id.clone(),
+                param_list: BlockchainConfig::PARAMS.to_vec(),
+            },
+            &response,
+        );
+        let server = MockLiteServer::new(fixtures).spawn().await?;
+        let client = server.connect().await?;
+
+        let config = client.get_config(id).await?;
+
+        assert_eq!(config.cur_validators.map(|v| v.list.len()), Some(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_get_config_rejects_forged_proof() -> anyhow::Result<()> {
+        let (id, mut response) = config_info(&blockchain_config());
+        let (_, forged) = config_info(&config(&validator_set(&[([1; 32], 10)])));
+        response.config_proof = forged.config_proof;
+        let fixtures = Fixtures::new().with(
+            &LiteServerGetConfigParams {
+                mode: 0,
+                id: id.clone(),
+                param_list: BlockchainConfig::PARAMS.to_vec(),
+            },
+            &response,
+        );
+        let server = MockLiteServer::new(fixtures).spawn().await?;
+        let client = server.connect().await?;
+
+        let actual = client.get_config(id).await;
+
+        assert!(matches!(
+            actual,
+            Err(Error::Proof(ProofError::RootHashMismatch { .. }))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_fails_in_flight_requests_on_connection_loss() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let (addr, proxy) = given_proxy(server.addr()).await?;
+        let mut client = LiteServerClient::connect(addr, server.server_key()).await?;
+        let request = tokio::spawn(client.clone().oneshot(WaitSeqno::with_timeout(
+            LiteServerGetMasterchainInfo::default(),
+            22560808,
+            Duration::from_secs(60),
+        )));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        proxy.abort();
+        let response = tokio::time::timeout(Duration::from_secs(1), request).await??;
+
+        assert!(matches!(response, Err(Error::Connection(_))));
+        assert!(matches!(
+            ServiceExt::<LiteServerGetMasterchainInfo>::ready(&mut client).await,
+            Err(Error::ChannelClosed)
+        ));
+
+        Ok(())
+    }
+
+    /// Forwards a single connection to `addr`, aborting the task cuts the connection.
+    async fn given_proxy(addr: SocketAddr) -> anyhow::Result<(SocketAddr, JoinHandle<()>)> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let proxy = listener.local_addr()?;
+        let task = tokio::spawn(async move {
+            let (mut inbound, _) = listener.accept().await.unwrap();
+            let mut outbound = TcpStream::connect(addr).await.unwrap();
+
+            let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+        });
+
+        Ok((proxy, task))
+    }
 }
@@ -1,8 +1,15 @@
 pub mod client;
+pub mod light_client;
 pub mod make;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod proof;
+pub mod reconnecting_client;
 pub mod request;
+pub mod retry;
 pub mod tl;
 pub mod tlb;
+pub mod ton;
 pub mod tracked_client;
 pub mod tracker;
 pub mod wait_seqno;
@@ -0,0 +1,354 @@
+//! Light client proving masterchain blocks by a chain of key blocks from the init block
+//! of the global config.
+//!
+//! [`LightClient`] wraps a liteserver client and answers masterchain info requests only
+//! when the last block is signed by the validators, so [`TrackedClient::verified`] built on
+//! top of it tracks and routes by verified blocks. [`TonClientBuilder::set_init_block`] enables
+//! it for [`TonClient`](crate::ton::TonClient).
+//!
+//! [`TrackedClient::verified`]: crate::tracked_client::TrackedClient::verified
+//! [`TonClientBuilder::set_init_block`]: crate::ton::TonClientBuilder::set_init_block
+use crate::proof::{verify_partial_block_proof, ProofError};
+use crate::request::Requestable;
+use crate::tl::{
+    LiteServerBoxedBlockLink, LiteServerGetBlockProof, LiteServerMasterchainInfo,
+    LiteServerPartialBlockProof, TonNodeBlockIdExt,
+};
+use anyhow::Context as _;
+use base64::Engine;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use ton_client_util::discover::config::TonConfig;
+use tower::{BoxError, Service, ServiceExt};
+
+/// `validator.init_block` of the global config.
+pub fn init_block(config: &TonConfig) -> anyhow::Result<TonNodeBlockIdExt> {
+    let block = config
+        .data
+        .get("validator")
+        .and_then(|validator| validator.get("init_block"))
+        .context("global config has no init block")?;
+
+    let int = |field: &str| {
+        block
+            .get(field)
+            .and_then(|value| value.as_i64())
+            .with_context(|| format!("init block has no {field}"))
+    };
+    let hash = |field: &str| -> anyhow::Result<[u8; 32]> {
+        let value = block
+            .get(field)
+            .and_then(|value| value.as_str())
+            .with_context(|| format!("init block has no {field}"))?;
+        let bytes = base64::engine::general_purpose::STANDARD.decode(value)?;
+
+        bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("init block {field} has to be 32 bytes"))
+    };
+
+    Ok(TonNodeBlockIdExt {
+        workchain: int("workchain")? as i32,
+        shard: int("shard")?,
+        seqno: int("seqno")? as i32,
+        root_hash: hash("root_hash")?,
+        file_hash: hash("file_hash")?,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct LightClient<S> {
+    inner: S,
+    key_block: Arc<Mutex<TonNodeBlockIdExt>>,
+}
+
+impl<S> LightClient<S> {
+    /// `init_block` is trusted, usually it's the init block of the global config.
+    pub fn new(inner: S, init_block: TonNodeBlockIdExt) -> Self {
+        Self {
+            inner,
+            key_block: Arc::new(Mutex::new(init_block)),
+        }
+    }
+
+    pub fn from_config(inner: S, config: &TonConfig) -> anyhow::Result<Self> {
+        Ok(Self::new(inner, init_block(config)?))
+    }
+
+    /// The latest key block proven so far.
+    pub fn key_block(&self) -> TonNodeBlockIdExt {
+        self.key_block.lock().expect("lock is not poisoned").clone()
+    }
+
+    fn advance(&self, key_block: &TonNodeBlockIdExt) {
+        let mut current = self.key_block.lock().expect("lock is not poisoned");
+        if key_block.seqno > current.seqno {
+            *current = key_block.clone();
+        }
+    }
+}
+
+impl<S> LightClient<S>
+where
+    S: Service<LiteServerGetBlockProof, Response = LiteServerPartialBlockProof> + Clone,
+    S::Error: Into<BoxError>,
+{
+    /// Proves `target` by the block links from the latest trusted key block, key blocks
+    /// passed on the way become trusted.
+    pub async fn verify(&self, target: &TonNodeBlockIdExt) -> Result<(), BoxError> {
+        loop {
+            let known = self.key_block();
+            if known == *target {
+                return Ok(());
+            }
+
+            let request = LiteServerGetBlockProof {
+                mode: 1,
+                known_block: known.clone(),
+                target_block: Some(target.clone()),
+            };
+            let proof = self
+                .inner
+                .clone()
+                .oneshot(request)
+                .await
+                .map_err(Into::into)?;
+            verify_partial_block_proof(&known, &proof)?;
+
+            let key_block = proof.steps.iter().rev().find_map(|step| match step {
+                LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link)
+                    if link.to_key_block.is_true() =>
+                {
+                    Some(&link.to)
+                }
+                _ => None,
+            });
+            if let Some(key_block) = key_block {
+                self.advance(key_block);
+            }
+
+            if proof.complete.is_true() {
+                if proof.to != *target {
+                    return Err(ProofError::IncompleteProof.into());
+                }
+
+                return Ok(());
+            }
+            // the next proof starts from a newer key block, otherwise it's the same proof
+            if key_block.is_none_or(|key_block| key_block.seqno <= known.seqno) {
+                return Err(ProofError::IncompleteProof.into());
+            }
+        }
+    }
+}
+
+impl<S, R> Service<R> for LightClient<S>
+where
+    R: Requestable<Response = LiteServerMasterchainInfo> + 'static,
+    S: Service<R, Response = LiteServerMasterchainInfo>,
+    S: Service<LiteServerGetBlockProof, Response = LiteServerPartialBlockProof>,
+    S: Clone + Send + Sync + 'static,
+    <S as Service<R>>::Error: Into<BoxError>,
+    <S as Service<R>>::Future: Send,
+    <S as Service<LiteServerGetBlockProof>>::Error: Into<BoxError>,
+    <S as Service<LiteServerGetBlockProof>>::Future: Send,
+{
+    type Response = LiteServerMasterchainInfo;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let this = self.clone();
+
+        async move {
+            let info = this
+                .inner
+                .clone()
+                .oneshot(request)
+                .await
+                .map_err(Into::into)?;
+            this.verify(&info.last).await?;
+
+            Ok(info)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::fixtures::*;
+    use crate::tl::{
+        BoolFalse, BoolTrue, BoxedBool, LiteServerGetMasterchainInfo, TonNodeZeroStateIdExt,
+    };
+    use adnl_tcp::key::Ed25519Key;
+    use futures::future::{ready, Ready};
+
+    #[derive(Clone)]
+    struct Liteserver {
+        last: TonNodeBlockIdExt,
+        proof: LiteServerPartialBlockProof,
+    }
+
+    impl Service<LiteServerGetMasterchainInfo> for Liteserver {
+        type Response = LiteServerMasterchainInfo;
+        type Error = BoxError;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: LiteServerGetMasterchainInfo) -> Self::Future {
+            ready(Ok(LiteServerMasterchainInfo {
+                last: self.last.clone(),
+                state_root_hash: [0; 32],
+                init: TonNodeZeroStateIdExt {
+                    workchain: -1,
+                    root_hash: [0; 32],
+                    file_hash: [0; 32],
+                },
+            }))
+        }
+    }
+
+    impl Service<LiteServerGetBlockProof> for Liteserver {
+        type Response = LiteServerPartialBlockProof;
+        type Error = BoxError;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: LiteServerGetBlockProof) -> Self::Future {
+            ready(Ok(self.proof.clone()))
+        }
+    }
+
+    fn given_light_client(keys: &[Ed25519Key]) -> (LightClient<Liteserver>, TonNodeBlockIdExt) {
+        let init = key_block(10, &validator_keys());
+        let last = block(
+            &block_info(-1, 20, true, 1100, 600, &zero_id()),
+            &empty(),
+            &empty(),
+        );
+        let link = block_link_forward(&init, 10, &last, 20, true, keys);
+        let liteserver = Liteserver {
+            last: link.to.clone(),
+            proof: LiteServerPartialBlockProof {
+                complete: BoxedBool::BoolTrue(BoolTrue {}),
+                from: link.from.clone(),
+                to: link.to.clone(),
+                steps: vec![LiteServerBoxedBlockLink::LiteServerBlockLinkForward(
+                    link.clone(),
+                )],
+            },
+        };
+
+        (LightClient::new(liteserver, link.from), link.to)
+    }
+
+    #[tokio::test]
+    async fn light_client_verifies_last_block() {
+        let (client, last) = given_light_client(&validator_keys());
+
+        let info = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await
+            .unwrap();
+
+        assert_eq!(info.last, last);
+        assert_eq!(client.key_block(), last);
+    }
+
+    #[tokio::test]
+    async fn light_client_rejects_unsigned_block() {
+        let (client, _) = given_light_client(&validator_keys()[..1]);
+        let init = client.key_block();
+
+        let error = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ProofError>(),
+            Some(ProofError::InsufficientSignatures { .. })
+        ));
+        assert_eq!(client.key_block(), init);
+    }
+
+    #[tokio::test]
+    async fn light_client_rejects_proof_to_older_key_block() {
+        let keys = validator_keys();
+        let old = key_block(10, &keys);
+        let back = block_link_back(&old, 10, 20);
+        let older_key_block = block(
+            &block_info(-1, 15, true, 1050, 550, &zero_id()),
+            &empty(),
+            &empty(),
+        );
+        let forward = block_link_forward(&old, 10, &older_key_block, 15, true, &keys);
+        let liteserver = Liteserver {
+            last: block_id(-1, 30, &empty()),
+            proof: LiteServerPartialBlockProof {
+                complete: BoxedBool::BoolFalse(BoolFalse {}),
+                from: back.from.clone(),
+                to: forward.to.clone(),
+                steps: vec![
+                    LiteServerBoxedBlockLink::LiteServerBlockLinkBack(back.clone()),
+                    LiteServerBoxedBlockLink::LiteServerBlockLinkForward(forward),
+                ],
+            },
+        };
+        let client = LightClient::new(liteserver, back.from.clone());
+
+        let error = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ProofError>(),
+            Some(ProofError::IncompleteProof)
+        ));
+        assert_eq!(client.key_block(), back.from);
+    }
+
+    #[test]
+    fn init_block_from_config() {
+        let config: TonConfig = serde_json::from_str(
+            r#"{
+                "liteservers": [],
+                "validator": {
+                    "init_block": {
+                        "workchain": -1,
+                        "shard": -9223372036854775808,
+                        "seqno": 34835953,
+                        "root_hash": "E9XzkGQ0Xl7xzVrMD8cm1JfGw0m2nDBBLiNNZWYd07c=",
+                        "file_hash": "HnrpnCM9EK0uGuNEU2sZeaEnokYsuzJB6urNeY0rL4c="
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let block = init_block(&config).unwrap();
+
+        assert_eq!(block.workchain, -1);
+        assert_eq!(block.shard, i64::MIN);
+        assert_eq!(block.seqno, 34835953);
+        assert_eq!(block.root_hash[0], 0x13);
+    }
+}
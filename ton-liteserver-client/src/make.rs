@@ -1,9 +1,10 @@
 use crate::client::LiteServerClient;
 use adnl_tcp::client::ServerKey;
+use adnl_tcp::proxy::Proxy;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use std::future::Future;
-use std::net::SocketAddrV4;
+use std::net::SocketAddr;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -12,14 +13,15 @@ use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tower::Service;
 
 pub struct MakeClient {
-    addr: SocketAddrV4,
+    addr: SocketAddr,
     key: ServerKey,
+    proxy: Option<Proxy>,
     retry_strategy: Box<dyn Iterator<Item = Duration> + Send>,
     sleep: Option<Pin<Box<Sleep>>>,
 }
 
 impl MakeClient {
-    pub fn new(addr: SocketAddrV4, key: ServerKey) -> Self {
+    pub fn new(addr: SocketAddr, key: ServerKey) -> Self {
         let retry_strategy = Box::new(
             ExponentialBackoff::from_millis(100)
                 .max_delay(Duration::from_secs(60))
@@ -29,10 +31,17 @@ impl MakeClient {
         Self {
             addr,
             key,
+            proxy: None,
             retry_strategy,
             sleep: None,
         }
     }
+
+    pub fn set_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+
+        self
+    }
 }
 
 impl Service<()> for MakeClient {
@@ -55,6 +64,13 @@ impl Service<()> for MakeClient {
             self.sleep.replace(Box::pin(sleep(duration)));
         }
 
-        LiteServerClient::connect(self.addr, self.key).boxed()
+        match self.proxy.clone() {
+            None => LiteServerClient::connect(self.addr, self.key).boxed(),
+            Some(proxy) => {
+                let (addr, key) = (self.addr, self.key);
+
+                async move { LiteServerClient::connect_with_proxy(addr, key, &proxy).await }.boxed()
+            }
+        }
     }
 }
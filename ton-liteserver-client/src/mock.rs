@@ -0,0 +1,641 @@
+//! Offline liteserver serving recorded responses, lets clients, trackers and routing
+//! be tested without network access.
+use crate::client::{Error, LiteServerClient};
+use crate::request::Requestable;
+use crate::tl::{
+    Bytes, LiteServerError, LiteServerGetMasterchainInfo, LiteServerMasterchainInfo,
+    LiteServerQuery, LiteServerWaitMasterchainSeqno,
+};
+use adnl_tcp::client::ServerKey;
+use adnl_tcp::deserializer::from_bytes_boxed;
+use adnl_tcp::key::Ed25519Key;
+use adnl_tcp::serializer::{to_bytes_boxed, SerializeBoxed};
+use adnl_tcp::server::Server;
+use anyhow::{anyhow, Context as _};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tower::Service;
+
+const WAIT_MASTERCHAIN_SEQNO_LEN: usize = 12;
+
+/// Recorded liteserver answers keyed by the serialized request.
+///
+/// Requests are stored without the `liteServer.query` wrapper and without the
+/// `liteServer.waitMasterchainSeqno` prefix. The file format is one
+/// `<request hex> <response hex>` pair per line, lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures {
+    responses: HashMap<Bytes, Bytes>,
+}
+
+impl Fixtures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read fixtures from {}", path.display()))?;
+
+        content.parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("cannot write fixtures to {}", path.display()))
+    }
+
+    pub fn insert<R>(&mut self, request: &R, response: &R::Response)
+    where
+        R: Requestable,
+        R::Response: SerializeBoxed,
+    {
+        self.insert_raw(to_bytes_boxed(request), to_bytes_boxed(response));
+    }
+
+    pub fn insert_error<R>(&mut self, request: &R, error: &LiteServerError)
+    where
+        R: Requestable,
+    {
+        self.insert_raw(to_bytes_boxed(request), to_bytes_boxed(error));
+    }
+
+    pub fn with<R>(mut self, request: &R, response: &R::Response) -> Self
+    where
+        R: Requestable,
+        R::Response: SerializeBoxed,
+    {
+        self.insert(request, response);
+
+        self
+    }
+
+    pub fn insert_raw(&mut self, request: Bytes, response: Bytes) {
+        let (_, request) = split_wait_masterchain_seqno(&request);
+
+        self.responses.insert(request.to_vec(), response);
+    }
+
+    pub fn get(&self, request: &[u8]) -> Option<&Bytes> {
+        self.responses.get(request)
+    }
+
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Seqno of the last masterchain block known to the fixtures.
+    pub fn last_seqno(&self) -> Option<i32> {
+        self.get(&to_bytes_boxed(&LiteServerGetMasterchainInfo::default()))
+            .and_then(|response| from_bytes_boxed::<LiteServerMasterchainInfo>(response).ok())
+            .map(|info| info.last.seqno)
+    }
+}
+
+impl std::str::FromStr for Fixtures {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fixtures = Self::new();
+
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (request, response) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| anyhow!("line {}: expected request and response", n + 1))?;
+            let request = hex::decode(request.trim())
+                .with_context(|| format!("line {}: malformed request", n + 1))?;
+            let response = hex::decode(response.trim())
+                .with_context(|| format!("line {}: malformed response", n + 1))?;
+
+            fixtures.insert_raw(request, response);
+        }
+
+        Ok(fixtures)
+    }
+}
+
+impl std::fmt::Display for Fixtures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sorted: BTreeMap<_, _> = self.responses.iter().collect();
+
+        for (request, response) in sorted {
+            writeln!(f, "{} {}", hex::encode(request), hex::encode(response))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn split_wait_masterchain_seqno(data: &[u8]) -> (Option<LiteServerWaitMasterchainSeqno>, &[u8]) {
+    if data.len() > WAIT_MASTERCHAIN_SEQNO_LEN {
+        if let Ok(prefix) =
+            from_bytes_boxed::<LiteServerWaitMasterchainSeqno>(&data[..WAIT_MASTERCHAIN_SEQNO_LEN])
+        {
+            return (Some(prefix), &data[WAIT_MASTERCHAIN_SEQNO_LEN..]);
+        }
+    }
+
+    (None, data)
+}
+
+/// Adnl query handler answering `liteServer.query` from [`Fixtures`].
+///
+/// Unknown requests are answered with error code 651, waiting for a masterchain seqno
+/// beyond the recorded one is answered with error code 652 once the wait timeout elapses.
+///
+/// Clones share the server key, so a server spawned again is reachable by the same clients.
+#[derive(Debug, Clone)]
+pub struct MockLiteServer {
+    fixtures: Arc<Fixtures>,
+    seed: [u8; 32],
+}
+
+impl MockLiteServer {
+    pub fn new(fixtures: Fixtures) -> Self {
+        Self {
+            fixtures: Arc::new(fixtures),
+            seed: rand::random(),
+        }
+    }
+
+    pub async fn spawn(self) -> anyhow::Result<MockLiteServerHandle> {
+        self.spawn_on((Ipv4Addr::LOCALHOST, 0)).await
+    }
+
+    pub async fn spawn_on(
+        self,
+        addr: impl Into<SocketAddr>,
+    ) -> anyhow::Result<MockLiteServerHandle> {
+        let listener = TcpListener::bind(addr.into()).await?;
+        let addr = listener.local_addr()?;
+
+        let key = Ed25519Key::from_seed(&self.seed);
+        let server_key = *key.public_key().as_bytes();
+        let cancellation_token = CancellationToken::new();
+
+        tokio::spawn(Server::builder(key).serve_with_shutdown(
+            listener,
+            self,
+            cancellation_token.clone().cancelled_owned(),
+        ));
+
+        Ok(MockLiteServerHandle {
+            addr,
+            server_key,
+            _drop_guard: cancellation_token.drop_guard(),
+        })
+    }
+
+    fn answer(&self, query: &[u8]) -> (Option<Duration>, Bytes) {
+        let Ok(query) = from_bytes_boxed::<LiteServerQuery>(query) else {
+            return (None, error(-400, "malformed liteServer.query"));
+        };
+
+        let (wait, request) = split_wait_masterchain_seqno(&query.data);
+        if let Some(wait) = wait {
+            if self
+                .fixtures
+                .last_seqno()
+                .is_none_or(|seqno| seqno < wait.seqno)
+            {
+                return (
+                    Some(Duration::from_millis(wait.timeout_ms.max(0) as u64)),
+                    error(652, "timeout"),
+                );
+            }
+        }
+
+        match self.fixtures.get(request) {
+            Some(response) => (None, response.clone()),
+            None => (None, error(651, "not found in fixtures")),
+        }
+    }
+}
+
+fn error(code: i32, message: &str) -> Bytes {
+    to_bytes_boxed(&LiteServerError {
+        code,
+        message: message.to_owned(),
+    })
+}
+
+impl Service<Bytes> for MockLiteServer {
+    type Response = Bytes;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Bytes, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, query: Bytes) -> Self::Future {
+        let (delay, answer) = self.answer(&query);
+
+        async move {
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+
+            Ok(answer)
+        }
+        .boxed()
+    }
+}
+
+/// Running [`MockLiteServer`], shuts down on drop.
+#[derive(Debug)]
+pub struct MockLiteServerHandle {
+    addr: SocketAddr,
+    server_key: ServerKey,
+    _drop_guard: DropGuard,
+}
+
+impl MockLiteServerHandle {
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn server_key(&self) -> ServerKey {
+        self.server_key
+    }
+
+    pub async fn connect(&self) -> anyhow::Result<LiteServerClient> {
+        LiteServerClient::connect(self.addr, self.server_key).await
+    }
+}
+
+/// Wraps a client and records every answer, the result can be saved as fixtures.
+#[derive(Debug, Clone)]
+pub struct Recorder<S> {
+    inner: S,
+    fixtures: Arc<Mutex<Fixtures>>,
+}
+
+impl<S> Recorder<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            fixtures: Default::default(),
+        }
+    }
+
+    pub fn fixtures(&self) -> Fixtures {
+        self.fixtures
+            .lock()
+            .expect("fixtures lock poisoned")
+            .clone()
+    }
+}
+
+impl<S, R> Service<R> for Recorder<S>
+where
+    R: Requestable,
+    R::Response: SerializeBoxed,
+    S: Service<R, Response = R::Response, Error = Error>,
+    S::Future: Send + 'static,
+{
+    type Response = R::Response;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<R::Response, Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let request = to_bytes_boxed(&req);
+        let fixtures = self.fixtures.clone();
+        let future = self.inner.call(req);
+
+        async move {
+            let response = future.await;
+            let recorded = match &response {
+                Ok(response) => Some(to_bytes_boxed(response)),
+                Err(Error::LiteServerError(error)) => Some(to_bytes_boxed(error)),
+                Err(_) => None,
+            };
+            if let Some(recorded) = recorded {
+                fixtures
+                    .lock()
+                    .expect("fixtures lock poisoned")
+                    .insert_raw(request, recorded);
+            }
+
+            response
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::proof::boc::{deserialize_single, serialize};
+    use crate::proof::fixtures::{
+        block, block_id, block_info, block_state_proof, build_mc_state_extra, build_shard_state,
+        empty, merkle_proof, prune_except, state_proof, zero_id, FULL_SHARD, SHARD_HASHES,
+    };
+    use crate::proof::verify::MASTERCHAIN;
+    use crate::proof::verify_all_shards_info;
+    use crate::tl::{
+        LiteServerAllShardsInfo, LiteServerBlockData, LiteServerBlockHeader,
+        LiteServerBoxedBlockData, LiteServerBoxedBlockHeader, LiteServerGetAllShardsInfo,
+        LiteServerGetBlock, LiteServerGetBlockHeader, LiteServerGetVersion, LiteServerLookupBlock,
+        TonNodeBlockId, TonNodeBlockIdExt, TonNodeZeroStateIdExt,
+    };
+    use crate::wait_seqno::WaitSeqno;
+    use tower::ServiceExt;
+    use tracing_test::traced_test;
+
+    pub(crate) const MASTERCHAIN_INFO_FIXTURES: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/masterchain_info.txt");
+    pub(crate) const BLOCKS_FIXTURES: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/blocks.txt");
+
+    /// Mock serving a chain with the last masterchain block 22560807 and four basechain
+    /// shards, the first available blocks are 200000 blocks back.
+    pub(crate) async fn given_blocks_server() -> anyhow::Result<MockLiteServerHandle> {
+        MockLiteServer::new(Fixtures::load(BLOCKS_FIXTURES)?)
+            .spawn()
+            .await
+    }
+
+    /// Regenerates `fixtures/blocks.txt`, run with `cargo test generate_blocks_fixtures -- --ignored`.
+    #[test]
+    #[ignore]
+    fn generate_blocks_fixtures() -> anyhow::Result<()> {
+        const LAST_SEQNO: i32 = 22560807;
+        const FIRST_BLOCKS_BACK: i32 = 200000;
+
+        let shard_hashes = deserialize_single(&hex::decode(SHARD_HASHES)?)?;
+        let state = build_shard_state(
+            &empty(),
+            Some(&build_mc_state_extra(
+                shard_hashes.reference(0)?,
+                &zero_id(),
+            )),
+        );
+        let last = block(
+            &block_info(
+                MASTERCHAIN,
+                LAST_SEQNO,
+                false,
+                1700000000,
+                47000000000000,
+                &zero_id(),
+            ),
+            &state,
+            &empty(),
+        );
+        let last_id = block_id(MASTERCHAIN, LAST_SEQNO, &last);
+
+        let mut fixtures = Fixtures::new();
+        fixtures.insert(
+            &LiteServerGetMasterchainInfo::default(),
+            &LiteServerMasterchainInfo {
+                last: last_id.clone(),
+                state_root_hash: state.hash(0),
+                init: TonNodeZeroStateIdExt {
+                    workchain: MASTERCHAIN,
+                    root_hash: [0; 32],
+                    file_hash: [0; 32],
+                },
+            },
+        );
+        let request = LiteServerGetAllShardsInfo::new(last_id.clone());
+        let response = LiteServerAllShardsInfo {
+            id: last_id.clone(),
+            proof: serialize(&[block_state_proof(&last), state_proof(&state)]),
+            data: serialize(&[shard_hashes.clone()]),
+        };
+        let shards = verify_all_shards_info(&request, &response)?;
+        fixtures.insert(&request, &response);
+        fixtures.insert(
+            &LiteServerGetBlockHeader::new(last_id.clone()),
+            &LiteServerBoxedBlockHeader::from(LiteServerBlockHeader {
+                id: last_id.clone(),
+                mode: 0,
+                header_proof: serialize(&[merkle_proof(&prune_except(&last, &[0]))]),
+            }),
+        );
+
+        let first_blocks = shards.iter().flat_map(|(chain_id, shards)| {
+            shards.iter().map(|shard| {
+                (
+                    *chain_id as i32,
+                    shard.next_validator_shard as i64,
+                    shard.seq_no as i32 - FIRST_BLOCKS_BACK,
+                    shard.start_lt - 1000000000,
+                )
+            })
+        });
+        let first_masterchain_block = (
+            MASTERCHAIN,
+            FULL_SHARD as i64,
+            LAST_SEQNO - FIRST_BLOCKS_BACK,
+            46000000000000,
+        );
+        for (workchain, shard, seqno, end_lt) in
+            std::iter::once(first_masterchain_block).chain(first_blocks)
+        {
+            let first_block = block(
+                &block_info(workchain, seqno, false, 1690000000, end_lt, &zero_id()),
+                &empty(),
+                &empty(),
+            );
+            let id = TonNodeBlockIdExt {
+                workchain,
+                shard,
+                seqno,
+                root_hash: first_block.hash(0),
+                file_hash: [seqno as u8; 32],
+            };
+            let lookup = LiteServerLookupBlock::seqno(TonNodeBlockId::new(workchain, shard, seqno));
+            fixtures.insert(
+                &lookup,
+                &LiteServerBoxedBlockHeader::from(LiteServerBlockHeader {
+                    id: id.clone(),
+                    mode: lookup.mode,
+                    header_proof: serialize(&[merkle_proof(&prune_except(&first_block, &[0]))]),
+                }),
+            );
+            fixtures.insert(
+                &LiteServerGetBlock::new(id.clone()),
+                &LiteServerBoxedBlockData::from(LiteServerBlockData {
+                    id,
+                    data: serialize(&[first_block]),
+                }),
+            );
+        }
+
+        let mut content = std::fs::read_to_string(BLOCKS_FIXTURES)?
+            .lines()
+            .take_while(|line| line.starts_with('#'))
+            .fold(String::new(), |content, line| content + line + "\n");
+        content.push_str(&fixtures.to_string());
+        std::fs::write(BLOCKS_FIXTURES, content)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn mock_get_masterchain_info() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let client = server.connect().await?;
+
+        let response = client
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?;
+
+        assert_eq!(response.last.workchain, -1);
+        assert_eq!(response.last.shard, -9223372036854775808);
+        assert_eq!(response.last.seqno, 22560807);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn mock_get_masterchain_info_ipv6() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn_on((std::net::Ipv6Addr::LOCALHOST, 0))
+            .await?;
+        assert!(server.addr().is_ipv6());
+        let client = server.connect().await?;
+
+        let response = client
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?;
+
+        assert_eq!(response.last.seqno, 22560807);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn mock_wait_seqno_known() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let client = server.connect().await?;
+
+        let response = client
+            .oneshot(WaitSeqno::new(
+                LiteServerGetMasterchainInfo::default(),
+                22560807,
+            ))
+            .await?;
+
+        assert_eq!(response.last.seqno, 22560807);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn mock_wait_seqno_timeout() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let client = server.connect().await?;
+
+        let response = client
+            .oneshot(WaitSeqno::with_timeout(
+                LiteServerGetMasterchainInfo::default(),
+                22560808,
+                Duration::from_millis(10),
+            ))
+            .await;
+
+        assert!(matches!(
+            response,
+            Err(Error::LiteServerError(LiteServerError { code: 652, .. }))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn mock_unknown_request() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::new()).spawn().await?;
+        let client = server.connect().await?;
+
+        let response = client.oneshot(LiteServerGetVersion::default()).await;
+
+        assert!(matches!(
+            response,
+            Err(Error::LiteServerError(LiteServerError { code: 651, .. }))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn recorder_records_responses() -> anyhow::Result<()> {
+        let fixtures = Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?;
+        let server = MockLiteServer::new(fixtures.clone()).spawn().await?;
+        let mut recorder = Recorder::new(server.connect().await?);
+
+        (&mut recorder)
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?;
+        let _ = (&mut recorder)
+            .oneshot(LiteServerGetVersion::default())
+            .await;
+
+        let recorded = recorder.fixtures();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded.last_seqno(), fixtures.last_seqno());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fixtures_roundtrip() -> anyhow::Result<()> {
+        let fixtures = Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?;
+
+        let actual: Fixtures = fixtures.to_string().parse()?;
+
+        assert_eq!(actual.responses, fixtures.responses);
+        assert_eq!(actual.last_seqno(), Some(22560807));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fixtures_malformed_line() {
+        let actual = "2ee6b589".parse::<Fixtures>();
+
+        assert_eq!(
+            actual.unwrap_err().to_string(),
+            "line 1: expected request and response"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::proof::boc::deserialize_single;
+use crate::proof::cell::ProofCell;
+use crate::proof::validator_set::ValidatorSet;
+use crate::proof::verify::{
+    check_block_id, check_old_mc_block, mc_block_extra, skip_currency_collection, state_root,
+    virtual_root, BlockInfo, MASTERCHAIN,
+};
+use crate::proof::ProofError;
+use crate::tl::{
+    LiteServerBlockLinkBack, LiteServerBlockLinkForward, LiteServerBoxedBlockLink,
+    LiteServerPartialBlockProof, TonNodeBlockIdExt,
+};
+use std::sync::Arc;
+
+/// Chain of links from the trusted block `known` to `proof.to`.
+pub fn verify_partial_block_proof(
+    known: &TonNodeBlockIdExt,
+    proof: &LiteServerPartialBlockProof,
+) -> Result<(), ProofError> {
+    check_block_id(known, &proof.from)?;
+
+    let mut current = &proof.from;
+    for step in &proof.steps {
+        let (from, to) = match step {
+            LiteServerBoxedBlockLink::LiteServerBlockLinkBack(link) => (&link.from, &link.to),
+            LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link) => (&link.from, &link.to),
+        };
+        check_block_id(current, from)?;
+        verify_block_link(step)?;
+
+        current = to;
+    }
+
+    check_block_id(current, &proof.to)
+}
+
+/// `link.to` is proven by the trusted `link.from`.
+pub fn verify_block_link(link: &LiteServerBoxedBlockLink) -> Result<(), ProofError> {
+    match link {
+        LiteServerBoxedBlockLink::LiteServerBlockLinkBack(link) => verify_block_link_back(link),
+        LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link) => {
+            verify_block_link_forward(link)
+        }
+    }
+}
+
+/// An older block is proven by `prev_blocks` of the state of the newer one.
+pub fn verify_block_link_back(link: &LiteServerBlockLinkBack) -> Result<(), ProofError> {
+    if link.from.workchain != MASTERCHAIN || link.to.seqno >= link.from.seqno {
+        return Err(ProofError::BlockLinkMismatch(
+            "backward link has to lead to an older masterchain block",
+        ));
+    }
+
+    let block_proof = deserialize_single(&link.proof)?;
+    let state_proof = deserialize_single(&link.state_proof)?;
+    let state = state_root(&link.from, &block_proof, &state_proof)?;
+
+    let key_block = check_old_mc_block(state, &link.to)?;
+    if key_block != link.to_key_block.is_true() {
+        return Err(ProofError::BlockLinkMismatch(
+            "key block flag doesn't match",
+        ));
+    }
+
+    if !link.dest_proof.is_empty() {
+        let dest_proof = deserialize_single(&link.dest_proof)?;
+        let info = dest_header(&link.to, &dest_proof)?;
+        if info.key_block != key_block {
+            return Err(ProofError::BlockLinkMismatch(
+                "key block flag doesn't match",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A newer block is signed by the validators from the config of the older key block.
+pub fn verify_block_link_forward(link: &LiteServerBlockLinkForward) -> Result<(), ProofError> {
+    if link.from.workchain != MASTERCHAIN
+        || link.to.workchain != MASTERCHAIN
+        || link.to.seqno <= link.from.seqno
+    {
+        return Err(ProofError::BlockLinkMismatch(
+            "forward link has to lead to a newer masterchain block",
+        ));
+    }
+
+    let config_proof = deserialize_single(&link.config_proof)?;
+    let config = key_block_config(virtual_root(&config_proof, &link.from.root_hash)?)?;
+    let validators = ValidatorSet::from_config(config)?;
+
+    let to_key_block = link.to_key_block.is_true();
+    if link.dest_proof.is_empty() {
+        if to_key_block {
+            return Err(ProofError::BlockLinkMismatch(
+                "key block without header proof",
+            ));
+        }
+    } else {
+        let dest_proof = deserialize_single(&link.dest_proof)?;
+        let info = dest_header(&link.to, &dest_proof)?;
+        if info.key_block != to_key_block {
+            return Err(ProofError::BlockLinkMismatch(
+                "key block flag doesn't match",
+            ));
+        }
+        if info.gen_catchain_seqno != link.signatures.catchain_seqno as u32
+            || info.gen_validator_list_hash_short != link.signatures.validator_set_hash as u32
+        {
+            return Err(ProofError::BlockLinkMismatch(
+                "signature set doesn't match the block header",
+            ));
+        }
+    }
+
+    validators.check_signatures(&link.to, &link.signatures.signatures)
+}
+
+/// `BlockInfo` of the block `id` proven by `proof`.
+fn dest_header<'a>(
+    id: &TonNodeBlockIdExt,
+    proof: &'a Arc<ProofCell>,
+) -> Result<BlockInfo<'a>, ProofError> {
+    let info = BlockInfo::read(virtual_root(proof, &id.root_hash)?)?;
+    if info.workchain != id.workchain || info.seq_no != id.seqno as u32 {
+        return Err(ProofError::HeaderMismatch(Box::new(id.clone())));
+    }
+
+    Ok(info)
+}
+
+/// ```tlb
+/// masterchain_block_extra#cca5 key_block:(## 1) shard_hashes:ShardHashes
+///   shard_fees:ShardFees ^[ ... ] config:key_block?ConfigParams = McBlockExtra;
+/// _ config_addr:bits256 config:^(Hashmap 32 ^Cell) = ConfigParams;
+/// ```
+fn key_block_config(block: &ProofCell) -> Result<&Arc<ProofCell>, ProofError> {
+    let mut reader = mc_block_extra(block)?;
+    if !reader.load_bit()? {
+        return Err(ProofError::BlockLinkMismatch(
+            "forward link has to start from a key block",
+        ));
+    }
+
+    // shard_hashes:(HashmapE 32 ^(BinTree ShardDescr))
+    if reader.load_bit()? {
+        reader.skip_references(1)?;
+    }
+    // shard_fees:(HashmapAugE 96 ShardFeeCreated ShardFeeCreated)
+    if reader.load_bit()? {
+        reader.skip_references(1)?;
+    }
+    // fees:CurrencyCollection create:CurrencyCollection
+    skip_currency_collection(&mut reader)?;
+    skip_currency_collection(&mut reader)?;
+    // ^[ prev_blk_signatures recover_create_msg mint_msg ]
+    reader.skip_references(1)?;
+    reader.skip(256)?;
+
+    reader.load_reference()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::fixtures::*;
+    use crate::tl::{BoolFalse, BoolTrue, BoxedBool};
+    use adnl_tcp::key::Ed25519Key;
+
+    fn given_forward_link(keys: &[Ed25519Key]) -> LiteServerBlockLinkForward {
+        let from = key_block(10, &validator_keys());
+        let to = block(
+            &block_info(MASTERCHAIN, 20, true, 1100, 600, &zero_id()),
+            &empty(),
+            &empty(),
+        );
+
+        block_link_forward(&from, 10, &to, 20, true, keys)
+    }
+
+    #[test]
+    fn verify_block_link_forward_accepts_signed_block() {
+        let link = given_forward_link(&validator_keys());
+
+        verify_block_link_forward(&link).unwrap();
+    }
+
+    #[test]
+    fn verify_block_link_forward_rejects_insufficient_signatures() {
+        let link = given_forward_link(&validator_keys()[..2]);
+
+        let error = verify_block_link_forward(&link).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ProofError::InsufficientSignatures {
+                signed: 20,
+                total: 30
+            }
+        ));
+    }
+
+    #[test]
+    fn verify_block_link_forward_rejects_forged_signature() {
+        let mut link = given_forward_link(&validator_keys());
+        link.signatures.signatures[1].signature[0] ^= 1;
+
+        let error = verify_block_link_forward(&link).unwrap_err();
+
+        assert!(matches!(error, ProofError::InvalidSignature(_)));
+    }
+
+    #[test]
+    fn verify_block_link_forward_rejects_unknown_validator() {
+        let mut keys = validator_keys();
+        keys.push(Ed25519Key::from_seed(&[9; 32]));
+        let link = given_forward_link(&keys);
+
+        let error = verify_block_link_forward(&link).unwrap_err();
+
+        assert!(matches!(error, ProofError::UnknownValidator(_)));
+    }
+
+    #[test]
+    fn verify_block_link_forward_rejects_wrong_key_block_flag() {
+        let mut link = given_forward_link(&validator_keys());
+        link.to_key_block = BoxedBool::BoolFalse(BoolFalse {});
+
+        let error = verify_block_link_forward(&link).unwrap_err();
+
+        assert!(matches!(error, ProofError::BlockLinkMismatch(_)));
+    }
+
+    fn given_back_link() -> LiteServerBlockLinkBack {
+        block_link_back(&key_block(10, &validator_keys()), 10, 20)
+    }
+
+    #[test]
+    fn verify_block_link_back_accepts_old_block() {
+        let link = given_back_link();
+
+        verify_block_link_back(&link).unwrap();
+    }
+
+    #[test]
+    fn verify_block_link_back_rejects_forged_block() {
+        let mut link = given_back_link();
+        link.to.root_hash = [1; 32];
+
+        let error = verify_block_link_back(&link).unwrap_err();
+
+        assert!(matches!(error, ProofError::MasterchainBlockMismatch(_)));
+    }
+
+    #[test]
+    fn verify_partial_block_proof_follows_links() {
+        let link = given_forward_link(&validator_keys());
+        let proof = LiteServerPartialBlockProof {
+            complete: BoxedBool::BoolTrue(BoolTrue {}),
+            from: link.from.clone(),
+            to: link.to.clone(),
+            steps: vec![LiteServerBoxedBlockLink::LiteServerBlockLinkForward(link)],
+        };
+
+        verify_partial_block_proof(&proof.from, &proof).unwrap();
+
+        let error = verify_partial_block_proof(&zero_id(), &proof).unwrap_err();
+        assert!(matches!(error, ProofError::BlockIdMismatch { .. }));
+    }
+}
@@ -0,0 +1,338 @@
+use crate::proof::cell::ProofCell;
+use crate::proof::ProofError;
+use crc::{Crc, CRC_32_ISCSI};
+use std::sync::Arc;
+
+const BOC_MAGIC: [u8; 4] = [0xb5, 0xee, 0x9c, 0x72];
+const CRC32C: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+
+/// Roots of a serialized bag of cells, exotic cells and stored hashes are checked.
+pub fn deserialize(bytes: &[u8]) -> Result<Vec<Arc<ProofCell>>, ProofError> {
+    let mut input = Input { bytes, position: 0 };
+
+    if input.take(4)? != BOC_MAGIC {
+        return Err(ProofError::InvalidBoc("unknown magic"));
+    }
+
+    let flags = input.uint(1)?;
+    let has_index = flags & 0x80 != 0;
+    let has_crc32c = flags & 0x40 != 0;
+    let size = (flags & 0x07) as usize;
+    if size == 0 || size > 4 {
+        return Err(ProofError::InvalidBoc("invalid reference size"));
+    }
+
+    let offset_size = input.uint(1)? as usize;
+    if offset_size == 0 || offset_size > 8 {
+        return Err(ProofError::InvalidBoc("invalid offset size"));
+    }
+
+    let cells_count = input.uint(size)? as usize;
+    let roots_count = input.uint(size)? as usize;
+    let absent_count = input.uint(size)?;
+    let _total_cells_size = input.uint(offset_size)?;
+    if roots_count == 0 || roots_count > cells_count || absent_count != 0 {
+        return Err(ProofError::InvalidBoc("invalid cells count"));
+    }
+
+    let roots = (0..roots_count)
+        .map(|_| input.uint(size).map(|root| root as usize))
+        .collect::<Result<Vec<_>, _>>()?;
+    if has_index {
+        input.take(cells_count * offset_size)?;
+    }
+
+    let mut raw_cells = Vec::with_capacity(cells_count.min(bytes.len()));
+    for index in 0..cells_count {
+        raw_cells.push(RawCell::read(&mut input, index, cells_count, size)?);
+    }
+
+    if has_crc32c {
+        let checksum = CRC32C.checksum(&bytes[..input.position]);
+        if input.take(4)? != checksum.to_le_bytes() {
+            return Err(ProofError::InvalidBoc("crc32c mismatch"));
+        }
+    }
+    if input.position != bytes.len() {
+        return Err(ProofError::InvalidBoc("trailing bytes"));
+    }
+
+    // references always point forward, so the cells are built from the last one
+    let mut cells: Vec<Option<Arc<ProofCell>>> = vec![None; cells_count];
+    for (index, raw) in raw_cells.into_iter().enumerate().rev() {
+        let references = raw
+            .references
+            .iter()
+            .map(|r| cells[*r].clone().expect("reference is built"))
+            .collect();
+        let cell = ProofCell::new(raw.exotic, raw.data, raw.bit_len, references)?;
+        if cell.level_mask() != raw.level_mask {
+            return Err(ProofError::InvalidCell("level mask mismatch"));
+        }
+        if let Some(hashes) = raw.hashes {
+            let levels = (0..=cell.level())
+                .filter(|level| *level == 0 || cell.level_mask() & (1 << (level - 1)) != 0);
+            if levels
+                .zip(hashes)
+                .any(|(level, hash)| cell.hash(level) != hash)
+            {
+                return Err(ProofError::HashMismatch);
+            }
+        }
+
+        cells[index] = Some(Arc::new(cell));
+    }
+
+    roots
+        .into_iter()
+        .map(|root| {
+            cells
+                .get(root)
+                .cloned()
+                .flatten()
+                .ok_or(ProofError::InvalidBoc("invalid root index"))
+        })
+        .collect()
+}
+
+/// The only root of a serialized bag of cells.
+pub fn deserialize_single(bytes: &[u8]) -> Result<Arc<ProofCell>, ProofError> {
+    let [root] = <[_; 1]>::try_from(deserialize(bytes)?)
+        .map_err(|_| ProofError::InvalidBoc("single root expected"))?;
+
+    Ok(root)
+}
+
+struct Input<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Input<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProofError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + len)
+            .ok_or(ProofError::InvalidBoc("unexpected end"))?;
+        self.position += len;
+
+        Ok(bytes)
+    }
+
+    fn uint(&mut self, len: usize) -> Result<u64, ProofError> {
+        Ok(self
+            .take(len)?
+            .iter()
+            .fold(0, |value, byte| value << 8 | u64::from(*byte)))
+    }
+}
+
+struct RawCell {
+    exotic: bool,
+    level_mask: u8,
+    hashes: Option<Vec<[u8; 32]>>,
+    data: Vec<u8>,
+    bit_len: usize,
+    references: Vec<usize>,
+}
+
+impl RawCell {
+    fn read(
+        input: &mut Input,
+        index: usize,
+        cells_count: usize,
+        size: usize,
+    ) -> Result<Self, ProofError> {
+        let d1 = input.uint(1)? as u8;
+        let d2 = input.uint(1)? as usize;
+
+        let references_count = (d1 & 0x07) as usize;
+        let exotic = d1 & 0x08 != 0;
+        let with_hashes = d1 & 0x10 != 0;
+        let level_mask = d1 >> 5;
+
+        let hashes = if with_hashes {
+            let count = level_mask.count_ones() as usize + 1;
+            let hashes = (0..count)
+                .map(|_| Ok(input.take(32)?.try_into().expect("32 bytes")))
+                .collect::<Result<Vec<_>, ProofError>>()?;
+            input.take(count * 2)?;
+
+            Some(hashes)
+        } else {
+            None
+        };
+
+        let data = input.take(d2.div_ceil(2))?.to_vec();
+        let bit_len = if d2 % 2 == 0 {
+            d2 / 2 * 8
+        } else {
+            // the completion tag is the lowest set bit of the last byte
+            let padding = data
+                .last()
+                .map_or(8, |last| last.trailing_zeros() as usize + 1);
+            if padding >= 8 {
+                return Err(ProofError::InvalidBoc("missing completion tag"));
+            }
+
+            d2 / 2 * 8 + 8 - padding
+        };
+
+        let references = (0..references_count)
+            .map(|_| input.uint(size).map(|r| r as usize))
+            .collect::<Result<Vec<_>, _>>()?;
+        if references.iter().any(|r| *r <= index || *r >= cells_count) {
+            return Err(ProofError::InvalidBoc("invalid reference index"));
+        }
+
+        Ok(Self {
+            exotic,
+            level_mask,
+            hashes,
+            data,
+            bit_len,
+            references,
+        })
+    }
+}
+
+/// Serializes the cells in depth-first order without deduplication, used by the tests to
+/// build proofs.
+#[cfg(test)]
+pub(crate) fn serialize(roots: &[Arc<ProofCell>]) -> Vec<u8> {
+    use crate::proof::cell::CellType;
+
+    fn subtree_size(cell: &ProofCell) -> usize {
+        1 + cell
+            .references()
+            .iter()
+            .map(|r| subtree_size(r))
+            .sum::<usize>()
+    }
+
+    fn write(cell: &ProofCell, index: usize, cells: &mut Vec<u8>) {
+        let exotic = u8::from(cell.cell_type() != CellType::Ordinary);
+        let bit_len = cell.bit_len();
+        cells.push(cell.references().len() as u8 + 8 * exotic + 32 * cell.level_mask());
+        cells.push((bit_len / 8 + bit_len.div_ceil(8)) as u8);
+
+        let mut data = cell.data().to_vec();
+        if bit_len % 8 != 0 {
+            data[bit_len / 8] |= 1 << (7 - bit_len % 8);
+        }
+        cells.extend(data);
+
+        let mut child = index + 1;
+        for reference in cell.references() {
+            cells.extend((child as u32).to_be_bytes());
+            child += subtree_size(reference);
+        }
+
+        let mut child = index + 1;
+        for reference in cell.references() {
+            write(reference, child, cells);
+            child += subtree_size(reference);
+        }
+    }
+
+    let mut cells = Vec::new();
+    let mut root_indexes = Vec::new();
+    let mut index = 0;
+    for root in roots {
+        root_indexes.push(index as u32);
+        write(root, index, &mut cells);
+        index += subtree_size(root);
+    }
+
+    let mut boc = BOC_MAGIC.to_vec();
+    boc.extend([4, 4]);
+    boc.extend((index as u32).to_be_bytes());
+    boc.extend((roots.len() as u32).to_be_bytes());
+    boc.extend(0u32.to_be_bytes());
+    boc.extend((cells.len() as u32).to_be_bytes());
+    boc.extend(root_indexes.iter().flat_map(|i| i.to_be_bytes()));
+    boc.extend(cells);
+
+    boc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::cell::CellType;
+    use crate::proof::fixtures::{merkle_proof, prune_except, SHARD_HASHES};
+    use toner::tlb::bits::bitvec::order::Msb0;
+    use toner::tlb::bits::bitvec::vec::BitVec;
+    use toner::tlb::bits::de::unpack_bytes_fully;
+    use toner::tlb::Cell;
+    use toner::ton::boc::BoC;
+
+    #[test]
+    fn deserialize_matches_toner() {
+        let bytes = hex::decode(SHARD_HASHES).unwrap();
+        let boc: BoC = unpack_bytes_fully(&bytes).unwrap();
+        let expected = boc.single_root().unwrap();
+
+        let actual = deserialize_single(&bytes).unwrap();
+
+        assert_eq!(actual.hash(0), expected.hash());
+        assert_eq!(&actual.to_cell(), expected.as_ref());
+    }
+
+    #[test]
+    fn deserialize_exotic_matches_toner() {
+        let bytes = hex::decode(SHARD_HASHES).unwrap();
+        let boc: BoC = unpack_bytes_fully(&bytes).unwrap();
+        let original = boc.single_root().unwrap();
+        let root = deserialize_single(&bytes).unwrap();
+        let proof = serialize(&[merkle_proof(&prune_except(&root, &[]))]);
+        let boc: BoC = unpack_bytes_fully(&proof).unwrap();
+        let expected = boc.single_root().unwrap();
+
+        let actual = deserialize_single(&proof).unwrap();
+
+        assert_eq!(actual.cell_type(), CellType::MerkleProof);
+        assert_eq!(
+            actual
+                .reference(0)
+                .unwrap()
+                .reference(0)
+                .unwrap()
+                .cell_type(),
+            CellType::PrunedBranch
+        );
+        assert_eq!(actual.reference(0).unwrap().hash(0), original.hash());
+        assert_same_tree(&actual, expected);
+    }
+
+    #[test]
+    fn serialize_roundtrip() {
+        let bytes = hex::decode(SHARD_HASHES).unwrap();
+        let root = deserialize_single(&bytes).unwrap();
+
+        let actual = deserialize_single(&serialize(&[Arc::clone(&root)])).unwrap();
+
+        assert_eq!(actual, root);
+    }
+
+    #[test]
+    fn deserialize_rejects_broken_input() {
+        let bytes = hex::decode(SHARD_HASHES).unwrap();
+
+        assert!(deserialize(&bytes[..bytes.len() - 1]).is_err());
+        assert!(deserialize(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(deserialize(&bytes[1..]).is_err());
+    }
+
+    /// Toner reads exotic cells as ordinary ones, so only data and references are compared.
+    fn assert_same_tree(actual: &ProofCell, expected: &Cell) {
+        let mut data = BitVec::<u8, Msb0>::from_vec(actual.data().to_vec());
+        data.truncate(actual.bit_len());
+
+        assert_eq!(data, expected.data);
+        assert_eq!(actual.references().len(), expected.references.len());
+        for (actual, expected) in actual.references().iter().zip(&expected.references) {
+            assert_same_tree(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use crate::proof::ProofError;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::Cell;
+
+const MAX_BITS: usize = 1023;
+const MAX_REFS: usize = 4;
+const MAX_LEVEL: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellType {
+    Ordinary,
+    PrunedBranch,
+    Library,
+    MerkleProof,
+    MerkleUpdate,
+}
+
+impl CellType {
+    fn from_tag(tag: u8) -> Result<Self, ProofError> {
+        match tag {
+            1 => Ok(Self::PrunedBranch),
+            2 => Ok(Self::Library),
+            3 => Ok(Self::MerkleProof),
+            4 => Ok(Self::MerkleUpdate),
+            _ => Err(ProofError::InvalidCell("unknown exotic cell type")),
+        }
+    }
+
+    fn is_merkle(&self) -> bool {
+        matches!(self, Self::MerkleProof | Self::MerkleUpdate)
+    }
+}
+
+/// Cell keeping its type and level, with the hashes and depths of every significant level.
+///
+/// Unlike [`Cell`] it can represent proofs: hashes of cells containing pruned branches
+/// are computed the way the node does, so `hash(0)` of any cell of a proof is the hash of
+/// the original cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCell {
+    cell_type: CellType,
+    data: Vec<u8>,
+    bit_len: usize,
+    references: Vec<Arc<ProofCell>>,
+    level_mask: u8,
+    hashes: Vec<[u8; 32]>,
+    depths: Vec<u16>,
+}
+
+impl ProofCell {
+    /// Validates the cell and computes its hashes, `data` holds `bit_len` bits, most significant first.
+    pub fn new(
+        exotic: bool,
+        mut data: Vec<u8>,
+        bit_len: usize,
+        references: Vec<Arc<ProofCell>>,
+    ) -> Result<Self, ProofError> {
+        if bit_len > MAX_BITS || data.len() * 8 < bit_len {
+            return Err(ProofError::InvalidCell("data overflow"));
+        }
+        if references.len() > MAX_REFS {
+            return Err(ProofError::InvalidCell("references overflow"));
+        }
+
+        data.truncate(bit_len.div_ceil(8));
+        if bit_len % 8 != 0 {
+            let last = data.len() - 1;
+            data[last] &= !0u8 << (8 - bit_len % 8);
+        }
+
+        let cell_type = if exotic {
+            let tag = data
+                .first()
+                .filter(|_| bit_len >= 8)
+                .ok_or(ProofError::InvalidCell("exotic cell without type"))?;
+
+            CellType::from_tag(*tag)?
+        } else {
+            CellType::Ordinary
+        };
+
+        let level_mask = match cell_type {
+            CellType::Ordinary => references.iter().fold(0, |mask, r| mask | r.level_mask),
+            CellType::PrunedBranch => {
+                let mask = *data
+                    .get(1)
+                    .ok_or(ProofError::InvalidCell("pruned branch without level mask"))?;
+                if mask == 0 || mask > 7 {
+                    return Err(ProofError::InvalidCell("invalid pruned branch level mask"));
+                }
+                let levels = mask.count_ones() as usize;
+                if !references.is_empty() || bit_len != 16 + levels * (256 + 16) {
+                    return Err(ProofError::InvalidCell("invalid pruned branch"));
+                }
+
+                mask
+            }
+            CellType::Library => {
+                if !references.is_empty() || bit_len != 8 + 256 {
+                    return Err(ProofError::InvalidCell("invalid library cell"));
+                }
+
+                0
+            }
+            CellType::MerkleProof => {
+                if references.len() != 1 || bit_len != 8 + 256 + 16 {
+                    return Err(ProofError::InvalidCell("invalid merkle proof"));
+                }
+
+                references[0].level_mask >> 1
+            }
+            CellType::MerkleUpdate => {
+                if references.len() != 2 || bit_len != 8 + 2 * 256 + 2 * 16 {
+                    return Err(ProofError::InvalidCell("invalid merkle update"));
+                }
+
+                (references[0].level_mask | references[1].level_mask) >> 1
+            }
+        };
+
+        let mut cell = Self {
+            cell_type,
+            data,
+            bit_len,
+            references,
+            level_mask,
+            hashes: Vec::new(),
+            depths: Vec::new(),
+        };
+        cell.compute_hashes();
+        cell.check_merkle_children()?;
+
+        Ok(cell)
+    }
+
+    pub fn cell_type(&self) -> CellType {
+        self.cell_type
+    }
+
+    pub fn level(&self) -> u8 {
+        8 - self.level_mask.leading_zeros() as u8
+    }
+
+    pub fn level_mask(&self) -> u8 {
+        self.level_mask
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn references(&self) -> &[Arc<ProofCell>] {
+        &self.references
+    }
+
+    pub fn reference(&self, index: usize) -> Result<&Arc<ProofCell>, ProofError> {
+        self.references.get(index).ok_or(ProofError::CellUnderflow)
+    }
+
+    /// Hash of the cell with the pruned branches of levels above `level` left in place,
+    /// `hash(0)` is the hash of the original cell.
+    pub fn hash(&self, level: u8) -> [u8; 32] {
+        let index = self.hash_index(level);
+        if self.cell_type == CellType::PrunedBranch && index < self.pruned_levels() {
+            let offset = 2 + index * 32;
+
+            return self.data[offset..offset + 32].try_into().expect("32 bytes");
+        }
+
+        self.hashes[index - self.hash_offset()]
+    }
+
+    pub fn depth(&self, level: u8) -> u16 {
+        let index = self.hash_index(level);
+        if self.cell_type == CellType::PrunedBranch && index < self.pruned_levels() {
+            let offset = 2 + self.pruned_levels() * 32 + index * 2;
+
+            return u16::from_be_bytes([self.data[offset], self.data[offset + 1]]);
+        }
+
+        self.depths[index - self.hash_offset()]
+    }
+
+    /// Reader of an ordinary cell, reading a pruned branch is an error as its content is not proven.
+    pub fn reader(&self) -> Result<CellReader<'_>, ProofError> {
+        match self.cell_type {
+            CellType::Ordinary => Ok(CellReader {
+                cell: self,
+                position: 0,
+                reference: 0,
+            }),
+            CellType::PrunedBranch => Err(ProofError::PrunedBranch),
+            _ => Err(ProofError::UnexpectedCellType(self.cell_type)),
+        }
+    }
+
+    /// Converts to a [`Cell`] for TL-B parsing. Pruned branches become empty cells,
+    /// so parsing pruned data fails instead of reading the stored hashes.
+    pub fn to_cell(&self) -> Cell {
+        if self.cell_type == CellType::PrunedBranch {
+            return Cell::default();
+        }
+
+        let mut data = BitVec::<u8, Msb0>::from_vec(self.data.clone());
+        data.truncate(self.bit_len);
+
+        Cell {
+            data,
+            references: self
+                .references
+                .iter()
+                .map(|r| Arc::new(r.to_cell()))
+                .collect(),
+        }
+    }
+
+    fn hash_index(&self, level: u8) -> usize {
+        (self.level_mask & ((1 << level.min(MAX_LEVEL)) - 1)).count_ones() as usize
+    }
+
+    fn pruned_levels(&self) -> usize {
+        self.level_mask.count_ones() as usize
+    }
+
+    /// Pruned branches store all the hashes but the last one.
+    fn hash_offset(&self) -> usize {
+        match self.cell_type {
+            CellType::PrunedBranch => self.pruned_levels(),
+            _ => 0,
+        }
+    }
+
+    fn compute_hashes(&mut self) {
+        let offset = self.hash_offset();
+        let mut hash_index = 0;
+        for level in 0..=self.level() {
+            if level != 0 && self.level_mask & (1 << (level - 1)) == 0 {
+                continue;
+            }
+            if hash_index < offset {
+                hash_index += 1;
+                continue;
+            }
+
+            let mut hasher = Sha256::new();
+            if hash_index == offset {
+                hasher.update(self.descriptors(self.level_mask & ((1 << level) - 1)));
+                hasher.update(self.padded_data());
+            } else {
+                hasher.update(self.hashes[hash_index - offset - 1]);
+            }
+
+            let child_level = if self.cell_type.is_merkle() {
+                level + 1
+            } else {
+                level
+            };
+
+            let mut depth = 0;
+            for reference in &self.references {
+                let child_depth = reference.depth(child_level);
+                hasher.update(child_depth.to_be_bytes());
+                depth = depth.max(child_depth + 1);
+            }
+            for reference in &self.references {
+                hasher.update(reference.hash(child_level));
+            }
+
+            self.hashes.push(hasher.finalize().into());
+            self.depths.push(depth);
+            hash_index += 1;
+        }
+    }
+
+    fn descriptors(&self, level_mask: u8) -> [u8; 2] {
+        let exotic = u8::from(self.cell_type != CellType::Ordinary);
+        let d1 = self.references.len() as u8 + 8 * exotic + 32 * level_mask;
+        let d2 = (self.bit_len / 8 + self.bit_len.div_ceil(8)) as u8;
+
+        [d1, d2]
+    }
+
+    /// Data with the completion tag set after the last bit of an incomplete byte.
+    fn padded_data(&self) -> Vec<u8> {
+        let mut data = self.data.clone();
+        if self.bit_len % 8 != 0 {
+            data[self.bit_len / 8] |= 1 << (7 - self.bit_len % 8);
+        }
+
+        data
+    }
+
+    fn check_merkle_children(&self) -> Result<(), ProofError> {
+        let children = match self.cell_type {
+            CellType::MerkleProof => 1,
+            CellType::MerkleUpdate => 2,
+            _ => return Ok(()),
+        };
+
+        for (i, reference) in self.references.iter().enumerate().take(children) {
+            let hash_offset = 1 + i * 32;
+            let depth_offset = 1 + children * 32 + i * 2;
+
+            if self.data[hash_offset..hash_offset + 32] != reference.hash(0) {
+                return Err(ProofError::InvalidCell("merkle cell hash mismatch"));
+            }
+            if self.data[depth_offset..depth_offset + 2] != reference.depth(0).to_be_bytes() {
+                return Err(ProofError::InvalidCell("merkle cell depth mismatch"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sequential reader of the data and references of an ordinary [`ProofCell`].
+#[derive(Debug, Clone)]
+pub struct CellReader<'a> {
+    cell: &'a ProofCell,
+    position: usize,
+    reference: usize,
+}
+
+impl<'a> CellReader<'a> {
+    pub fn remaining_bits(&self) -> usize {
+        self.cell.bit_len - self.position
+    }
+
+    pub fn load_bit(&mut self) -> Result<bool, ProofError> {
+        if self.remaining_bits() == 0 {
+            return Err(ProofError::CellUnderflow);
+        }
+
+        let bit = self.cell.data[self.position / 8] >> (7 - self.position % 8) & 1 == 1;
+        self.position += 1;
+
+        Ok(bit)
+    }
+
+    /// Big-endian unsigned integer of up to 64 bits.
+    pub fn load_uint(&mut self, bits: usize) -> Result<u64, ProofError> {
+        if bits > 64 || self.remaining_bits() < bits {
+            return Err(ProofError::CellUnderflow);
+        }
+
+        (0..bits).try_fold(0, |value, _| Ok(value << 1 | u64::from(self.load_bit()?)))
+    }
+
+    pub fn load_hash(&mut self) -> Result<[u8; 32], ProofError> {
+        let mut hash = [0; 32];
+        for byte in &mut hash {
+            *byte = self.load_uint(8)? as u8;
+        }
+
+        Ok(hash)
+    }
+
+    pub fn skip(&mut self, bits: usize) -> Result<(), ProofError> {
+        if self.remaining_bits() < bits {
+            return Err(ProofError::CellUnderflow);
+        }
+        self.position += bits;
+
+        Ok(())
+    }
+
+    pub fn load_reference(&mut self) -> Result<&'a Arc<ProofCell>, ProofError> {
+        let reference = self.cell.reference(self.reference)?;
+        self.reference += 1;
+
+        Ok(reference)
+    }
+
+    pub fn skip_references(&mut self, count: usize) -> Result<(), ProofError> {
+        if self.cell.references.len() < self.reference + count {
+            return Err(ProofError::CellUnderflow);
+        }
+        self.reference += count;
+
+        Ok(())
+    }
+
+    pub fn expect_tag(&mut self, bits: usize, tag: u64) -> Result<(), ProofError> {
+        let actual = self.load_uint(bits)?;
+        if actual != tag {
+            return Err(ProofError::UnexpectedTag {
+                expected: tag,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
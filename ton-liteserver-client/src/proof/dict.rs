@@ -0,0 +1,169 @@
+use crate::proof::cell::{CellReader, ProofCell};
+use crate::proof::ProofError;
+
+/// Reader positioned at the value of `key` in a `Hashmap n X` (or at the extra of a
+/// `HashmapAug n X Y` leaf), `None` when the labels prove that the key is absent.
+///
+/// Only the visited path has to be present, the rest of the dictionary may be pruned.
+pub fn hashmap_get<'a>(
+    root: &'a ProofCell,
+    key: &[u8],
+    n: usize,
+) -> Result<Option<CellReader<'a>>, ProofError> {
+    if key.len() * 8 < n {
+        return Err(ProofError::CellUnderflow);
+    }
+    let key_bit = |i: usize| key[i / 8] >> (7 - i % 8) & 1 == 1;
+
+    let mut cell = root;
+    let mut position = 0;
+    loop {
+        let mut reader = cell.reader()?;
+        let remaining = n - position;
+
+        let label = load_label(&mut reader, remaining)?;
+        if label.len() > remaining {
+            return Err(ProofError::InvalidCell("hashmap label overflow"));
+        }
+        if label
+            .iter()
+            .enumerate()
+            .any(|(i, bit)| *bit != key_bit(position + i))
+        {
+            return Ok(None);
+        }
+        position += label.len();
+
+        if position == n {
+            return Ok(Some(reader));
+        }
+
+        let left = reader.load_reference()?;
+        let right = reader.load_reference()?;
+        cell = if key_bit(position) { right } else { left };
+        position += 1;
+    }
+}
+
+/// `hme_empty$0` or `hme_root$1 root:^(Hashmap n X)`, the augmented variant is followed
+/// by the extra which is left unread.
+pub fn hashmap_e_get<'a>(
+    reader: &mut CellReader<'a>,
+    key: &[u8],
+    n: usize,
+) -> Result<Option<CellReader<'a>>, ProofError> {
+    if !reader.load_bit()? {
+        return Ok(None);
+    }
+
+    hashmap_get(reader.load_reference()?, key, n)
+}
+
+/// ```tlb
+/// hml_short$0 {m:#} {n:#} len:(Unary ~n) {n <= m} s:(n * Bit) = HmLabel ~n m;
+/// hml_long$10 {m:#} n:(#<= m) s:(n * Bit) = HmLabel ~n m;
+/// hml_same$11 {m:#} v:Bit n:(#<= m) = HmLabel ~n m;
+/// ```
+fn load_label(reader: &mut CellReader, max_len: usize) -> Result<Vec<bool>, ProofError> {
+    let len_bits = (usize::BITS - max_len.leading_zeros()) as usize;
+
+    if !reader.load_bit()? {
+        let mut len = 0;
+        while reader.load_bit()? {
+            len += 1;
+        }
+
+        return (0..len).map(|_| reader.load_bit()).collect();
+    }
+
+    if !reader.load_bit()? {
+        let len = reader.load_uint(len_bits)? as usize;
+
+        return (0..len.min(max_len + 1))
+            .map(|_| reader.load_bit())
+            .collect();
+    }
+
+    let bit = reader.load_bit()?;
+    let len = reader.load_uint(len_bits)? as usize;
+
+    Ok(vec![bit; len.min(max_len + 1)])
+}
+
+/// Leaf of a `BinTree X` for the shard `shard`, `None` when the tree splits the shards
+/// differently.
+///
+/// ```tlb
+/// bt_leaf$0 {X:Type} leaf:X = BinTree X;
+/// bt_fork$1 {X:Type} left:^(BinTree X) right:^(BinTree X) = BinTree X;
+/// ```
+pub fn bin_tree_get(root: &ProofCell, shard: u64) -> Result<Option<CellReader<'_>>, ProofError> {
+    let prefix_len = 63 - shard.trailing_zeros() as usize;
+
+    let mut cell = root;
+    for depth in 0..=prefix_len {
+        let mut reader = cell.reader()?;
+        if !reader.load_bit()? {
+            return Ok((depth == prefix_len).then_some(reader));
+        }
+        if depth == prefix_len {
+            return Ok(None);
+        }
+
+        let left = reader.load_reference()?;
+        let right = reader.load_reference()?;
+        cell = if shard >> (63 - depth) & 1 == 1 {
+            right
+        } else {
+            left
+        };
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::boc::deserialize_single;
+    use crate::proof::fixtures::SHARD_HASHES;
+
+    fn given_shard_hashes() -> std::sync::Arc<ProofCell> {
+        deserialize_single(&hex::decode(SHARD_HASHES).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn hashmap_get_present_and_absent_keys() {
+        let root = given_shard_hashes();
+        let mut reader = root.reader().unwrap();
+
+        let value = hashmap_e_get(&mut reader, &0u32.to_be_bytes(), 32).unwrap();
+        assert!(value.is_some());
+
+        let mut reader = root.reader().unwrap();
+        let value = hashmap_e_get(&mut reader, &1u32.to_be_bytes(), 32).unwrap();
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn bin_tree_get_shards() {
+        let root = given_shard_hashes();
+        let mut reader = root.reader().unwrap();
+        let mut value = hashmap_e_get(&mut reader, &0u32.to_be_bytes(), 32)
+            .unwrap()
+            .unwrap();
+        let tree = value.load_reference().unwrap();
+
+        for shard in [
+            0x2000000000000000u64,
+            0x6000000000000000,
+            0xa000000000000000,
+            0xe000000000000000,
+        ] {
+            let mut leaf = bin_tree_get(tree, shard).unwrap().unwrap();
+            assert!(matches!(leaf.load_uint(4).unwrap(), 0xa | 0xb));
+        }
+        assert!(bin_tree_get(tree, 0x8000000000000000).unwrap().is_none());
+        assert!(bin_tree_get(tree, 0x1000000000000000).unwrap().is_none());
+    }
+}
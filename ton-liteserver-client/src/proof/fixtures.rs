@@ -0,0 +1,719 @@
+//! Synthetic blocks and states for the proof tests, parts of the trees are pruned the
+//! way liteservers do.
+use crate::proof::boc::serialize;
+use crate::proof::cell::ProofCell;
+use crate::proof::verify::{
+    BLOCK_EXTRA_TAG, BLOCK_INFO_TAG, BLOCK_TAG, MASTERCHAIN, MC_BLOCK_EXTRA_TAG,
+    MC_STATE_EXTRA_TAG, SHARD_STATE_TAG,
+};
+use crate::tl::{
+    BoolFalse, BoolTrue, BoxedBool, LiteServerAccountId, LiteServerAccountState,
+    LiteServerBlockLinkBack, LiteServerBlockLinkForward, LiteServerConfigInfo,
+    LiteServerGetAccountState, LiteServerSignature, LiteServerSignatureSet, TonNodeBlockIdExt,
+};
+use adnl_tcp::key::Ed25519Key;
+use std::sync::Arc;
+
+/// `HashmapE 32 ^(BinTree ShardDescr)` of a mainnet block with the basechain split into 4 shards.
+pub(crate) const SHARD_HASHES: &str = "b5ee9c7201020d0100020c000101c0010103d040020201c003040201c005060201c0090a01db5014f0a6c8123be8880001559e44ca1a000001559e44ca1a3cc1d224aa5b9f1e6610d94e89e37decdb0d75981a5646e0a7e0c099461abacf307c8d69b412105ec8734aea8b926d380f91ff42c7e4f61cf731b2e9ff500913d00000460d810000000000000000123be87b3319d9020701db5014f07dc8123be8880001559e43d5f6000001559e43d5f7bca2dd37526cdc93834ae03666706139de4812cb71ff5d384506cb8a7e933e1fd04e3511e9949ecffba9f6b530e7c43182c325e25daad18d303adaccf4a315b8400000460d830000000000000000123be8733319d8d208001344d69059b2165a0bc02000134394054c02077359402001db5014f09b18123be8880001559e44ca1a000001559e44ca1b8cbe3ea21e6a78ccdb3e0a76f292fdf5c8580a40ea7f61004cdcb7b0fdfa2f78210ad6cdda8f5fd6b1c7678dae076bc87e7d2c4da65a0cc64a08c7db7e081b23600000460da50000000000000000123be87b3319d9020b01db5014f0a2f8123be8880001559e45442c000001559e45442c29bd15b1b5f524b85b1d91d21994dc39d8bee1a70831ac069dc00db0421e1e1e5b56542ec60ee32e6f66d846e736e92f450766e79d002c476077a0848f223599080000460d970000000000000000123be87b3319d8ea0c001346728c8162165a0bc0200013429cd691720ee6b28020";
+
+/// Config dictionary `Hashmap 32 ^Cell` passed to the transaction emulator in `tonlibjson-sys`,
+/// the current validator set holds 100 validators.
+pub(crate) const CONFIG: &[u8] =
+    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/config.boc"));
+
+/// `ShardAccount` of an active basechain wallet passed to the transaction emulator in
+/// `tonlibjson-sys`.
+pub(crate) const SHARD_ACCOUNT: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/fixtures/shard_account.boc"
+));
+
+/// Internal `Message` to the account of [`SHARD_ACCOUNT`] passed to the transaction emulator.
+pub(crate) const MESSAGE: &[u8] =
+    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/message.boc"));
+
+pub(crate) const FULL_SHARD: u64 = 0x8000000000000000;
+
+#[derive(Default)]
+pub(crate) struct CellBuilder {
+    data: Vec<u8>,
+    bit_len: usize,
+    references: Vec<Arc<ProofCell>>,
+}
+
+impl CellBuilder {
+    pub(crate) fn bit(mut self, bit: bool) -> Self {
+        if self.bit_len % 8 == 0 {
+            self.data.push(0);
+        }
+        if bit {
+            self.data[self.bit_len / 8] |= 1 << (7 - self.bit_len % 8);
+        }
+        self.bit_len += 1;
+
+        self
+    }
+
+    pub(crate) fn uint(self, bits: usize, value: u64) -> Self {
+        (0..bits)
+            .rev()
+            .fold(self, |builder, i| builder.bit(value >> i & 1 == 1))
+    }
+
+    pub(crate) fn bytes(self, bytes: &[u8]) -> Self {
+        bytes
+            .iter()
+            .fold(self, |builder, byte| builder.uint(8, u64::from(*byte)))
+    }
+
+    pub(crate) fn reference(mut self, cell: &Arc<ProofCell>) -> Self {
+        self.references.push(Arc::clone(cell));
+
+        self
+    }
+
+    pub(crate) fn build(self) -> Arc<ProofCell> {
+        Arc::new(ProofCell::new(false, self.data, self.bit_len, self.references).unwrap())
+    }
+
+    pub(crate) fn build_exotic(self) -> Arc<ProofCell> {
+        Arc::new(ProofCell::new(true, self.data, self.bit_len, self.references).unwrap())
+    }
+}
+
+pub(crate) fn empty() -> Arc<ProofCell> {
+    CellBuilder::default().build()
+}
+
+pub(crate) fn pruned(cell: &ProofCell, level_mask: u8) -> Arc<ProofCell> {
+    let levels = level_mask.count_ones();
+    let builder = CellBuilder::default().uint(8, 1).uint(8, level_mask.into());
+    let builder = (0..levels).fold(builder, |builder, _| builder.bytes(&cell.hash(0)));
+
+    (0..levels)
+        .fold(builder, |builder, _| builder.uint(16, cell.depth(0).into()))
+        .build_exotic()
+}
+
+pub(crate) fn merkle_proof(root: &Arc<ProofCell>) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(8, 3)
+        .bytes(&root.hash(0))
+        .uint(16, root.depth(0).into())
+        .reference(root)
+        .build_exotic()
+}
+
+pub(crate) fn merkle_update(old: &Arc<ProofCell>, new: &Arc<ProofCell>) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(8, 4)
+        .bytes(&old.hash(0))
+        .bytes(&new.hash(0))
+        .uint(16, old.depth(0).into())
+        .uint(16, new.depth(0).into())
+        .reference(old)
+        .reference(new)
+        .build_exotic()
+}
+
+pub(crate) fn with_references(cell: &ProofCell, references: Vec<Arc<ProofCell>>) -> Arc<ProofCell> {
+    Arc::new(ProofCell::new(false, cell.data().to_vec(), cell.bit_len(), references).unwrap())
+}
+
+/// Copy of `cell` with all the references but `keep` pruned.
+pub(crate) fn prune_except(cell: &ProofCell, keep: &[usize]) -> Arc<ProofCell> {
+    let references = cell
+        .references()
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            if keep.contains(&i) {
+                Arc::clone(r)
+            } else {
+                pruned(r, 1)
+            }
+        })
+        .collect();
+
+    with_references(cell, references)
+}
+
+pub(crate) fn ext_blk_ref(id: &TonNodeBlockIdExt, end_lt: u64) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(64, end_lt)
+        .uint(32, id.seqno as u64)
+        .bytes(&id.root_hash)
+        .bytes(&id.file_hash)
+        .build()
+}
+
+pub(crate) fn block_info(
+    workchain: i32,
+    seqno: i32,
+    key_block: bool,
+    gen_utime: u32,
+    end_lt: u64,
+    prev: &TonNodeBlockIdExt,
+) -> Arc<ProofCell> {
+    let not_master = workchain != MASTERCHAIN;
+    let builder = CellBuilder::default()
+        .uint(32, BLOCK_INFO_TAG)
+        .uint(32, 0)
+        .bit(not_master)
+        .uint(5, 0)
+        .bit(key_block)
+        .uint(9, 0)
+        .uint(32, seqno as u64)
+        .uint(32, 0)
+        // shard_ident$00 shard_pfx_bits:(#<= 60) workchain_id:int32 shard_prefix:uint64
+        .uint(2, 0)
+        .uint(6, 0)
+        .uint(32, workchain as u32 as u64)
+        .uint(64, 0)
+        .uint(32, gen_utime.into())
+        .uint(64, end_lt - 10)
+        .uint(64, end_lt)
+        .uint(64, 0)
+        .uint(64, 0);
+    let builder = if not_master {
+        builder.reference(&ext_blk_ref(prev, end_lt - 20))
+    } else {
+        builder
+    };
+
+    builder.reference(&ext_blk_ref(prev, end_lt - 20)).build()
+}
+
+pub(crate) fn block(
+    info: &Arc<ProofCell>,
+    state: &Arc<ProofCell>,
+    extra: &Arc<ProofCell>,
+) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(32, BLOCK_TAG)
+        .uint(32, -239i32 as u32 as u64)
+        .reference(info)
+        .reference(&empty())
+        .reference(&merkle_update(&empty(), state))
+        .reference(extra)
+        .build()
+}
+
+pub(crate) fn block_id(workchain: i32, seqno: i32, block: &ProofCell) -> TonNodeBlockIdExt {
+    TonNodeBlockIdExt {
+        workchain,
+        shard: FULL_SHARD as i64,
+        seqno,
+        root_hash: block.hash(0),
+        file_hash: [seqno as u8; 32],
+    }
+}
+
+pub(crate) fn zero_id() -> TonNodeBlockIdExt {
+    TonNodeBlockIdExt {
+        workchain: MASTERCHAIN,
+        shard: FULL_SHARD as i64,
+        seqno: 0,
+        root_hash: [0; 32],
+        file_hash: [0; 32],
+    }
+}
+
+/// Merkle proof of the block keeping `BlockInfo` and the hash of the new state.
+pub(crate) fn block_state_proof(block: &ProofCell) -> Arc<ProofCell> {
+    let update = block.reference(2).unwrap();
+    let update = merkle_update(
+        &pruned(update.reference(0).unwrap(), 3),
+        &pruned(update.reference(1).unwrap(), 3),
+    );
+
+    merkle_proof(&with_references(
+        block,
+        vec![
+            Arc::clone(block.reference(0).unwrap()),
+            pruned(block.reference(1).unwrap(), 1),
+            update,
+            pruned(block.reference(3).unwrap(), 1),
+        ],
+    ))
+}
+
+pub(crate) fn build_shard_state(
+    accounts: &Arc<ProofCell>,
+    custom: Option<&Arc<ProofCell>>,
+) -> Arc<ProofCell> {
+    let builder = CellBuilder::default()
+        .uint(32, SHARD_STATE_TAG)
+        .uint(64, 0)
+        .uint(64, 0)
+        .uint(64, 0)
+        .uint(64, 0)
+        .uint(64, 0)
+        .uint(9, 0)
+        .reference(&empty())
+        .reference(accounts)
+        .reference(&empty());
+
+    match custom {
+        Some(custom) => builder.bit(true).reference(custom),
+        None => builder.bit(false),
+    }
+    .build()
+}
+
+/// Merkle proof of the state keeping the accounts and the masterchain extra.
+pub(crate) fn state_proof(state: &ProofCell) -> Arc<ProofCell> {
+    merkle_proof(&prune_except(state, &[1, 3]))
+}
+
+pub(crate) fn accounts(id: &[u8; 32], account: &Arc<ProofCell>) -> Arc<ProofCell> {
+    let leaf = CellBuilder::default()
+        .uint(2, 0b10)
+        .uint(9, 256)
+        .bytes(id)
+        .uint(5, 0)
+        .uint(4, 1)
+        .uint(8, 100)
+        .bit(false)
+        .reference(account)
+        .bytes(&[0; 32])
+        .uint(64, 0)
+        .build();
+
+    CellBuilder::default()
+        .bit(true)
+        .reference(&leaf)
+        .uint(5, 0)
+        .uint(4, 1)
+        .uint(8, 100)
+        .bit(false)
+        .build()
+}
+
+/// Root of `ShardHashes` with the whole basechain described by `id`.
+pub(crate) fn shard_hashes(id: &TonNodeBlockIdExt) -> Arc<ProofCell> {
+    let descr = CellBuilder::default()
+        .bit(false)
+        .uint(4, 0xb)
+        .uint(32, id.seqno as u64)
+        .uint(32, 0)
+        .uint(64, 0)
+        .uint(64, 0)
+        .bytes(&id.root_hash)
+        .bytes(&id.file_hash)
+        .build();
+
+    CellBuilder::default()
+        .uint(2, 0b10)
+        .uint(6, 32)
+        .uint(32, 0)
+        .reference(&descr)
+        .build()
+}
+
+pub(crate) fn build_mc_state_extra(
+    shard_hashes: &Arc<ProofCell>,
+    prev: &TonNodeBlockIdExt,
+) -> Arc<ProofCell> {
+    let prev_block = CellBuilder::default()
+        .uint(2, 0b10)
+        .uint(6, 32)
+        .uint(32, prev.seqno as u64)
+        .uint(64, 0)
+        .bit(false)
+        .bit(false)
+        .uint(64, 0)
+        .uint(32, prev.seqno as u64)
+        .bytes(&prev.root_hash)
+        .bytes(&prev.file_hash)
+        .build();
+    let other = CellBuilder::default()
+        .uint(64, 0)
+        .uint(17, 0)
+        .bit(true)
+        .reference(&prev_block)
+        .uint(64, 0)
+        .bit(false)
+        .build();
+
+    CellBuilder::default()
+        .uint(16, MC_STATE_EXTRA_TAG)
+        .bit(true)
+        .reference(shard_hashes)
+        .bytes(&[0; 32])
+        .reference(&empty())
+        .reference(&other)
+        .build()
+}
+
+pub(crate) fn build_mc_block_extra(shard_hashes: &Arc<ProofCell>) -> Arc<ProofCell> {
+    let mc_extra = CellBuilder::default()
+        .uint(16, MC_BLOCK_EXTRA_TAG)
+        .bit(false)
+        .bit(true)
+        .reference(shard_hashes)
+        .build();
+
+    block_extra(&mc_extra)
+}
+
+/// `McBlockExtra` of a key block carrying `config`.
+pub(crate) fn key_block_extra(config: &Arc<ProofCell>) -> Arc<ProofCell> {
+    let mc_extra = CellBuilder::default()
+        .uint(16, MC_BLOCK_EXTRA_TAG)
+        .bit(true)
+        .bit(false)
+        .bit(false)
+        .uint(5, 0)
+        .uint(5, 0)
+        .reference(&empty())
+        .bytes(&[0; 32])
+        .reference(config)
+        .build();
+
+    block_extra(&mc_extra)
+}
+
+fn block_extra(mc_extra: &Arc<ProofCell>) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(32, BLOCK_EXTRA_TAG)
+        .reference(&empty())
+        .reference(&empty())
+        .reference(&empty())
+        .bytes(&[0; 64])
+        .bit(true)
+        .reference(mc_extra)
+        .build()
+}
+
+/// `Hashmap n X` with the values copied from the data and references of `entries`.
+pub(crate) fn hashmap(n: usize, entries: &[(u64, Arc<ProofCell>)]) -> Arc<ProofCell> {
+    let entries: Vec<_> = entries.iter().map(|(k, v)| (*k, v)).collect();
+
+    hashmap_node(n, &entries)
+}
+
+fn hashmap_node<'a>(m: usize, entries: &[(u64, &'a Arc<ProofCell>)]) -> Arc<ProofCell> {
+    let bit = |key: u64, i: usize| key >> (m - 1 - i) & 1 == 1;
+    let (first, _) = entries[0];
+    let label_len = if entries.len() == 1 {
+        m
+    } else {
+        (0..m)
+            .take_while(|i| {
+                entries
+                    .iter()
+                    .all(|(key, _)| bit(*key, *i) == bit(first, *i))
+            })
+            .count()
+    };
+
+    let builder = if m == 0 {
+        // hml_short$0, the empty label as serializers emit it
+        CellBuilder::default().uint(2, 0b00)
+    } else {
+        // hml_long$10
+        CellBuilder::default()
+            .uint(2, 0b10)
+            .uint((usize::BITS - m.leading_zeros()) as usize, label_len as u64)
+    };
+    let builder = (0..label_len).fold(builder, |builder, i| builder.bit(bit(first, i)));
+
+    if label_len == m {
+        let (_, value) = entries[0];
+        let builder = (0..value.bit_len()).fold(builder, |builder, i| {
+            builder.bit(value.data()[i / 8] >> (7 - i % 8) & 1 == 1)
+        });
+
+        return value
+            .references()
+            .iter()
+            .fold(builder, |builder, r| builder.reference(r))
+            .build();
+    }
+
+    let rest = m - label_len - 1;
+    let mask = (1u64 << rest) - 1;
+    let (left, right): (Vec<_>, Vec<_>) =
+        entries.iter().partition(|(key, _)| !bit(*key, label_len));
+    let strip = |entries: Vec<&(u64, &'a Arc<ProofCell>)>| -> Vec<_> {
+        entries.into_iter().map(|(k, v)| (k & mask, *v)).collect()
+    };
+
+    builder
+        .reference(&hashmap_node(rest, &strip(left)))
+        .reference(&hashmap_node(rest, &strip(right)))
+        .build()
+}
+
+/// `validators_ext` with all the validators in the main set.
+pub(crate) fn validator_set(validators: &[([u8; 32], u64)]) -> Arc<ProofCell> {
+    let list: Vec<_> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, (public_key, weight))| {
+            let descr = CellBuilder::default()
+                .uint(8, 0x53)
+                .uint(32, 0x8e81278a)
+                .bytes(public_key)
+                .uint(64, *weight)
+                .build();
+
+            (i as u64, descr)
+        })
+        .collect();
+    let total_weight = validators.iter().map(|(_, weight)| weight).sum();
+
+    CellBuilder::default()
+        .uint(8, 0x12)
+        .uint(32, 0)
+        .uint(32, u32::MAX.into())
+        .uint(16, validators.len() as u64)
+        .uint(16, validators.len() as u64)
+        .uint(64, total_weight)
+        .bit(true)
+        .reference(&hashmap(16, &list))
+        .build()
+}
+
+/// `Hashmap 32 ^Cell` of the config with the current validator set only.
+pub(crate) fn config(validator_set: &Arc<ProofCell>) -> Arc<ProofCell> {
+    let param = CellBuilder::default().reference(validator_set).build();
+
+    hashmap(32, &[(34, param)])
+}
+
+/// Config with the elector, global version, basechain, prices and the current validators.
+pub(crate) fn blockchain_config() -> Arc<ProofCell> {
+    let param = |cell: Arc<ProofCell>| CellBuilder::default().reference(&cell).build();
+
+    let elector = CellBuilder::default().bytes(&[3; 32]).build();
+    let global_version = CellBuilder::default()
+        .uint(8, 0xc4)
+        .uint(32, 7)
+        .uint(64, 0x2e)
+        .build();
+    let basechain = CellBuilder::default()
+        .uint(8, 0xa6)
+        .uint(32, 1)
+        .uint(8, 0)
+        .uint(8, 2)
+        .uint(8, 4)
+        .bit(true)
+        .bit(true)
+        .bit(true)
+        .uint(13, 0)
+        .bytes(&[1; 32])
+        .bytes(&[2; 32])
+        .uint(32, 0)
+        .uint(4, 1)
+        .uint(32, 0)
+        .uint(64, 0)
+        .build();
+    let workchains = CellBuilder::default()
+        .bit(true)
+        .reference(&hashmap(32, &[(0, basechain)]))
+        .build();
+    let storage_prices = CellBuilder::default()
+        .uint(8, 0xcc)
+        .uint(32, 0)
+        .uint(64, 1)
+        .uint(64, 500)
+        .uint(64, 1000)
+        .uint(64, 500000)
+        .build();
+    let gas_prices = CellBuilder::default()
+        .uint(8, 0xd1)
+        .uint(64, 100)
+        .uint(64, 40000)
+        .uint(8, 0xde)
+        .uint(64, 26214400)
+        .uint(64, 1000000)
+        .uint(64, 70000000)
+        .uint(64, 10000)
+        .uint(64, 10000000)
+        .uint(64, 100000000000)
+        .uint(64, 1000000000000)
+        .build();
+    let msg_forward_prices = CellBuilder::default()
+        .uint(8, 0xea)
+        .uint(64, 400000)
+        .uint(64, 26214400)
+        .uint(64, 2621440000)
+        .uint(32, 98304)
+        .uint(16, 21845)
+        .uint(16, 21845)
+        .build();
+
+    hashmap(
+        32,
+        &[
+            (1, param(elector)),
+            (8, param(global_version)),
+            (12, param(workchains)),
+            (18, param(hashmap(32, &[(0, storage_prices)]))),
+            (21, param(gas_prices)),
+            (25, param(msg_forward_prices)),
+            (34, param(validator_set(&[([1; 32], 10), ([2; 32], 20)]))),
+        ],
+    )
+}
+
+/// Account stored in a basechain shard block, proven by the masterchain block referring it.
+pub(crate) fn account_state(
+    account_id: [u8; 32],
+    account: &Arc<ProofCell>,
+) -> (LiteServerGetAccountState, LiteServerAccountState) {
+    let state = build_shard_state(&accounts(&account_id, account), None);
+    let shard_block = block(
+        &block_info(0, 3, false, 1000, 400, &zero_id()),
+        &state,
+        &empty(),
+    );
+    let shard_id = block_id(0, 3, &shard_block);
+
+    let mc_state = build_shard_state(
+        &empty(),
+        Some(&build_mc_state_extra(&shard_hashes(&shard_id), &zero_id())),
+    );
+    let mc_block = block(
+        &block_info(MASTERCHAIN, 7, false, 1000, 500, &zero_id()),
+        &mc_state,
+        &empty(),
+    );
+    let mc_id = block_id(MASTERCHAIN, 7, &mc_block);
+
+    let request = LiteServerGetAccountState {
+        id: mc_id.clone(),
+        account: LiteServerAccountId {
+            workchain: 0,
+            id: account_id,
+        },
+    };
+    let response = LiteServerAccountState {
+        id: mc_id,
+        shardblk: shard_id,
+        shard_proof: serialize(&[block_state_proof(&mc_block), state_proof(&mc_state)]),
+        proof: serialize(&[block_state_proof(&shard_block), state_proof(&state)]),
+        state: serialize(&[account.clone()]),
+    };
+
+    (request, response)
+}
+
+/// `McStateExtra` carrying `config`.
+pub(crate) fn config_mc_state_extra(config: &Arc<ProofCell>) -> Arc<ProofCell> {
+    CellBuilder::default()
+        .uint(16, MC_STATE_EXTRA_TAG)
+        .bit(false)
+        .bytes(&[5; 32])
+        .reference(config)
+        .build()
+}
+
+/// Masterchain block with the state carrying `config` and the proofs of the config.
+pub(crate) fn config_info(config: &Arc<ProofCell>) -> (TonNodeBlockIdExt, LiteServerConfigInfo) {
+    let state = build_shard_state(&empty(), Some(&config_mc_state_extra(config)));
+    let block = block(
+        &block_info(MASTERCHAIN, 20, false, 1100, 600, &zero_id()),
+        &state,
+        &empty(),
+    );
+    let id = block_id(MASTERCHAIN, 20, &block);
+
+    let response = LiteServerConfigInfo {
+        mode: 0,
+        id: id.clone(),
+        state_proof: serialize(&[block_state_proof(&block)]),
+        config_proof: serialize(&[state_proof(&state)]),
+    };
+
+    (id, response)
+}
+
+/// Link from a newer masterchain block to the non-key block `to`.
+pub(crate) fn block_link_back(
+    to: &ProofCell,
+    to_seqno: i32,
+    from_seqno: i32,
+) -> LiteServerBlockLinkBack {
+    let to_id = block_id(MASTERCHAIN, to_seqno, to);
+    let state = build_shard_state(&empty(), Some(&build_mc_state_extra(&empty(), &to_id)));
+    let from = block(
+        &block_info(MASTERCHAIN, from_seqno, false, 1100, 600, &zero_id()),
+        &state,
+        &empty(),
+    );
+
+    LiteServerBlockLinkBack {
+        to_key_block: BoxedBool::BoolFalse(BoolFalse {}),
+        from: block_id(MASTERCHAIN, from_seqno, &from),
+        to: to_id,
+        dest_proof: Vec::new(),
+        proof: serialize(&[block_state_proof(&from)]),
+        state_proof: serialize(&[state_proof(&state)]),
+    }
+}
+
+/// Validators signing the forward links, all of them have the same weight.
+pub(crate) fn validator_keys() -> Vec<Ed25519Key> {
+    (1..=3).map(|i| Ed25519Key::from_seed(&[i; 32])).collect()
+}
+
+/// Key block configured with the validators of `keys`.
+pub(crate) fn key_block(seqno: i32, keys: &[Ed25519Key]) -> Arc<ProofCell> {
+    let validators: Vec<_> = keys
+        .iter()
+        .map(|key| (key.public_key().to_bytes(), 10))
+        .collect();
+
+    block(
+        &block_info(MASTERCHAIN, seqno, true, 1000, 500, &zero_id()),
+        &empty(),
+        &key_block_extra(&config(&validator_set(&validators))),
+    )
+}
+
+pub(crate) fn signature(key: &Ed25519Key, id: &TonNodeBlockIdExt) -> LiteServerSignature {
+    let message = [
+        [0x70, 0x6e, 0x0b, 0xc5].as_slice(),
+        &id.root_hash,
+        &id.file_hash,
+    ]
+    .concat();
+
+    LiteServerSignature {
+        node_id_short: key.id().to_bytes(),
+        signature: key.sign(&message).to_vec(),
+    }
+}
+
+/// Link from the key block `from` to `to` signed by `keys`.
+pub(crate) fn block_link_forward(
+    from: &ProofCell,
+    from_seqno: i32,
+    to: &ProofCell,
+    to_seqno: i32,
+    to_key_block: bool,
+    keys: &[Ed25519Key],
+) -> LiteServerBlockLinkForward {
+    let to_id = block_id(MASTERCHAIN, to_seqno, to);
+
+    LiteServerBlockLinkForward {
+        to_key_block: if to_key_block {
+            BoxedBool::BoolTrue(BoolTrue {})
+        } else {
+            BoxedBool::BoolFalse(BoolFalse {})
+        },
+        from: block_id(MASTERCHAIN, from_seqno, from),
+        to: to_id.clone(),
+        dest_proof: serialize(&[merkle_proof(&prune_except(to, &[0]))]),
+        config_proof: serialize(&[merkle_proof(&prune_except(from, &[3]))]),
+        signatures: LiteServerSignatureSet {
+            validator_set_hash: 0,
+            catchain_seqno: 0,
+            signatures: keys.iter().map(|key| signature(key, &to_id)).collect(),
+        },
+    }
+}
@@ -0,0 +1,81 @@
+use crate::proof::Verifiable;
+use pin_project::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tower::{BoxError, Layer, Service};
+
+/// Rejects answers whose proofs don't match the requested block.
+#[derive(Debug, Default, Clone)]
+pub struct VerifyProofLayer;
+
+impl VerifyProofLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for VerifyProofLayer {
+    type Service = VerifyProof<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        VerifyProof::new(inner)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyProof<S> {
+    inner: S,
+}
+
+impl<S> VerifyProof<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S, R> Service<R> for VerifyProof<S>
+where
+    R: Verifiable + Clone,
+    S: Service<R, Response = R::Response>,
+    S::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = ResponseFuture<S::Future, R>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let response = self.inner.call(request.clone());
+
+        ResponseFuture { response, request }
+    }
+}
+
+#[pin_project]
+pub struct ResponseFuture<F, R> {
+    #[pin]
+    response: F,
+    request: R,
+}
+
+impl<F, R, E> Future for ResponseFuture<F, R>
+where
+    F: Future<Output = Result<R::Response, E>>,
+    R: Verifiable,
+    E: Into<BoxError>,
+{
+    type Output = Result<R::Response, BoxError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let response = ready!(this.response.poll(cx)).map_err(Into::into)?;
+        this.request.verify(&response)?;
+
+        Poll::Ready(Ok(response))
+    }
+}
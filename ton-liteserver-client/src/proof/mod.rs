@@ -0,0 +1,80 @@
+//! Verification of liteserver answers against the root hash of the requested block.
+//!
+//! Proofs are bags of cells with merkle proof roots, their virtual roots are checked
+//! against the trusted hash and only the proven part of the block or state is read.
+//! A forged or incomplete answer is rejected with a [`ProofError`].
+pub mod block_link;
+pub mod boc;
+pub mod cell;
+pub mod dict;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod layer;
+pub mod validator_set;
+pub mod verify;
+
+use crate::proof::cell::CellType;
+use crate::tl::TonNodeBlockIdExt;
+use thiserror::Error;
+
+pub use crate::proof::block_link::{verify_block_link, verify_partial_block_proof};
+pub use crate::proof::layer::{VerifyProof, VerifyProofLayer};
+pub use crate::proof::validator_set::ValidatorSet;
+pub use crate::proof::verify::{
+    config_params, verify_account_state, verify_all_shards_info, verify_block_header,
+    verify_config_info, verify_lookup_block, Verifiable,
+};
+
+#[derive(Debug, Error)]
+pub enum ProofError {
+    #[error("invalid bag of cells: {0}")]
+    InvalidBoc(&'static str),
+    #[error("invalid cell: {0}")]
+    InvalidCell(&'static str),
+    #[error("stored cell hash doesn't match the computed one")]
+    HashMismatch,
+    #[error("cell underflow")]
+    CellUnderflow,
+    #[error("pruned branch cannot be read")]
+    PrunedBranch,
+    #[error("unexpected {0:?} cell")]
+    UnexpectedCellType(CellType),
+    #[error("unexpected tag {actual:#x}, expected {expected:#x}")]
+    UnexpectedTag { expected: u64, actual: u64 },
+    #[error("cannot parse proven data: {0}")]
+    Tlb(String),
+    #[error("proof virtual hash doesn't match the expected root hash")]
+    RootHashMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+    #[error("block id mismatch, expected {expected:?}, got {actual:?}")]
+    BlockIdMismatch {
+        expected: Box<TonNodeBlockIdExt>,
+        actual: Box<TonNodeBlockIdExt>,
+    },
+    #[error("block header doesn't match {0:?}")]
+    HeaderMismatch(Box<TonNodeBlockIdExt>),
+    #[error("shard block {0:?} is not proven by the previous block")]
+    ShardBlockMismatch(Box<TonNodeBlockIdExt>),
+    #[error("masterchain block {0:?} is not proven by the client state")]
+    MasterchainBlockMismatch(Box<TonNodeBlockIdExt>),
+    #[error("account doesn't belong to the shard block")]
+    AccountOutsideShard,
+    #[error("account state doesn't match the proof")]
+    AccountStateMismatch,
+    #[error("shard hashes don't match the proof")]
+    ShardHashesMismatch,
+    #[error("block doesn't match the lookup criteria")]
+    LookupMismatch,
+    #[error("signature of unknown validator {0:?}")]
+    UnknownValidator([u8; 32]),
+    #[error("invalid signature of validator {0:?}")]
+    InvalidSignature([u8; 32]),
+    #[error("validators with weight {signed} of {total} signed the block")]
+    InsufficientSignatures { signed: u128, total: u128 },
+    #[error("invalid block link: {0}")]
+    BlockLinkMismatch(&'static str),
+    #[error("block proof doesn't reach the target block")]
+    IncompleteProof,
+}
@@ -0,0 +1,174 @@
+use crate::proof::cell::ProofCell;
+use crate::proof::dict::{hashmap_e_get, hashmap_get};
+use crate::proof::ProofError;
+use crate::tl::{LiteServerSignature, TonNodeBlockIdExt};
+use adnl_tcp::key::Ed25519KeyId;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use std::collections::{HashMap, HashSet};
+
+const CUR_VALIDATORS_PARAM: u32 = 34;
+const VALIDATORS_EXT_TAG: u64 = 0x12;
+const VALIDATOR_TAG: u64 = 0x53;
+const VALIDATOR_ADDR_TAG: u64 = 0x73;
+const ED25519_PUBKEY_TAG: u64 = 0x8e81278a;
+
+/// ```tl
+/// ton.blockId root_cell_hash:int256 file_hash:int256 = ton.BlockId;
+/// ```
+const TON_BLOCK_ID: [u8; 4] = [0x70, 0x6e, 0x0b, 0xc5];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validator {
+    pub public_key: [u8; 32],
+    pub weight: u64,
+}
+
+/// Validators signing masterchain blocks, the first `main` entries of the current set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSet {
+    pub utime_since: u32,
+    pub utime_until: u32,
+    pub validators: Vec<Validator>,
+}
+
+impl ValidatorSet {
+    /// `cur_validators` of the config dictionary `Hashmap 32 ^Cell`.
+    pub fn from_config(config: &ProofCell) -> Result<Self, ProofError> {
+        let mut param = hashmap_get(config, &CUR_VALIDATORS_PARAM.to_be_bytes(), 32)?
+            .ok_or(ProofError::InvalidCell("config has no validator set"))?;
+
+        Self::read(param.load_reference()?)
+    }
+
+    /// ```tlb
+    /// validators_ext#12 utime_since:uint32 utime_until:uint32 total:(## 16) main:(## 16)
+    ///     total_weight:uint64 list:(HashmapE 16 ValidatorDescr) = ValidatorSet;
+    /// validator#53 public_key:SigPubKey weight:uint64 = ValidatorDescr;
+    /// validator_addr#73 public_key:SigPubKey weight:uint64 adnl_addr:bits256 = ValidatorDescr;
+    /// ed25519_pubkey#8e81278a pubkey:bits256 = SigPubKey;
+    /// ```
+    pub fn read(cell: &ProofCell) -> Result<Self, ProofError> {
+        let mut reader = cell.reader()?;
+        reader.expect_tag(8, VALIDATORS_EXT_TAG)?;
+
+        let utime_since = reader.load_uint(32)? as u32;
+        let utime_until = reader.load_uint(32)? as u32;
+        let total = reader.load_uint(16)? as u16;
+        let main = reader.load_uint(16)? as u16;
+        if main == 0 || main > total {
+            return Err(ProofError::InvalidCell("invalid validator set size"));
+        }
+        // total_weight
+        reader.skip(64)?;
+
+        let validators = (0..main)
+            .map(|index| {
+                let mut descr = hashmap_e_get(&mut reader.clone(), &index.to_be_bytes(), 16)?
+                    .ok_or(ProofError::InvalidCell("validator set has no validator"))?;
+
+                let tag = descr.load_uint(8)?;
+                if tag != VALIDATOR_TAG && tag != VALIDATOR_ADDR_TAG {
+                    return Err(ProofError::UnexpectedTag {
+                        expected: VALIDATOR_TAG,
+                        actual: tag,
+                    });
+                }
+                descr.expect_tag(32, ED25519_PUBKEY_TAG)?;
+
+                Ok(Validator {
+                    public_key: descr.load_hash()?,
+                    weight: descr.load_uint(64)?,
+                })
+            })
+            .collect::<Result<_, ProofError>>()?;
+
+        Ok(Self {
+            utime_since,
+            utime_until,
+            validators,
+        })
+    }
+
+    /// Validators with more than 2/3 of the weight have to sign `ton.blockId` of the block,
+    /// signatures are keyed by the short id of the validator key.
+    pub fn check_signatures(
+        &self,
+        id: &TonNodeBlockIdExt,
+        signatures: &[LiteServerSignature],
+    ) -> Result<(), ProofError> {
+        let validators: HashMap<_, _> = self
+            .validators
+            .iter()
+            .map(|v| {
+                (
+                    Ed25519KeyId::from_public_key_bytes(&v.public_key).to_bytes(),
+                    v,
+                )
+            })
+            .collect();
+
+        let message = [TON_BLOCK_ID.as_slice(), &id.root_hash, &id.file_hash].concat();
+        let mut signed = HashSet::with_capacity(signatures.len());
+        let mut weight = 0;
+        for signature in signatures {
+            let validator = validators
+                .get(&signature.node_id_short)
+                .ok_or(ProofError::UnknownValidator(signature.node_id_short))?;
+            if !signed.insert(signature.node_id_short) {
+                return Err(ProofError::InvalidSignature(signature.node_id_short));
+            }
+
+            let key = VerifyingKey::from_bytes(&validator.public_key)
+                .map_err(|_| ProofError::InvalidSignature(signature.node_id_short))?;
+            Signature::from_slice(&signature.signature)
+                .and_then(|s| key.verify(&message, &s))
+                .map_err(|_| ProofError::InvalidSignature(signature.node_id_short))?;
+
+            weight += u128::from(validator.weight);
+        }
+
+        let total = self.validators.iter().map(|v| u128::from(v.weight)).sum();
+        if weight * 3 <= total * 2 {
+            return Err(ProofError::InsufficientSignatures {
+                signed: weight,
+                total,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::boc::deserialize_single;
+    use crate::proof::fixtures::CONFIG;
+    use toner::tlb::bits::de::unpack_bytes_fully;
+    use toner::ton::boc::BoC;
+
+    #[test]
+    fn validator_set_from_real_config() {
+        let boc: BoC = unpack_bytes_fully(CONFIG).unwrap();
+        let config = deserialize_single(CONFIG).unwrap();
+
+        let actual = ValidatorSet::from_config(&config).unwrap();
+
+        assert_eq!(config.hash(0), boc.single_root().unwrap().hash());
+        assert_eq!(actual.utime_since, 1678331656);
+        assert_eq!(actual.utime_until, 1678397192);
+        assert_eq!(actual.validators.len(), 100);
+        assert_eq!(
+            actual.validators[0],
+            Validator {
+                public_key: hex::decode(
+                    "03dc3bd601043d67ca971689fb170e433a99747f1cf122549cfd4e8f1c4dd4a1"
+                )
+                .unwrap()
+                .try_into()
+                .unwrap(),
+                weight: 5850531613571956,
+            }
+        );
+    }
+}
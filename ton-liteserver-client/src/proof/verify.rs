@@ -0,0 +1,932 @@
+use crate::proof::boc::{deserialize, deserialize_single};
+use crate::proof::cell::{CellReader, CellType, ProofCell};
+use crate::proof::dict::{bin_tree_get, hashmap_e_get, hashmap_get};
+use crate::proof::ProofError;
+use crate::request::Requestable;
+use crate::tl::{
+    LiteServerAccountState, LiteServerAllShardsInfo, LiteServerBlockHeader, LiteServerConfigInfo,
+    LiteServerGetAccountState, LiteServerGetAllShardsInfo, LiteServerGetBlockHeader,
+    LiteServerGetConfigAll, LiteServerGetConfigParams, LiteServerLookupBlockResult,
+    LiteServerLookupBlockWithProof, TonNodeBlockIdExt,
+};
+use crate::tlb::block_header::BlockHeader;
+use crate::tlb::ext_blk_ref::ExtBlkRef;
+use crate::tlb::shard_hashes::ShardHashes;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use toner::tlb::Cell;
+
+pub(crate) const MASTERCHAIN: i32 = -1;
+
+pub(crate) const BLOCK_TAG: u64 = 0x11ef55aa;
+pub(crate) const BLOCK_INFO_TAG: u64 = 0x9bc7a987;
+pub(crate) const BLOCK_EXTRA_TAG: u64 = 0x4a33f6fd;
+pub(crate) const MC_BLOCK_EXTRA_TAG: u64 = 0xcca5;
+pub(crate) const SHARD_STATE_TAG: u64 = 0x9023afe2;
+pub(crate) const MC_STATE_EXTRA_TAG: u64 = 0xcc26;
+/// Mode of config requests taking the config from the last key block.
+const CONFIG_FROM_KEY_BLOCK_MODE: i32 = 0x8000;
+
+/// Request whose answer carries proofs for the requested block.
+pub trait Verifiable: Requestable {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError>;
+}
+
+impl Verifiable for LiteServerGetBlockHeader {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_block_header(self, response).map(|_| ())
+    }
+}
+
+impl Verifiable for LiteServerGetAccountState {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_account_state(self, response)
+    }
+}
+
+impl Verifiable for LiteServerGetAllShardsInfo {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_all_shards_info(self, response).map(|_| ())
+    }
+}
+
+impl Verifiable for LiteServerLookupBlockWithProof {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_lookup_block(self, response)
+    }
+}
+
+impl Verifiable for LiteServerGetConfigAll {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_config_info(&self.id, response).map(|_| ())
+    }
+}
+
+impl Verifiable for LiteServerGetConfigParams {
+    fn verify(&self, response: &Self::Response) -> Result<(), ProofError> {
+        verify_config_info(&self.id, response).map(|_| ())
+    }
+}
+
+/// Header of the requested block, proven by its root hash.
+pub fn verify_block_header(
+    request: &LiteServerGetBlockHeader,
+    response: &LiteServerBlockHeader,
+) -> Result<BlockHeader, ProofError> {
+    check_block_id(&request.id, &response.id)?;
+
+    let proof = deserialize_single(&response.header_proof)?;
+    let block = virtual_root(&proof, &request.id.root_hash)?;
+
+    let cell = block.to_cell();
+    let header: BlockHeader = cell.parse_fully().map_err(tlb_error)?;
+
+    let shard = &header.info.shard;
+    if header.info.seq_no != request.id.seqno as u32
+        || shard.workchain_id != request.id.workchain
+        || shard_id(shard.shard_pfx_bits, shard.shard_prefix) != request.id.shard as u64
+    {
+        return Err(ProofError::HeaderMismatch(Box::new(request.id.clone())));
+    }
+
+    Ok(header)
+}
+
+/// The shard block is proven by the masterchain block when they differ, then the account
+/// state by the shard block. An empty state is only accepted for an absent account.
+pub fn verify_account_state(
+    request: &LiteServerGetAccountState,
+    response: &LiteServerAccountState,
+) -> Result<(), ProofError> {
+    check_block_id(&request.id, &response.id)?;
+
+    let shard_block = &response.shardblk;
+    let account_prefix = u64::from_be_bytes(request.account.id[..8].try_into().expect("8 bytes"));
+    if shard_block.workchain != request.account.workchain
+        || !shard_contains(shard_block.shard as u64, account_prefix)
+    {
+        return Err(ProofError::AccountOutsideShard);
+    }
+
+    if *shard_block != response.id {
+        let (block_proof, state_proof) = proof_pair(&response.shard_proof)?;
+        let state = state_root(&response.id, &block_proof, &state_proof)?;
+
+        let mut extra = mc_state_extra(state)?;
+        check_shard_top_block(&mut extra, shard_block)?;
+    }
+
+    let (block_proof, state_proof) = proof_pair(&response.proof)?;
+    let state = state_root(shard_block, &block_proof, &state_proof)?;
+    let (accounts, _) = shard_state(state)?;
+
+    // _ (HashmapAugE 256 ShardAccount DepthBalanceInfo) = ShardAccounts;
+    let mut accounts = accounts.reader()?;
+    let account_hash = match hashmap_e_get(&mut accounts, &request.account.id, 256)? {
+        None => None,
+        Some(mut leaf) => {
+            skip_depth_balance_info(&mut leaf)?;
+
+            // account_descr$_ account:^Account last_trans_hash:bits256 last_trans_lt:uint64
+            Some(leaf.load_reference()?.hash(0))
+        }
+    };
+
+    let state_hash = if response.state.is_empty() {
+        None
+    } else {
+        Some(deserialize_single(&response.state)?.hash(0))
+    };
+
+    if account_hash != state_hash {
+        return Err(ProofError::AccountStateMismatch);
+    }
+
+    Ok(())
+}
+
+/// Shard hashes of the masterchain state of the requested block.
+pub fn verify_all_shards_info(
+    request: &LiteServerGetAllShardsInfo,
+    response: &LiteServerAllShardsInfo,
+) -> Result<ShardHashes, ProofError> {
+    check_block_id(&request.id, &response.id)?;
+
+    let (block_proof, state_proof) = proof_pair(&response.proof)?;
+    let state = state_root(&response.id, &block_proof, &state_proof)?;
+    let mut expected = mc_state_extra(state)?;
+
+    let data = deserialize_single(&response.data)?;
+    let mut actual = data.reader()?;
+
+    let present = expected.load_bit()?;
+    if actual.load_bit()? != present {
+        return Err(ProofError::ShardHashesMismatch);
+    }
+    if present && expected.load_reference()?.hash(0) != actual.load_reference()?.hash(0) {
+        return Err(ProofError::ShardHashesMismatch);
+    }
+    if actual.remaining_bits() != 0 || data.references().len() != usize::from(present) {
+        return Err(ProofError::ShardHashesMismatch);
+    }
+
+    let cell = data.to_cell();
+
+    cell.parse_fully().map_err(tlb_error)
+}
+
+/// Config dictionary `Hashmap 32 ^Cell` of the masterchain state of the requested block,
+/// parameters which weren't requested are pruned.
+pub fn verify_config_info(
+    id: &TonNodeBlockIdExt,
+    response: &LiteServerConfigInfo,
+) -> Result<Arc<ProofCell>, ProofError> {
+    check_block_id(id, &response.id)?;
+    if response.mode & CONFIG_FROM_KEY_BLOCK_MODE != 0 {
+        return Err(ProofError::InvalidCell(
+            "key block config proofs are not supported",
+        ));
+    }
+
+    let block_proof = deserialize_single(&response.state_proof)?;
+    let state_proof = deserialize_single(&response.config_proof)?;
+    let state = state_root(&response.id, &block_proof, &state_proof)?;
+
+    // masterchain_state_extra#cc26 shard_hashes:ShardHashes config:ConfigParams
+    let mut extra = mc_state_extra(state)?;
+    if extra.load_bit()? {
+        extra.load_reference()?;
+    }
+    // _ config_addr:bits256 config:^(Hashmap 32 ^Cell) = ConfigParams;
+    extra.skip(256)?;
+
+    Ok(Arc::clone(extra.load_reference()?))
+}
+
+/// `ConfigParam` cells of `params` found in the config dictionary.
+pub fn config_params(
+    config: &ProofCell,
+    params: &[i32],
+) -> Result<BTreeMap<i32, Cell>, ProofError> {
+    params
+        .iter()
+        .filter_map(
+            |index| match hashmap_get(config, &index.to_be_bytes(), 32) {
+                Ok(None) => None,
+                Ok(Some(mut value)) => Some(
+                    value
+                        .load_reference()
+                        .map(|param| (*index, param.to_cell())),
+                ),
+                Err(e) => Some(Err(e)),
+            },
+        )
+        .collect()
+}
+
+/// The masterchain block of the answer is proven by the state of the client's masterchain
+/// block, the found block by the chain of shard links and the lookup criteria by its header
+/// and the header of its predecessor.
+pub fn verify_lookup_block(
+    request: &LiteServerLookupBlockWithProof,
+    response: &LiteServerLookupBlockResult,
+) -> Result<(), ProofError> {
+    if response.mc_block_id != request.mc_block_id {
+        let roots = deserialize(&response.client_mc_state_proof)?;
+        let (block_proof, state_proof) = match roots.as_slice() {
+            [block, state] => (Arc::clone(block), Arc::clone(state)),
+            [state] => (
+                deserialize_single(&response.mc_block_proof)?,
+                Arc::clone(state),
+            ),
+            _ => return Err(ProofError::InvalidBoc("unexpected number of roots")),
+        };
+        let state = state_root(&request.mc_block_id, &block_proof, &state_proof)?;
+
+        check_old_mc_block(state, &response.mc_block_id)?;
+    }
+
+    let mut current = &response.mc_block_id;
+    for link in &response.shard_links {
+        let proof = deserialize_single(&link.proof)?;
+        let block = virtual_root(&proof, &current.root_hash)?;
+
+        if current.workchain == MASTERCHAIN {
+            let mut shard_hashes = mc_block_shard_hashes(block)?;
+            check_shard_top_block(&mut shard_hashes, &link.id)?;
+        } else {
+            let prev_blocks = BlockInfo::read(block)?.prev_blocks()?;
+            if link.id.workchain != current.workchain
+                || !prev_blocks.iter().any(|prev| same_block(prev, &link.id))
+            {
+                return Err(ProofError::ShardBlockMismatch(Box::new(link.id.clone())));
+            }
+        }
+
+        current = &link.id;
+    }
+    check_block_id(current, &response.id)?;
+
+    let proof = deserialize_single(&response.header)?;
+    let info = BlockInfo::read(virtual_root(&proof, &response.id.root_hash)?)?;
+
+    let lt = request.lt.map(|lt| lt as u64);
+    let utime = request.utime.map(|utime| utime as u32);
+    if info.workchain != request.id.workchain
+        || info.workchain != response.id.workchain
+        || info.shard != response.id.shard as u64
+        || info.seq_no != response.id.seqno as u32
+        || !shard_contains(info.shard, request.id.shard as u64)
+        || (request.mode & 1 != 0 && info.seq_no != request.id.seqno as u32)
+        || lt.is_some_and(|lt| lt > info.end_lt)
+        || utime.is_some_and(|utime| utime > info.gen_utime)
+    {
+        return Err(ProofError::LookupMismatch);
+    }
+
+    if !response.prev_header.is_empty() {
+        let proof = deserialize_single(&response.prev_header)?;
+        let prev = info
+            .prev_blocks()?
+            .into_iter()
+            .find_map(|prev| virtual_root(&proof, &prev.root_hash).ok())
+            .ok_or(ProofError::LookupMismatch)?;
+        let prev = BlockInfo::read(prev)?;
+
+        if lt.is_some_and(|lt| prev.end_lt > lt)
+            || utime.is_some_and(|utime| prev.gen_utime > utime)
+        {
+            return Err(ProofError::LookupMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+/// Virtual root of a merkle proof, its original hash has to be `hash`.
+pub(crate) fn virtual_root<'a>(
+    proof: &'a Arc<ProofCell>,
+    hash: &[u8; 32],
+) -> Result<&'a Arc<ProofCell>, ProofError> {
+    if proof.cell_type() != CellType::MerkleProof {
+        return Err(ProofError::UnexpectedCellType(proof.cell_type()));
+    }
+
+    let root = proof.reference(0)?;
+    let actual = root.hash(0);
+    if actual != *hash {
+        return Err(ProofError::RootHashMismatch {
+            expected: *hash,
+            actual,
+        });
+    }
+
+    Ok(root)
+}
+
+/// Block proof and state proof serialized as two roots of a bag of cells.
+fn proof_pair(bytes: &[u8]) -> Result<(Arc<ProofCell>, Arc<ProofCell>), ProofError> {
+    let [block, state] = <[_; 2]>::try_from(deserialize(bytes)?)
+        .map_err(|_| ProofError::InvalidBoc("block and state proofs expected"))?;
+
+    Ok((block, state))
+}
+
+/// Virtual root of the state proof, proven by `state_update` of the block.
+pub(crate) fn state_root<'a>(
+    id: &TonNodeBlockIdExt,
+    block_proof: &Arc<ProofCell>,
+    state_proof: &'a Arc<ProofCell>,
+) -> Result<&'a Arc<ProofCell>, ProofError> {
+    let block = virtual_root(block_proof, &id.root_hash)?;
+    block.reader()?.expect_tag(32, BLOCK_TAG)?;
+
+    // info:^BlockInfo value_flow:^ValueFlow state_update:^(MERKLE_UPDATE ShardState)
+    let update = block.reference(2)?;
+    if update.cell_type() != CellType::MerkleUpdate {
+        return Err(ProofError::UnexpectedCellType(update.cell_type()));
+    }
+    let state_hash = update.reference(1)?.hash(0);
+
+    virtual_root(state_proof, &state_hash)
+}
+
+/// `accounts` and `custom` of a `ShardStateUnsplit`.
+fn shard_state(
+    state: &ProofCell,
+) -> Result<(&Arc<ProofCell>, Option<&Arc<ProofCell>>), ProofError> {
+    let mut reader = state.reader()?;
+    reader.expect_tag(32, SHARD_STATE_TAG)?;
+    // global_id shard_id seq_no vert_seq_no gen_utime gen_lt min_ref_mc_seqno before_split
+    reader.skip(32 + 104 + 32 + 32 + 32 + 64 + 32 + 1)?;
+
+    let _out_msg_queue_info = reader.load_reference()?;
+    let accounts = reader.load_reference()?;
+    let _ = reader.load_reference()?;
+    let custom = if reader.load_bit()? {
+        Some(reader.load_reference()?)
+    } else {
+        None
+    };
+
+    Ok((accounts, custom))
+}
+
+/// Reader of `McStateExtra` positioned at `shard_hashes`.
+fn mc_state_extra(state: &ProofCell) -> Result<CellReader<'_>, ProofError> {
+    let (_, custom) = shard_state(state)?;
+    let mut reader = custom
+        .ok_or(ProofError::InvalidCell("masterchain state without extra"))?
+        .reader()?;
+    reader.expect_tag(16, MC_STATE_EXTRA_TAG)?;
+
+    Ok(reader)
+}
+
+/// Reader of `McBlockExtra` of a masterchain block positioned at `key_block`.
+pub(crate) fn mc_block_extra(block: &ProofCell) -> Result<CellReader<'_>, ProofError> {
+    let mut reader = block.reader()?;
+    reader.expect_tag(32, BLOCK_TAG)?;
+
+    let mut extra = block.reference(3)?.reader()?;
+    extra.expect_tag(32, BLOCK_EXTRA_TAG)?;
+    // in_msg_descr out_msg_descr account_blocks rand_seed created_by
+    extra.skip_references(3)?;
+    extra.skip(256 + 256)?;
+    if !extra.load_bit()? {
+        return Err(ProofError::InvalidCell("masterchain block without extra"));
+    }
+
+    let mut reader = extra.load_reference()?.reader()?;
+    reader.expect_tag(16, MC_BLOCK_EXTRA_TAG)?;
+
+    Ok(reader)
+}
+
+/// Reader of `McBlockExtra` of a masterchain block positioned at `shard_hashes`.
+fn mc_block_shard_hashes(block: &ProofCell) -> Result<CellReader<'_>, ProofError> {
+    let mut reader = mc_block_extra(block)?;
+    // key_block
+    reader.skip(1)?;
+
+    Ok(reader)
+}
+
+/// `ShardHashes` has to describe `id` as the top block of its shard.
+fn check_shard_top_block(
+    shard_hashes: &mut CellReader,
+    id: &TonNodeBlockIdExt,
+) -> Result<(), ProofError> {
+    let mismatch = || ProofError::ShardBlockMismatch(Box::new(id.clone()));
+
+    let mut tree =
+        hashmap_e_get(shard_hashes, &id.workchain.to_be_bytes(), 32)?.ok_or_else(mismatch)?;
+    let mut descr = bin_tree_get(tree.load_reference()?, id.shard as u64)?.ok_or_else(mismatch)?;
+
+    let tag = descr.load_uint(4)?;
+    if tag != 0xa && tag != 0xb {
+        return Err(ProofError::UnexpectedTag {
+            expected: 0xb,
+            actual: tag,
+        });
+    }
+    let seq_no = descr.load_uint(32)?;
+    // reg_mc_seqno start_lt end_lt
+    descr.skip(32 + 64 + 64)?;
+    let root_hash = descr.load_hash()?;
+    let file_hash = descr.load_hash()?;
+
+    if seq_no != id.seqno as u32 as u64 || root_hash != id.root_hash || file_hash != id.file_hash {
+        return Err(mismatch());
+    }
+
+    Ok(())
+}
+
+/// `prev_blocks` of the masterchain state has to contain `id`, returns whether it's a key block.
+pub(crate) fn check_old_mc_block(
+    state: &ProofCell,
+    id: &TonNodeBlockIdExt,
+) -> Result<bool, ProofError> {
+    let mismatch = || ProofError::MasterchainBlockMismatch(Box::new(id.clone()));
+    if id.workchain != MASTERCHAIN {
+        return Err(mismatch());
+    }
+
+    let mut extra = mc_state_extra(state)?;
+    if extra.load_bit()? {
+        extra.skip_references(1)?;
+    }
+    // config:ConfigParams
+    extra.skip(256)?;
+    extra.skip_references(1)?;
+
+    // ^[ flags:(## 16) validator_info:ValidatorInfo prev_blocks:OldMcBlocksInfo ... ]
+    let mut reader = extra.load_reference()?.reader()?;
+    reader.skip(16 + 65)?;
+
+    // _ (HashmapAugE 32 KeyExtBlkRef KeyMaxLt) = OldMcBlocksInfo;
+    let mut leaf = hashmap_e_get(&mut reader, &id.seqno.to_be_bytes(), 32)?.ok_or_else(mismatch)?;
+    // key_max_lt extra, then key_ext_blk_ref: key:Bool end_lt
+    leaf.skip(65)?;
+    let key_block = leaf.load_bit()?;
+    leaf.skip(64)?;
+    let seq_no = leaf.load_uint(32)?;
+    let root_hash = leaf.load_hash()?;
+    let file_hash = leaf.load_hash()?;
+
+    if seq_no != id.seqno as u32 as u64 || root_hash != id.root_hash || file_hash != id.file_hash {
+        return Err(mismatch());
+    }
+
+    Ok(key_block)
+}
+
+/// ```tlb
+/// depth_balance$_ split_depth:(#<= 30) balance:CurrencyCollection = DepthBalanceInfo;
+/// ```
+fn skip_depth_balance_info(reader: &mut CellReader) -> Result<(), ProofError> {
+    reader.skip(5)?;
+
+    skip_currency_collection(reader)
+}
+
+/// ```tlb
+/// currencies$_ grams:Grams other:ExtraCurrencyCollection = CurrencyCollection;
+/// ```
+pub(crate) fn skip_currency_collection(reader: &mut CellReader) -> Result<(), ProofError> {
+    let len = reader.load_uint(4)? as usize;
+    reader.skip(len * 8)?;
+    if reader.load_bit()? {
+        reader.skip_references(1)?;
+    }
+
+    Ok(())
+}
+
+/// Fields of `BlockInfo` read directly, the header proofs of lookups prune its references.
+pub(crate) struct BlockInfo<'a> {
+    info: &'a ProofCell,
+    not_master: bool,
+    after_merge: bool,
+    pub(crate) key_block: bool,
+    pub(crate) seq_no: u32,
+    pub(crate) workchain: i32,
+    pub(crate) shard: u64,
+    pub(crate) gen_utime: u32,
+    pub(crate) end_lt: u64,
+    pub(crate) gen_validator_list_hash_short: u32,
+    pub(crate) gen_catchain_seqno: u32,
+}
+
+impl<'a> BlockInfo<'a> {
+    pub(crate) fn read(block: &'a ProofCell) -> Result<Self, ProofError> {
+        let mut reader = block.reader()?;
+        reader.expect_tag(32, BLOCK_TAG)?;
+
+        let info = block.reference(0)?;
+        let mut reader = info.reader()?;
+        reader.expect_tag(32, BLOCK_INFO_TAG)?;
+        // version
+        reader.skip(32)?;
+        let not_master = reader.load_bit()?;
+        let after_merge = reader.load_bit()?;
+        // before_split after_split want_split want_merge
+        reader.skip(4)?;
+        let key_block = reader.load_bit()?;
+        // vert_seqno_incr flags
+        reader.skip(1 + 8)?;
+        let seq_no = reader.load_uint(32)? as u32;
+        // vert_seq_no
+        reader.skip(32)?;
+        reader.expect_tag(2, 0)?;
+        let shard_pfx_bits = reader.load_uint(6)? as u8;
+        let workchain = reader.load_uint(32)? as u32 as i32;
+        let shard_prefix = reader.load_uint(64)?;
+        let gen_utime = reader.load_uint(32)? as u32;
+        // start_lt
+        reader.skip(64)?;
+        let end_lt = reader.load_uint(64)?;
+        let gen_validator_list_hash_short = reader.load_uint(32)? as u32;
+        let gen_catchain_seqno = reader.load_uint(32)? as u32;
+
+        Ok(Self {
+            info,
+            not_master,
+            after_merge,
+            key_block,
+            seq_no,
+            workchain,
+            shard: shard_id(shard_pfx_bits, shard_prefix),
+            gen_utime,
+            end_lt,
+            gen_validator_list_hash_short,
+            gen_catchain_seqno,
+        })
+    }
+
+    /// `prev_ref:^(BlkPrevInfo after_merge)`, following `master_ref` of shard blocks.
+    pub(crate) fn prev_blocks(&self) -> Result<Vec<ExtBlkRef>, ProofError> {
+        let prev_ref = self.info.reference(usize::from(self.not_master))?;
+
+        if self.after_merge {
+            (0..2)
+                .map(|i| read_ext_blk_ref(&mut prev_ref.reference(i)?.reader()?))
+                .collect()
+        } else {
+            Ok(vec![read_ext_blk_ref(&mut prev_ref.reader()?)?])
+        }
+    }
+}
+
+fn read_ext_blk_ref(reader: &mut CellReader) -> Result<ExtBlkRef, ProofError> {
+    Ok(ExtBlkRef {
+        end_lt: reader.load_uint(64)?,
+        seq_no: reader.load_uint(32)? as u32,
+        root_hash: reader.load_hash()?,
+        file_hash: reader.load_hash()?,
+    })
+}
+
+fn same_block(prev: &ExtBlkRef, id: &TonNodeBlockIdExt) -> bool {
+    prev.seq_no == id.seqno as u32
+        && prev.root_hash == id.root_hash
+        && prev.file_hash == id.file_hash
+}
+
+pub(crate) fn check_block_id(
+    expected: &TonNodeBlockIdExt,
+    actual: &TonNodeBlockIdExt,
+) -> Result<(), ProofError> {
+    if expected != actual {
+        return Err(ProofError::BlockIdMismatch {
+            expected: Box::new(expected.clone()),
+            actual: Box::new(actual.clone()),
+        });
+    }
+
+    Ok(())
+}
+
+/// Shard id with the tag bit set after `pfx_bits` bits of the prefix.
+fn shard_id(pfx_bits: u8, prefix: u64) -> u64 {
+    let tag = 1u64 << (63 - pfx_bits.min(63));
+
+    prefix & !(tag - 1) | tag
+}
+
+/// Whether `child`, a shard or an account prefix, is inside the shard `parent`.
+fn shard_contains(parent: u64, child: u64) -> bool {
+    let tag = parent & parent.wrapping_neg();
+
+    (parent ^ child) & (tag.wrapping_neg() << 1) == 0
+}
+
+fn tlb_error(error: impl Display) -> ProofError {
+    ProofError::Tlb(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::boc::serialize;
+    use crate::proof::fixtures::*;
+    use crate::proof::VerifyProofLayer;
+    use crate::tl::{LiteServerShardBlockLink, TonNodeBlockId};
+    use tower::{service_fn, BoxError, Layer, ServiceExt};
+
+    fn given_block_header() -> (LiteServerGetBlockHeader, LiteServerBlockHeader) {
+        let block = block(
+            &block_info(MASTERCHAIN, 7, false, 1000, 500, &zero_id()),
+            &empty(),
+            &empty(),
+        );
+        let id = block_id(MASTERCHAIN, 7, &block);
+        let proof = merkle_proof(&prune_except(&block, &[0]));
+
+        let request = LiteServerGetBlockHeader {
+            id: id.clone(),
+            mode: 0,
+        };
+        let response = LiteServerBlockHeader {
+            id,
+            mode: 0,
+            header_proof: serialize(&[proof]),
+        };
+
+        (request, response)
+    }
+
+    #[test]
+    fn verify_block_header_accepts_proof() {
+        let (request, response) = given_block_header();
+
+        let header = verify_block_header(&request, &response).unwrap();
+
+        assert_eq!(header.info.seq_no, 7);
+        assert_eq!(header.info.end_lt, 500);
+    }
+
+    #[test]
+    fn verify_block_header_rejects_forged_proof() {
+        let (mut request, mut response) = given_block_header();
+        request.id.root_hash = [1; 32];
+        response.id.root_hash = [1; 32];
+
+        let error = verify_block_header(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::RootHashMismatch { .. }));
+    }
+
+    #[test]
+    fn verify_block_header_rejects_header_of_other_block() {
+        let (mut request, mut response) = given_block_header();
+        request.id.seqno = 8;
+        response.id.seqno = 8;
+
+        let error = verify_block_header(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::HeaderMismatch(_)));
+    }
+
+    #[test]
+    fn verify_block_header_rejects_other_block_id() {
+        let (request, mut response) = given_block_header();
+        response.id.seqno = 8;
+
+        let error = verify_block_header(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::BlockIdMismatch { .. }));
+    }
+
+    #[test]
+    fn verify_account_state_accepts_proof() {
+        let (request, response) = given_account_state();
+
+        verify_account_state(&request, &response).unwrap();
+    }
+
+    #[test]
+    fn verify_account_state_accepts_absent_account() {
+        let (mut request, mut response) = given_account_state();
+        request.account.id = [0x56; 32];
+        response.state = Vec::new();
+
+        verify_account_state(&request, &response).unwrap();
+    }
+
+    #[test]
+    fn verify_account_state_rejects_forged_state() {
+        let (request, mut response) = given_account_state();
+        response.state = serialize(&[CellBuilder::default().uint(64, 43).build()]);
+
+        let error = verify_account_state(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::AccountStateMismatch));
+    }
+
+    #[test]
+    fn verify_account_state_rejects_unproven_shard_block() {
+        let (request, mut response) = given_account_state();
+        response.shardblk.file_hash = [1; 32];
+
+        let error = verify_account_state(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::ShardBlockMismatch(_)));
+    }
+
+    fn given_account_state() -> (LiteServerGetAccountState, LiteServerAccountState) {
+        account_state([0x55; 32], &CellBuilder::default().uint(64, 42).build())
+    }
+
+    fn given_all_shards_info() -> (LiteServerGetAllShardsInfo, LiteServerAllShardsInfo) {
+        let data = deserialize_single(&hex::decode(SHARD_HASHES).unwrap()).unwrap();
+        let shard_hashes = data.reference(0).unwrap();
+
+        let state = build_shard_state(
+            &empty(),
+            Some(&build_mc_state_extra(shard_hashes, &zero_id())),
+        );
+        let block = block(
+            &block_info(MASTERCHAIN, 7, false, 1000, 500, &zero_id()),
+            &state,
+            &empty(),
+        );
+        let id = block_id(MASTERCHAIN, 7, &block);
+
+        let request = LiteServerGetAllShardsInfo { id: id.clone() };
+        let response = LiteServerAllShardsInfo {
+            id,
+            proof: serialize(&[block_state_proof(&block), state_proof(&state)]),
+            data: serialize(&[data]),
+        };
+
+        (request, response)
+    }
+
+    #[test]
+    fn verify_all_shards_info_accepts_proof() {
+        let (request, response) = given_all_shards_info();
+
+        let shard_hashes = verify_all_shards_info(&request, &response).unwrap();
+
+        assert_eq!(shard_hashes.get(&0).map(Vec::len), Some(4));
+    }
+
+    #[test]
+    fn verify_all_shards_info_rejects_forged_data() {
+        let (request, mut response) = given_all_shards_info();
+        let forged = shard_hashes(&zero_id());
+        response.data = serialize(&[CellBuilder::default().bit(true).reference(&forged).build()]);
+
+        let error = verify_all_shards_info(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::ShardHashesMismatch));
+    }
+
+    fn given_lookup_block(
+        lt: i64,
+    ) -> (LiteServerLookupBlockWithProof, LiteServerLookupBlockResult) {
+        let prev_block = block(
+            &block_info(0, 2, false, 990, 390, &zero_id()),
+            &empty(),
+            &empty(),
+        );
+        let prev_id = block_id(0, 2, &prev_block);
+        let shard_block = block(
+            &block_info(0, 3, false, 1000, 400, &prev_id),
+            &empty(),
+            &empty(),
+        );
+        let shard_id = block_id(0, 3, &shard_block);
+
+        let mc_block = block(
+            &block_info(MASTERCHAIN, 7, false, 1000, 500, &zero_id()),
+            &empty(),
+            &build_mc_block_extra(&shard_hashes(&shard_id)),
+        );
+        let mc_id = block_id(MASTERCHAIN, 7, &mc_block);
+
+        let client_state =
+            build_shard_state(&empty(), Some(&build_mc_state_extra(&empty(), &mc_id)));
+        let client_block = block(
+            &block_info(MASTERCHAIN, 9, false, 1100, 600, &zero_id()),
+            &client_state,
+            &empty(),
+        );
+        let client_id = block_id(MASTERCHAIN, 9, &client_block);
+
+        let request = LiteServerLookupBlockWithProof {
+            mode: 2,
+            id: TonNodeBlockId {
+                workchain: 0,
+                shard: FULL_SHARD as i64,
+                seqno: 0,
+            },
+            mc_block_id: client_id,
+            lt: Some(lt),
+            utime: None,
+        };
+        let response = LiteServerLookupBlockResult {
+            id: shard_id.clone(),
+            mode: 2,
+            mc_block_id: mc_id,
+            client_mc_state_proof: serialize(&[
+                block_state_proof(&client_block),
+                state_proof(&client_state),
+            ]),
+            mc_block_proof: Vec::new(),
+            shard_links: vec![LiteServerShardBlockLink {
+                id: shard_id,
+                proof: serialize(&[merkle_proof(&prune_except(&mc_block, &[3]))]),
+            }],
+            header: serialize(&[merkle_proof(&prune_except(&shard_block, &[0]))]),
+            prev_header: serialize(&[merkle_proof(&prune_except(&prev_block, &[0]))]),
+        };
+
+        (request, response)
+    }
+
+    #[test]
+    fn verify_lookup_block_accepts_proof() {
+        let (request, response) = given_lookup_block(395);
+
+        verify_lookup_block(&request, &response).unwrap();
+    }
+
+    #[test]
+    fn verify_lookup_block_rejects_block_out_of_range() {
+        for lt in [385, 405] {
+            let (request, response) = given_lookup_block(lt);
+
+            let error = verify_lookup_block(&request, &response).unwrap_err();
+
+            assert!(matches!(error, ProofError::LookupMismatch));
+        }
+    }
+
+    #[test]
+    fn verify_lookup_block_rejects_unproven_masterchain_block() {
+        let (request, mut response) = given_lookup_block(395);
+        response.mc_block_id.file_hash = [1; 32];
+
+        let error = verify_lookup_block(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::MasterchainBlockMismatch(_)));
+    }
+
+    #[test]
+    fn verify_lookup_block_rejects_unproven_shard_block() {
+        let (request, mut response) = given_lookup_block(395);
+        response.shard_links[0].id.seqno = 4;
+        response.id.seqno = 4;
+
+        let error = verify_lookup_block(&request, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::ShardBlockMismatch(_)));
+    }
+
+    #[test]
+    fn verify_config_info_accepts_proof() {
+        let (id, response) = config_info(&blockchain_config());
+
+        let config = verify_config_info(&id, &response).unwrap();
+        let params = config_params(&config, &[21, 32]).unwrap();
+
+        assert_eq!(params.keys().collect::<Vec<_>>(), [&21]);
+    }
+
+    #[test]
+    fn verify_config_info_rejects_forged_config() {
+        let (id, mut response) = config_info(&blockchain_config());
+        let (_, forged) = config_info(&config(&validator_set(&[([1; 32], 10)])));
+        response.config_proof = forged.config_proof;
+
+        let error = verify_config_info(&id, &response).unwrap_err();
+
+        assert!(matches!(error, ProofError::RootHashMismatch { .. }));
+    }
+
+    #[tokio::test]
+    async fn layer_rejects_forged_answer() {
+        let (request, mut response) = given_block_header();
+        response.header_proof = serialize(&[merkle_proof(&empty())]);
+        let svc = service_fn(move |_: LiteServerGetBlockHeader| {
+            let response = response.clone();
+
+            async move { Ok::<_, BoxError>(response) }
+        });
+
+        let error = VerifyProofLayer::new()
+            .layer(svc)
+            .oneshot(request)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ProofError>(),
+            Some(ProofError::RootHashMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::client::{Error, LiteServerClient, ResponseFuture};
+use crate::make::MakeClient;
+use crate::request::Requestable;
+use futures::future::BoxFuture;
+use std::task::{ready, Context, Poll};
+use tower::Service;
+
+enum State {
+    Idle,
+    Connecting(BoxFuture<'static, anyhow::Result<LiteServerClient>>),
+    Connected(LiteServerClient),
+}
+
+/// [`LiteServerClient`] which connects again with the backoff of [`MakeClient`] once
+/// the connection fails, it isn't ready until then.
+pub struct ReconnectingClient {
+    make_client: MakeClient,
+    state: State,
+}
+
+impl ReconnectingClient {
+    pub fn new(make_client: MakeClient) -> Self {
+        Self {
+            make_client,
+            state: State::Idle,
+        }
+    }
+}
+
+impl<R> Service<R> for ReconnectingClient
+where
+    R: Requestable,
+{
+    type Response = R::Response;
+    type Error = Error;
+    type Future = ResponseFuture<R::Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    // never fails, waits for the backoff
+                    let _ = ready!(self.make_client.poll_ready(cx));
+
+                    self.state = State::Connecting(self.make_client.call(()));
+                }
+                State::Connecting(future) => match ready!(future.as_mut().poll(cx)) {
+                    Ok(client) => {
+                        tracing::debug!("connected");
+
+                        self.state = State::Connected(client);
+                    }
+                    Err(error) => {
+                        tracing::warn!(?error, "connection failed");
+
+                        self.state = State::Idle;
+                    }
+                },
+                State::Connected(client) => match ready!(Service::<R>::poll_ready(client, cx)) {
+                    Ok(()) => return Poll::Ready(Ok(())),
+                    Err(error) => {
+                        tracing::warn!(?error, "connection lost, reconnecting");
+
+                        self.state = State::Idle;
+                    }
+                },
+            }
+        }
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        match &mut self.state {
+            State::Connected(client) => client.call(req),
+            _ => ResponseFuture::failed(Error::Connection("not connected".to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make::MakeClient;
+    use crate::mock::tests::MASTERCHAIN_INFO_FIXTURES;
+    use crate::mock::{Fixtures, MockLiteServer};
+    use crate::tl::LiteServerGetMasterchainInfo;
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn reconnecting_client_reconnects() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?);
+        let handle = server.clone().spawn().await?;
+        let addr = handle.addr();
+        let mut client = ReconnectingClient::new(MakeClient::new(addr, handle.server_key()));
+        (&mut client)
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?;
+
+        drop(handle);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let _handle = server.spawn_on(addr).await?;
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            (&mut client).oneshot(LiteServerGetMasterchainInfo::default()),
+        )
+        .await??;
+
+        assert_eq!(response.last.seqno, 22560807);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_retry::strategy::{jitter, FibonacciBackoff};
+use ton_client_util::router::route::Error as RouterError;
+use tower::retry::budget::Budget;
+use tower::retry::Policy;
+
+/// Requests which are safe to send once again, e.g. `liteServer.sendMessage` is not.
+pub trait Retryable: Clone {}
+
+#[derive(Clone)]
+pub struct RetryPolicy {
+    budget: Arc<Budget>,
+    backoff: FibonacciBackoff,
+}
+
+impl RetryPolicy {
+    pub fn new(budget: Budget, first_delay_millis: u64, max_delay: Duration) -> Self {
+        let backoff = FibonacciBackoff::from_millis(first_delay_millis).max_delay(max_delay);
+
+        Self {
+            budget: Arc::new(budget),
+            backoff,
+        }
+    }
+}
+
+impl<T: Retryable, Res> Policy<T, Res, tower::BoxError> for RetryPolicy {
+    type Future = BoxFuture<'static, Self>;
+
+    fn retry(&self, _: &T, result: Result<&Res, &tower::BoxError>) -> Option<Self::Future> {
+        match result {
+            Ok(_) => {
+                self.budget.deposit();
+
+                None
+            }
+            Err(e) => {
+                if matches!(e.downcast_ref(), Some(RouterError::RouteUnknown)) {
+                    return None;
+                }
+
+                match self.budget.withdraw() {
+                    Ok(_) => {
+                        tracing::debug!(error = ?e, "retry {}", std::any::type_name::<T>());
+
+                        let mut policy = self.clone();
+
+                        Some(
+                            async move {
+                                let delay = policy.backoff.by_ref().map(jitter).next().unwrap();
+                                tokio::time::sleep(delay).await;
+
+                                policy
+                            }
+                            .boxed(),
+                        )
+                    }
+                    Err(_) => {
+                        tracing::warn!("retry budget is exhausted");
+
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn clone_request(&self, req: &T) -> Option<T> {
+        Some(req.clone())
+    }
+}
@@ -1,6 +1,11 @@
 #![allow(dead_code)]
 #![allow(unused_mut)]
 
+use crate::proof::boc::{deserialize, deserialize_single};
+use crate::proof::cell::ProofCell;
+use crate::retry::Retryable;
+use crate::tlb::block::Block;
+use crate::tlb::transaction::Transaction;
 use adnl_tcp::deserializer::{Deserialize, DeserializeBoxed, Deserializer, DeserializerBoxedError};
 use adnl_tcp::serializer::{Serialize, SerializeBoxed, Serializer};
 pub use adnl_tcp::types::*;
@@ -57,6 +62,51 @@ impl LiteServerGetAllShardsInfo {
     }
 }
 
+impl BoxedBool {
+    pub fn is_true(&self) -> bool {
+        matches!(self, Self::BoolTrue(_))
+    }
+}
+
+impl LiteServerTransactionList {
+    /// Transactions of the bag of cells, one root per transaction.
+    pub fn parse_transactions(&self) -> anyhow::Result<Vec<Transaction>> {
+        deserialize(&self.transactions)?
+            .iter()
+            .map(|root| parse_transaction(root))
+            .collect()
+    }
+}
+
+impl LiteServerTransactionInfo {
+    pub fn parse_transaction(&self) -> anyhow::Result<Transaction> {
+        parse_transaction(&*deserialize_single(&self.transaction)?)
+    }
+}
+
+impl LiteServerBlockData {
+    /// Full block, the root hash is checked against the block id.
+    pub fn parse_block(&self) -> anyhow::Result<Block> {
+        let root = deserialize_single(&self.data)?;
+        if root.hash(0) != self.id.root_hash {
+            anyhow::bail!(
+                "block data doesn't match root hash of block {}",
+                self.id.seqno
+            );
+        }
+
+        root.to_cell()
+            .parse_fully()
+            .map_err(|e| anyhow::anyhow!("invalid block: {e}"))
+    }
+}
+
+fn parse_transaction(root: &ProofCell) -> anyhow::Result<Transaction> {
+    root.to_cell()
+        .parse_fully()
+        .map_err(|e| anyhow::anyhow!("invalid transaction: {e}"))
+}
+
 /// ```tl
 /// liteServer.getMasterchainInfo = liteServer.MasterchainInfo;
 /// ```
@@ -67,6 +117,7 @@ impl ToRoute for LiteServerGetMasterchainInfo {
 }
 
 impl ToTimeout for LiteServerGetMasterchainInfo {}
+impl Retryable for LiteServerGetMasterchainInfo {}
 
 /// ```tl
 /// liteServer.getMasterchainInfoExt mode:# = liteServer.MasterchainInfoExt;
@@ -78,6 +129,7 @@ impl ToRoute for LiteServerGetMasterchainInfoExt {
 }
 
 impl ToTimeout for LiteServerGetMasterchainInfoExt {}
+impl Retryable for LiteServerGetMasterchainInfoExt {}
 
 /// ```tl
 /// liteServer.getBlock id:tonNode.blockIdExt = liteServer.BlockData;
@@ -95,6 +147,7 @@ impl ToRoute for LiteServerGetBlock {
 }
 
 impl ToTimeout for LiteServerGetBlock {}
+impl Retryable for LiteServerGetBlock {}
 
 /// ```tl
 /// liteServer.getState id:tonNode.blockIdExt = liteServer.BlockState;
@@ -112,6 +165,7 @@ impl ToRoute for LiteServerGetState {
 }
 
 impl ToTimeout for LiteServerGetState {}
+impl Retryable for LiteServerGetState {}
 
 /// ```tl
 /// liteServer.getBlockHeader id:tonNode.blockIdExt mode:# = liteServer.BlockHeader;
@@ -129,6 +183,7 @@ impl ToRoute for LiteServerGetBlockHeader {
 }
 
 impl ToTimeout for LiteServerGetBlockHeader {}
+impl Retryable for LiteServerGetBlockHeader {}
 
 /// ```tl
 /// liteServer.sendMessage body:bytes = liteServer.SendMsgStatus;
@@ -157,6 +212,7 @@ impl ToRoute for LiteServerGetAccountState {
 }
 
 impl ToTimeout for LiteServerGetAccountState {}
+impl Retryable for LiteServerGetAccountState {}
 
 /// ```tl
 /// liteServer.getAccountStatePrunned id:tonNode.blockIdExt account:liteServer.accountId = liteServer.AccountState;
@@ -174,6 +230,7 @@ impl ToRoute for LiteServerGetAccountStatePrunned {
 }
 
 impl ToTimeout for LiteServerGetAccountStatePrunned {}
+impl Retryable for LiteServerGetAccountStatePrunned {}
 
 /// ```tl
 /// liteServer.runSmcMethod mode:# id:tonNode.blockIdExt account:liteServer.accountId method_id:long params:bytes = liteServer.RunMethodResult;
@@ -191,6 +248,7 @@ impl ToRoute for LiteServerRunSmcMethod {
 }
 
 impl ToTimeout for LiteServerRunSmcMethod {}
+impl Retryable for LiteServerRunSmcMethod {}
 
 /// ```tl
 /// liteServer.getShardInfo id:tonNode.blockIdExt workchain:int shard:long exact:Bool = liteServer.ShardInfo;
@@ -208,6 +266,7 @@ impl ToRoute for LiteServerGetShardInfo {
 }
 
 impl ToTimeout for LiteServerGetShardInfo {}
+impl Retryable for LiteServerGetShardInfo {}
 
 /// ```tl
 /// liteServer.getAllShardsInfo id:tonNode.blockIdExt = liteServer.AllShardsInfo;
@@ -225,6 +284,7 @@ impl ToRoute for LiteServerGetAllShardsInfo {
 }
 
 impl ToTimeout for LiteServerGetAllShardsInfo {}
+impl Retryable for LiteServerGetAllShardsInfo {}
 
 /// ```tl
 /// liteServer.getOneTransaction id:tonNode.blockIdExt account:liteServer.accountId lt:long = liteServer.TransactionInfo;
@@ -242,6 +302,7 @@ impl ToRoute for LiteServerGetOneTransaction {
 }
 
 impl ToTimeout for LiteServerGetOneTransaction {}
+impl Retryable for LiteServerGetOneTransaction {}
 
 /// ```tl
 /// liteServer.getTransactions count:# account:liteServer.accountId lt:long hash:int256 = liteServer.TransactionList;
@@ -259,6 +320,7 @@ impl ToRoute for LiteServerGetTransactions {
 }
 
 impl ToTimeout for LiteServerGetTransactions {}
+impl Retryable for LiteServerGetTransactions {}
 
 /// ```tl
 /// liteServer.lookupBlock mode:# id:tonNode.blockId lt:mode.1?long utime:mode.2?int = liteServer.BlockHeader;
@@ -289,6 +351,7 @@ impl ToRoute for LiteServerLookupBlock {
 }
 
 impl ToTimeout for LiteServerLookupBlock {}
+impl Retryable for LiteServerLookupBlock {}
 
 /// ```tl
 /// liteServer.lookupBlockWithProof mode:# id:tonNode.blockId mc_block_id:tonNode.blockIdExt lt:mode.1?long utime:mode.2?int = liteServer.LookupBlockResult;
@@ -319,6 +382,7 @@ impl ToRoute for LiteServerLookupBlockWithProof {
 }
 
 impl ToTimeout for LiteServerLookupBlockWithProof {}
+impl Retryable for LiteServerLookupBlockWithProof {}
 
 /// ```tl
 /// liteServer.listBlockTransactions id:tonNode.blockIdExt mode:# count:# after:mode.7?liteServer.transactionId3 reverse_order:mode.6?true want_proof:mode.5?true = liteServer.BlockTransactions;
@@ -336,6 +400,7 @@ impl ToRoute for LiteServerListBlockTransactions {
 }
 
 impl ToTimeout for LiteServerListBlockTransactions {}
+impl Retryable for LiteServerListBlockTransactions {}
 
 /// ```tl
 /// liteServer.listBlockTransactionsExt id:tonNode.blockIdExt mode:# count:# after:mode.7?liteServer.transactionId3 reverse_order:mode.6?true want_proof:mode.5?true = liteServer.BlockTransactionsExt;
@@ -353,6 +418,7 @@ impl ToRoute for LiteServerListBlockTransactionsExt {
 }
 
 impl ToTimeout for LiteServerListBlockTransactionsExt {}
+impl Retryable for LiteServerListBlockTransactionsExt {}
 
 /// ```tl
 /// liteServer.getBlockProof mode:# known_block:tonNode.blockIdExt target_block:mode.0?tonNode.blockIdExt = liteServer.PartialBlockProof;
@@ -371,6 +437,7 @@ impl ToRoute for LiteServerGetBlockProof {
 }
 
 impl ToTimeout for LiteServerGetBlockProof {}
+impl Retryable for LiteServerGetBlockProof {}
 
 /// ```tl
 /// liteServer.getConfigAll mode:# id:tonNode.blockIdExt = liteServer.ConfigInfo;
@@ -388,6 +455,7 @@ impl ToRoute for LiteServerGetConfigAll {
 }
 
 impl ToTimeout for LiteServerGetConfigAll {}
+impl Retryable for LiteServerGetConfigAll {}
 
 /// ```tl
 /// liteServer.getConfigParams mode:# id:tonNode.blockIdExt param_list:(vector int) = liteServer.ConfigInfo;
@@ -405,6 +473,7 @@ impl ToRoute for LiteServerGetConfigParams {
 }
 
 impl ToTimeout for LiteServerGetConfigParams {}
+impl Retryable for LiteServerGetConfigParams {}
 
 /// ```tl
 /// liteServer.getValidatorStats#091a58bc mode:# id:tonNode.blockIdExt limit:int start_after:mode.0?int256 modified_after:mode.2?int = liteServer.ValidatorStats;
@@ -422,6 +491,7 @@ impl ToRoute for LiteServerGetValidatorStats {
 }
 
 impl ToTimeout for LiteServerGetValidatorStats {}
+impl Retryable for LiteServerGetValidatorStats {}
 
 /// ```tl
 /// liteServer.getLibraries library_list:(vector int256) = liteServer.LibraryResult;
@@ -434,6 +504,7 @@ impl ToRoute for LiteServerGetLibraries {
 }
 
 impl ToTimeout for LiteServerGetLibraries {}
+impl Retryable for LiteServerGetLibraries {}
 
 /// ```tl
 /// liteServer.getLibrariesWithProof id:tonNode.blockIdExt mode:# library_list:(vector int256) = liteServer.LibraryResultWithProof;
@@ -451,6 +522,7 @@ impl ToRoute for LiteServerGetLibrariesWithProof {
 }
 
 impl ToTimeout for LiteServerGetLibrariesWithProof {}
+impl Retryable for LiteServerGetLibrariesWithProof {}
 
 /// ```tl
 /// liteServer.getShardBlockProof id:tonNode.blockIdExt = liteServer.ShardBlockProof;
@@ -468,6 +540,7 @@ impl ToRoute for LiteServerGetShardBlockProof {
 }
 
 impl ToTimeout for LiteServerGetShardBlockProof {}
+impl Retryable for LiteServerGetShardBlockProof {}
 
 #[cfg(test)]
 mod tests {
@@ -475,6 +548,38 @@ mod tests {
     use adnl_tcp::deserializer::from_bytes_boxed;
     use adnl_tcp::serializer::to_bytes_boxed;
     use base64::Engine;
+    use std::task::{Context, Poll};
+    use toner::tlb::bits::ser::pack_with;
+    use toner::ton::boc::{BagOfCellsArgs, BoC};
+
+    #[test]
+    fn block_data_parse_block() {
+        let root = crate::tlb::block::tests::given_block();
+        let data = pack_with(
+            BoC::from_root(root),
+            BagOfCellsArgs {
+                has_idx: false,
+                has_crc32c: true,
+            },
+        )
+        .unwrap()
+        .into_vec();
+        let mut block = LiteServerBlockData {
+            id: TonNodeBlockIdExt {
+                workchain: 0,
+                shard: i64::MIN,
+                seqno: 20,
+                root_hash: deserialize_single(&data).unwrap().hash(0),
+                file_hash: [0; 32],
+            },
+            data,
+        };
+
+        assert_eq!(block.parse_block().unwrap().transactions().count(), 2);
+
+        block.id.root_hash = [0; 32];
+        assert!(block.parse_block().is_err());
+    }
 
     #[test]
     fn serialize_adnl_query_test() {
@@ -598,4 +703,44 @@ mod tests {
             }
         )
     }
+
+    /// Records the last request and fails it.
+    #[derive(Default)]
+    struct Recorder(Vec<u8>);
+
+    impl<R: crate::request::Requestable> tower::Service<R> for Recorder {
+        type Response = R::Response;
+        type Error = &'static str;
+        type Future = std::future::Ready<Result<R::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: R) -> Self::Future {
+            self.0 = to_bytes_boxed(&req);
+
+            std::future::ready(Err("recorded"))
+        }
+    }
+
+    #[tokio::test]
+    async fn service_trait_sets_mode_bits() {
+        let mut svc = Recorder::default();
+        let id = TonNodeBlockIdExt {
+            workchain: -1,
+            shard: i64::MIN,
+            seqno: 1,
+            root_hash: [0; 32],
+            file_hash: [0; 32],
+        };
+
+        let _ = svc
+            .list_block_transactions(id, 7 | 1 << 7, 16, None, false, true)
+            .await;
+        let request = from_bytes_boxed::<LiteServerListBlockTransactions>(&svc.0).unwrap();
+
+        assert_eq!(request.mode, 7 | 1 << 5);
+        assert!(request.after.is_none());
+    }
 }
@@ -0,0 +1,30 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+
+/// ```tlb
+/// acst_unchanged$0 = AccStatusChange;
+/// acst_frozen$10 = AccStatusChange;
+/// acst_deleted$11 = AccStatusChange;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccStatusChange {
+    Unchanged,
+    Frozen,
+    Deleted,
+}
+
+impl BitUnpack for AccStatusChange {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        if !reader.unpack::<bool>()? {
+            return Ok(Self::Unchanged);
+        }
+
+        if reader.unpack()? {
+            Ok(Self::Deleted)
+        } else {
+            Ok(Self::Frozen)
+        }
+    }
+}
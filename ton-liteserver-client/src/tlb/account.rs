@@ -0,0 +1,256 @@
+use crate::tlb::account_state::AccountState;
+use crate::tlb::account_storage::AccountStorage;
+use crate::tlb::storage_info::StorageInfo;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::Cell;
+use toner::ton::currency::CurrencyCollection;
+use toner::ton::MsgAddress;
+
+/// ```tlb
+/// account_none$0 = Account;
+/// account$1 addr:MsgAddressInt storage_stat:StorageInfo storage:AccountStorage = Account;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Account {
+    None,
+    Existing {
+        address: MsgAddress,
+        storage_stat: StorageInfo,
+        storage: AccountStorage,
+    },
+}
+
+impl Account {
+    pub fn storage(&self) -> Option<&AccountStorage> {
+        match self {
+            Self::None => None,
+            Self::Existing { storage, .. } => Some(storage),
+        }
+    }
+
+    pub fn balance(&self) -> Option<&CurrencyCollection> {
+        self.storage().map(|storage| &storage.balance)
+    }
+
+    /// Code of an active account.
+    pub fn code(&self) -> Option<&Cell> {
+        match &self.storage()?.state {
+            AccountState::Active(state_init) => state_init.code.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Data of an active account.
+    pub fn data(&self) -> Option<&Cell> {
+        match &self.storage()?.state {
+            AccountState::Active(state_init) => state_init.data.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> CellDeserialize<'de> for Account {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        if !parser.unpack()? {
+            return Ok(Self::None);
+        }
+
+        let address = parser.unpack()?;
+        let storage_stat = parser.unpack()?;
+        let storage = parser.parse()?;
+
+        Ok(Self::Existing {
+            address,
+            storage_stat,
+            storage,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::proof::fixtures::SHARD_ACCOUNT;
+    use crate::tlb::account::Account;
+    use crate::tlb::account_state::AccountState;
+    use crate::tlb::shard_account::ShardAccount;
+    use num_bigint::BigUint;
+    use toner::tlb::bits::de::unpack_bytes_fully;
+    use toner::tlb::bits::r#as::NBits;
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::r#as::Ref;
+    use toner::tlb::Cell;
+    use toner::ton::boc::BoC;
+    use toner::ton::currency::{CurrencyCollection, Grams};
+    use toner::ton::state_init::StateInit;
+    use toner::ton::MsgAddress;
+
+    pub(crate) fn given_account(state_tag: bool, code: &Cell, data: &Cell) -> Cell {
+        let state_init = StateInit::<Cell, Cell> {
+            code: Some(code.clone()),
+            data: Some(data.clone()),
+            ..Default::default()
+        };
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(true)
+            .unwrap()
+            .pack(MsgAddress {
+                workchain_id: 0,
+                address: [7; 32],
+            })
+            .unwrap()
+            // storage_used: 2 cells, 300 bits
+            .pack_as::<_, NBits<3>>(1u8)
+            .unwrap()
+            .pack(2u8)
+            .unwrap()
+            .pack_as::<_, NBits<3>>(2u8)
+            .unwrap()
+            .pack(300u16)
+            .unwrap()
+            // storage_extra_none
+            .pack_as::<_, NBits<3>>(0u8)
+            .unwrap()
+            .pack(1700000000u32)
+            .unwrap()
+            .pack_as::<_, Option<Grams>>(None::<BigUint>)
+            .unwrap()
+            .pack(42u64)
+            .unwrap()
+            .store(CurrencyCollection {
+                grams: BigUint::from(1_000_000_000u64),
+                ..Default::default()
+            })
+            .unwrap()
+            .pack(state_tag)
+            .unwrap();
+        if state_tag {
+            builder.store(state_init).unwrap();
+        } else {
+            builder.pack(true).unwrap().pack([9u8; 32]).unwrap();
+        }
+
+        builder.into_cell()
+    }
+
+    pub(crate) fn given_cell(byte: u8) -> Cell {
+        let mut builder = Cell::builder();
+        builder.pack(byte).unwrap();
+
+        builder.into_cell()
+    }
+
+    #[test]
+    fn parse_active_account() {
+        let code = given_cell(1);
+        let data = given_cell(2);
+        let cell = given_account(true, &code, &data);
+
+        let account: Account = cell.parse_fully().unwrap();
+
+        let Account::Existing {
+            address,
+            storage_stat,
+            storage,
+        } = &account
+        else {
+            panic!("account has to exist");
+        };
+        assert_eq!(address.address, [7; 32]);
+        assert_eq!(storage_stat.used.cells, 2);
+        assert_eq!(storage_stat.used.bits, 300);
+        assert_eq!(storage_stat.last_paid, 1700000000);
+        assert_eq!(storage.last_trans_lt, 42);
+        assert_eq!(
+            account.balance().map(|b| &b.grams),
+            Some(&BigUint::from(1_000_000_000u64))
+        );
+        assert_eq!(account.code(), Some(&code));
+        assert_eq!(account.data(), Some(&data));
+    }
+
+    #[test]
+    fn parse_frozen_account() {
+        let cell = given_account(false, &given_cell(1), &given_cell(2));
+
+        let account: Account = cell.parse_fully().unwrap();
+
+        assert_eq!(
+            account.storage().map(|s| &s.state),
+            Some(&AccountState::Frozen {
+                state_hash: [9; 32]
+            })
+        );
+        assert_eq!(account.code(), None);
+    }
+
+    #[test]
+    fn parse_shard_account() {
+        let mut builder = Cell::builder();
+        builder
+            .store_as::<_, Ref>(given_account(true, &given_cell(1), &given_cell(2)))
+            .unwrap()
+            .pack([3u8; 32])
+            .unwrap()
+            .pack(42u64)
+            .unwrap();
+        let cell = builder.into_cell();
+
+        let shard_account: ShardAccount = cell.parse_fully().unwrap();
+
+        assert_eq!(shard_account.last_trans_lt, 42);
+        assert!(matches!(shard_account.account, Account::Existing { .. }));
+    }
+
+    #[test]
+    fn parse_account_none() {
+        let mut builder = Cell::builder();
+        builder.pack(false).unwrap();
+
+        let account: Account = builder.into_cell().parse_fully().unwrap();
+
+        assert_eq!(account, Account::None);
+        assert_eq!(account.balance(), None);
+    }
+
+    #[test]
+    fn parse_real_shard_account() {
+        let boc: BoC = unpack_bytes_fully(SHARD_ACCOUNT).unwrap();
+
+        let shard_account: ShardAccount = boc.single_root().unwrap().parse_fully().unwrap();
+
+        assert_eq!(shard_account.last_trans_lt, 36305705000008);
+        assert_eq!(
+            hex::encode(shard_account.last_trans_hash),
+            "d237888b0da6109136755f5825731493d7ae44fabf77e75270742510bb39de69"
+        );
+        let Account::Existing {
+            address,
+            storage_stat,
+            storage,
+        } = &shard_account.account
+        else {
+            panic!("account has to exist");
+        };
+        assert_eq!(address.workchain_id, 0);
+        assert_eq!(
+            hex::encode(address.address),
+            "ffec98a777bcda358e1e5feaa6975d4f69d0f21e5cc9021aa0b9d485cc79caad"
+        );
+        assert_eq!(storage_stat.used.cells, 22);
+        assert_eq!(storage_stat.used.bits, 7386);
+        assert_eq!(storage_stat.last_paid, 1679669852);
+        assert_eq!(storage.last_trans_lt, 36305705000011);
+        assert_eq!(storage.balance.grams, BigUint::from(42365028u64));
+        assert_eq!(
+            shard_account
+                .account
+                .code()
+                .map(|code| hex::encode(code.hash())),
+            Some("48629075cfcfaa00447e41b87eefd7fa50a4e5b9d29015fa2f8835641eefd3b6".to_owned())
+        );
+        assert!(shard_account.account.data().is_some());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::tlb::hash_update::HashUpdate;
+use crate::tlb::hashmap_aug::parse_hashmap_aug;
+use crate::tlb::transaction::Transaction;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{Data, ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::currency::CurrencyCollection;
+
+/// ```tlb
+/// acc_trans#5 account_addr:bits256
+///   transactions:(HashmapAug 64 ^Transaction CurrencyCollection)
+///   state_update:^(HASH_UPDATE Account) = AccountBlock;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountBlock {
+    pub account_addr: [u8; 32],
+    /// Transactions by logical time.
+    pub transactions: BTreeMap<u64, Transaction>,
+    pub state_update: HashUpdate,
+}
+
+impl<'de> CellDeserialize<'de> for AccountBlock {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<4>>()?;
+        if tag != 0x5 {
+            return Err(Error::custom(format!(
+                "unexpected account block tag {tag:#x}"
+            )));
+        }
+
+        let account_addr = parser.unpack()?;
+        let (transactions, _) =
+            parse_hashmap_aug::<_, Ref<ParseFully>, CurrencyCollection>(parser, 64)?;
+        let state_update = parser.parse_as::<_, Ref<ParseFully<Data>>>()?;
+
+        Ok(Self {
+            account_addr,
+            transactions: transactions
+                .into_iter()
+                .map(|(k, v)| (k.load_be::<u64>(), v))
+                .collect(),
+            state_update,
+        })
+    }
+}
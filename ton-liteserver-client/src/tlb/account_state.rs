@@ -0,0 +1,31 @@
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::state_init::StateInit;
+
+/// ```tlb
+/// account_uninit$00 = AccountState;
+/// account_active$1 _:StateInit = AccountState;
+/// account_frozen$01 state_hash:bits256 = AccountState;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountState {
+    Uninit,
+    Active(StateInit),
+    Frozen { state_hash: [u8; 32] },
+}
+
+impl<'de> CellDeserialize<'de> for AccountState {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        if parser.unpack()? {
+            return Ok(Self::Active(parser.parse()?));
+        }
+
+        if parser.unpack()? {
+            Ok(Self::Frozen {
+                state_hash: parser.unpack()?,
+            })
+        } else {
+            Ok(Self::Uninit)
+        }
+    }
+}
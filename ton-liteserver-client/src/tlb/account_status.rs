@@ -0,0 +1,32 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+
+/// ```tlb
+/// acc_state_uninit$00 = AccountStatus;
+/// acc_state_frozen$01 = AccountStatus;
+/// acc_state_active$10 = AccountStatus;
+/// acc_state_nonexist$11 = AccountStatus;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountStatus {
+    Uninit,
+    Frozen,
+    Active,
+    NonExist,
+}
+
+impl BitUnpack for AccountStatus {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<2>>()?;
+
+        Ok(match tag {
+            0b00 => Self::Uninit,
+            0b01 => Self::Frozen,
+            0b10 => Self::Active,
+            _ => Self::NonExist,
+        })
+    }
+}
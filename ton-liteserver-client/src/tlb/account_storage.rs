@@ -0,0 +1,29 @@
+use crate::tlb::account_state::AccountState;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::currency::CurrencyCollection;
+
+/// ```tlb
+/// account_storage$_ last_trans_lt:uint64 balance:CurrencyCollection state:AccountState
+///     = AccountStorage;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStorage {
+    pub last_trans_lt: u64,
+    pub balance: CurrencyCollection,
+    pub state: AccountState,
+}
+
+impl<'de> CellDeserialize<'de> for AccountStorage {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let last_trans_lt = parser.unpack()?;
+        let balance = parser.parse()?;
+        let state = parser.parse()?;
+
+        Ok(Self {
+            last_trans_lt,
+            balance,
+            state,
+        })
+    }
+}
@@ -0,0 +1,270 @@
+use crate::tlb::block_extra::BlockExtra;
+use crate::tlb::block_info::BlockInfo;
+use crate::tlb::transaction::Transaction;
+use crate::tlb::value_flow::ValueFlow;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::{Cell, Error};
+
+/// ```tlb
+/// block#11ef55aa global_id:int32
+///   info:^BlockInfo value_flow:^ValueFlow
+///   state_update:^(MERKLE_UPDATE ShardState)
+///   extra:^BlockExtra = Block;
+/// ```
+#[derive(Debug)]
+pub struct Block {
+    pub global_id: i32,
+    pub info: BlockInfo,
+    pub value_flow: ValueFlow,
+    /// Merkle update of the shard state, kept as is.
+    pub state_update: Cell,
+    pub extra: BlockExtra,
+}
+
+impl Block {
+    /// Transactions of all accounts, ordered by account address and logical time.
+    pub fn transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.extra
+            .account_blocks
+            .values()
+            .flat_map(|block| block.transactions.values())
+    }
+}
+
+impl<'de> CellDeserialize<'de> for Block {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u32 = parser.unpack()?;
+        if tag != 0x11ef55aa {
+            return Err(Error::custom(format!("unexpected block tag {tag:#x}")));
+        }
+
+        let global_id = parser.unpack()?;
+        let info = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let value_flow = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let state_update = parser.parse_as::<_, Ref>()?;
+        let extra = parser.parse_as::<_, Ref<ParseFully>>()?;
+
+        Ok(Self {
+            global_id,
+            info,
+            value_flow,
+            state_update,
+            extra,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::proof::fixtures::{block_info, zero_id};
+    use crate::tlb::block::Block;
+    use crate::tlb::in_msg::InMsg;
+    use crate::tlb::transaction::tests::{given_in_msg, given_transaction, grams};
+    use num_bigint::BigUint;
+    use toner::tlb::bits::r#as::NBits;
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::r#as::Ref;
+    use toner::tlb::Cell;
+    use toner::ton::currency::Grams;
+
+    fn given_value_flow() -> Cell {
+        let mut inbound = Cell::builder();
+        let mut outbound = Cell::builder();
+        for value in 1..=4 {
+            inbound.store(grams(value)).unwrap();
+            outbound.store(grams(value * 10)).unwrap();
+        }
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(0x3ebf98b7u32)
+            .unwrap()
+            .store_as::<_, Ref>(inbound.into_cell())
+            .unwrap()
+            .store(grams(5))
+            .unwrap()
+            .store(grams(6))
+            .unwrap()
+            .store_as::<_, Ref>(outbound.into_cell())
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    /// Single message `[9; 32]` imported from outside.
+    fn given_in_msg_descr() -> Cell {
+        let mut root = Cell::builder();
+        // hml_long$10 n:(#<= 256)
+        root.pack([true, false])
+            .unwrap()
+            .pack_as::<_, NBits<9>>(256u16)
+            .unwrap()
+            .pack([9u8; 32])
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(0u8))
+            .unwrap()
+            .store(grams(0))
+            .unwrap()
+            // msg_import_ext$000
+            .pack_as::<_, NBits<3>>(0u8)
+            .unwrap()
+            .store_as::<_, Ref>(given_in_msg())
+            .unwrap()
+            .store_as::<_, Ref>(given_transaction(0, false))
+            .unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(root.into_cell())
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(0u8))
+            .unwrap()
+            .store(grams(0))
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    /// Account `[7; 32]` with transactions at 100 and 101.
+    fn given_account_blocks() -> Cell {
+        let leaf = |transaction: Cell| {
+            let mut builder = Cell::builder();
+            // hml_short$0 len:(Unary ~0)
+            builder
+                .pack([false, false])
+                .unwrap()
+                .store(grams(1))
+                .unwrap()
+                .store_as::<_, Ref>(transaction)
+                .unwrap();
+
+            builder.into_cell()
+        };
+
+        let mut state_update = Cell::builder();
+        state_update
+            .pack(0x72u8)
+            .unwrap()
+            .pack([4u8; 32])
+            .unwrap()
+            .pack([5u8; 32])
+            .unwrap();
+
+        let mut account_block = Cell::builder();
+        account_block
+            // hml_long$10 n:(#<= 256)
+            .pack([true, false])
+            .unwrap()
+            .pack_as::<_, NBits<9>>(256u16)
+            .unwrap()
+            .pack([7u8; 32])
+            .unwrap()
+            .store(grams(2))
+            .unwrap()
+            // acc_trans#5
+            .pack_as::<_, NBits<4>>(0x5u8)
+            .unwrap()
+            .pack([7u8; 32])
+            .unwrap()
+            // hml_long$10 n:(#<= 64)
+            .pack([true, false])
+            .unwrap()
+            .pack_as::<_, NBits<7>>(63u8)
+            .unwrap()
+            .pack_as::<_, NBits<63>>(100u64 >> 1)
+            .unwrap()
+            .store_as::<_, Ref>(leaf(given_transaction(0, false)))
+            .unwrap()
+            .store_as::<_, Ref>(leaf(given_transaction(35, true)))
+            .unwrap()
+            .store(grams(2))
+            .unwrap()
+            .store_as::<_, Ref>(state_update.into_cell())
+            .unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(account_block.into_cell())
+            .unwrap()
+            .store(grams(2))
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    fn given_block_extra() -> Cell {
+        let mut out_msg_descr = Cell::builder();
+        out_msg_descr.pack(false).unwrap().store(grams(0)).unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(0x4a33f6fdu32)
+            .unwrap()
+            .store_as::<_, Ref>(given_in_msg_descr())
+            .unwrap()
+            .store_as::<_, Ref>(out_msg_descr.into_cell())
+            .unwrap()
+            .store_as::<_, Ref>(given_account_blocks())
+            .unwrap()
+            .pack([1u8; 32])
+            .unwrap()
+            .pack([2u8; 32])
+            .unwrap()
+            .pack(false)
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    /// Basechain block with a single account and two transactions.
+    pub(crate) fn given_block() -> Cell {
+        let mut builder = Cell::builder();
+        builder
+            .pack(0x11ef55aau32)
+            .unwrap()
+            .pack(-239i32)
+            .unwrap()
+            .store_as::<_, Ref>(block_info(0, 20, false, 1100, 600, &zero_id()).to_cell())
+            .unwrap()
+            .store_as::<_, Ref>(given_value_flow())
+            .unwrap()
+            .store_as::<_, Ref>(Cell::default())
+            .unwrap()
+            .store_as::<_, Ref>(given_block_extra())
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    #[test]
+    fn parse_block() {
+        let cell = given_block();
+
+        let block: Block = cell.parse_fully().unwrap();
+
+        assert_eq!(block.global_id, -239);
+        assert_eq!(block.info.seq_no, 20);
+        assert_eq!(block.value_flow.burned, Some(grams(6)));
+        assert_eq!(block.value_flow.minted, grams(40));
+        assert!(block.extra.custom.is_none());
+        assert!(block.extra.out_msg_descr.is_empty());
+        assert!(matches!(
+            block.extra.in_msg_descr.get(&[9; 32]),
+            Some(InMsg::External { transaction, .. }) if transaction.lt == 100
+        ));
+
+        let account = block.extra.account_blocks.get(&[7; 32]).unwrap();
+        assert_eq!(
+            account.transactions.keys().copied().collect::<Vec<_>>(),
+            vec![100, 101]
+        );
+        assert_eq!(account.state_update.old_hash, [4; 32]);
+        assert_eq!(block.transactions().count(), 2);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::tlb::in_msg_descr::InMsgDescr;
+use crate::tlb::mc_block_extra::McBlockExtra;
+use crate::tlb::out_msg_descr::OutMsgDescr;
+use crate::tlb::shard_account_blocks::ShardAccountBlocks;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+
+/// ```tlb
+/// block_extra#4a33f6fd in_msg_descr:^InMsgDescr
+///   out_msg_descr:^OutMsgDescr
+///   account_blocks:^ShardAccountBlocks
+///   rand_seed:bits256
+///   created_by:bits256
+///   custom:(Maybe ^McBlockExtra) = BlockExtra;
+/// ```
+#[derive(Debug)]
+pub struct BlockExtra {
+    pub in_msg_descr: InMsgDescr,
+    pub out_msg_descr: OutMsgDescr,
+    pub account_blocks: ShardAccountBlocks,
+    pub rand_seed: [u8; 32],
+    pub created_by: [u8; 32],
+    /// Present in masterchain blocks only.
+    pub custom: Option<McBlockExtra>,
+}
+
+impl<'de> CellDeserialize<'de> for BlockExtra {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u32 = parser.unpack()?;
+        if tag != 0x4a33f6fd {
+            return Err(Error::custom(format!(
+                "unexpected block extra tag {tag:#x}"
+            )));
+        }
+
+        let in_msg_descr = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let out_msg_descr = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let account_blocks = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let rand_seed = parser.unpack()?;
+        let created_by = parser.unpack()?;
+        let custom = parser.parse_as::<_, Option<Ref<ParseFully>>>()?;
+
+        Ok(Self {
+            in_msg_descr,
+            out_msg_descr,
+            account_blocks,
+            rand_seed,
+            created_by,
+            custom,
+        })
+    }
+}
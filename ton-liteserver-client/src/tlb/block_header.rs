@@ -4,6 +4,7 @@ use toner::tlb::bits::r#as::NBits;
 use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
 use toner::tlb::r#as::{ParseFully, Ref};
 use toner::tlb::Cell;
+use toner::tlb::Error;
 
 /// ```tlb
 /// block#11ef55aa
@@ -24,7 +25,7 @@ impl<'de> CellDeserialize<'de> for BlockHeader {
     fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
         let tag: u32 = parser.unpack_as::<_, NBits<32>>()?;
         if tag != 0x11ef55aa {
-            unimplemented!("actual tag is {:x}", tag)
+            return Err(Error::custom(format!("unexpected block tag {tag:#x}")));
         };
 
         let global_id = parser.unpack()?;
@@ -5,6 +5,7 @@ use crate::tlb::shard_ident::ShardIdent;
 use toner::tlb::bits::de::BitReaderExt;
 use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
 use toner::tlb::r#as::{Data, Ref, Same};
+use toner::tlb::Error;
 
 /// ```tlb
 ///  block_info#9bc7a987
@@ -54,7 +55,7 @@ impl<'de> CellDeserialize<'de> for BlockInfo {
     fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
         let tag: u32 = parser.unpack()?;
         if tag != 0x9bc7a987 {
-            unreachable!()
+            return Err(Error::custom(format!("unexpected block info tag {tag:#x}")));
         };
 
         let version = parser.unpack()?;
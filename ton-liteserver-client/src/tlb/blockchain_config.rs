@@ -0,0 +1,174 @@
+use crate::tlb::gas_limits_prices::GasLimitsPrices;
+use crate::tlb::global_version::GlobalVersion;
+use crate::tlb::msg_forward_prices::MsgForwardPrices;
+use crate::tlb::storage_prices::StoragePrices;
+use crate::tlb::validator_set::ValidatorSet;
+use crate::tlb::workchain_descr::WorkchainDescr;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellParser, CellParserError};
+use toner::tlb::r#as::{Data, NoArgs};
+use toner::tlb::Cell;
+use toner::ton::hashmap::{Hashmap, HashmapE};
+
+/// Commonly used parameters of the blockchain config, absent ones are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct BlockchainConfig {
+    /// `_ elector_addr:bits256 = ConfigParam 1;`
+    pub elector_addr: Option<[u8; 32]>,
+    /// `_ GlobalVersion = ConfigParam 8;`
+    pub global_version: Option<GlobalVersion>,
+    /// `_ workchains:(HashmapE 32 WorkchainDescr) = ConfigParam 12;`
+    pub workchains: Option<BTreeMap<i32, WorkchainDescr>>,
+    /// `_ (Hashmap 32 StoragePrices) = ConfigParam 18;`
+    pub storage_prices: Option<Vec<StoragePrices>>,
+    /// `config_mc_gas_prices#_ GasLimitsPrices = ConfigParam 20;`
+    pub masterchain_gas_prices: Option<GasLimitsPrices>,
+    /// `config_gas_prices#_ GasLimitsPrices = ConfigParam 21;`
+    pub gas_prices: Option<GasLimitsPrices>,
+    /// `config_mc_fwd_prices#_ MsgForwardPrices = ConfigParam 24;`
+    pub masterchain_msg_forward_prices: Option<MsgForwardPrices>,
+    /// `config_fwd_prices#_ MsgForwardPrices = ConfigParam 25;`
+    pub msg_forward_prices: Option<MsgForwardPrices>,
+    /// `_ prev_validators:ValidatorSet = ConfigParam 32;`
+    pub prev_validators: Option<ValidatorSet>,
+    /// `_ cur_validators:ValidatorSet = ConfigParam 34;`
+    pub cur_validators: Option<ValidatorSet>,
+    /// `_ next_validators:ValidatorSet = ConfigParam 36;`
+    pub next_validators: Option<ValidatorSet>,
+}
+
+impl BlockchainConfig {
+    /// Indexes of the parameters above.
+    pub const PARAMS: [i32; 11] = [1, 8, 12, 18, 20, 21, 24, 25, 32, 34, 36];
+
+    /// Decodes the known parameters of `ConfigParam` cells by indexes, the rest are ignored.
+    pub fn from_params(params: &BTreeMap<i32, Cell>) -> Result<Self, CellParserError<'_>> {
+        let param = |index: i32| params.get(&index).map(Cell::parser);
+
+        Ok(Self {
+            elector_addr: parse(param(1), |p| p.unpack())?,
+            global_version: parse(param(8), |p| p.unpack())?,
+            workchains: parse(param(12), |p| {
+                let workchains = p.parse_as_with::<
+                    BTreeMap<BitVec<u8, Msb0>, WorkchainDescr>,
+                    HashmapE<NoArgs<_, Data>, ()>
+                >((32, ()))?;
+
+                Ok(workchains
+                    .into_iter()
+                    .map(|(k, v)| (k.load_be::<u32>() as i32, v))
+                    .collect())
+            })?,
+            storage_prices: parse(param(18), |p| {
+                let prices = p.parse_as_with::<
+                    BTreeMap<BitVec<u8, Msb0>, StoragePrices>,
+                    Hashmap<NoArgs<_, Data>, ()>
+                >((32, ()))?;
+
+                Ok(prices.into_values().collect())
+            })?,
+            masterchain_gas_prices: parse(param(20), |p| p.unpack())?,
+            gas_prices: parse(param(21), |p| p.unpack())?,
+            masterchain_msg_forward_prices: parse(param(24), |p| p.unpack())?,
+            msg_forward_prices: parse(param(25), |p| p.unpack())?,
+            prev_validators: parse(param(32), |p| p.parse())?,
+            cur_validators: parse(param(34), |p| p.parse())?,
+            next_validators: parse(param(36), |p| p.parse())?,
+        })
+    }
+}
+
+fn parse<'de, T>(
+    parser: Option<CellParser<'de>>,
+    f: impl FnOnce(&mut CellParser<'de>) -> Result<T, CellParserError<'de>>,
+) -> Result<Option<T>, CellParserError<'de>> {
+    let Some(mut parser) = parser else {
+        return Ok(None);
+    };
+
+    let value = f(&mut parser)?;
+    parser.ensure_empty()?;
+
+    Ok(Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::boc::deserialize_single;
+    use crate::proof::config_params;
+    use crate::proof::fixtures::{blockchain_config, CONFIG};
+    use crate::tlb::workchain_format::WorkchainFormat;
+
+    #[test]
+    fn parse_blockchain_config() -> anyhow::Result<()> {
+        let params = config_params(&blockchain_config(), &BlockchainConfig::PARAMS)?;
+
+        let config = BlockchainConfig::from_params(&params)?;
+
+        assert_eq!(config.elector_addr, Some([3; 32]));
+        assert_eq!(config.global_version.map(|v| v.version), Some(7));
+        let workchains = config.workchains.unwrap();
+        assert_eq!(workchains.len(), 1);
+        assert_eq!(workchains[&0].max_split, 4);
+        assert_eq!(
+            workchains[&0].format,
+            WorkchainFormat::Basic {
+                vm_version: 0,
+                vm_mode: 0
+            }
+        );
+        assert_eq!(config.storage_prices.unwrap()[0].mc_cell_price_ps, 500000);
+        assert!(config.masterchain_gas_prices.is_none());
+        let gas_prices = config.gas_prices.unwrap();
+        assert_eq!(gas_prices.flat_gas_limit, 100);
+        assert_eq!(gas_prices.gas_price, 26214400);
+        assert_eq!(gas_prices.special_gas_limit, 70000000);
+        assert_eq!(config.msg_forward_prices.unwrap().cell_price, 2621440000);
+        assert!(config.prev_validators.is_none());
+        let validators = config.cur_validators.unwrap();
+        assert_eq!(validators.total_weight, 30);
+        assert_eq!(validators.list[1].public_key, [2; 32]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_real_blockchain_config() -> anyhow::Result<()> {
+        let config = deserialize_single(CONFIG)?;
+        let params = config_params(&config, &BlockchainConfig::PARAMS)?;
+
+        let config = BlockchainConfig::from_params(&params)?;
+
+        assert_eq!(config.elector_addr, Some([0x33; 32]));
+        let global_version = config.global_version.unwrap();
+        assert_eq!(global_version.version, 2);
+        assert_eq!(global_version.capabilities, 46);
+        let workchains = config.workchains.unwrap();
+        assert_eq!(workchains.len(), 1);
+        assert_eq!(workchains[&0].enabled_since, 1573821854);
+        assert_eq!(workchains[&0].max_split, 6);
+        assert_eq!(
+            workchains[&0].format,
+            WorkchainFormat::Basic {
+                vm_version: -1,
+                vm_mode: 0
+            }
+        );
+        assert_eq!(config.storage_prices.unwrap()[0].cell_price_ps, 500);
+        assert_eq!(config.masterchain_gas_prices.unwrap().gas_price, 655360000);
+        let gas_prices = config.gas_prices.unwrap();
+        assert_eq!(gas_prices.gas_price, 65536000);
+        assert_eq!(gas_prices.block_gas_limit, 10000000);
+        assert_eq!(config.msg_forward_prices.unwrap().cell_price, 6553600000);
+        assert_eq!(config.prev_validators.unwrap().list.len(), 288);
+        assert_eq!(config.cur_validators.unwrap().list.len(), 287);
+        assert!(config.next_validators.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use num_bigint::BigUint;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::Error;
+use toner::ton::currency::{CurrencyCollection, Grams};
+use toner::ton::MsgAddress;
+
+/// ```tlb
+/// addr_none$00 = MsgAddressExt;
+/// addr_extern$01 len:(## 9) external_address:(bits len) = MsgAddressExt;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MsgAddressExt {
+    None,
+    Extern(BitVec<u8, Msb0>),
+}
+
+impl BitUnpack for MsgAddressExt {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<2>>()?;
+        match tag {
+            0b00 => Ok(Self::None),
+            0b01 => {
+                let len: u16 = reader.unpack_as::<_, NBits<9>>()?;
+                let address = (0..len)
+                    .map(|_| reader.unpack::<bool>())
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Self::Extern(address))
+            }
+            tag => Err(Error::custom(format!(
+                "unexpected external address tag {tag:#b}"
+            ))),
+        }
+    }
+}
+
+/// ```tlb
+/// int_msg_info$0 ihr_disabled:Bool bounce:Bool bounced:Bool
+///   src:MsgAddressInt dest:MsgAddressInt
+///   value:CurrencyCollection ihr_fee:Grams fwd_fee:Grams
+///   created_lt:uint64 created_at:uint32 = CommonMsgInfo;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalMsgInfo {
+    pub ihr_disabled: bool,
+    pub bounce: bool,
+    pub bounced: bool,
+    pub src: MsgAddress,
+    pub dest: MsgAddress,
+    pub value: CurrencyCollection,
+    pub ihr_fee: BigUint,
+    pub fwd_fee: BigUint,
+    pub created_lt: u64,
+    pub created_at: u32,
+}
+
+/// ```tlb
+/// ext_in_msg_info$10 src:MsgAddressExt dest:MsgAddressInt import_fee:Grams = CommonMsgInfo;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalInMsgInfo {
+    pub src: MsgAddressExt,
+    pub dest: MsgAddress,
+    pub import_fee: BigUint,
+}
+
+/// ```tlb
+/// ext_out_msg_info$11 src:MsgAddressInt dest:MsgAddressExt
+///   created_lt:uint64 created_at:uint32 = CommonMsgInfo;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalOutMsgInfo {
+    pub src: MsgAddress,
+    pub dest: MsgAddressExt,
+    pub created_lt: u64,
+    pub created_at: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommonMsgInfo {
+    Internal(InternalMsgInfo),
+    ExternalIn(ExternalInMsgInfo),
+    ExternalOut(ExternalOutMsgInfo),
+}
+
+impl<'de> CellDeserialize<'de> for CommonMsgInfo {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        if !parser.unpack::<bool>()? {
+            return Ok(Self::Internal(InternalMsgInfo {
+                ihr_disabled: parser.unpack()?,
+                bounce: parser.unpack()?,
+                bounced: parser.unpack()?,
+                src: parser.unpack()?,
+                dest: parser.unpack()?,
+                value: parser.parse()?,
+                ihr_fee: parser.unpack_as::<_, Grams>()?,
+                fwd_fee: parser.unpack_as::<_, Grams>()?,
+                created_lt: parser.unpack()?,
+                created_at: parser.unpack()?,
+            }));
+        }
+
+        if !parser.unpack::<bool>()? {
+            return Ok(Self::ExternalIn(ExternalInMsgInfo {
+                src: parser.unpack()?,
+                dest: parser.unpack()?,
+                import_fee: parser.unpack_as::<_, Grams>()?,
+            }));
+        }
+
+        Ok(Self::ExternalOut(ExternalOutMsgInfo {
+            src: parser.unpack()?,
+            dest: parser.unpack()?,
+            created_lt: parser.unpack()?,
+            created_at: parser.unpack()?,
+        }))
+    }
+}
@@ -0,0 +1,44 @@
+use crate::tlb::blockchain_config::BlockchainConfig;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{NoArgs, ParseFully, Ref};
+use toner::tlb::Cell;
+use toner::ton::hashmap::Hashmap;
+
+/// ```tlb
+/// _ config_addr:bits256 config:^(Hashmap 32 ^Cell) = ConfigParams;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigParams {
+    pub config_addr: [u8; 32],
+    /// Raw `ConfigParam` cells by indexes.
+    pub config: BTreeMap<i32, Cell>,
+}
+
+impl ConfigParams {
+    pub fn blockchain_config(&self) -> Result<BlockchainConfig, CellParserError<'_>> {
+        BlockchainConfig::from_params(&self.config)
+    }
+}
+
+impl<'de> CellDeserialize<'de> for ConfigParams {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let config_addr = parser.unpack()?;
+        let config = parser.parse_as_with::<
+            BTreeMap<BitVec<u8, Msb0>, Cell>,
+            Ref<ParseFully<Hashmap<Ref<NoArgs<_>>, ()>>>
+        >((32, ()))?;
+
+        Ok(Self {
+            config_addr,
+            config: config
+                .into_iter()
+                .map(|(k, v)| (k.load_be::<u32>() as i32, v))
+                .collect(),
+        })
+    }
+}
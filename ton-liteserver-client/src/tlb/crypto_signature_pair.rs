@@ -0,0 +1,31 @@
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::Error;
+
+/// ```tlb
+/// sig_pair$_ node_id_short:bits256 sign:CryptoSignature = CryptoSignaturePair;
+/// ed25519_signature#5 R:bits256 s:bits256 = CryptoSignatureSimple;
+/// _ CryptoSignatureSimple = CryptoSignature;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoSignaturePair {
+    pub node_id_short: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl<'de> CellDeserialize<'de> for CryptoSignaturePair {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let node_id_short = parser.unpack()?;
+        let tag: u8 = parser.unpack_as::<_, NBits<4>>()?;
+        if tag != 0x5 {
+            return Err(Error::custom(format!("unexpected signature tag {tag:#x}")));
+        }
+        let signature = parser.unpack()?;
+
+        Ok(Self {
+            node_id_short,
+            signature,
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// gas_prices#dd gas_price:uint64 gas_limit:uint64 gas_credit:uint64
+///   block_gas_limit:uint64 freeze_due_limit:uint64 delete_due_limit:uint64
+///   = GasLimitsPrices;
+/// gas_prices_ext#de gas_price:uint64 gas_limit:uint64 special_gas_limit:uint64 gas_credit:uint64
+///   block_gas_limit:uint64 freeze_due_limit:uint64 delete_due_limit:uint64
+///   = GasLimitsPrices;
+/// gas_flat_pfx#d1 flat_gas_limit:uint64 flat_gas_price:uint64 other:GasLimitsPrices
+///   = GasLimitsPrices;
+/// ```
+/// Absent flat limit and price are zero, absent `special_gas_limit` equals `gas_limit`,
+/// the same way the node reads them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimitsPrices {
+    pub flat_gas_limit: u64,
+    pub flat_gas_price: u64,
+    /// Price of a gas unit in nanotons shifted left by 16 bits.
+    pub gas_price: u64,
+    pub gas_limit: u64,
+    pub special_gas_limit: u64,
+    pub gas_credit: u64,
+    pub block_gas_limit: u64,
+    pub freeze_due_limit: u64,
+    pub delete_due_limit: u64,
+}
+
+impl BitUnpack for GasLimitsPrices {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let mut tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        let (flat_gas_limit, flat_gas_price) = if tag == 0xd1 {
+            let flat = (reader.unpack()?, reader.unpack()?);
+            tag = reader.unpack_as::<_, NBits<8>>()?;
+
+            flat
+        } else {
+            (0, 0)
+        };
+
+        let gas_price = reader.unpack()?;
+        let gas_limit = reader.unpack()?;
+        let special_gas_limit = match tag {
+            0xdd => gas_limit,
+            0xde => reader.unpack()?,
+            _ => {
+                return Err(Error::custom(format!(
+                    "unexpected gas limits prices tag {tag:#x}"
+                )))
+            }
+        };
+
+        Ok(Self {
+            flat_gas_limit,
+            flat_gas_price,
+            gas_price,
+            gas_limit,
+            special_gas_limit,
+            gas_credit: reader.unpack()?,
+            block_gas_limit: reader.unpack()?,
+            freeze_due_limit: reader.unpack()?,
+            delete_due_limit: reader.unpack()?,
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
 use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
 
 /// ```tlb
 /// capabilities#c4 version:uint32 capabilities:uint64 = GlobalVersion;
@@ -17,7 +18,9 @@ impl BitUnpack for GlobalVersion {
     {
         let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
         if tag != 0xC4 {
-            unreachable!()
+            return Err(Error::custom(format!(
+                "unexpected global version tag {tag:#x}"
+            )));
         }
 
         let version = reader.unpack()?;
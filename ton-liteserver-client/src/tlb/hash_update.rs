@@ -0,0 +1,31 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// update_hashes#72 {X:Type} old_hash:bits256 new_hash:bits256 = HASH_UPDATE X;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashUpdate {
+    pub old_hash: [u8; 32],
+    pub new_hash: [u8; 32],
+}
+
+impl BitUnpack for HashUpdate {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        if tag != 0x72 {
+            return Err(Error::custom(format!(
+                "unexpected hash update tag {tag:#x}"
+            )));
+        }
+
+        let old_hash = reader.unpack()?;
+        let new_hash = reader.unpack()?;
+
+        Ok(Self { old_hash, new_hash })
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::bitvec::view::BitView;
+use toner::tlb::bits::de::{BitReader, BitReaderExt};
+use toner::tlb::bits::r#as::{Unary, VarNBits};
+use toner::tlb::de::r#as::CellDeserializeAs;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::Ref;
+use toner::tlb::Error;
+
+pub(crate) type Key = BitVec<u8, Msb0>;
+
+/// Key of a `HashmapAugE 256` as bytes.
+pub(crate) fn key_to_hash(key: &Key) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.view_bits_mut::<Msb0>().copy_from_bitslice(key);
+
+    hash
+}
+
+/// Leaves of the dictionary by keys and the extra of the root.
+///
+/// toner doesn't expose nodes of `HashmapAugE`, so the dictionary is walked here.
+///
+/// ```tlb
+/// ahme_empty$0 {n:#} {X:Type} {Y:Type} extra:Y = HashmapAugE n X Y;
+/// ahme_root$1 {n:#} {X:Type} {Y:Type} root:^(HashmapAug n X Y) extra:Y = HashmapAugE n X Y;
+/// ```
+pub(crate) fn parse_hashmap_aug_e<'de, T, As, Y>(
+    parser: &mut CellParser<'de>,
+    n: u32,
+) -> Result<(BTreeMap<Key, T>, Y), CellParserError<'de>>
+where
+    As: CellDeserializeAs<'de, T>,
+    Y: CellDeserialize<'de>,
+{
+    let mut output = BTreeMap::new();
+    if parser.unpack()? {
+        let mut root: CellParser = parser.parse_as::<_, Ref>()?;
+        let _: Y = parse_hashmap_aug_node::<T, As, Y>(&mut root, n, Key::new(), &mut output)?;
+        root.ensure_empty()?;
+    }
+    let extra = parser.parse()?;
+
+    Ok((output, extra))
+}
+
+/// Leaves of the dictionary by keys and the extra of the root.
+///
+/// ```tlb
+/// ahm_edge#_ {n:#} {X:Type} {Y:Type} {l:#} {m:#} label:(HmLabel ~l n) {n = (~m) + l}
+///   node:(HashmapAugNode m X Y) = HashmapAug n X Y;
+/// ```
+pub(crate) fn parse_hashmap_aug<'de, T, As, Y>(
+    parser: &mut CellParser<'de>,
+    n: u32,
+) -> Result<(BTreeMap<Key, T>, Y), CellParserError<'de>>
+where
+    As: CellDeserializeAs<'de, T>,
+    Y: CellDeserialize<'de>,
+{
+    let mut output = BTreeMap::new();
+    let extra = parse_hashmap_aug_node::<T, As, Y>(parser, n, Key::new(), &mut output)?;
+
+    Ok((output, extra))
+}
+
+/// ```tlb
+/// ahmn_leaf#_ {X:Type} {Y:Type} extra:Y value:X = HashmapAugNode 0 X Y;
+/// ahmn_fork#_ {n:#} {X:Type} {Y:Type} left:^(HashmapAug n X Y)
+///   right:^(HashmapAug n X Y) extra:Y = HashmapAugNode (n + 1) X Y;
+/// ```
+fn parse_hashmap_aug_node<'de, T, As, Y>(
+    parser: &mut CellParser<'de>,
+    n: u32,
+    mut prefix: Key,
+    output: &mut BTreeMap<Key, T>,
+) -> Result<Y, CellParserError<'de>>
+where
+    As: CellDeserializeAs<'de, T>,
+    Y: CellDeserialize<'de>,
+{
+    let label = unpack_label(&mut *parser, n)?;
+    let m = n - label.len() as u32;
+    prefix.extend_from_bitslice(&label);
+
+    if m == 0 {
+        let extra = parser.parse()?;
+        output.insert(prefix, parser.parse_as::<_, As>()?);
+
+        return Ok(extra);
+    }
+
+    let children: [CellParser; 2] = parser.parse_as::<_, [Ref; 2]>()?;
+    for (bit, mut child) in children.into_iter().enumerate() {
+        let mut prefix = prefix.clone();
+        prefix.push(bit == 1);
+
+        let _: Y = parse_hashmap_aug_node::<T, As, Y>(&mut child, m - 1, prefix, output)?;
+        child.ensure_empty()?;
+    }
+
+    parser.parse()
+}
+
+/// ```tlb
+/// hml_short$0 {m:#} {n:#} len:(Unary ~n) {n <= m} s:(n * Bit) = HmLabel ~n m;
+/// hml_long$10 {m:#} n:(#<= m) s:(n * Bit) = HmLabel ~n m;
+/// hml_same$11 {m:#} v:Bit n:(#<= m) = HmLabel ~n m;
+/// ```
+fn unpack_label<R: BitReader>(mut reader: R, m: u32) -> Result<Key, R::Error> {
+    // n:(#<= m)
+    let len_bits = u32::BITS - m.leading_zeros();
+    let (n, same): (u32, _) = if !reader.unpack::<bool>()? {
+        // hml_short$0
+        (reader.unpack_as::<_, Unary>()?, None)
+    } else if !reader.unpack::<bool>()? {
+        // hml_long$10
+        (reader.unpack_as_with::<_, VarNBits>(len_bits)?, None)
+    } else {
+        // hml_same$11
+        let value: bool = reader.unpack()?;
+        (reader.unpack_as_with::<_, VarNBits>(len_bits)?, Some(value))
+    };
+    if n > m {
+        return Err(Error::custom(format!("label length {n} exceeds {m}")));
+    }
+
+    match same {
+        Some(value) => Ok(Key::repeat(value, n as usize)),
+        None => reader.unpack_with(n as usize),
+    }
+}
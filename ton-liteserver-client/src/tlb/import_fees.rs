@@ -0,0 +1,25 @@
+use num_bigint::BigUint;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::currency::{CurrencyCollection, Grams};
+
+/// ```tlb
+/// import_fees$_ fees_collected:Grams value_imported:CurrencyCollection = ImportFees;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportFees {
+    pub fees_collected: BigUint,
+    pub value_imported: CurrencyCollection,
+}
+
+impl<'de> CellDeserialize<'de> for ImportFees {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let fees_collected = parser.unpack_as::<_, Grams>()?;
+        let value_imported = parser.parse()?;
+
+        Ok(Self {
+            fees_collected,
+            value_imported,
+        })
+    }
+}
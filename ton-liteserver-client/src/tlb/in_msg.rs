@@ -0,0 +1,163 @@
+use crate::tlb::message::Message;
+use crate::tlb::msg_envelope::MsgEnvelope;
+use crate::tlb::transaction::Transaction;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::{Cell, Error};
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// msg_import_ext$000 msg:^(Message Any) transaction:^Transaction = InMsg;
+/// msg_import_ihr$010 msg:^(Message Any) transaction:^Transaction
+///   ihr_fee:Grams proof_created:^Cell = InMsg;
+/// msg_import_imm$011 in_msg:^MsgEnvelope transaction:^Transaction fwd_fee:Grams = InMsg;
+/// msg_import_fin$100 in_msg:^MsgEnvelope transaction:^Transaction fwd_fee:Grams = InMsg;
+/// msg_import_tr$101 in_msg:^MsgEnvelope out_msg:^MsgEnvelope transit_fee:Grams = InMsg;
+/// msg_discard_fin$110 in_msg:^MsgEnvelope transaction_id:uint64 fwd_fee:Grams = InMsg;
+/// msg_discard_tr$111 in_msg:^MsgEnvelope transaction_id:uint64
+///   fwd_fee:Grams proof_delivered:^Cell = InMsg;
+/// msg_import_deferred_fin$00100 in_msg:^MsgEnvelope
+///   transaction:^Transaction fwd_fee:Grams = InMsg;
+/// msg_import_deferred_tr$00101 in_msg:^MsgEnvelope out_msg:^MsgEnvelope = InMsg;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InMsg {
+    External {
+        msg: Message,
+        transaction: Box<Transaction>,
+    },
+    Ihr {
+        msg: Message,
+        transaction: Box<Transaction>,
+        ihr_fee: BigUint,
+        proof_created: Cell,
+    },
+    Immediate {
+        in_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+        fwd_fee: BigUint,
+    },
+    Final {
+        in_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+        fwd_fee: BigUint,
+    },
+    Transit {
+        in_msg: MsgEnvelope,
+        out_msg: MsgEnvelope,
+        transit_fee: BigUint,
+    },
+    DiscardedFinal {
+        in_msg: MsgEnvelope,
+        transaction_id: u64,
+        fwd_fee: BigUint,
+    },
+    DiscardedTransit {
+        in_msg: MsgEnvelope,
+        transaction_id: u64,
+        fwd_fee: BigUint,
+        proof_delivered: Cell,
+    },
+    DeferredFinal {
+        in_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+        fwd_fee: BigUint,
+    },
+    DeferredTransit {
+        in_msg: MsgEnvelope,
+        out_msg: MsgEnvelope,
+    },
+}
+
+impl InMsg {
+    /// The imported message.
+    pub fn message(&self) -> &Message {
+        match self {
+            Self::External { msg, .. } | Self::Ihr { msg, .. } => msg,
+            Self::Immediate { in_msg, .. }
+            | Self::Final { in_msg, .. }
+            | Self::Transit { in_msg, .. }
+            | Self::DiscardedFinal { in_msg, .. }
+            | Self::DiscardedTransit { in_msg, .. }
+            | Self::DeferredFinal { in_msg, .. }
+            | Self::DeferredTransit { in_msg, .. } => &in_msg.msg,
+        }
+    }
+
+    /// The transaction processing the message in this block, if any.
+    pub fn transaction(&self) -> Option<&Transaction> {
+        match self {
+            Self::External { transaction, .. }
+            | Self::Ihr { transaction, .. }
+            | Self::Immediate { transaction, .. }
+            | Self::Final { transaction, .. }
+            | Self::DeferredFinal { transaction, .. } => Some(transaction),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> CellDeserialize<'de> for InMsg {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<3>>()?;
+
+        Ok(match tag {
+            0b000 => Self::External {
+                msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b010 => Self::Ihr {
+                msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                ihr_fee: parser.unpack_as::<_, Grams>()?,
+                proof_created: parser.parse_as::<_, Ref>()?,
+            },
+            0b011 => Self::Immediate {
+                in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                fwd_fee: parser.unpack_as::<_, Grams>()?,
+            },
+            0b100 => Self::Final {
+                in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                fwd_fee: parser.unpack_as::<_, Grams>()?,
+            },
+            0b101 => Self::Transit {
+                in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transit_fee: parser.unpack_as::<_, Grams>()?,
+            },
+            0b110 => Self::DiscardedFinal {
+                in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction_id: parser.unpack()?,
+                fwd_fee: parser.unpack_as::<_, Grams>()?,
+            },
+            0b111 => Self::DiscardedTransit {
+                in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction_id: parser.unpack()?,
+                fwd_fee: parser.unpack_as::<_, Grams>()?,
+                proof_delivered: parser.parse_as::<_, Ref>()?,
+            },
+            _ => match parser.unpack_as::<u8, NBits<2>>()? {
+                0b00 => Self::DeferredFinal {
+                    in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    fwd_fee: parser.unpack_as::<_, Grams>()?,
+                },
+                0b01 => Self::DeferredTransit {
+                    in_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                },
+                tail => {
+                    return Err(Error::custom(format!(
+                        "unexpected in msg tag {:#07b}",
+                        (tag << 2) | tail
+                    )))
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use crate::tlb::hashmap_aug::{key_to_hash, parse_hashmap_aug_e};
+use crate::tlb::import_fees::ImportFees;
+use crate::tlb::in_msg::InMsg;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::Same;
+
+/// ```tlb
+/// _ (HashmapAugE 256 InMsg ImportFees) = InMsgDescr;
+/// ```
+/// Inbound messages by hashes.
+#[derive(Debug, Clone, Default)]
+pub struct InMsgDescr(BTreeMap<[u8; 32], InMsg>);
+
+impl Deref for InMsgDescr {
+    type Target = BTreeMap<[u8; 32], InMsg>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de> CellDeserialize<'de> for InMsgDescr {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let (hashmap, _) = parse_hashmap_aug_e::<_, Same, ImportFees>(parser, 256)?;
+
+        let inner = hashmap
+            .into_iter()
+            .map(|(k, v)| (key_to_hash(&k), v))
+            .collect();
+
+        Ok(Self(inner))
+    }
+}
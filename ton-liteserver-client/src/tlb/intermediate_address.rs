@@ -0,0 +1,38 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+
+/// ```tlb
+/// interm_addr_regular$0 use_dest_bits:(#<= 96) = IntermediateAddress;
+/// interm_addr_simple$10 workchain_id:int8 addr_pfx:uint64 = IntermediateAddress;
+/// interm_addr_ext$11 workchain_id:int32 addr_pfx:uint64 = IntermediateAddress;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntermediateAddress {
+    Regular { use_dest_bits: u8 },
+    Simple { workchain_id: i8, addr_pfx: u64 },
+    Ext { workchain_id: i32, addr_pfx: u64 },
+}
+
+impl BitUnpack for IntermediateAddress {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        if !reader.unpack::<bool>()? {
+            return Ok(Self::Regular {
+                use_dest_bits: reader.unpack_as::<_, NBits<7>>()?,
+            });
+        }
+
+        Ok(match reader.unpack()? {
+            false => Self::Simple {
+                workchain_id: reader.unpack()?,
+                addr_pfx: reader.unpack()?,
+            },
+            true => Self::Ext {
+                workchain_id: reader.unpack()?,
+                addr_pfx: reader.unpack()?,
+            },
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use crate::tlb::config_params::ConfigParams;
+use crate::tlb::crypto_signature_pair::CryptoSignaturePair;
+use crate::tlb::in_msg::InMsg;
+use crate::tlb::shard_fees::ShardFees;
+use crate::tlb::shard_hashes::ShardHashes;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{NoArgs, ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::hashmap::HashmapE;
+
+/// ```tlb
+/// masterchain_block_extra#cca5
+///   key_block:(## 1)
+///   shard_hashes:ShardHashes
+///   shard_fees:ShardFees
+///   ^[ prev_blk_signatures:(HashmapE 16 CryptoSignaturePair)
+///      recover_create_msg:(Maybe ^InMsg)
+///      mint_msg:(Maybe ^InMsg) ]
+///   config:key_block?ConfigParams
+/// = McBlockExtra;
+/// ```
+#[derive(Debug)]
+pub struct McBlockExtra {
+    pub key_block: bool,
+    pub shard_hashes: ShardHashes,
+    pub shard_fees: ShardFees,
+    pub prev_blk_signatures: Vec<CryptoSignaturePair>,
+    pub recover_create_msg: Option<InMsg>,
+    pub mint_msg: Option<InMsg>,
+    pub config: Option<ConfigParams>,
+}
+
+impl<'de> CellDeserialize<'de> for McBlockExtra {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u16 = parser.unpack()?;
+        if tag != 0xcca5 {
+            return Err(Error::custom(format!(
+                "unexpected mc block extra tag {tag:#x}"
+            )));
+        }
+
+        let key_block = parser.unpack()?;
+        let shard_hashes = parser.parse()?;
+        let shard_fees = parser.parse()?;
+        let McBlockExtraRef {
+            prev_blk_signatures,
+            recover_create_msg,
+            mint_msg,
+        } = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let config = match key_block {
+            true => Some(parser.parse()?),
+            false => None,
+        };
+
+        Ok(Self {
+            key_block,
+            shard_hashes,
+            shard_fees,
+            prev_blk_signatures,
+            recover_create_msg,
+            mint_msg,
+            config,
+        })
+    }
+}
+
+/// `^[ prev_blk_signatures:(HashmapE 16 CryptoSignaturePair) recover_create_msg:(Maybe ^InMsg) mint_msg:(Maybe ^InMsg) ]`
+struct McBlockExtraRef {
+    prev_blk_signatures: Vec<CryptoSignaturePair>,
+    recover_create_msg: Option<InMsg>,
+    mint_msg: Option<InMsg>,
+}
+
+impl<'de> CellDeserialize<'de> for McBlockExtraRef {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let prev_blk_signatures = parser.parse_as_with::<
+            BTreeMap<BitVec<u8, Msb0>, CryptoSignaturePair>,
+            HashmapE<NoArgs<_>, ()>
+        >((16, ()))?;
+        let recover_create_msg = parser.parse_as::<_, Option<Ref<ParseFully>>>()?;
+        let mint_msg = parser.parse_as::<_, Option<Ref<ParseFully>>>()?;
+
+        Ok(Self {
+            prev_blk_signatures: prev_blk_signatures.into_values().collect(),
+            recover_create_msg,
+            mint_msg,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tlb::mc_block_extra::McBlockExtra;
+    use crate::tlb::transaction::tests::grams;
+    use toner::tlb::bits::r#as::NBits;
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::r#as::Ref;
+    use toner::tlb::Cell;
+
+    #[test]
+    fn parse_key_block_extra() {
+        let mut signatures = Cell::builder();
+        // hml_long$10 n:(#<= 16)
+        signatures
+            .pack([true, false])
+            .unwrap()
+            .pack_as::<_, NBits<5>>(16u8)
+            .unwrap()
+            .pack(0u16)
+            .unwrap()
+            .pack([1u8; 32])
+            .unwrap()
+            .pack_as::<_, NBits<4>>(0x5u8)
+            .unwrap()
+            .pack([2u8; 64])
+            .unwrap();
+        let mut extra_ref = Cell::builder();
+        extra_ref
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(signatures.into_cell())
+            .unwrap()
+            .pack([false, false])
+            .unwrap();
+
+        let mut param = Cell::builder();
+        param.pack(42u32).unwrap();
+        let mut config = Cell::builder();
+        // hml_long$10 n:(#<= 32)
+        config
+            .pack([true, false])
+            .unwrap()
+            .pack_as::<_, NBits<6>>(32u8)
+            .unwrap()
+            .pack(34u32)
+            .unwrap()
+            .store_as::<_, Ref>(param.into_cell())
+            .unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack(0xcca5u16)
+            .unwrap()
+            .pack(true)
+            .unwrap()
+            // empty shard_hashes and shard_fees
+            .pack(false)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            .store(grams(3))
+            .unwrap()
+            .store(grams(4))
+            .unwrap()
+            .store_as::<_, Ref>(extra_ref.into_cell())
+            .unwrap()
+            .pack([3u8; 32])
+            .unwrap()
+            .store_as::<_, Ref>(config.into_cell())
+            .unwrap();
+
+        let extra: McBlockExtra = builder.into_cell().parse_fully().unwrap();
+
+        assert!(extra.key_block);
+        assert!(extra.shard_hashes.is_empty());
+        assert!(extra.shard_fees.is_empty());
+        assert_eq!(extra.prev_blk_signatures.len(), 1);
+        assert_eq!(extra.prev_blk_signatures[0].signature, [2; 64]);
+        assert!(extra.mint_msg.is_none());
+        let config = extra.config.unwrap();
+        assert_eq!(config.config_addr, [3; 32]);
+        assert_eq!(config.config.keys().copied().collect::<Vec<_>>(), vec![34]);
+    }
+}
@@ -3,6 +3,7 @@ use toner::tlb::bits::de::BitReaderExt;
 use toner::tlb::bits::r#as::NBits;
 use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
 use toner::tlb::r#as::{Ref, Same};
+use toner::tlb::Error;
 
 /// ```tlb
 /// !merkle_proof#03 {X:Type} virtual_hash:bits256 depth:uint16 virtual_root:^X = MERKLE_PROOF X;
@@ -18,7 +19,9 @@ impl<'de> CellDeserialize<'de> for MerkleProof {
     fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
         let tag: u8 = parser.unpack_as::<_, NBits<8>>()?;
         if tag != 0x03 {
-            unreachable!()
+            return Err(Error::custom(format!(
+                "unexpected merkle proof tag {tag:#x}"
+            )));
         };
 
         let virtual_hash = parser.unpack()?;
@@ -0,0 +1,65 @@
+use crate::tlb::common_msg_info::CommonMsgInfo;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::EitherInlineOrRef;
+use toner::tlb::Cell;
+use toner::ton::state_init::StateInit;
+
+/// ```tlb
+/// message$_ {X:Type} info:CommonMsgInfo
+///   init:(Maybe (Either StateInit ^StateInit))
+///   body:(Either X ^X) = Message X;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub info: CommonMsgInfo,
+    pub init: Option<StateInit>,
+    pub body: Cell,
+}
+
+impl<'de> CellDeserialize<'de> for Message {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let info = parser.parse()?;
+        let init = parser.parse_as::<_, Option<EitherInlineOrRef>>()?;
+        let body = parser.parse_as::<_, EitherInlineOrRef>()?;
+
+        Ok(Self { info, init, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::fixtures::MESSAGE;
+    use num_bigint::BigUint;
+    use toner::tlb::bits::de::unpack_bytes_fully;
+    use toner::ton::boc::BoC;
+
+    #[test]
+    fn parse_real_message() {
+        let boc: BoC = unpack_bytes_fully(MESSAGE).unwrap();
+
+        let message: Message = boc.single_root().unwrap().parse_fully().unwrap();
+
+        let CommonMsgInfo::Internal(info) = &message.info else {
+            panic!("message has to be internal");
+        };
+        assert!(info.ihr_disabled);
+        assert!(info.bounce);
+        assert!(!info.bounced);
+        assert_eq!(
+            info.src.to_string(),
+            "EQAOhoXDQYY9-5iW53yv4lCCViwoZOzEEOXuMReoasWjr8qa"
+        );
+        assert_eq!(
+            info.dest.to_string(),
+            "EQD_7Jind7zaNY4eX-qml11PadDyHlzJAhqgudSFzHnKrUn5"
+        );
+        assert_eq!(info.value.grams, BigUint::from(500_000_000u64));
+        assert_eq!(info.created_lt, 0);
+        assert!(message.init.is_none());
+        assert_eq!(
+            hex::encode(message.body.hash()),
+            "a337874fce2e8eddb4f3e4a4930657c10da0764697a02c45fe34bd74a5444f2a"
+        );
+    }
+}
@@ -1,12 +1,61 @@
+pub mod acc_status_change;
+pub mod account;
+pub mod account_block;
+pub mod account_state;
+pub mod account_status;
+pub mod account_storage;
 pub mod blk_master_info;
 pub mod blk_prev_info;
+pub mod block;
+pub mod block_extra;
 pub mod block_header;
 pub mod block_id_ext;
 pub mod block_info;
+pub mod blockchain_config;
+pub mod common_msg_info;
+pub mod config_params;
+pub mod crypto_signature_pair;
 pub mod ext_blk_ref;
 pub mod future_split_merge;
+pub mod gas_limits_prices;
 pub mod global_version;
+pub mod hash_update;
+pub(crate) mod hashmap_aug;
+pub mod import_fees;
+pub mod in_msg;
+pub mod in_msg_descr;
+pub mod intermediate_address;
+pub mod mc_block_extra;
 pub mod merkle_proof;
+pub mod message;
+pub mod msg_envelope;
+pub mod msg_forward_prices;
+pub mod msg_metadata;
+pub mod out_msg;
+pub mod out_msg_descr;
+pub mod shard_account;
+pub mod shard_account_blocks;
 pub mod shard_descr;
+pub mod shard_fee_created;
+pub mod shard_fees;
 pub mod shard_hashes;
 pub mod shard_ident;
+pub mod storage_info;
+pub mod storage_prices;
+pub mod tr_action_phase;
+pub mod tr_bounce_phase;
+pub mod tr_compute_phase;
+pub mod tr_credit_phase;
+pub mod tr_storage_phase;
+pub mod transaction;
+pub mod transaction_descr;
+pub mod validator_descr;
+pub mod validator_set;
+pub mod value_flow;
+pub(crate) mod var_uint;
+pub mod wc_split_merge_timings;
+pub mod workchain_descr;
+pub mod workchain_format;
+
+pub use toner::ton::currency::CurrencyCollection;
+pub use toner::ton::state_init::StateInit;
@@ -0,0 +1,59 @@
+use crate::tlb::intermediate_address::IntermediateAddress;
+use crate::tlb::message::Message;
+use crate::tlb::msg_metadata::MsgMetadata;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// msg_envelope#4 cur_addr:IntermediateAddress
+///   next_addr:IntermediateAddress fwd_fee_remaining:Grams
+///   msg:^(Message Any) = MsgEnvelope;
+/// msg_envelope_v2#5 cur_addr:IntermediateAddress
+///   next_addr:IntermediateAddress fwd_fee_remaining:Grams
+///   msg:^(Message Any)
+///   emitted_lt:(Maybe uint64)
+///   metadata:(Maybe MsgMetadata) = MsgEnvelope;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgEnvelope {
+    pub cur_addr: IntermediateAddress,
+    pub next_addr: IntermediateAddress,
+    pub fwd_fee_remaining: BigUint,
+    pub msg: Message,
+    pub emitted_lt: Option<u64>,
+    pub metadata: Option<MsgMetadata>,
+}
+
+impl<'de> CellDeserialize<'de> for MsgEnvelope {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<4>>()?;
+        if tag != 0x4 && tag != 0x5 {
+            return Err(Error::custom(format!(
+                "unexpected msg envelope tag {tag:#x}"
+            )));
+        }
+
+        let cur_addr = parser.unpack()?;
+        let next_addr = parser.unpack()?;
+        let fwd_fee_remaining = parser.unpack_as::<_, Grams>()?;
+        let msg = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let (emitted_lt, metadata) = match tag {
+            0x5 => (parser.unpack()?, parser.unpack()?),
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            cur_addr,
+            next_addr,
+            fwd_fee_remaining,
+            msg,
+            emitted_lt,
+            metadata,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// msg_forward_prices#ea lump_price:uint64 bit_price:uint64 cell_price:uint64
+///   ihr_price_factor:uint32 first_frac:uint16 next_frac:uint16 = MsgForwardPrices;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MsgForwardPrices {
+    pub lump_price: u64,
+    pub bit_price: u64,
+    pub cell_price: u64,
+    pub ihr_price_factor: u32,
+    pub first_frac: u16,
+    pub next_frac: u16,
+}
+
+impl BitUnpack for MsgForwardPrices {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        if tag != 0xea {
+            return Err(Error::custom(format!(
+                "unexpected msg forward prices tag {tag:#x}"
+            )));
+        }
+
+        Ok(Self {
+            lump_price: reader.unpack()?,
+            bit_price: reader.unpack()?,
+            cell_price: reader.unpack()?,
+            ihr_price_factor: reader.unpack()?,
+            first_frac: reader.unpack()?,
+            next_frac: reader.unpack()?,
+        })
+    }
+}
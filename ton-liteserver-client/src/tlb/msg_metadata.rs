@@ -0,0 +1,38 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+use toner::ton::MsgAddress;
+
+/// ```tlb
+/// msg_metadata#0 depth:uint32 initiator_addr:MsgAddressInt initiator_lt:uint64 = MsgMetadata;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgMetadata {
+    pub depth: u32,
+    pub initiator_addr: MsgAddress,
+    pub initiator_lt: u64,
+}
+
+impl BitUnpack for MsgMetadata {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<4>>()?;
+        if tag != 0 {
+            return Err(Error::custom(format!(
+                "unexpected msg metadata tag {tag:#x}"
+            )));
+        }
+
+        let depth = reader.unpack()?;
+        let initiator_addr = reader.unpack()?;
+        let initiator_lt = reader.unpack()?;
+
+        Ok(Self {
+            depth,
+            initiator_addr,
+            initiator_lt,
+        })
+    }
+}
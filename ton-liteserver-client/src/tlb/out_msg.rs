@@ -0,0 +1,160 @@
+use crate::tlb::in_msg::InMsg;
+use crate::tlb::message::Message;
+use crate::tlb::msg_envelope::MsgEnvelope;
+use crate::tlb::transaction::Transaction;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+
+/// ```tlb
+/// msg_export_ext$000 msg:^(Message Any) transaction:^Transaction = OutMsg;
+/// msg_export_imm$010 out_msg:^MsgEnvelope transaction:^Transaction reimport:^InMsg = OutMsg;
+/// msg_export_new$001 out_msg:^MsgEnvelope transaction:^Transaction = OutMsg;
+/// msg_export_tr$011 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+/// msg_export_deq$1100 out_msg:^MsgEnvelope import_block_lt:uint63 = OutMsg;
+/// msg_export_deq_short$1101 msg_env_hash:bits256 next_workchain:int32
+///   next_addr_pfx:uint64 import_block_lt:uint64 = OutMsg;
+/// msg_export_tr_req$111 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+/// msg_export_deq_imm$100 out_msg:^MsgEnvelope reimport:^InMsg = OutMsg;
+/// msg_export_new_defer$10100 out_msg:^MsgEnvelope transaction:^Transaction = OutMsg;
+/// msg_export_deferred_tr$10101 out_msg:^MsgEnvelope imported:^InMsg = OutMsg;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutMsg {
+    External {
+        msg: Message,
+        transaction: Box<Transaction>,
+    },
+    Immediate {
+        out_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+        reimport: Box<InMsg>,
+    },
+    New {
+        out_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+    },
+    Transit {
+        out_msg: MsgEnvelope,
+        imported: Box<InMsg>,
+    },
+    Dequeue {
+        out_msg: MsgEnvelope,
+        import_block_lt: u64,
+    },
+    DequeueShort {
+        msg_env_hash: [u8; 32],
+        next_workchain: i32,
+        next_addr_pfx: u64,
+        import_block_lt: u64,
+    },
+    TransitRequired {
+        out_msg: MsgEnvelope,
+        imported: Box<InMsg>,
+    },
+    DequeueImmediate {
+        out_msg: MsgEnvelope,
+        reimport: Box<InMsg>,
+    },
+    NewDeferred {
+        out_msg: MsgEnvelope,
+        transaction: Box<Transaction>,
+    },
+    DeferredTransit {
+        out_msg: MsgEnvelope,
+        imported: Box<InMsg>,
+    },
+}
+
+impl OutMsg {
+    /// The exported message, `None` for a short dequeue record.
+    pub fn message(&self) -> Option<&Message> {
+        match self {
+            Self::External { msg, .. } => Some(msg),
+            Self::DequeueShort { .. } => None,
+            Self::Immediate { out_msg, .. }
+            | Self::New { out_msg, .. }
+            | Self::Transit { out_msg, .. }
+            | Self::Dequeue { out_msg, .. }
+            | Self::TransitRequired { out_msg, .. }
+            | Self::DequeueImmediate { out_msg, .. }
+            | Self::NewDeferred { out_msg, .. }
+            | Self::DeferredTransit { out_msg, .. } => Some(&out_msg.msg),
+        }
+    }
+
+    /// The transaction creating the message in this block, if any.
+    pub fn transaction(&self) -> Option<&Transaction> {
+        match self {
+            Self::External { transaction, .. }
+            | Self::Immediate { transaction, .. }
+            | Self::New { transaction, .. }
+            | Self::NewDeferred { transaction, .. } => Some(transaction),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> CellDeserialize<'de> for OutMsg {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<3>>()?;
+
+        Ok(match tag {
+            0b000 => Self::External {
+                msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b010 => Self::Immediate {
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                reimport: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b001 => Self::New {
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b011 => Self::Transit {
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                imported: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b110 => match parser.unpack()? {
+                false => Self::Dequeue {
+                    out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    import_block_lt: parser.unpack_as::<_, NBits<63>>()?,
+                },
+                true => Self::DequeueShort {
+                    msg_env_hash: parser.unpack()?,
+                    next_workchain: parser.unpack()?,
+                    next_addr_pfx: parser.unpack()?,
+                    import_block_lt: parser.unpack()?,
+                },
+            },
+            0b111 => Self::TransitRequired {
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                imported: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            0b100 => Self::DequeueImmediate {
+                out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                reimport: parser.parse_as::<_, Ref<ParseFully>>()?,
+            },
+            _ => match parser.unpack_as::<u8, NBits<2>>()? {
+                0b00 => Self::NewDeferred {
+                    out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    transaction: parser.parse_as::<_, Ref<ParseFully>>()?,
+                },
+                0b01 => Self::DeferredTransit {
+                    out_msg: parser.parse_as::<_, Ref<ParseFully>>()?,
+                    imported: parser.parse_as::<_, Ref<ParseFully>>()?,
+                },
+                tail => {
+                    return Err(Error::custom(format!(
+                        "unexpected out msg tag {:#07b}",
+                        (tag << 2) | tail
+                    )))
+                }
+            },
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use crate::tlb::account::Account;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+
+/// ```tlb
+/// account_descr$_ account:^Account last_trans_hash:bits256 last_trans_lt:uint64 = ShardAccount;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardAccount {
+    pub account: Account,
+    pub last_trans_hash: [u8; 32],
+    pub last_trans_lt: u64,
+}
+
+impl<'de> CellDeserialize<'de> for ShardAccount {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let account = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let last_trans_hash = parser.unpack()?;
+        let last_trans_lt = parser.unpack()?;
+
+        Ok(Self {
+            account,
+            last_trans_hash,
+            last_trans_lt,
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use crate::tlb::account_block::AccountBlock;
+use crate::tlb::hashmap_aug::{key_to_hash, parse_hashmap_aug_e};
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::Same;
+use toner::ton::currency::CurrencyCollection;
+
+/// ```tlb
+/// _ (HashmapAugE 256 AccountBlock CurrencyCollection) = ShardAccountBlocks;
+/// ```
+/// Account blocks by account addresses.
+#[derive(Debug, Clone, Default)]
+pub struct ShardAccountBlocks(BTreeMap<[u8; 32], AccountBlock>);
+
+impl Deref for ShardAccountBlocks {
+    type Target = BTreeMap<[u8; 32], AccountBlock>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de> CellDeserialize<'de> for ShardAccountBlocks {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let (hashmap, _) = parse_hashmap_aug_e::<_, Same, CurrencyCollection>(parser, 256)?;
+
+        let inner = hashmap
+            .into_iter()
+            .map(|(k, v)| (key_to_hash(&k), v))
+            .collect();
+
+        Ok(Self(inner))
+    }
+}
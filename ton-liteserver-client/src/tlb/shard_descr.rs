@@ -4,6 +4,7 @@ use toner::tlb::bits::de::BitReaderExt;
 use toner::tlb::bits::r#as::NBits;
 use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
 use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
 use toner::ton::currency::CurrencyCollection;
 
 /// ```tlb
@@ -78,7 +79,11 @@ impl<'de> CellDeserialize<'de> for ShardDescr {
         let (fees_collected, funds_created) = match tag {
             0xa => parser.parse_as::<_, Ref<ParseFully>>()?,
             0xb => parser.parse()?,
-            _ => unreachable!(),
+            _ => {
+                return Err(Error::custom(format!(
+                    "unexpected shard descr tag {tag:#x}"
+                )))
+            }
         };
 
         Ok(Self {
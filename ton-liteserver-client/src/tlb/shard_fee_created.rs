@@ -0,0 +1,20 @@
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::currency::CurrencyCollection;
+
+/// ```tlb
+/// shard_fee_created$_ fees:CurrencyCollection create:CurrencyCollection = ShardFeeCreated;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardFeeCreated {
+    pub fees: CurrencyCollection,
+    pub create: CurrencyCollection,
+}
+
+impl<'de> CellDeserialize<'de> for ShardFeeCreated {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let fees = parser.parse()?;
+        let create = parser.parse()?;
+
+        Ok(Self { fees, create })
+    }
+}
@@ -0,0 +1,40 @@
+use crate::tlb::hashmap_aug::parse_hashmap_aug_e;
+use crate::tlb::shard_fee_created::ShardFeeCreated;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::Same;
+
+/// ```tlb
+/// _ (HashmapAugE 96 ShardFeeCreated ShardFeeCreated) = ShardFees;
+/// ```
+/// Fees by `(workchain, shard)`.
+#[derive(Debug, Clone, Default)]
+pub struct ShardFees(BTreeMap<(i32, u64), ShardFeeCreated>);
+
+impl Deref for ShardFees {
+    type Target = BTreeMap<(i32, u64), ShardFeeCreated>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'de> CellDeserialize<'de> for ShardFees {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let (hashmap, _) = parse_hashmap_aug_e::<_, Same, ShardFeeCreated>(parser, 96)?;
+
+        let inner = hashmap
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    (k[..32].load_be::<u32>() as i32, k[32..].load_be::<u64>()),
+                    v,
+                )
+            })
+            .collect();
+
+        Ok(Self(inner))
+    }
+}
@@ -1,5 +1,6 @@
 use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
 use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
 
 /// `tlb
 /// shard_ident$00
@@ -21,7 +22,9 @@ impl BitUnpack for ShardIdent {
     {
         let tag: u8 = reader.unpack_as::<_, NBits<2>>()?;
         if tag != 0x00 {
-            unreachable!()
+            return Err(Error::custom(format!(
+                "unexpected shard ident tag {tag:#x}"
+            )));
         }
 
         let shard_pfx_bits = reader.unpack_as::<_, NBits<6>>()?;
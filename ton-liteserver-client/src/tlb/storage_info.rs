@@ -0,0 +1,70 @@
+use crate::tlb::var_uint::unpack_var_uint;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// storage_used$_ cells:(VarUInteger 7) bits:(VarUInteger 7) = StorageUsed;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageUsed {
+    pub cells: u64,
+    pub bits: u64,
+}
+
+impl BitUnpack for StorageUsed {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let cells = unpack_var_uint(&mut reader, 3)?;
+        let bits = unpack_var_uint(&mut reader, 3)?;
+
+        Ok(Self { cells, bits })
+    }
+}
+
+/// ```tlb
+/// storage_extra_none$000 = StorageExtraInfo;
+/// storage_extra_info$001 dict_hash:uint256 = StorageExtraInfo;
+/// storage_info$_ used:StorageUsed storage_extra:StorageExtraInfo last_paid:uint32
+///     due_payment:(Maybe Grams) = StorageInfo;
+/// ```
+/// NOTE: `storage_extra_none` takes the place of the removed `public_cells`, which was always zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageInfo {
+    pub used: StorageUsed,
+    pub dict_hash: Option<[u8; 32]>,
+    pub last_paid: u32,
+    pub due_payment: Option<BigUint>,
+}
+
+impl BitUnpack for StorageInfo {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let used = reader.unpack()?;
+        let tag: u8 = reader.unpack_as::<_, NBits<3>>()?;
+        let dict_hash = match tag {
+            0b000 => None,
+            0b001 => Some(reader.unpack()?),
+            tag => {
+                return Err(Error::custom(format!(
+                    "unexpected storage extra info tag {tag:#b}"
+                )))
+            }
+        };
+        let last_paid = reader.unpack()?;
+        let due_payment = reader.unpack_as::<_, Option<Grams>>()?;
+
+        Ok(Self {
+            used,
+            dict_hash,
+            last_paid,
+            due_payment,
+        })
+    }
+}
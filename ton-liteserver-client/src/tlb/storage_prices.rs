@@ -0,0 +1,38 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// storage_prices#cc utime_since:uint32 bit_price_ps:uint64 cell_price_ps:uint64
+///   mc_bit_price_ps:uint64 mc_cell_price_ps:uint64 = StoragePrices;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoragePrices {
+    pub utime_since: u32,
+    pub bit_price_ps: u64,
+    pub cell_price_ps: u64,
+    pub mc_bit_price_ps: u64,
+    pub mc_cell_price_ps: u64,
+}
+
+impl BitUnpack for StoragePrices {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        if tag != 0xcc {
+            return Err(Error::custom(format!(
+                "unexpected storage prices tag {tag:#x}"
+            )));
+        }
+
+        Ok(Self {
+            utime_since: reader.unpack()?,
+            bit_price_ps: reader.unpack()?,
+            cell_price_ps: reader.unpack()?,
+            mc_bit_price_ps: reader.unpack()?,
+            mc_cell_price_ps: reader.unpack()?,
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use crate::tlb::acc_status_change::AccStatusChange;
+use crate::tlb::storage_info::StorageUsed;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// tr_phase_action$_ success:Bool valid:Bool no_funds:Bool
+///   status_change:AccStatusChange
+///   total_fwd_fees:(Maybe Grams) total_action_fees:(Maybe Grams)
+///   result_code:int32 result_arg:(Maybe int32) tot_actions:uint16
+///   spec_actions:uint16 skipped_actions:uint16 msgs_created:uint16
+///   action_list_hash:bits256 tot_msg_size:StorageUsed
+///   = TrActionPhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrActionPhase {
+    pub success: bool,
+    pub valid: bool,
+    pub no_funds: bool,
+    pub status_change: AccStatusChange,
+    pub total_fwd_fees: Option<BigUint>,
+    pub total_action_fees: Option<BigUint>,
+    pub result_code: i32,
+    pub result_arg: Option<i32>,
+    pub tot_actions: u16,
+    pub spec_actions: u16,
+    pub skipped_actions: u16,
+    pub msgs_created: u16,
+    pub action_list_hash: [u8; 32],
+    pub tot_msg_size: StorageUsed,
+}
+
+impl<'de> CellDeserialize<'de> for TrActionPhase {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        Ok(Self {
+            success: parser.unpack()?,
+            valid: parser.unpack()?,
+            no_funds: parser.unpack()?,
+            status_change: parser.unpack()?,
+            total_fwd_fees: parser.unpack_as::<_, Option<Grams>>()?,
+            total_action_fees: parser.unpack_as::<_, Option<Grams>>()?,
+            result_code: parser.unpack()?,
+            result_arg: parser.unpack()?,
+            tot_actions: parser.unpack()?,
+            spec_actions: parser.unpack()?,
+            skipped_actions: parser.unpack()?,
+            msgs_created: parser.unpack()?,
+            action_list_hash: parser.unpack()?,
+            tot_msg_size: parser.unpack()?,
+        })
+    }
+}
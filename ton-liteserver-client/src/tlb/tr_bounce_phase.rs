@@ -0,0 +1,47 @@
+use crate::tlb::storage_info::StorageUsed;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// tr_phase_bounce_negfunds$00 = TrBouncePhase;
+/// tr_phase_bounce_nofunds$01 msg_size:StorageUsed req_fwd_fees:Grams = TrBouncePhase;
+/// tr_phase_bounce_ok$1 msg_size:StorageUsed msg_fees:Grams fwd_fees:Grams = TrBouncePhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrBouncePhase {
+    NegativeFunds,
+    NoFunds {
+        msg_size: StorageUsed,
+        req_fwd_fees: BigUint,
+    },
+    Ok {
+        msg_size: StorageUsed,
+        msg_fees: BigUint,
+        fwd_fees: BigUint,
+    },
+}
+
+impl BitUnpack for TrBouncePhase {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        if reader.unpack()? {
+            return Ok(Self::Ok {
+                msg_size: reader.unpack()?,
+                msg_fees: reader.unpack_as::<_, Grams>()?,
+                fwd_fees: reader.unpack_as::<_, Grams>()?,
+            });
+        }
+
+        if !reader.unpack::<bool>()? {
+            return Ok(Self::NegativeFunds);
+        }
+
+        Ok(Self::NoFunds {
+            msg_size: reader.unpack()?,
+            req_fwd_fees: reader.unpack_as::<_, Grams>()?,
+        })
+    }
+}
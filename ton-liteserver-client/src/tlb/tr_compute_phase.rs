@@ -0,0 +1,148 @@
+use crate::tlb::var_uint::unpack_var_uint;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// cskip_no_state$00 = ComputeSkipReason;
+/// cskip_bad_state$01 = ComputeSkipReason;
+/// cskip_no_gas$10 = ComputeSkipReason;
+/// cskip_suspended$110 = ComputeSkipReason;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeSkipReason {
+    NoState,
+    BadState,
+    NoGas,
+    Suspended,
+}
+
+impl BitUnpack for ComputeSkipReason {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: (bool, bool) = (reader.unpack()?, reader.unpack()?);
+
+        match tag {
+            (false, false) => Ok(Self::NoState),
+            (false, true) => Ok(Self::BadState),
+            (true, false) => Ok(Self::NoGas),
+            (true, true) => {
+                if reader.unpack::<bool>()? {
+                    return Err(Error::custom("unexpected compute skip reason tag 0b111"));
+                }
+
+                Ok(Self::Suspended)
+            }
+        }
+    }
+}
+
+/// ```tlb
+/// tr_phase_compute_vm$1 success:Bool msg_state_used:Bool
+///   account_activated:Bool gas_fees:Grams
+///   ^[ gas_used:(VarUInteger 7)
+///   gas_limit:(VarUInteger 7) gas_credit:(Maybe (VarUInteger 3))
+///   mode:int8 exit_code:int32 exit_arg:(Maybe int32)
+///   vm_steps:uint32
+///   vm_init_state_hash:bits256 vm_final_state_hash:bits256 ]
+///   = TrComputePhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrComputePhaseVm {
+    pub success: bool,
+    pub msg_state_used: bool,
+    pub account_activated: bool,
+    pub gas_fees: BigUint,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub gas_credit: Option<u64>,
+    pub mode: i8,
+    pub exit_code: i32,
+    pub exit_arg: Option<i32>,
+    pub vm_steps: u32,
+    pub vm_init_state_hash: [u8; 32],
+    pub vm_final_state_hash: [u8; 32],
+}
+
+/// ```tlb
+/// tr_phase_compute_skipped$0 reason:ComputeSkipReason = TrComputePhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrComputePhase {
+    Skipped { reason: ComputeSkipReason },
+    Vm(TrComputePhaseVm),
+}
+
+impl<'de> CellDeserialize<'de> for TrComputePhase {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        if !parser.unpack::<bool>()? {
+            return Ok(Self::Skipped {
+                reason: parser.unpack()?,
+            });
+        }
+
+        let success = parser.unpack()?;
+        let msg_state_used = parser.unpack()?;
+        let account_activated = parser.unpack()?;
+        let gas_fees = parser.unpack_as::<_, Grams>()?;
+        let details: ComputeVmDetails = parser.parse_as::<_, Ref<ParseFully>>()?;
+
+        Ok(Self::Vm(TrComputePhaseVm {
+            success,
+            msg_state_used,
+            account_activated,
+            gas_fees,
+            gas_used: details.gas_used,
+            gas_limit: details.gas_limit,
+            gas_credit: details.gas_credit,
+            mode: details.mode,
+            exit_code: details.exit_code,
+            exit_arg: details.exit_arg,
+            vm_steps: details.vm_steps,
+            vm_init_state_hash: details.vm_init_state_hash,
+            vm_final_state_hash: details.vm_final_state_hash,
+        }))
+    }
+}
+
+/// Referenced part of `tr_phase_compute_vm`.
+struct ComputeVmDetails {
+    gas_used: u64,
+    gas_limit: u64,
+    gas_credit: Option<u64>,
+    mode: i8,
+    exit_code: i32,
+    exit_arg: Option<i32>,
+    vm_steps: u32,
+    vm_init_state_hash: [u8; 32],
+    vm_final_state_hash: [u8; 32],
+}
+
+impl<'de> CellDeserialize<'de> for ComputeVmDetails {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let gas_used = unpack_var_uint(parser, 3)?;
+        let gas_limit = unpack_var_uint(parser, 3)?;
+        let gas_credit = if parser.unpack()? {
+            Some(unpack_var_uint(parser, 2)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            gas_used,
+            gas_limit,
+            gas_credit,
+            mode: parser.unpack()?,
+            exit_code: parser.unpack()?,
+            exit_arg: parser.unpack()?,
+            vm_steps: parser.unpack()?,
+            vm_init_state_hash: parser.unpack()?,
+            vm_final_state_hash: parser.unpack()?,
+        })
+    }
+}
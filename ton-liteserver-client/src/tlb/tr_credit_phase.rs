@@ -0,0 +1,26 @@
+use num_bigint::BigUint;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::ton::currency::{CurrencyCollection, Grams};
+
+/// ```tlb
+/// tr_phase_credit$_ due_fees_collected:(Maybe Grams)
+///   credit:CurrencyCollection = TrCreditPhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrCreditPhase {
+    pub due_fees_collected: Option<BigUint>,
+    pub credit: CurrencyCollection,
+}
+
+impl<'de> CellDeserialize<'de> for TrCreditPhase {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let due_fees_collected = parser.unpack_as::<_, Option<Grams>>()?;
+        let credit = parser.parse()?;
+
+        Ok(Self {
+            due_fees_collected,
+            credit,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use crate::tlb::acc_status_change::AccStatusChange;
+use num_bigint::BigUint;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::ton::currency::Grams;
+
+/// ```tlb
+/// tr_phase_storage$_ storage_fees_collected:Grams
+///   storage_fees_due:(Maybe Grams)
+///   status_change:AccStatusChange = TrStoragePhase;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrStoragePhase {
+    pub storage_fees_collected: BigUint,
+    pub storage_fees_due: Option<BigUint>,
+    pub status_change: AccStatusChange,
+}
+
+impl BitUnpack for TrStoragePhase {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let storage_fees_collected = reader.unpack_as::<_, Grams>()?;
+        let storage_fees_due = reader.unpack_as::<_, Option<Grams>>()?;
+        let status_change = reader.unpack()?;
+
+        Ok(Self {
+            storage_fees_collected,
+            storage_fees_due,
+            status_change,
+        })
+    }
+}
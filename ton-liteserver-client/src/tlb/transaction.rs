@@ -0,0 +1,453 @@
+use crate::tlb::account_status::AccountStatus;
+use crate::tlb::hash_update::HashUpdate;
+use crate::tlb::message::Message;
+use crate::tlb::transaction_descr::TransactionDescr;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::field::BitField;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{Data, NoArgs, ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::currency::CurrencyCollection;
+use toner::ton::hashmap::HashmapE;
+
+/// ```tlb
+/// transaction$0111 account_addr:bits256 lt:uint64
+///   prev_trans_hash:bits256 prev_trans_lt:uint64 now:uint32
+///   outmsg_cnt:uint15
+///   orig_status:AccountStatus end_status:AccountStatus
+///   ^[ in_msg:(Maybe ^(Message Any)) out_msgs:(HashmapE 15 ^(Message Any)) ]
+///   total_fees:CurrencyCollection state_update:^(HASH_UPDATE Account)
+///   description:^TransactionDescr = Transaction;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub account_addr: [u8; 32],
+    pub lt: u64,
+    pub prev_trans_hash: [u8; 32],
+    pub prev_trans_lt: u64,
+    pub now: u32,
+    pub outmsg_cnt: u16,
+    pub orig_status: AccountStatus,
+    pub end_status: AccountStatus,
+    pub in_msg: Option<Message>,
+    /// Outbound messages in the order they were created.
+    pub out_msgs: Vec<Message>,
+    pub total_fees: CurrencyCollection,
+    pub state_update: HashUpdate,
+    pub description: TransactionDescr,
+}
+
+impl<'de> CellDeserialize<'de> for Transaction {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<4>>()?;
+        if tag != 0b0111 {
+            return Err(Error::custom(format!(
+                "unexpected transaction tag {tag:#06b}"
+            )));
+        }
+
+        let account_addr = parser.unpack()?;
+        let lt = parser.unpack()?;
+        let prev_trans_hash = parser.unpack()?;
+        let prev_trans_lt = parser.unpack()?;
+        let now = parser.unpack()?;
+        let outmsg_cnt = parser.unpack_as::<_, NBits<15>>()?;
+        let orig_status = parser.unpack()?;
+        let end_status = parser.unpack()?;
+        let messages: TransactionMessages = parser.parse_as::<_, Ref<ParseFully>>()?;
+        let total_fees = parser.parse()?;
+        let state_update = parser.parse_as::<_, Ref<ParseFully<Data>>>()?;
+        let description = parser.parse_as::<_, Ref<ParseFully>>()?;
+
+        Ok(Self {
+            account_addr,
+            lt,
+            prev_trans_hash,
+            prev_trans_lt,
+            now,
+            outmsg_cnt,
+            orig_status,
+            end_status,
+            in_msg: messages.in_msg,
+            out_msgs: messages.out_msgs,
+            total_fees,
+            state_update,
+            description,
+        })
+    }
+}
+
+/// `^[ in_msg:(Maybe ^(Message Any)) out_msgs:(HashmapE 15 ^(Message Any)) ]`
+struct TransactionMessages {
+    in_msg: Option<Message>,
+    out_msgs: Vec<Message>,
+}
+
+impl<'de> CellDeserialize<'de> for TransactionMessages {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let in_msg = parser.parse_as::<_, Option<Ref<ParseFully>>>()?;
+        let out_msgs = parser.parse_as_with::<
+            BTreeMap<BitVec<u8, Msb0>, Message>,
+            HashmapE<Ref<ParseFully<NoArgs<_>>>, ()>
+        >((15, ()))?;
+
+        Ok(Self {
+            in_msg,
+            out_msgs: out_msgs
+                .into_iter()
+                .map(|(k, v)| (k.load_be::<u16>(), v))
+                .collect::<BTreeMap<_, _>>()
+                .into_values()
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::tlb::account_status::AccountStatus;
+    use crate::tlb::common_msg_info::{CommonMsgInfo, MsgAddressExt};
+    use crate::tlb::tr_bounce_phase::TrBouncePhase;
+    use crate::tlb::tr_compute_phase::TrComputePhase;
+    use crate::tlb::transaction::Transaction;
+    use crate::tlb::transaction_descr::TransactionDescr;
+    use num_bigint::BigUint;
+    use toner::tlb::bits::de::BitReaderExt;
+    use toner::tlb::bits::r#as::NBits;
+    use toner::tlb::bits::ser::BitWriterExt;
+    use toner::tlb::r#as::Ref;
+    use toner::tlb::Cell;
+    use toner::ton::currency::{CurrencyCollection, Grams};
+    use toner::ton::MsgAddress;
+
+    fn address(byte: u8) -> MsgAddress {
+        MsgAddress {
+            workchain_id: 0,
+            address: [byte; 32],
+        }
+    }
+
+    pub(crate) fn grams(value: u64) -> CurrencyCollection {
+        CurrencyCollection {
+            grams: BigUint::from(value),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn given_in_msg() -> Cell {
+        let mut builder = Cell::builder();
+        builder
+            .pack(false)
+            .unwrap()
+            .pack([true, true, false])
+            .unwrap()
+            .pack(address(1))
+            .unwrap()
+            .pack(address(7))
+            .unwrap()
+            .store(grams(1_000_000_000))
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(0u8))
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(1000u16))
+            .unwrap()
+            .pack(90u64)
+            .unwrap()
+            .pack(1700000000u32)
+            .unwrap()
+            // no init, inline body
+            .pack([false, false])
+            .unwrap()
+            .pack(0x12345678u32)
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    fn given_out_msg() -> Cell {
+        let mut builder = Cell::builder();
+        builder
+            .pack([true, true])
+            .unwrap()
+            .pack(address(7))
+            .unwrap()
+            // addr_extern$01 len:8
+            .pack_as::<_, NBits<2>>(0b01u8)
+            .unwrap()
+            .pack_as::<_, NBits<9>>(8u16)
+            .unwrap()
+            .pack(0xabu8)
+            .unwrap()
+            .pack(101u64)
+            .unwrap()
+            .pack(1700000000u32)
+            .unwrap()
+            .pack([false, false])
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    fn given_description(exit_code: i32, bounce: bool) -> Cell {
+        let mut details = Cell::builder();
+        details
+            // gas_used: 1500, gas_limit: 10000
+            .pack_as::<_, NBits<3>>(2u8)
+            .unwrap()
+            .pack(1500u16)
+            .unwrap()
+            .pack_as::<_, NBits<3>>(2u8)
+            .unwrap()
+            .pack(10000u16)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            .pack(0i8)
+            .unwrap()
+            .pack(exit_code)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            .pack(50u32)
+            .unwrap()
+            .pack([1u8; 32])
+            .unwrap()
+            .pack([2u8; 32])
+            .unwrap();
+
+        let mut action = Cell::builder();
+        action
+            .pack([true, true, false, false])
+            .unwrap()
+            .pack([false, false])
+            .unwrap()
+            .pack(0i32)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            .pack([1u16, 0, 0, 1])
+            .unwrap()
+            .pack([3u8; 32])
+            .unwrap()
+            // tot_msg_size: 1 cell, 100 bits
+            .pack_as::<_, NBits<3>>(1u8)
+            .unwrap()
+            .pack(1u8)
+            .unwrap()
+            .pack_as::<_, NBits<3>>(1u8)
+            .unwrap()
+            .pack(100u8)
+            .unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack_as::<_, NBits<4>>(0b0000u8)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            // storage_ph
+            .pack(true)
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(10u8))
+            .unwrap()
+            .pack([false, false])
+            .unwrap()
+            // credit_ph
+            .pack([true, false])
+            .unwrap()
+            .store(grams(1_000_000_000))
+            .unwrap()
+            // compute_ph
+            .pack([true, exit_code == 0, false, false])
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(5000u16))
+            .unwrap()
+            .store_as::<_, Ref>(details.into_cell())
+            .unwrap()
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(action.into_cell())
+            .unwrap()
+            .pack(exit_code != 0)
+            .unwrap();
+        if bounce {
+            // tr_phase_bounce_ok$1
+            builder
+                .pack([true, true])
+                .unwrap()
+                .pack_as::<_, NBits<3>>(0u8)
+                .unwrap()
+                .pack_as::<_, NBits<3>>(0u8)
+                .unwrap()
+                .pack_as::<_, Grams>(BigUint::from(1u8))
+                .unwrap()
+                .pack_as::<_, Grams>(BigUint::from(2u8))
+                .unwrap();
+        } else {
+            builder.pack(false).unwrap();
+        }
+        builder.pack(false).unwrap();
+
+        builder.into_cell()
+    }
+
+    pub(crate) fn given_transaction(exit_code: i32, bounce: bool) -> Cell {
+        let mut messages = Cell::builder();
+        messages
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(given_in_msg())
+            .unwrap();
+        let mut out_msgs = Cell::builder();
+        // hml_same$11 v:0 n:15
+        out_msgs
+            .pack_as::<_, NBits<2>>(0b11u8)
+            .unwrap()
+            .pack(false)
+            .unwrap()
+            .pack_as::<_, NBits<4>>(15u8)
+            .unwrap()
+            .store_as::<_, Ref>(given_out_msg())
+            .unwrap();
+        messages
+            .pack(true)
+            .unwrap()
+            .store_as::<_, Ref>(out_msgs.into_cell())
+            .unwrap();
+
+        let mut state_update = Cell::builder();
+        state_update
+            .pack(0x72u8)
+            .unwrap()
+            .pack([4u8; 32])
+            .unwrap()
+            .pack([5u8; 32])
+            .unwrap();
+
+        let mut builder = Cell::builder();
+        builder
+            .pack_as::<_, NBits<4>>(0b0111u8)
+            .unwrap()
+            .pack([7u8; 32])
+            .unwrap()
+            .pack(100u64)
+            .unwrap()
+            .pack([8u8; 32])
+            .unwrap()
+            .pack(95u64)
+            .unwrap()
+            .pack(1700000001u32)
+            .unwrap()
+            .pack_as::<_, NBits<15>>(1u16)
+            .unwrap()
+            .pack_as::<_, NBits<2>>(0b10u8)
+            .unwrap()
+            .pack_as::<_, NBits<2>>(0b10u8)
+            .unwrap()
+            .store_as::<_, Ref>(messages.into_cell())
+            .unwrap()
+            .store(grams(5010))
+            .unwrap()
+            .store_as::<_, Ref>(state_update.into_cell())
+            .unwrap()
+            .store_as::<_, Ref>(given_description(exit_code, bounce))
+            .unwrap();
+
+        builder.into_cell()
+    }
+
+    #[test]
+    fn parse_ordinary_transaction() {
+        let cell = given_transaction(0, false);
+
+        let transaction: Transaction = cell.parse_fully().unwrap();
+
+        assert_eq!(transaction.lt, 100);
+        assert_eq!(transaction.end_status, AccountStatus::Active);
+        assert_eq!(transaction.state_update.new_hash, [5; 32]);
+        assert!(matches!(
+            transaction.in_msg.as_ref().map(|m| &m.info),
+            Some(CommonMsgInfo::Internal(info)) if info.bounce && info.created_lt == 90
+        ));
+        assert_eq!(transaction.out_msgs.len(), 1);
+        assert!(matches!(
+            &transaction.out_msgs[0].info,
+            CommonMsgInfo::ExternalOut(info) if info.dest != MsgAddressExt::None
+        ));
+
+        let Some(TrComputePhase::Vm(compute)) = transaction.description.compute_ph() else {
+            panic!("compute phase has to be executed");
+        };
+        assert!(compute.success);
+        assert_eq!(compute.gas_used, 1500);
+        assert_eq!(compute.gas_limit, 10000);
+        assert_eq!(compute.exit_code, 0);
+        assert_eq!(compute.vm_steps, 50);
+        let action = transaction.description.action().unwrap();
+        assert_eq!(action.msgs_created, 1);
+        assert_eq!(action.tot_msg_size.bits, 100);
+        assert_eq!(transaction.description.bounce(), None);
+    }
+
+    #[test]
+    fn parse_bounced_transaction() {
+        let cell = given_transaction(35, true);
+
+        let transaction: Transaction = cell.parse_fully().unwrap();
+
+        assert!(transaction.description.aborted());
+        assert!(matches!(
+            transaction.description.compute_ph(),
+            Some(TrComputePhase::Vm(compute)) if !compute.success && compute.exit_code == 35
+        ));
+        assert!(matches!(
+            transaction.description.bounce(),
+            Some(TrBouncePhase::Ok { fwd_fees, .. }) if *fwd_fees == BigUint::from(2u8)
+        ));
+    }
+
+    #[test]
+    fn parse_storage_transaction_descr() {
+        let mut builder = Cell::builder();
+        builder
+            .pack_as::<_, NBits<4>>(0b0001u8)
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(10u8))
+            .unwrap()
+            .pack([false, true, false])
+            .unwrap();
+
+        let descr: TransactionDescr = builder.into_cell().parse_fully().unwrap();
+
+        assert!(matches!(descr, TransactionDescr::Storage { .. }));
+        assert_eq!(descr.compute_ph(), None);
+    }
+
+    #[test]
+    fn unpack_bounce_phase_no_funds() {
+        let mut builder = Cell::builder();
+        builder
+            .pack([false, true])
+            .unwrap()
+            .pack_as::<_, NBits<3>>(1u8)
+            .unwrap()
+            .pack(2u8)
+            .unwrap()
+            .pack_as::<_, NBits<3>>(0u8)
+            .unwrap()
+            .pack_as::<_, Grams>(BigUint::from(3u8))
+            .unwrap();
+        let cell = builder.into_cell();
+
+        let phase: TrBouncePhase = cell.parser().unpack().unwrap();
+
+        assert!(matches!(
+            phase,
+            TrBouncePhase::NoFunds { msg_size, .. } if msg_size.cells == 2
+        ));
+    }
+}
@@ -0,0 +1,227 @@
+use crate::tlb::tr_action_phase::TrActionPhase;
+use crate::tlb::tr_bounce_phase::TrBouncePhase;
+use crate::tlb::tr_compute_phase::TrComputePhase;
+use crate::tlb::tr_credit_phase::TrCreditPhase;
+use crate::tlb::tr_storage_phase::TrStoragePhase;
+use crate::tlb::transaction::Transaction;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+
+/// ```tlb
+/// split_merge_info$_ cur_shard_pfx_len:(## 6)
+///   acc_split_depth:(## 6) this_addr:bits256 sibling_addr:bits256
+///   = SplitMergeInfo;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMergeInfo {
+    pub cur_shard_pfx_len: u8,
+    pub acc_split_depth: u8,
+    pub this_addr: [u8; 32],
+    pub sibling_addr: [u8; 32],
+}
+
+impl BitUnpack for SplitMergeInfo {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        Ok(Self {
+            cur_shard_pfx_len: reader.unpack_as::<_, NBits<6>>()?,
+            acc_split_depth: reader.unpack_as::<_, NBits<6>>()?,
+            this_addr: reader.unpack()?,
+            sibling_addr: reader.unpack()?,
+        })
+    }
+}
+
+/// ```tlb
+/// trans_ord$0000 credit_first:Bool
+///   storage_ph:(Maybe TrStoragePhase)
+///   credit_ph:(Maybe TrCreditPhase)
+///   compute_ph:TrComputePhase action:(Maybe ^TrActionPhase)
+///   aborted:Bool bounce:(Maybe TrBouncePhase)
+///   destroyed:Bool
+///   = TransactionDescr;
+/// trans_storage$0001 storage_ph:TrStoragePhase
+///   = TransactionDescr;
+/// trans_tick_tock$001 is_tock:Bool storage_ph:TrStoragePhase
+///   compute_ph:TrComputePhase action:(Maybe ^TrActionPhase)
+///   aborted:Bool destroyed:Bool = TransactionDescr;
+/// trans_split_prepare$0100 split_info:SplitMergeInfo
+///   storage_ph:(Maybe TrStoragePhase)
+///   compute_ph:TrComputePhase action:(Maybe ^TrActionPhase)
+///   aborted:Bool destroyed:Bool
+///   = TransactionDescr;
+/// trans_split_install$0101 split_info:SplitMergeInfo
+///   prepare_transaction:^Transaction
+///   installed:Bool = TransactionDescr;
+/// trans_merge_prepare$0110 split_info:SplitMergeInfo
+///   storage_ph:TrStoragePhase aborted:Bool
+///   = TransactionDescr;
+/// trans_merge_install$0111 split_info:SplitMergeInfo
+///   prepare_transaction:^Transaction
+///   storage_ph:(Maybe TrStoragePhase)
+///   credit_ph:(Maybe TrCreditPhase)
+///   compute_ph:TrComputePhase action:(Maybe ^TrActionPhase)
+///   aborted:Bool destroyed:Bool
+///   = TransactionDescr;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionDescr {
+    Ordinary {
+        credit_first: bool,
+        storage_ph: Option<TrStoragePhase>,
+        credit_ph: Option<TrCreditPhase>,
+        compute_ph: TrComputePhase,
+        action: Option<TrActionPhase>,
+        aborted: bool,
+        bounce: Option<TrBouncePhase>,
+        destroyed: bool,
+    },
+    Storage {
+        storage_ph: TrStoragePhase,
+    },
+    TickTock {
+        is_tock: bool,
+        storage_ph: TrStoragePhase,
+        compute_ph: TrComputePhase,
+        action: Option<TrActionPhase>,
+        aborted: bool,
+        destroyed: bool,
+    },
+    SplitPrepare {
+        split_info: SplitMergeInfo,
+        storage_ph: Option<TrStoragePhase>,
+        compute_ph: TrComputePhase,
+        action: Option<TrActionPhase>,
+        aborted: bool,
+        destroyed: bool,
+    },
+    SplitInstall {
+        split_info: SplitMergeInfo,
+        prepare_transaction: Box<Transaction>,
+        installed: bool,
+    },
+    MergePrepare {
+        split_info: SplitMergeInfo,
+        storage_ph: TrStoragePhase,
+        aborted: bool,
+    },
+    MergeInstall {
+        split_info: SplitMergeInfo,
+        prepare_transaction: Box<Transaction>,
+        storage_ph: Option<TrStoragePhase>,
+        credit_ph: Option<TrCreditPhase>,
+        compute_ph: TrComputePhase,
+        action: Option<TrActionPhase>,
+        aborted: bool,
+        destroyed: bool,
+    },
+}
+
+impl TransactionDescr {
+    pub fn compute_ph(&self) -> Option<&TrComputePhase> {
+        match self {
+            Self::Ordinary { compute_ph, .. }
+            | Self::TickTock { compute_ph, .. }
+            | Self::SplitPrepare { compute_ph, .. }
+            | Self::MergeInstall { compute_ph, .. } => Some(compute_ph),
+            _ => None,
+        }
+    }
+
+    pub fn action(&self) -> Option<&TrActionPhase> {
+        match self {
+            Self::Ordinary { action, .. }
+            | Self::TickTock { action, .. }
+            | Self::SplitPrepare { action, .. }
+            | Self::MergeInstall { action, .. } => action.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn bounce(&self) -> Option<&TrBouncePhase> {
+        match self {
+            Self::Ordinary { bounce, .. } => bounce.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn aborted(&self) -> bool {
+        match self {
+            Self::Ordinary { aborted, .. }
+            | Self::TickTock { aborted, .. }
+            | Self::SplitPrepare { aborted, .. }
+            | Self::MergePrepare { aborted, .. }
+            | Self::MergeInstall { aborted, .. } => *aborted,
+            Self::Storage { .. } | Self::SplitInstall { .. } => false,
+        }
+    }
+}
+
+impl<'de> CellDeserialize<'de> for TransactionDescr {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack_as::<_, NBits<3>>()?;
+        if tag == 0b001 {
+            return Ok(Self::TickTock {
+                is_tock: parser.unpack()?,
+                storage_ph: parser.unpack()?,
+                compute_ph: parser.parse()?,
+                action: parser.parse_as::<_, Option<Ref<ParseFully>>>()?,
+                aborted: parser.unpack()?,
+                destroyed: parser.unpack()?,
+            });
+        }
+
+        let tag = tag << 1 | u8::from(parser.unpack::<bool>()?);
+        match tag {
+            0b0000 => Ok(Self::Ordinary {
+                credit_first: parser.unpack()?,
+                storage_ph: parser.unpack()?,
+                credit_ph: parser.parse()?,
+                compute_ph: parser.parse()?,
+                action: parser.parse_as::<_, Option<Ref<ParseFully>>>()?,
+                aborted: parser.unpack()?,
+                bounce: parser.unpack()?,
+                destroyed: parser.unpack()?,
+            }),
+            0b0001 => Ok(Self::Storage {
+                storage_ph: parser.unpack()?,
+            }),
+            0b0100 => Ok(Self::SplitPrepare {
+                split_info: parser.unpack()?,
+                storage_ph: parser.unpack()?,
+                compute_ph: parser.parse()?,
+                action: parser.parse_as::<_, Option<Ref<ParseFully>>>()?,
+                aborted: parser.unpack()?,
+                destroyed: parser.unpack()?,
+            }),
+            0b0101 => Ok(Self::SplitInstall {
+                split_info: parser.unpack()?,
+                prepare_transaction: Box::new(parser.parse_as::<_, Ref<ParseFully>>()?),
+                installed: parser.unpack()?,
+            }),
+            0b0110 => Ok(Self::MergePrepare {
+                split_info: parser.unpack()?,
+                storage_ph: parser.unpack()?,
+                aborted: parser.unpack()?,
+            }),
+            0b0111 => Ok(Self::MergeInstall {
+                split_info: parser.unpack()?,
+                prepare_transaction: Box::new(parser.parse_as::<_, Ref<ParseFully>>()?),
+                storage_ph: parser.unpack()?,
+                credit_ph: parser.parse()?,
+                compute_ph: parser.parse()?,
+                action: parser.parse_as::<_, Option<Ref<ParseFully>>>()?,
+                aborted: parser.unpack()?,
+                destroyed: parser.unpack()?,
+            }),
+            tag => Err(Error::custom(format!(
+                "unexpected transaction descr tag {tag:#06b}"
+            ))),
+        }
+    }
+}
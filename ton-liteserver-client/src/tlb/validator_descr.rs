@@ -0,0 +1,48 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// validator#53 public_key:SigPubKey weight:uint64 = ValidatorDescr;
+/// validator_addr#73 public_key:SigPubKey weight:uint64 adnl_addr:bits256 = ValidatorDescr;
+/// ed25519_pubkey#8e81278a pubkey:bits256 = SigPubKey;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorDescr {
+    pub public_key: [u8; 32],
+    pub weight: u64,
+    pub adnl_addr: Option<[u8; 32]>,
+}
+
+impl BitUnpack for ValidatorDescr {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        if tag != 0x53 && tag != 0x73 {
+            return Err(Error::custom(format!(
+                "unexpected validator descr tag {tag:#x}"
+            )));
+        }
+        let key_tag: u32 = reader.unpack()?;
+        if key_tag != 0x8e81278a {
+            return Err(Error::custom(format!(
+                "unexpected public key tag {key_tag:#x}"
+            )));
+        }
+
+        let public_key = reader.unpack()?;
+        let weight = reader.unpack()?;
+        let adnl_addr = match tag {
+            0x73 => Some(reader.unpack()?),
+            _ => None,
+        };
+
+        Ok(Self {
+            public_key,
+            weight,
+            adnl_addr,
+        })
+    }
+}
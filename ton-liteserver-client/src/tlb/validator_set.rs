@@ -0,0 +1,80 @@
+use crate::tlb::validator_descr::ValidatorDescr;
+use std::collections::BTreeMap;
+use toner::tlb::bits::bitvec::order::Msb0;
+use toner::tlb::bits::bitvec::vec::BitVec;
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{Data, NoArgs};
+use toner::tlb::Error;
+use toner::ton::hashmap::{Hashmap, HashmapE};
+
+/// ```tlb
+/// validators#11 utime_since:uint32 utime_until:uint32
+///   total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+///   list:(Hashmap 16 ValidatorDescr) = ValidatorSet;
+/// validators_ext#12 utime_since:uint32 utime_until:uint32
+///   total:(## 16) main:(## 16) { main <= total } { main >= 1 }
+///   total_weight:uint64 list:(HashmapE 16 ValidatorDescr) = ValidatorSet;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSet {
+    pub utime_since: u32,
+    pub utime_until: u32,
+    pub total: u16,
+    /// Validators of the masterchain, the first `main` entries of `list`.
+    pub main: u16,
+    /// Summed up for `validators#11`.
+    pub total_weight: u64,
+    /// Validators in the order of their indexes.
+    pub list: Vec<ValidatorDescr>,
+}
+
+impl<'de> CellDeserialize<'de> for ValidatorSet {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u8 = parser.unpack()?;
+        if tag != 0x11 && tag != 0x12 {
+            return Err(Error::custom(format!(
+                "unexpected validator set tag {tag:#x}"
+            )));
+        }
+
+        let utime_since = parser.unpack()?;
+        let utime_until = parser.unpack()?;
+        let total = parser.unpack()?;
+        let main = parser.unpack()?;
+        if main == 0 || main > total {
+            return Err(Error::custom(format!(
+                "invalid validator set size {main} of {total}"
+            )));
+        }
+
+        let (total_weight, list) = match tag {
+            0x11 => {
+                let list = parser.parse_as_with::<
+                    BTreeMap<BitVec<u8, Msb0>, ValidatorDescr>,
+                    Hashmap<NoArgs<_, Data>, ()>
+                >((16, ()))?;
+
+                (list.values().map(|v| v.weight).sum(), list)
+            }
+            _ => {
+                let total_weight = parser.unpack()?;
+                let list = parser.parse_as_with::<
+                    BTreeMap<BitVec<u8, Msb0>, ValidatorDescr>,
+                    HashmapE<NoArgs<_, Data>, ()>
+                >((16, ()))?;
+
+                (total_weight, list)
+            }
+        };
+
+        Ok(Self {
+            utime_since,
+            utime_until,
+            total,
+            main,
+            total_weight,
+            list: list.into_values().collect(),
+        })
+    }
+}
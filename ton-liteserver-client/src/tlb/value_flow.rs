@@ -0,0 +1,78 @@
+use toner::tlb::bits::de::BitReaderExt;
+use toner::tlb::de::{CellDeserialize, CellParser, CellParserError};
+use toner::tlb::r#as::{ParseFully, Ref};
+use toner::tlb::Error;
+use toner::ton::currency::CurrencyCollection;
+
+/// ```tlb
+/// value_flow#b8e48dfb ^[ from_prev_blk:CurrencyCollection
+///   to_next_blk:CurrencyCollection
+///   imported:CurrencyCollection
+///   exported:CurrencyCollection ]
+///   fees_collected:CurrencyCollection
+///   ^[
+///   fees_imported:CurrencyCollection
+///   recovered:CurrencyCollection
+///   created:CurrencyCollection
+///   minted:CurrencyCollection
+///   ] = ValueFlow;
+///
+/// value_flow_v2#3ebf98b7 ^[ from_prev_blk:CurrencyCollection
+///   to_next_blk:CurrencyCollection
+///   imported:CurrencyCollection
+///   exported:CurrencyCollection ]
+///   fees_collected:CurrencyCollection
+///   burned:CurrencyCollection
+///   ^[
+///   fees_imported:CurrencyCollection
+///   recovered:CurrencyCollection
+///   created:CurrencyCollection
+///   minted:CurrencyCollection
+///   ] = ValueFlow;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueFlow {
+    pub from_prev_blk: CurrencyCollection,
+    pub to_next_blk: CurrencyCollection,
+    pub imported: CurrencyCollection,
+    pub exported: CurrencyCollection,
+    pub fees_collected: CurrencyCollection,
+    /// Present since `value_flow_v2`.
+    pub burned: Option<CurrencyCollection>,
+    pub fees_imported: CurrencyCollection,
+    pub recovered: CurrencyCollection,
+    pub created: CurrencyCollection,
+    pub minted: CurrencyCollection,
+}
+
+impl<'de> CellDeserialize<'de> for ValueFlow {
+    fn parse(parser: &mut CellParser<'de>) -> Result<Self, CellParserError<'de>> {
+        let tag: u32 = parser.unpack()?;
+        if tag != 0xb8e48dfb && tag != 0x3ebf98b7 {
+            return Err(Error::custom(format!("unexpected value flow tag {tag:#x}")));
+        }
+
+        let (from_prev_blk, to_next_blk, imported, exported) =
+            parser.parse_as::<_, Ref<ParseFully>>()?;
+        let fees_collected = parser.parse()?;
+        let burned = match tag {
+            0x3ebf98b7 => Some(parser.parse()?),
+            _ => None,
+        };
+        let (fees_imported, recovered, created, minted) =
+            parser.parse_as::<_, Ref<ParseFully>>()?;
+
+        Ok(Self {
+            from_prev_blk,
+            to_next_blk,
+            imported,
+            exported,
+            fees_collected,
+            burned,
+            fees_imported,
+            recovered,
+            created,
+            minted,
+        })
+    }
+}
@@ -0,0 +1,24 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt};
+use toner::tlb::Error;
+
+/// ```tlb
+/// var_uint$_ {n:#} len:(#< n) value:(uint (len * 8)) = VarUInteger n;
+/// ```
+/// `len_bits` is the width of `len`, values up to 8 bytes long are supported.
+pub(crate) fn unpack_var_uint<R>(reader: &mut R, len_bits: usize) -> Result<u64, R::Error>
+where
+    R: BitReader,
+{
+    let len = (0..len_bits).try_fold(0usize, |len, _| {
+        Ok(len << 1 | usize::from(reader.unpack::<bool>()?))
+    })?;
+    if len > 8 {
+        return Err(Error::custom(format!("VarUInteger of {len} bytes")));
+    }
+
+    (0..len).try_fold(0, |value, _| {
+        let byte: u8 = reader.unpack()?;
+
+        Ok(value << 8 | u64::from(byte))
+    })
+}
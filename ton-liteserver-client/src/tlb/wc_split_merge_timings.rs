@@ -0,0 +1,38 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// wc_split_merge_timings#0
+///   split_merge_delay:uint32 split_merge_interval:uint32
+///   min_split_merge_interval:uint32 max_split_merge_delay:uint32
+///   = WcSplitMergeTimings;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WcSplitMergeTimings {
+    pub split_merge_delay: u32,
+    pub split_merge_interval: u32,
+    pub min_split_merge_interval: u32,
+    pub max_split_merge_delay: u32,
+}
+
+impl BitUnpack for WcSplitMergeTimings {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<4>>()?;
+        if tag != 0 {
+            return Err(Error::custom(format!(
+                "unexpected split merge timings tag {tag:#x}"
+            )));
+        }
+
+        Ok(Self {
+            split_merge_delay: reader.unpack()?,
+            split_merge_interval: reader.unpack()?,
+            min_split_merge_interval: reader.unpack()?,
+            max_split_merge_delay: reader.unpack()?,
+        })
+    }
+}
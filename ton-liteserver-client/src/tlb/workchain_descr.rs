@@ -0,0 +1,88 @@
+use crate::tlb::wc_split_merge_timings::WcSplitMergeTimings;
+use crate::tlb::workchain_format::WorkchainFormat;
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// workchain#a6 enabled_since:uint32 actual_min_split:(## 8)
+///   min_split:(## 8) max_split:(## 8) { actual_min_split <= min_split }
+///   basic:(## 1) active:Bool accept_msgs:Bool flags:(## 13) { flags = 0 }
+///   zerostate_root_hash:bits256 zerostate_file_hash:bits256
+///   version:uint32 format:(WorkchainFormat basic)
+///   = WorkchainDescr;
+/// workchain_v2#a7 enabled_since:uint32 actual_min_split:(## 8)
+///   min_split:(## 8) max_split:(## 8) { actual_min_split <= min_split }
+///   basic:(## 1) active:Bool accept_msgs:Bool flags:(## 13) { flags = 0 }
+///   zerostate_root_hash:bits256 zerostate_file_hash:bits256
+///   version:uint32 format:(WorkchainFormat basic)
+///   split_merge_timings:WcSplitMergeTimings
+///   = WorkchainDescr;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkchainDescr {
+    pub enabled_since: u32,
+    pub actual_min_split: u8,
+    pub min_split: u8,
+    pub max_split: u8,
+    pub basic: bool,
+    pub active: bool,
+    pub accept_msgs: bool,
+    pub flags: u16,
+    pub zerostate_root_hash: [u8; 32],
+    pub zerostate_file_hash: [u8; 32],
+    pub version: u32,
+    pub format: WorkchainFormat,
+    /// Present since `workchain_v2`.
+    pub split_merge_timings: Option<WcSplitMergeTimings>,
+}
+
+impl BitUnpack for WorkchainDescr {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<8>>()?;
+        if tag != 0xa6 && tag != 0xa7 {
+            return Err(Error::custom(format!(
+                "unexpected workchain descr tag {tag:#x}"
+            )));
+        }
+
+        let enabled_since = reader.unpack()?;
+        let actual_min_split = reader.unpack()?;
+        let min_split = reader.unpack()?;
+        let max_split = reader.unpack()?;
+        let basic = reader.unpack()?;
+        let active = reader.unpack()?;
+        let accept_msgs = reader.unpack()?;
+        let flags = reader.unpack_as::<_, NBits<13>>()?;
+        let zerostate_root_hash = reader.unpack()?;
+        let zerostate_file_hash = reader.unpack()?;
+        let version = reader.unpack()?;
+        let format: WorkchainFormat = reader.unpack()?;
+        if basic != matches!(format, WorkchainFormat::Basic { .. }) {
+            return Err(Error::custom("workchain format doesn't match basic flag"));
+        }
+        let split_merge_timings = match tag {
+            0xa7 => Some(reader.unpack()?),
+            _ => None,
+        };
+
+        Ok(Self {
+            enabled_since,
+            actual_min_split,
+            min_split,
+            max_split,
+            basic,
+            active,
+            accept_msgs,
+            flags,
+            zerostate_root_hash,
+            zerostate_file_hash,
+            version,
+            format,
+            split_merge_timings,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use toner::tlb::bits::de::{BitReader, BitReaderExt, BitUnpack};
+use toner::tlb::bits::r#as::NBits;
+use toner::tlb::Error;
+
+/// ```tlb
+/// wfmt_basic#1 vm_version:int32 vm_mode:uint64 = WorkchainFormat 1;
+/// wfmt_ext#0 min_addr_len:(## 12) max_addr_len:(## 12) addr_len_step:(## 12)
+///   { min_addr_len >= 64 } { min_addr_len <= max_addr_len }
+///   { max_addr_len <= 1023 } { addr_len_step <= 1023 }
+///   workchain_type_id:(## 32) { workchain_type_id >= 1 }
+///   = WorkchainFormat 0;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkchainFormat {
+    Basic {
+        vm_version: i32,
+        vm_mode: u64,
+    },
+    Extended {
+        min_addr_len: u16,
+        max_addr_len: u16,
+        addr_len_step: u16,
+        workchain_type_id: u32,
+    },
+}
+
+impl BitUnpack for WorkchainFormat {
+    fn unpack<R>(mut reader: R) -> Result<Self, R::Error>
+    where
+        R: BitReader,
+    {
+        let tag: u8 = reader.unpack_as::<_, NBits<4>>()?;
+
+        Ok(match tag {
+            0x1 => Self::Basic {
+                vm_version: reader.unpack()?,
+                vm_mode: reader.unpack()?,
+            },
+            0x0 => Self::Extended {
+                min_addr_len: reader.unpack_as::<_, NBits<12>>()?,
+                max_addr_len: reader.unpack_as::<_, NBits<12>>()?,
+                addr_len_step: reader.unpack_as::<_, NBits<12>>()?,
+                workchain_type_id: reader.unpack()?,
+            },
+            _ => {
+                return Err(Error::custom(format!(
+                    "unexpected workchain format tag {tag:#x}"
+                )))
+            }
+        })
+    }
+}
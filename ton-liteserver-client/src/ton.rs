@@ -0,0 +1,470 @@
+use crate::client::Error;
+use crate::light_client::LightClient;
+use crate::make::MakeClient;
+use crate::reconnecting_client::ReconnectingClient;
+use crate::retry::RetryPolicy;
+use crate::tl::{
+    LiteServerGetMasterchainInfo, LiteServerLookupBlock, LiteServerMasterchainInfo,
+    LiteServerSendMessage, LiteServerSendMsgStatus, TonNodeBlockId, TonNodeBlockIdExt,
+};
+use crate::tracked_client::TrackedClient;
+use anyhow::anyhow;
+use base64::Engine;
+use futures::{Stream, StreamExt};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tokio_util::either;
+use ton_client_util::discover::config::{LiteServer, LiteServerId};
+use ton_client_util::discover::{
+    read_ton_config_from_file_stream, read_ton_config_from_url_stream, IpPreference,
+    LiteServerDiscover,
+};
+use ton_client_util::router::balance::Balance;
+use ton_client_util::service::shared::SharedService;
+use ton_client_util::service::timeout::Timeout;
+use tower::discover::Change;
+use tower::limit::ConcurrencyLimit;
+use tower::retry::budget::Budget;
+use tower::retry::Retry;
+use tower::util::{Either, MapErr};
+use tower::{BoxError, Layer, Service, ServiceExt};
+use url::Url;
+
+#[cfg(not(feature = "testnet"))]
+pub fn default_ton_config_url() -> Url {
+    Url::from_str("https://raw.githubusercontent.com/ton-blockchain/ton-blockchain.github.io/main/global.config.json").unwrap()
+}
+
+#[cfg(feature = "testnet")]
+pub fn default_ton_config_url() -> Url {
+    Url::from_str("https://raw.githubusercontent.com/ton-blockchain/ton-blockchain.github.io/main/testnet-global.config.json").unwrap()
+}
+
+type ErrorFn = fn(BoxError) -> Error;
+type Client =
+    TrackedClient<ConcurrencyLimit<MapErr<Timeout<SharedService<ReconnectingClient>>, ErrorFn>>>;
+type BoxClientDiscover =
+    Pin<Box<dyn Stream<Item = Result<Change<LiteServerId, Client>, anyhow::Error>> + Send>>;
+type SharedBalance = SharedService<Balance<Client, BoxClientDiscover>>;
+type Inner = Timeout<Either<Retry<RetryPolicy, SharedBalance>, SharedBalance>>;
+
+/// Client balancing requests between the liteservers of the global config.
+#[derive(Clone)]
+pub struct TonClient {
+    client: Inner,
+    balance: SharedBalance,
+    light_client: Option<LightClient<Inner>>,
+    timeout: Duration,
+}
+
+enum ConfigSource {
+    FromFile { path: PathBuf },
+    FromUrl { url: Url, interval: Duration },
+}
+
+pub struct TonClientBuilder {
+    config_source: ConfigSource,
+    ip_preference: IpPreference,
+    timeout: Duration,
+    liteserver_timeout: Duration,
+    concurrency_limit: usize,
+    init_block: Option<TonNodeBlockIdExt>,
+    retry_enabled: bool,
+    retry_budget_ttl: Duration,
+    retry_min_per_sec: u32,
+    retry_percent: f32,
+    retry_first_delay: Duration,
+    retry_max_delay: Duration,
+}
+
+impl Default for TonClientBuilder {
+    fn default() -> Self {
+        Self {
+            config_source: ConfigSource::FromUrl {
+                url: default_ton_config_url(),
+                interval: Duration::from_secs(60),
+            },
+            ip_preference: IpPreference::default(),
+            timeout: Duration::from_secs(10),
+            liteserver_timeout: Duration::from_secs(5),
+            concurrency_limit: 1000,
+            init_block: None,
+            retry_enabled: true,
+            retry_budget_ttl: Duration::from_secs(10),
+            retry_min_per_sec: 10,
+            retry_percent: 0.1,
+            retry_first_delay: Duration::from_millis(128),
+            retry_max_delay: Duration::from_millis(4096),
+        }
+    }
+}
+
+impl TonClientBuilder {
+    pub fn from_config_path(path: PathBuf) -> Self {
+        Self {
+            config_source: ConfigSource::FromFile { path },
+            ..Default::default()
+        }
+    }
+
+    pub fn from_config_url(url: Url, interval: Duration) -> Self {
+        Self {
+            config_source: ConfigSource::FromUrl { url, interval },
+            ..Default::default()
+        }
+    }
+
+    pub fn set_ip_preference(mut self, ip_preference: IpPreference) -> Self {
+        self.ip_preference = ip_preference;
+
+        self
+    }
+
+    pub fn disable_retry(mut self) -> Self {
+        self.retry_enabled = false;
+
+        self
+    }
+
+    pub fn set_retry_budget_ttl(mut self, budget_ttl: Duration) -> Self {
+        self.retry_budget_ttl = budget_ttl;
+
+        self
+    }
+
+    pub fn set_retry_min_per_sec(mut self, retry_min_per_sec: u32) -> Self {
+        self.retry_min_per_sec = retry_min_per_sec;
+
+        self
+    }
+
+    pub fn set_retry_percent(mut self, retry_percent: f32) -> Self {
+        self.retry_percent = retry_percent;
+
+        self
+    }
+
+    pub fn set_retry_first_delay(mut self, first_delay: Duration) -> Self {
+        self.retry_first_delay = first_delay;
+
+        self
+    }
+
+    pub fn set_retry_max_delay(mut self, delay: Duration) -> Self {
+        self.retry_max_delay = delay;
+
+        self
+    }
+
+    /// Timeout of a request including all of its retries.
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Timeout of a single attempt to a liteserver.
+    pub fn set_liteserver_timeout(mut self, timeout: Duration) -> Self {
+        self.liteserver_timeout = timeout;
+
+        self
+    }
+
+    /// Limit of in-flight requests per liteserver.
+    pub fn set_concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = limit;
+
+        self
+    }
+
+    /// Verifies the last masterchain block by the key blocks since the trusted `init_block`,
+    /// usually [`init_block`](crate::light_client::init_block) of the global config.
+    /// Requests are routed to a liteserver only by the blocks proven this way.
+    pub fn set_init_block(mut self, init_block: TonNodeBlockIdExt) -> Self {
+        self.init_block = Some(init_block);
+
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<TonClient> {
+        let stream = match self.config_source {
+            ConfigSource::FromFile { path } => {
+                let mut interval = tokio::time::interval(Duration::from_secs(1));
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+                either::Either::Left(read_ton_config_from_file_stream(path, interval))
+            }
+            ConfigSource::FromUrl { url, interval } => {
+                let mut interval = tokio::time::interval(interval);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+                either::Either::Right(read_ton_config_from_url_stream(url, interval))
+            }
+        };
+
+        let (liteserver_timeout, concurrency_limit) =
+            (self.liteserver_timeout, self.concurrency_limit);
+        let init_block = self.init_block.clone();
+        let client_discover = LiteServerDiscover::with_ip_preference(stream, self.ip_preference)
+            .map(move |change| match change {
+                Ok(Change::Insert(k, config)) => {
+                    let Some(ls) = config.liteservers.into_iter().next() else {
+                        unreachable!()
+                    };
+                    let client = make_client(
+                        ls,
+                        liteserver_timeout,
+                        concurrency_limit,
+                        init_block.clone(),
+                    )?;
+
+                    Ok(Change::Insert(k, client))
+                }
+                Ok(Change::Remove(k)) => Ok(Change::Remove(k)),
+                Err(_) => unreachable!(),
+            });
+
+        let balance = SharedService::new(Balance::new(client_discover.boxed()));
+        let client = tower::util::option_layer(self.retry_enabled.then(|| {
+            tower::retry::RetryLayer::new(RetryPolicy::new(
+                Budget::new(
+                    self.retry_budget_ttl,
+                    self.retry_min_per_sec,
+                    self.retry_percent,
+                ),
+                self.retry_first_delay.as_millis() as u64,
+                self.retry_max_delay,
+            ))
+        }))
+        .layer(balance.clone());
+        let client = Timeout::new(client, self.timeout);
+        let light_client = self
+            .init_block
+            .map(|init_block| LightClient::new(client.clone(), init_block));
+
+        Ok(TonClient {
+            client,
+            balance,
+            light_client,
+            timeout: self.timeout,
+        })
+    }
+}
+
+/// Reconnecting client of the liteserver `ls` tracking its known blocks, only the ones proven
+/// from `init_block` if it's set.
+fn make_client(
+    ls: LiteServer,
+    timeout: Duration,
+    concurrency_limit: usize,
+    init_block: Option<TonNodeBlockIdExt>,
+) -> anyhow::Result<Client> {
+    let mut key = [0; 32];
+    base64::engine::general_purpose::STANDARD.decode_slice(&ls.id.key, &mut key)?;
+    let proxy = ls.proxy.clone();
+    let addr = SocketAddr::try_from(ls)?;
+    let make_client = match proxy {
+        None => MakeClient::new(addr, key),
+        Some(proxy) => MakeClient::new(addr, key).set_proxy(proxy),
+    };
+
+    let client = ReconnectingClient::new(make_client);
+    let client = Timeout::new(SharedService::new(client), timeout);
+    let client = MapErr::new(client, timeout_error as ErrorFn);
+
+    let client = ConcurrencyLimit::new(client, concurrency_limit);
+
+    Ok(match init_block {
+        Some(init_block) => TrackedClient::verified(client, init_block),
+        None => TrackedClient::new(client),
+    })
+}
+
+fn timeout_error(e: BoxError) -> Error {
+    match e.downcast::<Error>() {
+        Ok(e) => *e,
+        Err(_) => Error::Elapsed,
+    }
+}
+
+impl TonClient {
+    pub async fn ready(&mut self) -> anyhow::Result<()> {
+        self.get_masterchain_info().await?;
+        tracing::info!("ready");
+
+        Ok(())
+    }
+
+    /// Verified by the key blocks if the builder has an init block.
+    pub async fn get_masterchain_info(&self) -> anyhow::Result<LiteServerMasterchainInfo> {
+        let request = LiteServerGetMasterchainInfo::default();
+
+        match &self.light_client {
+            Some(light_client) => light_client.clone().oneshot(request).await,
+            None => self.clone().oneshot(request).await,
+        }
+        .map_err(|e| anyhow!(e))
+    }
+
+    pub async fn look_up_block_by_seqno(
+        &self,
+        chain: i32,
+        shard: i64,
+        seqno: i32,
+    ) -> anyhow::Result<TonNodeBlockIdExt> {
+        if seqno <= 0 {
+            return Err(anyhow!("seqno must be greater than 0"));
+        }
+
+        self.clone()
+            .oneshot(LiteServerLookupBlock::seqno(TonNodeBlockId::new(
+                chain, shard, seqno,
+            )))
+            .await
+            .map(|header| header.id)
+            .map_err(|e| anyhow!(e))
+    }
+
+    pub async fn look_up_block_by_lt(
+        &self,
+        chain: i32,
+        shard: i64,
+        lt: i64,
+    ) -> anyhow::Result<TonNodeBlockIdExt> {
+        if lt <= 0 {
+            return Err(anyhow!("lt must be greater than 0"));
+        }
+
+        self.clone()
+            .oneshot(LiteServerLookupBlock {
+                mode: 2,
+                id: TonNodeBlockId::new(chain, shard, 0),
+                lt: Some(lt),
+                utime: None,
+            })
+            .await
+            .map(|header| header.id)
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Sends the external message `body` once, it's never retried.
+    pub async fn send_message(&self, body: Vec<u8>) -> anyhow::Result<LiteServerSendMsgStatus> {
+        Timeout::new(self.balance.clone(), self.timeout)
+            .oneshot(LiteServerSendMessage { body })
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+}
+
+impl<R> Service<R> for TonClient
+where
+    Inner: Service<R>,
+{
+    type Response = <Inner as Service<R>>::Response;
+    type Error = <Inner as Service<R>>::Error;
+    type Future = <Inner as Service<R>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.client.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        self.client.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Fixtures, MockLiteServer, MockLiteServerHandle};
+    use serde_json::Value;
+    use ton_client_util::discover::config::TonConfig;
+    use tracing_test::traced_test;
+
+    const MASTERCHAIN_INFO_FIXTURES: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/masterchain_info.txt");
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_discovers_liteservers_from_config() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let path = given_config_path(&server).await?;
+
+        let mut client = TonClientBuilder::from_config_path(path.clone())
+            .set_timeout(Duration::from_secs(30))
+            .build()?;
+        client.ready().await?;
+        let info = client.get_masterchain_info().await?;
+        tokio::fs::remove_file(path).await?;
+
+        assert_eq!(info.last.seqno, 22560807);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn client_verifies_last_block_by_init_block() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let path = given_config_path(&server).await?;
+        let mut client = TonClientBuilder::from_config_path(path.clone())
+            .set_timeout(Duration::from_secs(30))
+            .build()?;
+        client.ready().await?;
+        let last = client.get_masterchain_info().await?.last;
+        let mut unknown = last.clone();
+        unknown.seqno -= 1;
+
+        let trusted = TonClientBuilder::from_config_path(path.clone())
+            .set_init_block(last.clone())
+            .build()?
+            .get_masterchain_info()
+            .await;
+        // the liteserver never gets ready, it has no block proven from the init block
+        let untrusted = TonClientBuilder::from_config_path(path.clone())
+            .set_timeout(Duration::from_secs(3))
+            .disable_retry()
+            .set_init_block(unknown)
+            .build()?;
+        let untrusted = tokio::time::timeout(
+            Duration::from_secs(3),
+            untrusted.look_up_block_by_seqno(-1, i64::MIN, last.seqno),
+        )
+        .await;
+        tokio::fs::remove_file(path).await?;
+
+        assert_eq!(trusted?.last, last);
+        assert!(untrusted.is_err());
+
+        Ok(())
+    }
+
+    async fn given_config_path(server: &MockLiteServerHandle) -> anyhow::Result<PathBuf> {
+        let config = TonConfig {
+            liteservers: vec![LiteServer {
+                id: LiteServerId {
+                    r#type: "pub.ed25519".to_owned(),
+                    key: base64::engine::general_purpose::STANDARD.encode(server.server_key()),
+                },
+                ip: Some(server.addr().ip()),
+                host: None,
+                port: server.addr().port(),
+                proxy: None,
+            }],
+            data: Value::Object(Default::default()),
+        };
+        let path = std::env::temp_dir().join(format!("ton-config-{}.json", server.addr().port()));
+        tokio::fs::write(&path, config.to_string()).await?;
+
+        Ok(path)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::light_client::LightClient;
+use crate::tl::TonNodeBlockIdExt;
 use crate::tracker::masterchain_first_block_tracker::{
     MasterchainFirstBlockTracker, MasterchainFirstBlockTrackerActor,
 };
@@ -44,14 +46,36 @@ impl<S> Load for TrackedClient<S> {
 impl<S> TrackedClient<S>
 where
     S: Clone,
-    MasterchainLastBlockTrackerActor<S>: Actor,
     MasterchainLastBlockHeaderTrackerActor<S>: Actor,
     MasterchainFirstBlockTrackerActor<S>: Actor,
     WorkchainsLastBlocksTrackerActor<S>: Actor,
     WorkchainsFirstBlocksTrackerActor<S>: Actor,
 {
-    pub fn new(inner: S) -> Self {
+    pub fn new(inner: S) -> Self
+    where
+        MasterchainLastBlockTrackerActor<S>: Actor,
+    {
         let masterchain_last_block_tracker = MasterchainLastBlockTracker::new(inner.clone());
+
+        Self::with_masterchain_last_block_tracker(inner, masterchain_last_block_tracker)
+    }
+
+    /// Tracks the last masterchain block only once it's proven from the trusted `init_block`,
+    /// the other trackers follow it, so requests are routed by verified blocks.
+    pub fn verified(inner: S, init_block: TonNodeBlockIdExt) -> Self
+    where
+        MasterchainLastBlockTrackerActor<LightClient<S>>: Actor,
+    {
+        let masterchain_last_block_tracker =
+            MasterchainLastBlockTracker::new(LightClient::new(inner.clone(), init_block));
+
+        Self::with_masterchain_last_block_tracker(inner, masterchain_last_block_tracker)
+    }
+
+    fn with_masterchain_last_block_tracker(
+        inner: S,
+        masterchain_last_block_tracker: MasterchainLastBlockTracker,
+    ) -> Self {
         let masterchain_last_block_header_tracker = MasterchainLastBlockHeaderTracker::new(
             inner.clone(),
             masterchain_last_block_tracker.clone(),
@@ -157,3 +181,97 @@ where
         self.inner.call(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::tests::{given_blocks_server, MASTERCHAIN_INFO_FIXTURES};
+    use crate::mock::{Fixtures, MockLiteServer};
+    use crate::proof::fixtures::FULL_SHARD;
+    use crate::tl::{LiteServerGetMasterchainInfo, LiteServerMasterchainInfo};
+    use adnl_tcp::deserializer::from_bytes_boxed;
+    use adnl_tcp::serializer::to_bytes_boxed;
+    use anyhow::anyhow;
+    use ton_client_util::router::balance::Balance;
+    use tower::discover::ServiceList;
+    use tower::ServiceExt;
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn tracked_client_ready() -> anyhow::Result<()> {
+        let server = MockLiteServer::new(Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?)
+            .spawn()
+            .await?;
+        let mut client = TrackedClient::new(server.connect().await?);
+
+        ServiceExt::<LiteServerGetMasterchainInfo>::ready(&mut client).await?;
+
+        assert_eq!(client.last_seqno(), Some(22560807));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn tracked_client_contains_tracked_blocks() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let client = TrackedClient::new(server.connect().await?);
+        let masterchain = |seqno| BlockCriteria::Seqno {
+            shard: FULL_SHARD as i64,
+            seqno,
+        };
+        let basechain = |seqno| BlockCriteria::Seqno {
+            shard: 0x2000000000000000,
+            seqno,
+        };
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !client.contains(&-1, &masterchain(22460807))
+                || !client.contains(&0, &basechain(43814457))
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+
+        assert!(!client.contains(&-1, &masterchain(22360806)));
+        assert!(!client.contains(&-1, &masterchain(22560808)));
+        assert!(!client.contains(&0, &basechain(43714456)));
+        assert!(!client.contains(&0, &basechain(43914458)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn balance_routes_latest_to_highest_seqno() -> anyhow::Result<()> {
+        let fixtures = Fixtures::load(MASTERCHAIN_INFO_FIXTURES)?;
+        let mut info = from_bytes_boxed::<LiteServerMasterchainInfo>(
+            fixtures
+                .get(&to_bytes_boxed(&LiteServerGetMasterchainInfo::default()))
+                .unwrap(),
+        )?;
+        info.last.seqno += 10;
+        let ahead = Fixtures::new().with(&LiteServerGetMasterchainInfo::default(), &info);
+
+        let lagging = MockLiteServer::new(fixtures).spawn().await?;
+        let ahead = MockLiteServer::new(ahead).spawn().await?;
+        let mut clients = vec![
+            TrackedClient::new(lagging.connect().await?),
+            TrackedClient::new(ahead.connect().await?),
+        ];
+        for client in clients.iter_mut() {
+            ServiceExt::<LiteServerGetMasterchainInfo>::ready(client).await?;
+        }
+
+        let response = Balance::new(ServiceList::new::<LiteServerGetMasterchainInfo>(clients))
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await
+            .map_err(|e| anyhow!(e))?;
+
+        assert_eq!(response.last.seqno, 22560817);
+
+        Ok(())
+    }
+}
@@ -123,30 +123,25 @@ impl MasterchainFirstBlockTracker {
 #[cfg(test)]
 mod test {
     use super::MasterchainFirstBlockTracker;
-    use crate::client::tests::provided_client;
+    use crate::mock::tests::given_blocks_server;
     use crate::tracker::masterchain_last_block_tracker::MasterchainLastBlockTracker;
+    use std::time::Duration;
     use tracing_test::traced_test;
 
-    #[ignore]
     #[tokio::test]
     #[traced_test]
-    async fn masterchain_first_block_tracker_delay() {
-        let client = provided_client().await.unwrap();
+    async fn masterchain_first_block_tracker() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let client = server.connect().await?;
         let last_tracker = MasterchainLastBlockTracker::new(client.clone());
         let first_tracker = MasterchainFirstBlockTracker::new(client, last_tracker);
-        let mut prev_seqno = None;
 
         let mut receiver = first_tracker.receiver();
+        tokio::time::timeout(Duration::from_secs(5), receiver.changed()).await??;
 
-        for _ in 0..5 {
-            receiver.changed().await.unwrap();
+        let seqno = receiver.borrow().as_ref().map(|header| header.info.seq_no);
+        assert_eq!(seqno, Some(22360807));
 
-            let current_seqno = receiver.borrow().as_ref().unwrap().info.seq_no;
-            println!("current_seqno = {}", current_seqno);
-            if let Some(seqno) = prev_seqno {
-                assert!(current_seqno >= seqno);
-            }
-            prev_seqno.replace(current_seqno);
-        }
+        Ok(())
     }
 }
@@ -1,6 +1,6 @@
+use crate::proof::verify_block_header;
 use crate::tl::{LiteServerBoxedBlockHeader, LiteServerGetBlockHeader};
 use crate::tlb::block_header::BlockHeader;
-use crate::tlb::merkle_proof::MerkleProof;
 use crate::tracker::masterchain_last_block_tracker::MasterchainLastBlockTracker;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -9,8 +9,6 @@ use tokio::sync::watch::Ref;
 use tokio_util::sync::{CancellationToken, DropGuard};
 use ton_client_util::actor::cancellable_actor::CancellableActor;
 use ton_client_util::actor::Actor;
-use toner::tlb::bits::de::unpack_bytes_fully;
-use toner::ton::boc::BoC;
 use tower::{Service, ServiceExt};
 
 pub struct MasterchainLastBlockHeaderTrackerActor<S> {
@@ -56,20 +54,16 @@ where
                 .last
                 .clone();
 
-            match (&mut self.client)
-                .oneshot(LiteServerGetBlockHeader::new(last_block_id))
-                .await
-            {
-                Ok(response) => {
-                    let header_bytes = response.header_proof;
-
-                    let boc: BoC = unpack_bytes_fully(&header_bytes).unwrap();
-                    let root = boc.single_root().unwrap();
-
-                    let header: MerkleProof = root.parse_fully().unwrap();
-
-                    self.sender.send(Some(header.virtual_root)).unwrap();
-                }
+            let request = LiteServerGetBlockHeader::new(last_block_id);
+            match (&mut self.client).oneshot(request.clone()).await {
+                Ok(response) => match verify_block_header(&request, &response) {
+                    Ok(header) => {
+                        self.sender.send(Some(header)).unwrap();
+                    }
+                    Err(error) => {
+                        tracing::warn!(block_id = ?request.id, ?error, "block header proof is invalid")
+                    }
+                },
                 Err(error) => {
                     tracing::warn!(?error)
                 }
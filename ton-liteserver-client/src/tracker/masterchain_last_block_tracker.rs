@@ -120,28 +120,52 @@ impl MasterchainLastBlockTracker {
 #[cfg(test)]
 mod test {
     use super::MasterchainLastBlockTracker;
-    use crate::client::tests::provided_client;
+    use crate::light_client::LightClient;
+    use crate::mock::tests::given_blocks_server;
+    use crate::tl::LiteServerGetMasterchainInfo;
+    use std::time::Duration;
+    use tower::ServiceExt;
     use tracing_test::traced_test;
 
-    #[ignore]
     #[tokio::test]
     #[traced_test]
-    async fn masterchain_last_block_delay_test() {
-        let client = provided_client().await.unwrap();
-        let tracker = MasterchainLastBlockTracker::new(client);
-        let mut prev_seqno = None;
+    async fn masterchain_last_block_tracker() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let mut tracker = MasterchainLastBlockTracker::new(server.connect().await?);
 
-        let mut receiver = tracker.receiver();
+        let info =
+            tokio::time::timeout(Duration::from_secs(5), tracker.wait_masterchain_info()).await??;
 
-        for _ in 0..5 {
-            receiver.changed().await.unwrap();
+        assert_eq!(info.last.workchain, -1);
+        assert_eq!(info.last.seqno, 22560807);
 
-            let current_seqno = receiver.borrow().as_ref().unwrap().last.seqno;
-            println!("current_seqno = {}", current_seqno);
-            if let Some(seqno) = prev_seqno {
-                assert!(current_seqno > seqno);
-            }
-            prev_seqno.replace(current_seqno);
-        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn masterchain_last_block_tracker_tracks_proven_blocks_only() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let client = server.connect().await?;
+        let last = client
+            .clone()
+            .oneshot(LiteServerGetMasterchainInfo::default())
+            .await?
+            .last;
+        let mut unknown = last.clone();
+        unknown.seqno -= 1;
+
+        let mut trusted =
+            MasterchainLastBlockTracker::new(LightClient::new(client.clone(), last.clone()));
+        let mut untrusted = MasterchainLastBlockTracker::new(LightClient::new(client, unknown));
+        let info =
+            tokio::time::timeout(Duration::from_secs(5), trusted.wait_masterchain_info()).await??;
+        let untrusted =
+            tokio::time::timeout(Duration::from_secs(1), untrusted.wait_masterchain_info()).await;
+
+        assert_eq!(info.last, last);
+        assert!(untrusted.is_err());
+
+        Ok(())
     }
 }
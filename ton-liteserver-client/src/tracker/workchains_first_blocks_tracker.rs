@@ -175,27 +175,41 @@ impl WorkchainsFirstBlocksTracker {
 #[cfg(test)]
 mod test {
     use super::WorkchainsFirstBlocksTracker;
-    use crate::client::tests::provided_client;
+    use crate::mock::tests::given_blocks_server;
     use crate::tracker::masterchain_last_block_tracker::MasterchainLastBlockTracker;
     use crate::tracker::workchains_last_blocks_tracker::WorkchainsLastBlocksTracker;
+    use std::collections::BTreeSet;
+    use std::time::Duration;
     use tracing_test::traced_test;
 
-    #[ignore]
     #[tokio::test]
     #[traced_test]
-    async fn workchains_first_block_tracker() {
-        let client = provided_client().await.unwrap();
+    async fn workchains_first_block_tracker() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let client = server.connect().await?;
         let masterchain_tracker = MasterchainLastBlockTracker::new(client.clone());
         let workchain_tracker =
             WorkchainsLastBlocksTracker::new(client.clone(), masterchain_tracker);
         let first_tracker = WorkchainsFirstBlocksTracker::new(client.clone(), workchain_tracker);
 
         let mut receiver = first_tracker.receiver();
-
-        println!("wait");
-
-        while let Ok(block) = receiver.recv().await {
-            println!("{:?}", block)
+        let mut seqnos = BTreeSet::new();
+        while seqnos.len() < 4 {
+            let block = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await??;
+            seqnos.insert(block.info.seq_no);
         }
+
+        assert_eq!(
+            seqnos.into_iter().collect::<Vec<_>>(),
+            vec![43713145, 43714083, 43714335, 43714457]
+        );
+        assert_eq!(
+            first_tracker
+                .get_first_block_id_for_shard(&(0, 0x6000000000000000))
+                .map(|header| header.info.seq_no),
+            Some(43713145)
+        );
+
+        Ok(())
     }
 }
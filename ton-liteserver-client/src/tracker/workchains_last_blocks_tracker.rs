@@ -1,6 +1,6 @@
+use crate::proof::verify_all_shards_info;
 use crate::tl::{LiteServerAllShardsInfo, LiteServerGetAllShardsInfo, TonNodeBlockIdExt};
 use crate::tlb::shard_descr::ShardDescr;
-use crate::tlb::shard_hashes::ShardHashes;
 use crate::tracker::masterchain_last_block_tracker::MasterchainLastBlockTracker;
 use crate::tracker::ShardId;
 use dashmap::DashMap;
@@ -11,8 +11,6 @@ use tokio_util::sync::{CancellationToken, DropGuard};
 use ton_client_util::actor::cancellable_actor::CancellableActor;
 use ton_client_util::actor::Actor;
 use ton_client_util::router::shard_prefix::ShardPrefix;
-use toner::tlb::bits::de::unpack_bytes_fully;
-use toner::ton::boc::BoC;
 use tower::{Service, ServiceExt};
 
 pub struct WorkchainsLastBlocksTrackerActor<S> {
@@ -61,16 +59,17 @@ where
                 .last
                 .clone();
 
-            match (&mut self.client)
-                .oneshot(LiteServerGetAllShardsInfo::new(last_block_id))
-                .await
-            {
+            let request = LiteServerGetAllShardsInfo::new(last_block_id);
+            match (&mut self.client).oneshot(request.clone()).await {
                 Ok(shards_description) => {
-                    let boc: BoC = unpack_bytes_fully(&shards_description.data).unwrap();
-                    let root = boc.single_root().unwrap();
-                    let shard_hashes: ShardHashes = root.parse_fully().unwrap();
+                    let shard_hashes = match verify_all_shards_info(&request, &shards_description) {
+                        Ok(shard_hashes) => shard_hashes,
+                        Err(error) => {
+                            tracing::warn!(block_id = ?request.id, ?error, "shards info proof is invalid");
+                            continue;
+                        }
+                    };
 
-                    // TODO[akostylev0]: verify proofs
                     shard_hashes
                         .iter()
                         .flat_map(|(chain_id, shards)| {
@@ -166,22 +165,82 @@ impl WorkchainsLastBlocksTracker {
 #[cfg(test)]
 mod test {
     use super::WorkchainsLastBlocksTracker;
-    use crate::client::tests::provided_client;
+    use crate::mock::tests::{given_blocks_server, BLOCKS_FIXTURES};
+    use crate::mock::{Fixtures, MockLiteServer};
+    use crate::tl::{
+        LiteServerAllShardsInfo, LiteServerGetAllShardsInfo, LiteServerGetMasterchainInfo,
+        LiteServerMasterchainInfo,
+    };
     use crate::tracker::masterchain_last_block_tracker::MasterchainLastBlockTracker;
+    use adnl_tcp::deserializer::from_bytes_boxed;
+    use adnl_tcp::serializer::to_bytes_boxed;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
     use tracing_test::traced_test;
 
-    #[ignore]
     #[tokio::test]
     #[traced_test]
-    async fn workchain_last_block_tracker() {
-        let client = provided_client().await.unwrap();
+    async fn workchain_last_block_tracker() -> anyhow::Result<()> {
+        let server = given_blocks_server().await?;
+        let client = server.connect().await?;
         let last_tracker = MasterchainLastBlockTracker::new(client.clone());
         let workchain_tracker = WorkchainsLastBlocksTracker::new(client, last_tracker);
 
         let mut receiver = workchain_tracker.receiver();
-
-        while let Ok(v) = receiver.recv().await {
-            println!("{:x}", v.shard as u64);
+        let mut shards = BTreeMap::new();
+        while shards.len() < 4 {
+            let block = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await??;
+            shards.insert(block.shard as u64, block.seqno);
         }
+
+        assert_eq!(
+            shards.into_iter().collect::<Vec<_>>(),
+            vec![
+                (0x2000000000000000, 43914457),
+                (0x6000000000000000, 43913145),
+                (0xa000000000000000, 43914083),
+                (0xe000000000000000, 43914335),
+            ]
+        );
+        assert_eq!(
+            workchain_tracker
+                .get_shard(&(0, 0x6000000000000000))
+                .map(|shard| shard.seq_no),
+            Some(43913145)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn workchain_last_block_tracker_skips_invalid_proof() -> anyhow::Result<()> {
+        let mut fixtures = Fixtures::load(BLOCKS_FIXTURES)?;
+        let info: LiteServerMasterchainInfo = from_bytes_boxed(
+            fixtures
+                .get(&to_bytes_boxed(&LiteServerGetMasterchainInfo::default()))
+                .unwrap(),
+        )?;
+        let request = LiteServerGetAllShardsInfo::new(info.last);
+        let mut forged: LiteServerAllShardsInfo =
+            from_bytes_boxed(fixtures.get(&to_bytes_boxed(&request)).unwrap())?;
+        *forged.data.last_mut().unwrap() ^= 1;
+        fixtures.insert(&request, &forged);
+        let server = MockLiteServer::new(fixtures).spawn().await?;
+        let client = server.connect().await?;
+        let last_tracker = MasterchainLastBlockTracker::new(client.clone());
+        let workchain_tracker = WorkchainsLastBlocksTracker::new(client, last_tracker);
+
+        let received = tokio::time::timeout(
+            Duration::from_millis(500),
+            workchain_tracker.receiver().recv(),
+        )
+        .await;
+
+        assert!(received.is_err());
+        assert!(logs_contain("shards info proof is invalid"));
+        assert!(logs_contain("seqno: 22560807"));
+
+        Ok(())
     }
 }
@@ -1,3 +1,4 @@
+use crate::json_repr::JsonRepr;
 use anyhow::{anyhow, Context};
 use base64::display::Base64Display;
 use base64::Engine;
@@ -35,6 +36,16 @@ impl<'de> Deserialize<'de> for AccountAddressData {
     }
 }
 
+impl JsonRepr for AccountAddressData {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize(serializer)
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
 impl FromStr for AccountAddressData {
     type Err = anyhow::Error;
 
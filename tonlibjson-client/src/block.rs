@@ -1,13 +1,14 @@
 use crate::address::{AccountAddressData, InternalAccountAddress, ShardContextAccountAddress};
 use crate::deserialize::{
-    deserialize_default_as_none, deserialize_empty_as_none, deserialize_number_from_string,
-    deserialize_ton_account_balance, serialize_none_as_empty,
+    deserialize_empty_as_none, deserialize_number_from_string, deserialize_ton_account_balance,
+    serialize_none_as_empty,
 };
+use crate::json_repr::{self, JsonRepr};
 use crate::request::Requestable;
 use anyhow::anyhow;
 use derive_new::new;
 use serde::de::DeserializeOwned;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::Ordering;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter};
@@ -111,6 +112,22 @@ impl Default for InternalTransactionId {
     }
 }
 
+/// tonlib writes the zero transaction id when there is no transaction.
+impl JsonRepr for Option<InternalTransactionId> {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            None => InternalTransactionId::default().serialize(serializer),
+            Some(id) => id.serialize(serializer),
+        }
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = InternalTransactionId::deserialize(deserializer)?;
+
+        Ok((id != InternalTransactionId::default()).then_some(id))
+    }
+}
+
 impl AccountAddress {
     // TODO[akostylev0]
     pub fn new(account_address: &str) -> anyhow::Result<Self> {
@@ -559,6 +576,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_zero_transaction_id_as_none() {
+        let json = json!({
+            "@type": "raw.transactions",
+            "transactions": [],
+            "previous_transaction_id": {
+                "@type": "internal.transactionId",
+                "lt": "0",
+                "hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+            }
+        });
+
+        let transactions = serde_json::from_value::<RawTransactions>(json).unwrap();
+
+        assert!(transactions.previous_transaction_id.is_none());
+    }
+
+    #[test]
+    fn transaction_id_roundtrip() {
+        let json = json!({
+            "@type": "internal.transactionId",
+            "lt": 47000000000001i64,
+            "hash": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE="
+        });
+
+        let id = serde_json::from_value::<InternalTransactionId>(json.clone()).unwrap();
+
+        assert_eq!(id.lt, 47000000000001);
+        assert_eq!(serde_json::to_value(id).unwrap(), json);
+    }
+
+    #[test]
+    fn transaction_amounts_roundtrip() {
+        let json = json!({
+            "@type": "raw.transaction",
+            "address": {"@type": "accountAddress", "account_address": ""},
+            "utime": 1700000000,
+            "data": "te6ccgEBAQEAAgAAAA==",
+            "transaction_id": {
+                "@type": "internal.transactionId",
+                "lt": "47000000000001",
+                "hash": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE="
+            },
+            "fee": "1000",
+            "storage_fee": "10",
+            "other_fee": "990",
+            "out_msgs": []
+        });
+
+        let transaction = serde_json::from_value::<RawTransaction>(json).unwrap();
+        let actual = serde_json::to_value(&transaction).unwrap();
+
+        assert_eq!(transaction.fee, 1000);
+        assert_eq!(actual["fee"], json!("1000"));
+        assert_eq!(actual["storage_fee"], json!("10"));
+        assert_eq!(actual["other_fee"], json!("990"));
+    }
+
+    fn given_transaction_id() -> InternalTransactionId {
+        InternalTransactionId {
+            lt: 47000000000001,
+            hash: "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_owned(),
+        }
+    }
+
+    fn given_block_id() -> TonBlockIdExt {
+        TonBlockIdExt {
+            workchain: -1,
+            shard: i64::MIN,
+            seqno: 34000000,
+            root_hash: "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_owned(),
+            file_hash: "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=".to_owned(),
+        }
+    }
+
+    #[test]
+    fn raw_get_transactions_serializes_lt_as_number() {
+        let address =
+            AccountAddress::new("EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS").unwrap();
+
+        let json = serde_json::to_value(RawGetTransactionsV2::new(
+            address,
+            given_transaction_id(),
+            16,
+            false,
+        ))
+        .unwrap();
+
+        assert_eq!(json["from_transaction_id"]["lt"], json!(47000000000001i64));
+    }
+
+    #[test]
+    fn blocks_get_block_header_serializes_shard_as_number() {
+        let json = serde_json::to_value(BlocksGetBlockHeader::new(given_block_id())).unwrap();
+
+        assert_eq!(json["id"]["shard"], json!(i64::MIN));
+    }
+
+    #[test]
+    fn raw_get_account_state_by_transaction_serializes_lt_as_number() {
+        let address =
+            AccountAddress::new("EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS").unwrap();
+
+        let json = serde_json::to_value(RawGetAccountStateByTransaction::new(
+            address,
+            given_transaction_id(),
+        ))
+        .unwrap();
+
+        assert_eq!(json["transaction_id"]["lt"], json!(47000000000001i64));
+    }
+
+    #[test]
+    fn get_shard_account_cell_by_transaction_serializes_lt_as_number() {
+        let address =
+            AccountAddress::new("EQCjk1hh952vWaE9bRguFkAhDAL5jj3xj9p0uPWrFBq_GEMS").unwrap();
+
+        let json = serde_json::to_value(GetShardAccountCellByTransaction::new(
+            address,
+            given_transaction_id(),
+        ))
+        .unwrap();
+
+        assert_eq!(json["transaction_id"]["lt"], json!(47000000000001i64));
+    }
+
+    #[test]
+    fn blocks_lookup_block_serializes_shard_and_lt_as_numbers() {
+        let lookup = BlocksLookupBlock::logical_time(given_block_id().into(), 47000000000001);
+
+        let json = serde_json::to_value(lookup).unwrap();
+
+        assert_eq!(json["id"]["shard"], json!(i64::MIN));
+        assert_eq!(json["lt"], json!(47000000000001i64));
+    }
+
+    #[test]
+    fn blocks_get_transactions_serializes_lt_as_number() {
+        let after = BlocksAccountTransactionId {
+            account: "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_owned(),
+            lt: 47000000000001,
+        };
+
+        let json = serde_json::to_value(BlocksGetTransactions::unverified(
+            given_block_id(),
+            Some(after),
+            false,
+            16,
+        ))
+        .unwrap();
+
+        assert_eq!(json["id"]["shard"], json!(i64::MIN));
+        assert_eq!(json["after"]["lt"], json!(47000000000001i64));
+    }
+
     #[test]
     #[traced_test]
     fn account_address_workchain_id() {
@@ -21,16 +21,6 @@ where
     }
 }
 
-pub fn deserialize_default_as_none<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
-where
-    D: Deserializer<'de>,
-    T: Default + serde::Deserialize<'de> + PartialEq,
-{
-    let v = T::deserialize(deserializer)?;
-
-    Ok(if v == T::default() { None } else { Some(v) })
-}
-
 pub fn deserialize_empty_as_none<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
@@ -0,0 +1,218 @@
+//! tonlib JSON representation of typed fields, used by the generated code for fields with a
+//! configured Rust type: `#[serde(serialize_with = "json_repr::serialize")]`.
+//!
+//! Bytes and `int256` are base64 strings, 32-bit integers are numbers, 64-bit and big integers
+//! are decimal strings as tonlib writes them in responses and numbers are accepted in both forms
+//! when deserializing. Requests keep the plain `int64` numbers, so configure these types on
+//! response fields only.
+
+use crate::deserialize::deserialize_number_from_string;
+use base64::Engine;
+use num_bigint::BigUint;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub trait JsonRepr: Sized {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+pub fn serialize<T: JsonRepr, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize_repr(serializer)
+}
+
+pub fn deserialize<'de, T: JsonRepr, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    T::deserialize_repr(deserializer)
+}
+
+struct Repr<'a, T>(&'a T);
+
+impl<T: JsonRepr> Serialize for Repr<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_repr(serializer)
+    }
+}
+
+struct Owned<T>(T);
+
+impl<'de, T: JsonRepr> Deserialize<'de> for Owned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_repr(deserializer).map(Owned)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber<T> {
+    String(String),
+    Number(T),
+}
+
+macro_rules! number_repr {
+    ($($ty:ty => $serialize:expr),*) => {
+        $(
+            impl JsonRepr for $ty {
+                fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    $serialize(self, serializer)
+                }
+
+                fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_number_from_string(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+number_repr!(
+    i32 => Serialize::serialize,
+    u32 => Serialize::serialize,
+    i64 => serialize_as_string,
+    u64 => serialize_as_string
+);
+
+fn serialize_as_string<T: std::fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+impl JsonRepr for BigUint {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match StringOrNumber::<u64>::deserialize(deserializer)? {
+            StringOrNumber::String(s) => s.parse().map_err(D::Error::custom),
+            StringOrNumber::Number(n) => Ok(n.into()),
+        }
+    }
+}
+
+impl JsonRepr for Vec<u8> {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(self))
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        base64::engine::general_purpose::STANDARD
+            .decode(s)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl JsonRepr for [u8; 32] {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(self))
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<u8>::deserialize_repr(deserializer)?
+            .try_into()
+            .map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"32 bytes"))
+    }
+}
+
+impl<T: JsonRepr> JsonRepr for Option<T> {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            None => serializer.serialize_none(),
+            Some(value) => serializer.serialize_some(&Repr(value)),
+        }
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Option::<Owned<T>>::deserialize(deserializer)?.map(|Owned(value)| value))
+    }
+}
+
+impl<T: JsonRepr> JsonRepr for Vec<T> {
+    fn serialize_repr<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Repr))
+    }
+
+    fn deserialize_repr<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Owned<T>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|Owned(value)| value)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Typed {
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        seqno: i32,
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        amount: u64,
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        big: BigUint,
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        body: Vec<u8>,
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        hash: [u8; 32],
+        #[serde(default)]
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        lt: Option<i64>,
+        #[serde(serialize_with = "serialize", deserialize_with = "deserialize")]
+        hashes: Vec<[u8; 32]>,
+    }
+
+    fn given_json() -> serde_json::Value {
+        json!({
+            "seqno": -1,
+            "amount": "18446744073709551615",
+            "big": "340282366920938463463374607431768211456",
+            "body": "te6ccgEBAQEAAgAAAA==",
+            "hash": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=",
+            "lt": "47000000000001",
+            "hashes": ["AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI="]
+        })
+    }
+
+    #[test]
+    fn typed_fields_roundtrip() {
+        let json = given_json();
+
+        let typed: Typed = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(typed.seqno, -1);
+        assert_eq!(typed.amount, u64::MAX);
+        assert_eq!(typed.big, BigUint::from(1u8) << 128);
+        assert_eq!(typed.body.len(), 13);
+        assert_eq!(typed.hash, [1; 32]);
+        assert_eq!(typed.lt, Some(47000000000001));
+        assert_eq!(typed.hashes, vec![[2; 32]]);
+        assert_eq!(serde_json::to_value(&typed).unwrap(), json);
+    }
+
+    #[test]
+    fn typed_fields_accept_numbers_and_missing_options() {
+        let mut json = given_json();
+        json["amount"] = json!(5);
+        json.as_object_mut().unwrap().remove("lt");
+
+        let typed: Typed = serde_json::from_value(json).unwrap();
+
+        assert_eq!(typed.amount, 5);
+        assert_eq!(typed.lt, None);
+    }
+
+    #[test]
+    fn typed_fields_reject_invalid_hash() {
+        let mut json = given_json();
+        json["hash"] = json!("AQID");
+
+        assert!(serde_json::from_value::<Typed>(json).is_err());
+    }
+}
@@ -4,6 +4,7 @@ mod client;
 mod cursor_client;
 mod deserialize;
 mod error;
+pub mod json_repr;
 mod make;
 mod metric;
 mod request;